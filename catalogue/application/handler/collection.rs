@@ -1,9 +1,10 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 
 use common::error::Error;
-use common::event::{Event, EventHandler};
+use common::event::{Event, EventHandler, RetryPolicy};
 use common::result::Result;
 use shared::event::CollectionEvent;
 
@@ -67,4 +68,9 @@ impl EventHandler for CollectionHandler {
 
         Ok(true)
     }
+
+    // The publishing repositories might not be up to date yet.
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::new(3, Duration::from_millis(100))
+    }
 }
@@ -1,4 +1,6 @@
 mod collection;
+mod projection;
 mod publication;
 pub use collection::*;
+pub use projection::*;
 pub use publication::*;
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use common::event::{EventHandler, Projection};
+use common::result::Result;
+
+use crate::application::handler::{CollectionHandler, PublicationHandler};
+use crate::domain::catalogue::{
+    Catalogue, CatalogueRepository, CollectionService, PublicationService,
+};
+
+// The catalogue, built from publication and collection events.
+pub struct CatalogueProjection {
+    catalogue_repo: Arc<dyn CatalogueRepository>,
+
+    collection_serv: Arc<dyn CollectionService>,
+    publication_serv: Arc<dyn PublicationService>,
+}
+
+impl CatalogueProjection {
+    pub fn new(
+        catalogue_repo: Arc<dyn CatalogueRepository>,
+        collection_serv: Arc<dyn CollectionService>,
+        publication_serv: Arc<dyn PublicationService>,
+    ) -> Self {
+        CatalogueProjection {
+            catalogue_repo,
+            collection_serv,
+            publication_serv,
+        }
+    }
+}
+
+#[async_trait]
+impl Projection for CatalogueProjection {
+    fn name(&self) -> &str {
+        "catalogue"
+    }
+
+    async fn reset(&self) -> Result<()> {
+        let catalogue = self.catalogue_repo.find().await?;
+        let mut catalogue = Catalogue::new(catalogue.base().id().clone())?;
+        self.catalogue_repo.save(&mut catalogue).await
+    }
+
+    fn handlers(&self) -> Vec<Box<dyn EventHandler>> {
+        vec![
+            Box::new(PublicationHandler::new(
+                self.catalogue_repo.clone(),
+                self.publication_serv.clone(),
+            )),
+            Box::new(CollectionHandler::new(
+                self.catalogue_repo.clone(),
+                self.collection_serv.clone(),
+            )),
+        ]
+    }
+}
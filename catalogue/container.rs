@@ -1,9 +1,9 @@
 use std::sync::Arc;
 
-use common::event::{EventPublisher, EventSubscriber};
+use common::event::{EventPublisher, EventSubscriber, Projection};
 use common::result::Result;
 
-use crate::application::handler::{CollectionHandler, PublicationHandler};
+use crate::application::handler::CatalogueProjection;
 use crate::domain::catalogue::{CatalogueRepository, CollectionService, PublicationService};
 
 pub struct Container<EPub> {
@@ -37,13 +37,9 @@ where
     where
         ES: EventSubscriber,
     {
-        let handler =
-            PublicationHandler::new(self.catalogue_repo.clone(), self.publication_serv.clone());
-        event_sub.subscribe(Box::new(handler)).await?;
-
-        let handler =
-            CollectionHandler::new(self.catalogue_repo.clone(), self.collection_serv.clone());
-        event_sub.subscribe(Box::new(handler)).await?;
+        for handler in self.projection().handlers().into_iter() {
+            event_sub.subscribe(handler).await?;
+        }
 
         Ok(())
     }
@@ -52,6 +48,14 @@ where
         &self.event_pub
     }
 
+    pub fn projection(&self) -> CatalogueProjection {
+        CatalogueProjection::new(
+            self.catalogue_repo.clone(),
+            self.collection_serv.clone(),
+            self.publication_serv.clone(),
+        )
+    }
+
     pub fn catalogue_repo(&self) -> &dyn CatalogueRepository {
         self.catalogue_repo.as_ref()
     }
@@ -51,9 +51,16 @@ impl Catalogue {
         &self.publications
     }
 
-    // TODO: improve logic!
+    // Replaces the publication if it was already added, so events can be handled again.
     pub fn add_publication(&mut self, publication: Publication) {
-        self.publications.push(publication);
+        match self
+            .publications
+            .iter()
+            .position(|p| p.id() == publication.id())
+        {
+            Some(i) => self.publications[i] = publication,
+            None => self.publications.push(publication),
+        }
         self.base.update();
     }
 
@@ -64,7 +71,14 @@ impl Catalogue {
     }
 
     pub fn add_collection(&mut self, collection: Collection) {
-        self.collections.push(collection);
+        match self
+            .collections
+            .iter()
+            .position(|c| c.id() == collection.id())
+        {
+            Some(i) => self.collections[i] = collection,
+            None => self.collections.push(collection),
+        }
         self.base.update();
     }
 
@@ -25,9 +25,8 @@ impl CatalogueRepository for InMemCatalogueRepository {
         Ok(self.catalogue.lock().await.clone())
     }
 
-    async fn save(&self, _catalogue: &mut Catalogue) -> Result<()> {
-        let mut catalogue = self.catalogue.lock().await;
-        *catalogue = catalogue.clone();
+    async fn save(&self, catalogue: &mut Catalogue) -> Result<()> {
+        *self.catalogue.lock().await = catalogue.clone();
         Ok(())
     }
 }
@@ -1,8 +1,66 @@
 use std::env;
 
+// Client registered in an OpenID Connect provider.
+pub struct OidcProviderConfig {
+    discovery_url: String,
+    client_id: String,
+    client_secret: String,
+    redirect_url: String,
+}
+
+impl OidcProviderConfig {
+    // The provider is configured only if its client id is set.
+    fn get(name: &str, default_discovery_url: &str) -> Option<Self> {
+        let prefix = format!("OIDC_{}", name.to_uppercase());
+
+        let client_id = env::var(format!("{}_CLIENT_ID", prefix)).ok()?;
+        Some(OidcProviderConfig {
+            discovery_url: env::var(format!("{}_DISCOVERY_URL", prefix))
+                .unwrap_or(default_discovery_url.to_owned()),
+            client_id,
+            client_secret: env::var(format!("{}_CLIENT_SECRET", prefix)).unwrap_or_default(),
+            redirect_url: env::var(format!("{}_REDIRECT_URL", prefix))
+                .unwrap_or(format!("http://localhost:3000/api/oidc/{}/callback", name)),
+        })
+    }
+
+    pub fn discovery_url(&self) -> &str {
+        &self.discovery_url
+    }
+
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    pub fn client_secret(&self) -> &str {
+        &self.client_secret
+    }
+
+    pub fn redirect_url(&self) -> &str {
+        &self.redirect_url
+    }
+}
+
 pub struct Config {
     port: u16,
     env: String,
+    persistence: String,
+    database_path: String,
+    event_store_path: String,
+    jwt_algorithm: String,
+    jwt_secret: Option<String>,
+    jwt_private_key_path: Option<String>,
+    jwt_public_key_path: Option<String>,
+    jwt_key_id: String,
+    jwt_previous_keys: Vec<String>,
+    jwt_expiration: u64,
+    jwt_issuer: String,
+    jwt_audience: String,
+    oidc_google: Option<OidcProviderConfig>,
+    oidc_facebook: Option<OidcProviderConfig>,
+    web_url: String,
+    payment_webhook_secret: Option<String>,
+    trusted_proxies: Vec<String>,
 }
 
 impl Config {
@@ -16,6 +74,51 @@ impl Config {
                 _ => 80,
             },
             env: env::var("ENV").unwrap_or("development".to_owned()),
+            persistence: env::var("PERSISTENCE").unwrap_or("inmem".to_owned()),
+            database_path: env::var("DATABASE_PATH").unwrap_or("omics.db".to_owned()),
+            event_store_path: env::var("EVENT_STORE_PATH").unwrap_or("events.jsonl".to_owned()),
+            jwt_algorithm: env::var("JWT_ALGORITHM").unwrap_or("HS256".to_owned()),
+            jwt_secret: env::var("JWT_SECRET").ok(),
+            jwt_private_key_path: env::var("JWT_PRIVATE_KEY_PATH").ok(),
+            jwt_public_key_path: env::var("JWT_PUBLIC_KEY_PATH").ok(),
+            jwt_key_id: env::var("JWT_KEY_ID").unwrap_or("default".to_owned()),
+            // Keys of the form <kid>:<algorithm>:<path> separated by commas.
+            jwt_previous_keys: match env::var("JWT_PREVIOUS_KEYS") {
+                Ok(keys) => keys
+                    .split(',')
+                    .map(|key| key.trim().to_owned())
+                    .filter(|key| !key.is_empty())
+                    .collect(),
+                _ => Vec::new(),
+            },
+            jwt_expiration: match env::var("JWT_EXPIRATION") {
+                Ok(expiration) => match expiration.parse() {
+                    Ok(expiration) => expiration,
+                    _ => 900,
+                },
+                _ => 900,
+            },
+            jwt_issuer: env::var("JWT_ISSUER").unwrap_or("omics".to_owned()),
+            jwt_audience: env::var("JWT_AUDIENCE").unwrap_or("omics".to_owned()),
+            oidc_google: OidcProviderConfig::get(
+                "google",
+                "https://accounts.google.com/.well-known/openid-configuration",
+            ),
+            oidc_facebook: OidcProviderConfig::get(
+                "facebook",
+                "https://www.facebook.com/.well-known/openid-configuration/",
+            ),
+            web_url: env::var("WEB_URL").unwrap_or("http://localhost:4200".to_owned()),
+            payment_webhook_secret: env::var("PAYMENT_WEBHOOK_SECRET").ok(),
+            // Addresses separated by commas.
+            trusted_proxies: match env::var("TRUSTED_PROXIES") {
+                Ok(proxies) => proxies
+                    .split(',')
+                    .map(|proxy| proxy.trim().to_owned())
+                    .filter(|proxy| !proxy.is_empty())
+                    .collect(),
+                _ => Vec::new(),
+            },
         }
     }
 
@@ -26,4 +129,74 @@ impl Config {
     pub fn env(&self) -> &str {
         &self.env
     }
+
+    pub fn persistence(&self) -> &str {
+        &self.persistence
+    }
+
+    pub fn database_path(&self) -> &str {
+        &self.database_path
+    }
+
+    pub fn event_store_path(&self) -> &str {
+        &self.event_store_path
+    }
+
+    pub fn jwt_algorithm(&self) -> &str {
+        &self.jwt_algorithm
+    }
+
+    pub fn jwt_secret(&self) -> Option<&str> {
+        self.jwt_secret.as_deref()
+    }
+
+    pub fn jwt_private_key_path(&self) -> Option<&str> {
+        self.jwt_private_key_path.as_deref()
+    }
+
+    pub fn jwt_public_key_path(&self) -> Option<&str> {
+        self.jwt_public_key_path.as_deref()
+    }
+
+    pub fn jwt_key_id(&self) -> &str {
+        &self.jwt_key_id
+    }
+
+    pub fn jwt_previous_keys(&self) -> &[String] {
+        &self.jwt_previous_keys
+    }
+
+    // Seconds a token is valid since it is issued.
+    pub fn jwt_expiration(&self) -> u64 {
+        self.jwt_expiration
+    }
+
+    pub fn jwt_issuer(&self) -> &str {
+        &self.jwt_issuer
+    }
+
+    pub fn jwt_audience(&self) -> &str {
+        &self.jwt_audience
+    }
+
+    pub fn oidc_google(&self) -> Option<&OidcProviderConfig> {
+        self.oidc_google.as_ref()
+    }
+
+    pub fn oidc_facebook(&self) -> Option<&OidcProviderConfig> {
+        self.oidc_facebook.as_ref()
+    }
+
+    pub fn web_url(&self) -> &str {
+        &self.web_url
+    }
+
+    // Proxies whose forwarded headers are trusted.
+    pub fn trusted_proxies(&self) -> &[String] {
+        &self.trusted_proxies
+    }
+
+    pub fn payment_webhook_secret(&self) -> Option<&str> {
+        self.payment_webhook_secret.as_deref()
+    }
 }
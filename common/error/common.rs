@@ -7,6 +7,17 @@ impl Error {
             .build()
     }
 
+    // The entity was modified by someone else after being loaded.
+    pub fn conflict<S: Into<String>>(entity: S) -> Error {
+        Error::new(entity.into(), "conflict".to_owned())
+            .set_status(409)
+            .build()
+    }
+
+    pub fn is_conflict(&self) -> bool {
+        self.code() == "conflict" && self.status() == Some(409)
+    }
+
     pub fn unauthorized() -> Error {
         Error::new("authorization", "unauthorized")
             .set_status(401)
@@ -1,11 +1,21 @@
+mod dead_letter;
 mod handler;
+mod outbox;
+mod projection;
 mod publisher;
 mod repository;
+mod retry;
 mod subscriber;
+mod upcaster;
+pub use dead_letter::*;
 pub use handler::*;
+pub use outbox::*;
+pub use projection::*;
 pub use publisher::*;
 pub use repository::*;
+pub use retry::*;
 pub use subscriber::*;
+pub use upcaster::*;
 
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
@@ -20,6 +30,8 @@ pub struct Event {
     id: EventId,
     topic: String,
     code: String,
+    // Schema version of the payload, see `UpcasterRegistry`.
+    version: u32,
     timestamp: DateTime<Utc>,
     payload: Vec<u8>,
 }
@@ -30,11 +42,30 @@ impl Event {
             id: EventId::new(Uuid::new_v4().to_string()).unwrap(),
             topic: topic.into(),
             code: code.into(),
+            version: 1,
             timestamp: Utc::now(),
             payload,
         }
     }
 
+    pub fn build(
+        id: EventId,
+        topic: String,
+        code: String,
+        version: u32,
+        timestamp: DateTime<Utc>,
+        payload: Vec<u8>,
+    ) -> Self {
+        Event {
+            id,
+            topic,
+            code,
+            version,
+            timestamp,
+            payload,
+        }
+    }
+
     pub fn id(&self) -> &EventId {
         &self.id
     }
@@ -47,6 +78,14 @@ impl Event {
         &self.code
     }
 
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
     pub fn timestamp(&self) -> &DateTime<Utc> {
         &self.timestamp
     }
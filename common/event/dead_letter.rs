@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::event::Event;
+use crate::model::StringId;
+use crate::result::Result;
+
+pub type DeadLetterId = StringId;
+
+// An event that a handler could not process after exhausting its retries.
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    id: DeadLetterId,
+    event: Event,
+    handler: String,
+    error: String,
+    attempts: u32,
+    failed_at: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    pub fn new<S: Into<String>>(event: Event, handler: S, error: S, attempts: u32) -> Self {
+        DeadLetter {
+            id: DeadLetterId::new(Uuid::new_v4().to_string()).unwrap(),
+            event,
+            handler: handler.into(),
+            error: error.into(),
+            attempts,
+            failed_at: Utc::now(),
+        }
+    }
+
+    pub fn id(&self) -> &DeadLetterId {
+        &self.id
+    }
+
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    pub fn handler(&self) -> &str {
+        &self.handler
+    }
+
+    pub fn error(&self) -> &str {
+        &self.error
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn failed_at(&self) -> &DateTime<Utc> {
+        &self.failed_at
+    }
+
+    pub fn failed_again<S: Into<String>>(&mut self, error: S, attempts: u32) {
+        self.error = error.into();
+        self.attempts += attempts;
+        self.failed_at = Utc::now();
+    }
+}
+
+#[async_trait]
+pub trait DeadLetterRepository: Sync + Send {
+    async fn find_all(&self) -> Result<Vec<DeadLetter>>;
+    async fn find_by_id(&self, id: &DeadLetterId) -> Result<DeadLetter>;
+
+    async fn save(&self, dead_letter: &DeadLetter) -> Result<()>;
+    async fn delete(&self, id: &DeadLetterId) -> Result<()>;
+}
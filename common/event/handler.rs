@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use crate::event::Event;
+use crate::event::{Event, RetryPolicy};
 use crate::result::Result;
 
 #[async_trait]
@@ -8,4 +8,13 @@ pub trait EventHandler: Sync + Send {
     fn topic(&self) -> &str;
 
     async fn handle(&mut self, event: &Event) -> Result<bool>;
+
+    // Identifies the handler in dead letters, so failed events can be replayed to it.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::none()
+    }
 }
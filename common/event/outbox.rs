@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use crate::event::{Event, EventId};
+use crate::result::Result;
+
+// Stores the events that have to be published. Events stay pending until they are marked as
+// published, so each of them is delivered at least once even if the process stops in between.
+#[async_trait]
+pub trait Outbox: Sync + Send {
+    // Events already added are ignored.
+    async fn add_all(&self, events: &[Event]) -> Result<()>;
+
+    // Pending events in the order they were added.
+    async fn pending(&self) -> Result<Vec<Event>>;
+
+    async fn mark_as_published(&self, id: &EventId) -> Result<()>;
+}
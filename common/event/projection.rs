@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+
+use crate::event::EventHandler;
+use crate::result::Result;
+
+#[derive(Debug, Default)]
+pub struct RebuildResult {
+    pub events: u32,
+    pub ok_handlers: u32,
+    pub err_handlers: u32,
+}
+
+impl RebuildResult {
+    pub fn events(&self) -> u32 {
+        self.events
+    }
+
+    pub fn ok_handlers(&self) -> u32 {
+        self.ok_handlers
+    }
+
+    pub fn err_handlers(&self) -> u32 {
+        self.err_handlers
+    }
+}
+
+// A read model built from events. It can be rebuilt from scratch by resetting it and sending
+// every stored event to its handlers again.
+#[async_trait]
+pub trait Projection: Sync + Send {
+    fn name(&self) -> &str;
+
+    async fn reset(&self) -> Result<()>;
+
+    // Handlers that keep the projection up to date. The same handlers are subscribed to the
+    // event bus and used to rebuild the projection.
+    fn handlers(&self) -> Vec<Box<dyn EventHandler>>;
+}
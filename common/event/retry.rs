@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+// How many times a failing handler is executed again for the same event, and how long to wait
+// between attempts. The backoff is doubled after each retry.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, backoff: Duration) -> Self {
+        RetryPolicy {
+            max_retries,
+            backoff,
+        }
+    }
+
+    pub fn none() -> Self {
+        Self::new(0, Duration::from_millis(0))
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    // Time to wait before the given retry (starting at 1).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.backoff.checked_mul(factor).unwrap_or(self.backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(100));
+        assert_eq!(policy.max_retries(), 3);
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_retries(), 0);
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::error::Error;
+use crate::event::Event;
+use crate::result::Result;
+
+// Migrates the payload of an event from a schema version to the next one.
+pub type Upcaster = fn(Value) -> Result<Value>;
+
+// Upcasters by topic and the version they migrate from. Stored events keep the version they were
+// created with, so their payloads are upcasted to the current shape before being deserialized.
+#[derive(Default)]
+pub struct UpcasterRegistry {
+    upcasters: HashMap<(String, u32), Upcaster>,
+}
+
+impl UpcasterRegistry {
+    pub fn new() -> Self {
+        UpcasterRegistry {
+            upcasters: HashMap::new(),
+        }
+    }
+
+    pub fn register<S: Into<String>>(&mut self, topic: S, from_version: u32, upcaster: Upcaster) {
+        self.upcasters
+            .insert((topic.into(), from_version), upcaster);
+    }
+
+    // Applies, in order, every upcaster registered for the topic of the event from its version.
+    pub fn upcast(&self, event: &Event) -> Result<Event> {
+        let mut version = event.version();
+        if !self
+            .upcasters
+            .contains_key(&(event.topic().to_owned(), version))
+        {
+            return Ok(event.clone());
+        }
+
+        let mut payload: Value = serde_json::from_slice(event.payload()).map_err(|err| {
+            Error::internal("upcaster", "invalid_payload")
+                .add_context("event_id", event.id().value())
+                .wrap_raw(err)
+                .build()
+        })?;
+
+        while let Some(upcaster) = self.upcasters.get(&(event.topic().to_owned(), version)) {
+            payload = upcaster(payload).map_err(|err| {
+                Error::internal("upcaster", "upcast")
+                    .add_context("event_id", event.id().value())
+                    .add_context("version", &version.to_string())
+                    .wrap(err)
+                    .build()
+            })?;
+            version += 1;
+        }
+
+        let payload = serde_json::to_vec(&payload).map_err(|err| {
+            Error::internal("upcaster", "invalid_payload")
+                .add_context("event_id", event.id().value())
+                .wrap_raw(err)
+                .build()
+        })?;
+
+        Ok(Event::build(
+            event.id().clone(),
+            event.topic().to_owned(),
+            event.code().to_owned(),
+            version,
+            *event.timestamp(),
+            payload,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn add_field(mut payload: Value) -> Result<Value> {
+        payload["field"] = json!("default");
+        Ok(payload)
+    }
+
+    fn rename_field(mut payload: Value) -> Result<Value> {
+        let value = payload["field"].take();
+        payload["renamed"] = value;
+        Ok(payload)
+    }
+
+    #[test]
+    fn upcast() {
+        let mut registry = UpcasterRegistry::new();
+        registry.register("entity", 1, add_field);
+        registry.register("entity", 2, rename_field);
+
+        let event = Event::new("entity", "created", b"{\"id\":\"#01\"}".to_vec());
+        let upcasted = registry.upcast(&event).unwrap();
+        assert_eq!(upcasted.id(), event.id());
+        assert_eq!(upcasted.version(), 3);
+        let payload: Value = serde_json::from_slice(upcasted.payload()).unwrap();
+        assert_eq!(
+            payload,
+            json!({"id": "#01", "field": null, "renamed": "default"})
+        );
+
+        let mut event = Event::new("entity", "created", b"{\"field\":\"value\"}".to_vec());
+        event.set_version(2);
+        let upcasted = registry.upcast(&event).unwrap();
+        assert_eq!(upcasted.version(), 3);
+        let payload: Value = serde_json::from_slice(upcasted.payload()).unwrap();
+        assert_eq!(payload["renamed"], "value");
+
+        // Current and unknown topics are not modified
+        let upcasted = registry.upcast(&upcasted).unwrap();
+        assert_eq!(upcasted.version(), 3);
+        let event = Event::new("other", "created", b"not json".to_vec());
+        assert_eq!(registry.upcast(&event).unwrap().payload(), b"not json");
+
+        let event = Event::new("entity", "created", b"not json".to_vec());
+        assert!(registry.upcast(&event).is_err());
+    }
+}
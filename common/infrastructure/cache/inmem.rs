@@ -54,6 +54,20 @@ impl<K, V: Clone> InMemCache<K, V> {
     }
 }
 
+impl<K: Hash + Eq, V: Clone> InMemCache<K, V> {
+    // Stores the value if the check over the current one (if any) succeeds. Both operations are
+    // done while holding the lock, so no other value can be stored in between.
+    pub async fn set_checked<F>(&self, k: K, v: V, check: F) -> Result<()>
+    where
+        F: FnOnce(Option<&V>) -> Result<()>,
+    {
+        let mut cache = self.data.lock().await;
+        check(cache.get(&k))?;
+        cache.insert(k, v);
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl<K: Hash + Eq + Send + Sync, V: Clone + Send + Sync> Cache<K, V> for InMemCache<K, V> {
     async fn get(&self, k: &K) -> Option<V> {
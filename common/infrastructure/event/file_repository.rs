@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::error::Error;
+use crate::event::{Event, EventId, EventRepository};
+use crate::result::Result;
+
+// A line of the event file.
+#[derive(Serialize, Deserialize)]
+struct Record {
+    sequence: u64,
+    id: String,
+    topic: String,
+    code: String,
+    // Records written before events were versioned have the first version.
+    #[serde(default = "first_version")]
+    version: u32,
+    timestamp: DateTime<Utc>,
+    payload: Vec<u8>,
+}
+
+fn first_version() -> u32 {
+    1
+}
+
+struct Log {
+    // Written from blocking tasks, so it is shared with them.
+    file: Arc<File>,
+    events: Vec<Event>,
+    // Sequence number of each event. The sequence of events[i] is i + 1.
+    sequences: HashMap<EventId, u64>,
+}
+
+// Append-only event store backed by a file with one JSON record per line. Every event gets a
+// monotonic sequence number, which defines the order returned by `find_all` and `find_from`.
+pub struct FileEventRepository {
+    path: PathBuf,
+    log: Mutex<Log>,
+}
+
+impl FileEventRepository {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let path_str = path.to_string_lossy().to_string();
+
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)
+            .map_err(|err| {
+                Error::internal("event_repository", "open")
+                    .add_context("path", &path_str)
+                    .wrap_raw(err)
+                    .build()
+            })?;
+
+        let mut content = Vec::new();
+        (&file).read_to_end(&mut content).map_err(|err| {
+            Error::internal("event_repository", "read")
+                .add_context("path", &path_str)
+                .wrap_raw(err)
+                .build()
+        })?;
+
+        // A last line without its newline is a record that was being appended when the server
+        // stopped. It was not saved, so it is discarded.
+        let complete = content
+            .iter()
+            .rposition(|b| *b == b'\n')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        if complete < content.len() {
+            file.set_len(complete as u64).map_err(|err| {
+                Error::internal("event_repository", "truncate")
+                    .add_context("path", &path_str)
+                    .wrap_raw(err)
+                    .build()
+            })?;
+        }
+
+        let mut events = Vec::new();
+        let mut sequences = HashMap::new();
+        for (i, line) in content[..complete].split(|b| *b == b'\n').enumerate() {
+            if line.iter().all(|b| b.is_ascii_whitespace()) {
+                continue;
+            }
+
+            let record: Record = serde_json::from_slice(line).map_err(|err| {
+                Error::internal("event_repository", "invalid_record")
+                    .add_context("path", &path_str)
+                    .add_context("line", &(i + 1).to_string())
+                    .wrap_raw(err)
+                    .build()
+            })?;
+
+            let sequence = events.len() as u64 + 1;
+            if record.sequence != sequence {
+                return Err(Error::internal("event_repository", "invalid_sequence")
+                    .add_context("path", &path_str)
+                    .add_context("line", &(i + 1).to_string())
+                    .build());
+            }
+
+            let event = Event::build(
+                EventId::new(record.id)?,
+                record.topic,
+                record.code,
+                record.version,
+                record.timestamp,
+                record.payload,
+            );
+            sequences.insert(event.id().clone(), sequence);
+            events.push(event);
+        }
+
+        Ok(FileEventRepository {
+            path,
+            log: Mutex::new(Log {
+                file: Arc::new(file),
+                events,
+                sequences,
+            }),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub async fn last_sequence(&self) -> u64 {
+        self.log.lock().await.events.len() as u64
+    }
+}
+
+#[async_trait]
+impl EventRepository for FileEventRepository {
+    async fn find_all(&self) -> Result<Vec<Event>> {
+        Ok(self.log.lock().await.events.clone())
+    }
+
+    async fn find_from(&self, id: &EventId) -> Result<Vec<Event>> {
+        let log = self.log.lock().await;
+        let sequence = log
+            .sequences
+            .get(id)
+            .ok_or(Error::new("event", "not_found"))?;
+
+        Ok(log.events[*sequence as usize..].to_vec())
+    }
+
+    async fn save(&self, event: &Event) -> Result<()> {
+        let mut log = self.log.lock().await;
+
+        // Events are stored once, so publishing the same event twice is harmless.
+        if log.sequences.contains_key(event.id()) {
+            return Ok(());
+        }
+
+        let sequence = log.events.len() as u64 + 1;
+        let record = Record {
+            sequence,
+            id: event.id().to_string(),
+            topic: event.topic().to_owned(),
+            code: event.code().to_owned(),
+            version: event.version(),
+            timestamp: *event.timestamp(),
+            payload: event.payload().to_vec(),
+        };
+
+        let mut line = serde_json::to_string(&record).map_err(|err| {
+            Error::internal("event_repository", "serialize")
+                .wrap_raw(err)
+                .build()
+        })?;
+        line.push('\n');
+
+        // The lock is kept until the line is written, so records are appended in order.
+        let file = Arc::clone(&log.file);
+        tokio::task::spawn_blocking(move || {
+            let mut file = file.as_ref();
+            file.write_all(line.as_bytes())
+                .and_then(|_| file.sync_data())
+        })
+        .await
+        .unwrap_or_else(|err| Err(io::Error::other(err)))
+        .map_err(|err| {
+            Error::internal("event_repository", "write")
+                .add_context("event_id", event.id().value())
+                .wrap_raw(err)
+                .build()
+        })?;
+
+        log.sequences.insert(event.id().clone(), sequence);
+        log.events.push(event.clone());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::fs;
+
+    use uuid::Uuid;
+
+    fn temp_path() -> PathBuf {
+        env::temp_dir().join(format!("omics-events-{}.jsonl", Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn append_and_reopen() {
+        let path = temp_path();
+
+        let e1 = Event::new("publication", "created", b"{\"id\":\"#01\"}".to_vec());
+        let e2 = Event::new("publication", "updated", vec![0, 1, 2, 255]);
+        let e3 = Event::new("user", "registered", Vec::new());
+
+        {
+            let repo = FileEventRepository::open(&path).unwrap();
+            repo.save(&e1).await.unwrap();
+            repo.save(&e2).await.unwrap();
+            repo.save(&e2).await.unwrap();
+            assert_eq!(repo.last_sequence().await, 2);
+        }
+
+        let repo = FileEventRepository::open(&path).unwrap();
+        assert_eq!(repo.last_sequence().await, 2);
+        repo.save(&e3).await.unwrap();
+
+        let events = repo.find_all().await.unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].id(), e1.id());
+        assert_eq!(events[0].topic(), "publication");
+        assert_eq!(events[0].timestamp(), e1.timestamp());
+        assert_eq!(events[0].payload(), e1.payload());
+        assert_eq!(events[1].payload(), &[0, 1, 2, 255]);
+        assert_eq!(events[2].code(), "registered");
+
+        let events = repo.find_from(e1.id()).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id(), e2.id());
+        assert_eq!(events[1].id(), e3.id());
+        assert!(repo.find_from(e3.id()).await.unwrap().is_empty());
+        assert!(repo
+            .find_from(&EventId::new("unknown").unwrap())
+            .await
+            .is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn versions() {
+        let path = temp_path();
+        fs::write(
+            &path,
+            "{\"sequence\":1,\"id\":\"#event01\",\"topic\":\"user\",\"code\":\"registered\",\
+            \"timestamp\":\"2020-10-01T10:00:00Z\",\"payload\":[]}\n",
+        )
+        .unwrap();
+
+        let repo = FileEventRepository::open(&path).unwrap();
+        let mut event = Event::new("user", "updated", Vec::new());
+        event.set_version(3);
+        repo.save(&event).await.unwrap();
+
+        let repo = FileEventRepository::open(&path).unwrap();
+        let events = repo.find_all().await.unwrap();
+        assert_eq!(events[0].version(), 1);
+        assert_eq!(events[1].version(), 3);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn invalid_file() {
+        let path = temp_path();
+        fs::write(&path, "{\"sequence\":2}\n").unwrap();
+        assert!(FileEventRepository::open(&path).is_err());
+
+        // Only the last line can be incomplete.
+        fs::write(&path, "{\"sequence\":1,\"id\"\n{\"sequence\":2}\n").unwrap();
+        assert!(FileEventRepository::open(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn incomplete_record() {
+        let path = temp_path();
+        let e1 = Event::new("user", "registered", Vec::new());
+        {
+            let repo = FileEventRepository::open(&path).unwrap();
+            repo.save(&e1).await.unwrap();
+        }
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"sequence\":2,\"id\":\"#ev").unwrap();
+
+        let repo = FileEventRepository::open(&path).unwrap();
+        assert_eq!(repo.last_sequence().await, 1);
+        let e2 = Event::new("user", "updated", Vec::new());
+        repo.save(&e2).await.unwrap();
+
+        let repo = FileEventRepository::open(&path).unwrap();
+        let events = repo.find_all().await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].id(), e2.id());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
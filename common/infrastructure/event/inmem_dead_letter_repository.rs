@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use crate::error::Error;
+use crate::event::{DeadLetter, DeadLetterId, DeadLetterRepository};
+use crate::result::Result;
+
+#[derive(Default)]
+pub struct InMemDeadLetterRepository {
+    dead_letters: Mutex<Vec<DeadLetter>>,
+}
+
+impl InMemDeadLetterRepository {
+    pub fn new() -> Self {
+        InMemDeadLetterRepository {
+            dead_letters: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl DeadLetterRepository for InMemDeadLetterRepository {
+    async fn find_all(&self) -> Result<Vec<DeadLetter>> {
+        Ok(self.dead_letters.lock().await.clone())
+    }
+
+    async fn find_by_id(&self, id: &DeadLetterId) -> Result<DeadLetter> {
+        self.dead_letters
+            .lock()
+            .await
+            .iter()
+            .find(|dead_letter| dead_letter.id() == id)
+            .cloned()
+            .ok_or(Error::new("dead_letter", "not_found"))
+    }
+
+    async fn save(&self, dead_letter: &DeadLetter) -> Result<()> {
+        let mut dead_letters = self.dead_letters.lock().await;
+        match dead_letters
+            .iter_mut()
+            .find(|dl| dl.id() == dead_letter.id())
+        {
+            Some(dl) => *dl = dead_letter.clone(),
+            None => dead_letters.push(dead_letter.clone()),
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: &DeadLetterId) -> Result<()> {
+        let mut dead_letters = self.dead_letters.lock().await;
+        let len = dead_letters.len();
+        dead_letters.retain(|dead_letter| dead_letter.id() != id);
+
+        if dead_letters.len() == len {
+            return Err(Error::new("dead_letter", "not_found"));
+        }
+
+        Ok(())
+    }
+}
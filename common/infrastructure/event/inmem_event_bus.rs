@@ -3,61 +3,254 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use regex::Regex;
 use tokio::sync::oneshot::{self, Receiver};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::time;
 
 use crate::error::Error;
-use crate::event::{Event, EventHandler, EventPublisher, EventSubscriber, PublicationResult};
+use crate::event::{
+    DeadLetter, DeadLetterId, DeadLetterRepository, Event, EventHandler, EventPublisher,
+    EventSubscriber, PublicationResult, UpcasterRegistry,
+};
+use crate::infrastructure::event::InMemDeadLetterRepository;
 use crate::result::Result;
 
-#[derive(Default)]
+const MAX_IN_FLIGHT_EVENTS: usize = 64;
+
+// A handler with its topic already compiled. Each handler has its own lock, so it handles one
+// event at a time while the other handlers go on with theirs.
+struct Subscription {
+    name: String,
+    topic: Regex,
+    handler: Mutex<Box<dyn EventHandler>>,
+}
+
 pub struct InMemEventBus {
-    handlers: Arc<Mutex<Vec<Box<dyn EventHandler>>>>,
+    handlers: Arc<Mutex<Vec<Arc<Subscription>>>>,
+    dead_letter_repo: Arc<dyn DeadLetterRepository>,
+    upcasters: Arc<UpcasterRegistry>,
+    // Limits the events being handled at the same time.
+    in_flight: Arc<Semaphore>,
 }
 
 impl InMemEventBus {
     pub fn new() -> Self {
+        Self::with_dead_letter_repository(Arc::new(InMemDeadLetterRepository::new()))
+    }
+
+    pub fn with_dead_letter_repository(dead_letter_repo: Arc<dyn DeadLetterRepository>) -> Self {
         InMemEventBus {
             handlers: Arc::new(Mutex::new(Vec::new())),
+            dead_letter_repo,
+            upcasters: Arc::new(UpcasterRegistry::new()),
+            in_flight: Arc::new(Semaphore::new(MAX_IN_FLIGHT_EVENTS)),
+        }
+    }
+
+    // Events are upcasted to their current version before being sent to the handlers.
+    pub fn set_upcasters(&mut self, upcasters: UpcasterRegistry) {
+        self.upcasters = Arc::new(upcasters);
+    }
+
+    pub fn set_max_in_flight_events(&mut self, max: usize) {
+        self.in_flight = Arc::new(Semaphore::new(max));
+    }
+
+    pub fn dead_letter_repo(&self) -> &dyn DeadLetterRepository {
+        self.dead_letter_repo.as_ref()
+    }
+
+    // Sends the event of a dead letter again to the handler that failed. The dead letter is
+    // removed if the handler succeeds, otherwise it is kept with the new error.
+    pub async fn replay(&self, id: &DeadLetterId) -> Result<()> {
+        let mut dead_letter = self.dead_letter_repo.find_by_id(id).await?;
+
+        let subscription = self
+            .handlers
+            .lock()
+            .await
+            .iter()
+            .find(|subscription| subscription.name == dead_letter.handler())
+            .cloned()
+            .ok_or_else(|| {
+                Error::new("dead_letter", "handler_not_found")
+                    .add_context("handler", dead_letter.handler())
+                    .build()
+            })?;
+
+        // The event could have been kept because it could not be upcasted.
+        let event = match self.upcasters.upcast(dead_letter.event()) {
+            Ok(event) => event,
+            Err(err) => {
+                dead_letter.failed_again(err.to_string(), 0);
+                self.dead_letter_repo.save(&dead_letter).await?;
+
+                return Err(Error::new("dead_letter", "replay_failed").wrap(err).build());
+            }
+        };
+
+        let mut handler = subscription.handler.lock().await;
+        let (attempts, res) = handle_with_retries(handler.as_mut(), &event).await;
+        match res {
+            Ok(_) => self.dead_letter_repo.delete(id).await,
+            Err(err) => {
+                dead_letter.failed_again(err.to_string(), attempts);
+                self.dead_letter_repo.save(&dead_letter).await?;
+
+                Err(Error::new("dead_letter", "replay_failed").wrap(err).build())
+            }
+        }
+    }
+}
+
+impl Default for InMemEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Executes the handler as many times as its retry policy allows, waiting between attempts.
+// Returns the number of attempts and the result of the last one.
+async fn handle_with_retries(handler: &mut dyn EventHandler, event: &Event) -> (u32, Result<bool>) {
+    let policy = handler.retry_policy();
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+
+        match handler.handle(event).await {
+            Ok(res) => return (attempts, Ok(res)),
+            Err(err) => {
+                if attempts > policy.max_retries() {
+                    return (attempts, Err(err));
+                }
+
+                time::delay_for(policy.backoff(attempts)).await;
+            }
         }
     }
 }
 
+// Handles the event with the subscribed handler, keeping a dead letter if it fails. Returns
+// whether the handler succeeded.
+async fn dispatch(
+    subscription: Arc<Subscription>,
+    event: Arc<Event>,
+    dead_letter_repo: Arc<dyn DeadLetterRepository>,
+) -> bool {
+    let mut handler = subscription.handler.lock().await;
+    let (attempts, res) = handle_with_retries(handler.as_mut(), &event).await;
+
+    if let Err(err) = res {
+        let err = Error::internal("event_publisher", "handler_error")
+            .wrap(err)
+            .build();
+        println!("{:?}", err);
+
+        keep_dead_letter(
+            &subscription,
+            &event,
+            &err,
+            attempts,
+            dead_letter_repo.as_ref(),
+        )
+        .await;
+
+        return false;
+    }
+
+    true
+}
+
+async fn keep_dead_letter(
+    subscription: &Subscription,
+    event: &Event,
+    err: &Error,
+    attempts: u32,
+    dead_letter_repo: &dyn DeadLetterRepository,
+) {
+    let dead_letter = DeadLetter::new(
+        event.clone(),
+        &subscription.name,
+        &err.to_string(),
+        attempts,
+    );
+    if let Err(err) = dead_letter_repo.save(&dead_letter).await {
+        println!("{:?}", err);
+    }
+}
+
 #[async_trait]
 impl EventPublisher for InMemEventBus {
     async fn publish(&self, event: Event) -> Result<Receiver<PublicationResult>> {
         self.publish_all(vec![event]).await
     }
 
+    // Events are handled in order. The handlers of each event run concurrently.
     async fn publish_all(&self, events: Vec<Event>) -> Result<Receiver<PublicationResult>> {
         let handlers = Arc::clone(&self.handlers);
+        let dead_letter_repo = Arc::clone(&self.dead_letter_repo);
+        let upcasters = Arc::clone(&self.upcasters);
+        let in_flight = Arc::clone(&self.in_flight);
         let (tx, rx) = oneshot::channel();
         let mut publication_result = PublicationResult::default();
 
         tokio::spawn(async move {
             for event in events.into_iter() {
-                let mut handlers = handlers.lock().await;
-                for handler in handlers.iter_mut() {
-                    match Regex::new(handler.topic()) {
-                        Ok(re) => {
-                            if re.is_match(event.topic()) {
-                                // Execute handler
-                                if let Err(err) = handler.handle(&event).await {
-                                    let err = Error::internal("event_publisher", "handler_error")
-                                        .wrap(err)
-                                        .build();
-                                    println!("{:?}", err);
-
-                                    publication_result.err_handlers += 1;
-                                } else {
-                                    publication_result.ok_handlers += 1;
-                                }
-                            }
+                let subscriptions: Vec<Arc<Subscription>> = handlers
+                    .lock()
+                    .await
+                    .iter()
+                    .filter(|subscription| subscription.topic.is_match(event.topic()))
+                    .cloned()
+                    .collect();
+
+                // An event that cannot be upcasted fails for every handler, and is kept as a
+                // dead letter for each of them to be replayed once it can.
+                let event = match upcasters.upcast(&event) {
+                    Ok(event) => Arc::new(event),
+                    Err(err) => {
+                        let err = Error::internal("event_publisher", "upcast_error")
+                            .wrap(err)
+                            .build();
+
+                        for subscription in subscriptions.iter() {
+                            keep_dead_letter(
+                                subscription,
+                                &event,
+                                &err,
+                                0,
+                                dead_letter_repo.as_ref(),
+                            )
+                            .await;
+                            publication_result.err_handlers += 1;
                         }
+
+                        continue;
+                    }
+                };
+
+                let _permit = Arc::clone(&in_flight).acquire_owned().await;
+
+                let mut dispatches = Vec::new();
+                for subscription in subscriptions.into_iter() {
+                    dispatches.push(tokio::spawn(dispatch(
+                        subscription,
+                        Arc::clone(&event),
+                        Arc::clone(&dead_letter_repo),
+                    )));
+                }
+
+                for dispatch in dispatches.into_iter() {
+                    match dispatch.await {
+                        Ok(true) => publication_result.ok_handlers += 1,
+                        Ok(false) => publication_result.err_handlers += 1,
                         Err(err) => {
-                            let err = Error::internal("event_publisher", "invalid_topic_regex")
+                            let err = Error::internal("event_publisher", "handler_panicked")
                                 .wrap_raw(err)
                                 .build();
                             println!("{:?}", err);
+
+                            publication_result.err_handlers += 1;
                         }
                     }
                 }
@@ -75,8 +268,19 @@ impl EventPublisher for InMemEventBus {
 #[async_trait]
 impl EventSubscriber for InMemEventBus {
     async fn subscribe(&self, handler: Box<dyn EventHandler>) -> Result<bool> {
+        let topic = Regex::new(handler.topic()).map_err(|err| {
+            Error::new("event_subscriber", "invalid_topic_regex")
+                .add_context("topic", handler.topic())
+                .wrap_raw(err)
+                .build()
+        })?;
+
         let mut handlers = self.handlers.lock().await;
-        handlers.push(handler);
+        handlers.push(Arc::new(Subscription {
+            name: handler.name().to_owned(),
+            topic,
+            handler: Mutex::new(handler),
+        }));
         Ok(true)
     }
 }
@@ -85,8 +289,11 @@ impl EventSubscriber for InMemEventBus {
 mod tests {
     use super::*;
 
+    use std::sync::atomic::{AtomicU32, Ordering};
     use std::sync::Arc;
+    use std::time::Duration;
 
+    use crate::event::RetryPolicy;
     use crate::mocks::Counter;
 
     fn create_event(topic: &str) -> Event {
@@ -147,6 +354,87 @@ mod tests {
         }
     }
 
+    // Fails until the given number of failures has been reached.
+    struct FlakyHandler {
+        failures: Arc<AtomicU32>,
+        calls: Arc<AtomicU32>,
+        max_retries: u32,
+    }
+
+    impl FlakyHandler {
+        fn new(failures: u32, max_retries: u32) -> Self {
+            FlakyHandler {
+                failures: Arc::new(AtomicU32::new(failures)),
+                calls: Arc::new(AtomicU32::new(0)),
+                max_retries,
+            }
+        }
+
+        fn clone(&self) -> Self {
+            FlakyHandler {
+                failures: Arc::clone(&self.failures),
+                calls: Arc::clone(&self.calls),
+                max_retries: self.max_retries,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EventHandler for FlakyHandler {
+        fn topic(&self) -> &str {
+            "flaky"
+        }
+
+        async fn handle(&mut self, _: &Event) -> Result<bool> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.failures.load(Ordering::SeqCst) > 0 {
+                self.failures.fetch_sub(1, Ordering::SeqCst);
+                return Err(Error::new("flaky_handler", "error"));
+            }
+            Ok(true)
+        }
+
+        fn retry_policy(&self) -> RetryPolicy {
+            RetryPolicy::new(self.max_retries, Duration::from_millis(1))
+        }
+    }
+
+    // Tracks how many handlers are running at the same time.
+    #[derive(Default)]
+    struct Gauge {
+        running: AtomicU32,
+        max: AtomicU32,
+    }
+
+    struct SlowHandler {
+        topic: String,
+        gauge: Arc<Gauge>,
+    }
+
+    impl SlowHandler {
+        fn new(topic: &str, gauge: &Arc<Gauge>) -> Self {
+            SlowHandler {
+                topic: topic.to_owned(),
+                gauge: Arc::clone(gauge),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EventHandler for SlowHandler {
+        fn topic(&self) -> &str {
+            &self.topic
+        }
+
+        async fn handle(&mut self, _: &Event) -> Result<bool> {
+            let running = self.gauge.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.gauge.max.fetch_max(running, Ordering::SeqCst);
+            time::delay_for(Duration::from_millis(100)).await;
+            self.gauge.running.fetch_sub(1, Ordering::SeqCst);
+            Ok(true)
+        }
+    }
+
     #[tokio::test]
     async fn create() {
         let eb = InMemEventBus::new();
@@ -405,4 +693,189 @@ mod tests {
         assert_eq!(res.ok_handlers(), 3);
         assert_eq!(res.err_handlers(), 1);
     }
+
+    #[tokio::test]
+    async fn retries() {
+        let eb = InMemEventBus::new();
+        let handler = FlakyHandler::new(2, 3);
+        eb.subscribe(Box::new(handler.clone())).await.unwrap();
+
+        let res = eb
+            .publish(create_event("flaky"))
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(res.ok_handlers(), 1);
+        assert_eq!(res.err_handlers(), 0);
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 3);
+        assert!(eb.dead_letter_repo().find_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn dead_letters() {
+        let eb = InMemEventBus::new();
+        let handler = FlakyHandler::new(4, 1);
+        eb.subscribe(Box::new(handler.clone())).await.unwrap();
+
+        let event = create_event("flaky");
+        let res = eb.publish(event.clone()).await.unwrap().await.unwrap();
+        assert_eq!(res.ok_handlers(), 0);
+        assert_eq!(res.err_handlers(), 1);
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 2);
+
+        let dead_letters = eb.dead_letter_repo().find_all().await.unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        let dead_letter = &dead_letters[0];
+        assert_eq!(dead_letter.event().id(), event.id());
+        assert_eq!(dead_letter.handler(), handler.name());
+        assert_eq!(dead_letter.attempts(), 2);
+
+        // Fails twice more
+        assert!(eb.replay(dead_letter.id()).await.is_err());
+        let failed = eb
+            .dead_letter_repo()
+            .find_by_id(dead_letter.id())
+            .await
+            .unwrap();
+        assert_eq!(failed.attempts(), 4);
+
+        eb.replay(dead_letter.id()).await.unwrap();
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 5);
+        assert!(eb.dead_letter_repo().find_all().await.unwrap().is_empty());
+        assert!(eb.replay(dead_letter.id()).await.is_err());
+    }
+
+    // Version and payload of each handled event.
+    type Payloads = Arc<std::sync::Mutex<Vec<(u32, Vec<u8>)>>>;
+
+    struct PayloadHandler {
+        payloads: Payloads,
+    }
+
+    #[async_trait]
+    impl EventHandler for PayloadHandler {
+        fn topic(&self) -> &str {
+            "versioned"
+        }
+
+        async fn handle(&mut self, event: &Event) -> Result<bool> {
+            self.payloads
+                .lock()
+                .unwrap()
+                .push((event.version(), event.payload().to_vec()));
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn upcasting() {
+        let mut registry = UpcasterRegistry::new();
+        registry.register("versioned", 1, |_| Ok(serde_json::json!({"v": 2})));
+
+        let mut eb = InMemEventBus::new();
+        eb.set_upcasters(registry);
+        let payloads = Arc::new(std::sync::Mutex::new(Vec::new()));
+        eb.subscribe(Box::new(PayloadHandler {
+            payloads: Arc::clone(&payloads),
+        }))
+        .await
+        .unwrap();
+
+        let mut current = Event::new("versioned", "code", b"{\"v\":3}".to_vec());
+        current.set_version(2);
+        let res = eb
+            .publish_all(vec![
+                Event::new("versioned", "code", b"{\"v\":1}".to_vec()),
+                current,
+                Event::new("versioned", "code", b"invalid".to_vec()),
+            ])
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(res.ok_handlers(), 2);
+        assert_eq!(res.err_handlers(), 1);
+
+        {
+            let payloads = payloads.lock().unwrap();
+            assert_eq!(payloads.len(), 2);
+            assert_eq!(payloads[0], (2, b"{\"v\":2}".to_vec()));
+            assert_eq!(payloads[1], (2, b"{\"v\":3}".to_vec()));
+        }
+
+        // The event that could not be upcasted is kept, and fails again when replayed.
+        let dead_letters = eb.dead_letter_repo().find_all().await.unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].event().payload(), b"invalid");
+        assert_eq!(dead_letters[0].attempts(), 0);
+        assert!(eb.replay(dead_letters[0].id()).await.is_err());
+        assert_eq!(payloads.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn invalid_topic() {
+        let eb = InMemEventBus::new();
+        assert!(eb
+            .subscribe(Box::new(BasicHandler::new("*")))
+            .await
+            .is_err());
+        assert!(eb
+            .subscribe(Box::new(BasicHandler::new("(ent")))
+            .await
+            .is_err());
+        assert_eq!(eb.handlers.lock().await.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn slow_handlers() {
+        let eb = InMemEventBus::new();
+        let gauge = Arc::new(Gauge::default());
+        eb.subscribe(Box::new(SlowHandler::new("slow", &gauge)))
+            .await
+            .unwrap();
+        let handler = BasicHandler::new("fast");
+        eb.subscribe(Box::new(handler.clone())).await.unwrap();
+
+        // A slow handler does not stall the other ones.
+        let slow = eb.publish(create_event("slow")).await.unwrap();
+        let res = time::timeout(
+            Duration::from_millis(50),
+            eb.publish(create_event("fast")).await.unwrap(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(res.ok_handlers(), 1);
+        assert_eq!(handler.counter().count("fast"), 1);
+        assert_eq!(slow.await.unwrap().ok_handlers(), 1);
+
+        // But it handles an event at a time.
+        let r1 = eb.publish(create_event("slow")).await.unwrap();
+        let r2 = eb.publish(create_event("slow")).await.unwrap();
+        r1.await.unwrap();
+        r2.await.unwrap();
+        assert_eq!(gauge.max.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn in_flight_events() {
+        for (max, concurrent) in &[(2, 2), (1, 1)] {
+            let gauge = Arc::new(Gauge::default());
+            let mut eb = InMemEventBus::new();
+            eb.set_max_in_flight_events(*max);
+            eb.subscribe(Box::new(SlowHandler::new("^one$", &gauge)))
+                .await
+                .unwrap();
+            eb.subscribe(Box::new(SlowHandler::new("^two$", &gauge)))
+                .await
+                .unwrap();
+
+            let r1 = eb.publish(create_event("one")).await.unwrap();
+            let r2 = eb.publish(create_event("two")).await.unwrap();
+            r1.await.unwrap();
+            r2.await.unwrap();
+            assert_eq!(gauge.max.load(Ordering::SeqCst), *concurrent);
+        }
+    }
 }
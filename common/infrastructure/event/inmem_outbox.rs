@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use crate::error::Error;
+use crate::event::{Event, EventId, Outbox};
+use crate::result::Result;
+use crate::transaction::Transaction;
+
+#[derive(Default)]
+struct Entries {
+    pending: Vec<Event>,
+    // Ids of every event ever added, published or not.
+    ids: HashSet<EventId>,
+}
+
+#[derive(Default)]
+pub struct InMemOutbox {
+    entries: Mutex<Entries>,
+}
+
+impl InMemOutbox {
+    pub fn new() -> Self {
+        InMemOutbox {
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    pub fn transaction(&self) -> InMemOutboxTransaction<'_> {
+        InMemOutboxTransaction {
+            outbox: self,
+            events: None,
+        }
+    }
+}
+
+#[async_trait]
+impl Outbox for InMemOutbox {
+    async fn add_all(&self, events: &[Event]) -> Result<()> {
+        let mut tx = self.transaction();
+
+        if let Some(err) = tx.begin() {
+            return Err(err);
+        }
+
+        for event in events.iter() {
+            tx.add(event.clone())?;
+        }
+
+        match tx.commit() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    async fn pending(&self) -> Result<Vec<Event>> {
+        Ok(self.entries.lock().unwrap().pending.clone())
+    }
+
+    async fn mark_as_published(&self, id: &EventId) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        let len = entries.pending.len();
+        entries.pending.retain(|event| event.id() != id);
+
+        if entries.pending.len() == len {
+            return Err(Error::new("outbox", "not_found"));
+        }
+
+        Ok(())
+    }
+}
+
+// Events added through a transaction become pending all together once it is committed.
+pub struct InMemOutboxTransaction<'a> {
+    outbox: &'a InMemOutbox,
+    events: Option<Vec<Event>>,
+}
+
+impl<'a> InMemOutboxTransaction<'a> {
+    pub fn add(&mut self, event: Event) -> Result<()> {
+        match &mut self.events {
+            Some(events) => {
+                events.push(event);
+                Ok(())
+            }
+            None => Err(Error::internal("outbox", "transaction_not_started")),
+        }
+    }
+}
+
+impl<'a> Transaction for InMemOutboxTransaction<'a> {
+    fn begin(&mut self) -> Option<Error> {
+        if self.events.is_some() {
+            return Some(Error::internal("outbox", "transaction_already_started"));
+        }
+
+        self.events = Some(Vec::new());
+        None
+    }
+
+    fn commit(&mut self) -> Option<Error> {
+        let events = match self.events.take() {
+            Some(events) => events,
+            None => return Some(Error::internal("outbox", "transaction_not_started")),
+        };
+
+        let mut entries = self.outbox.entries.lock().unwrap();
+        for event in events.into_iter() {
+            if entries.ids.insert(event.id().clone()) {
+                entries.pending.push(event);
+            }
+        }
+
+        None
+    }
+
+    fn rollback(&mut self) -> Option<Error> {
+        self.events = None;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn add_and_publish() {
+        let outbox = InMemOutbox::new();
+        let e1 = Event::new("topic", "code1", Vec::new());
+        let e2 = Event::new("topic", "code2", Vec::new());
+
+        let events = vec![e1.clone(), e2.clone()];
+        outbox.add_all(&events).await.unwrap();
+        outbox.add_all(&events[..1]).await.unwrap();
+
+        let pending = outbox.pending().await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].id(), e1.id());
+
+        outbox.mark_as_published(e1.id()).await.unwrap();
+        assert!(outbox.mark_as_published(e1.id()).await.is_err());
+
+        // Published events are not added again
+        outbox.add_all(&events[..1]).await.unwrap();
+        let pending = outbox.pending().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id(), e2.id());
+    }
+
+    #[tokio::test]
+    async fn transaction() {
+        let outbox = InMemOutbox::new();
+
+        let mut tx = outbox.transaction();
+        assert!(tx.add(Event::new("topic", "code", Vec::new())).is_err());
+        assert!(tx.begin().is_none());
+        assert!(tx.begin().is_some());
+        tx.add(Event::new("topic", "code", Vec::new())).unwrap();
+        assert!(tx.rollback().is_none());
+        assert!(tx.commit().is_some());
+        assert!(outbox.pending().await.unwrap().is_empty());
+
+        let mut tx = outbox.transaction();
+        assert!(tx.begin().is_none());
+        tx.add(Event::new("topic", "code", Vec::new())).unwrap();
+        tx.add(Event::new("topic", "code", Vec::new())).unwrap();
+        assert!(outbox.pending().await.unwrap().is_empty());
+        assert!(tx.commit().is_none());
+        assert_eq!(outbox.pending().await.unwrap().len(), 2);
+    }
+}
@@ -1,19 +1,19 @@
 use async_trait::async_trait;
+use tokio::sync::Mutex;
 
-use crate::cache::Cache;
-
+use crate::error::Error;
 use crate::event::{Event, EventId, EventRepository};
-use crate::infrastructure::cache::InMemCache;
 use crate::result::Result;
 
+#[derive(Default)]
 pub struct InMemEventRepository {
-    cache: InMemCache<EventId, Event>,
+    events: Mutex<Vec<Event>>,
 }
 
 impl InMemEventRepository {
     pub fn new() -> Self {
         InMemEventRepository {
-            cache: InMemCache::new(),
+            events: Mutex::new(Vec::new()),
         }
     }
 }
@@ -21,14 +21,58 @@ impl InMemEventRepository {
 #[async_trait]
 impl EventRepository for InMemEventRepository {
     async fn find_all(&self) -> Result<Vec<Event>> {
-        Ok(self.cache.all().await)
+        Ok(self.events.lock().await.clone())
     }
 
-    async fn find_from(&self, _id: &EventId) -> Result<Vec<Event>> {
-        Ok(self.cache.all().await)
+    async fn find_from(&self, id: &EventId) -> Result<Vec<Event>> {
+        let events = self.events.lock().await;
+        let index = events
+            .iter()
+            .position(|event| event.id() == id)
+            .ok_or(Error::new("event", "not_found"))?;
+
+        Ok(events[index + 1..].to_vec())
     }
 
     async fn save(&self, event: &Event) -> Result<()> {
-        self.cache.set(event.id().clone(), event.clone()).await
+        let mut events = self.events.lock().await;
+        if events.iter().any(|e| e.id() == event.id()) {
+            return Ok(());
+        }
+
+        events.push(event.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn find_from() {
+        let repo = InMemEventRepository::new();
+        let e1 = Event::new("topic", "code1", Vec::new());
+        let e2 = Event::new("topic", "code2", Vec::new());
+        let e3 = Event::new("topic", "code3", Vec::new());
+        repo.save(&e1).await.unwrap();
+        repo.save(&e2).await.unwrap();
+        repo.save(&e3).await.unwrap();
+        repo.save(&e2).await.unwrap();
+
+        let events = repo.find_all().await.unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1].code(), "code2");
+
+        let events = repo.find_from(e1.id()).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].code(), "code2");
+        assert_eq!(events[1].code(), "code3");
+
+        assert!(repo.find_from(e3.id()).await.unwrap().is_empty());
+        assert!(repo
+            .find_from(&EventId::new("unknown").unwrap())
+            .await
+            .is_err());
     }
 }
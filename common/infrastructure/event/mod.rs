@@ -1,4 +1,16 @@
+mod file_repository;
+mod inmem_dead_letter_repository;
 mod inmem_event_bus;
+mod inmem_outbox;
 mod inmem_repository;
+mod outbox_event_publisher;
+mod outbox_relay;
+mod projection_runner;
+pub use file_repository::*;
+pub use inmem_dead_letter_repository::*;
 pub use inmem_event_bus::*;
+pub use inmem_outbox::*;
 pub use inmem_repository::*;
+pub use outbox_event_publisher::*;
+pub use outbox_relay::*;
+pub use projection_runner::*;
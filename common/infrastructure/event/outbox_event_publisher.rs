@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::oneshot::{self, Receiver};
+
+use crate::event::{Event, EventPublisher, Outbox, PublicationResult};
+use crate::infrastructure::event::OutboxRelay;
+use crate::result::Result;
+
+// Adds the events to the outbox and relays them right away. Events can be already in the
+// outbox if they were stored together with their aggregate. The publication result includes
+// every pending event relayed, not only the given ones.
+pub struct OutboxEventPublisher {
+    outbox: Arc<dyn Outbox>,
+    relay: Arc<OutboxRelay>,
+}
+
+impl OutboxEventPublisher {
+    pub fn new(outbox: Arc<dyn Outbox>, relay: Arc<OutboxRelay>) -> Self {
+        OutboxEventPublisher { outbox, relay }
+    }
+}
+
+#[async_trait]
+impl EventPublisher for OutboxEventPublisher {
+    async fn publish(&self, event: Event) -> Result<Receiver<PublicationResult>> {
+        self.publish_all(vec![event]).await
+    }
+
+    async fn publish_all(&self, events: Vec<Event>) -> Result<Receiver<PublicationResult>> {
+        self.outbox.add_all(&events).await?;
+
+        let relay = Arc::clone(&self.relay);
+        let (tx, rx) = oneshot::channel();
+
+        tokio::spawn(async move {
+            match relay.relay().await {
+                Ok(publication_result) => if tx.send(publication_result).is_err() {},
+                Err(err) => println!("{:?}", err),
+            }
+        });
+
+        Ok(rx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::infrastructure::event::{InMemEventBus, InMemOutbox};
+
+    #[tokio::test]
+    async fn publish() {
+        let outbox = Arc::new(InMemOutbox::new());
+        let relay = Arc::new(OutboxRelay::new(
+            outbox.clone(),
+            Arc::new(InMemEventBus::new()),
+        ));
+        let event_pub = OutboxEventPublisher::new(outbox.clone(), relay);
+
+        let events = vec![
+            Event::new("topic", "code", Vec::new()),
+            Event::new("topic", "code", Vec::new()),
+        ];
+        outbox.add_all(&events[..1]).await.unwrap();
+
+        let res = event_pub.publish_all(events).await.unwrap().await.unwrap();
+        assert_eq!(res.published_events(), 2);
+        assert!(outbox.pending().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio::time;
+
+use crate::error::Error;
+use crate::event::{EventPublisher, Outbox, PublicationResult};
+use crate::result::Result;
+
+// Publishes the pending events of an outbox, marking each of them as published only after the
+// publisher has handled it. If the process stops in between, the event is published again.
+pub struct OutboxRelay {
+    outbox: Arc<dyn Outbox>,
+    event_pub: Arc<dyn EventPublisher>,
+    // Relays are not executed concurrently, otherwise the same events would be published twice.
+    running: Mutex<()>,
+}
+
+impl OutboxRelay {
+    pub fn new(outbox: Arc<dyn Outbox>, event_pub: Arc<dyn EventPublisher>) -> Self {
+        OutboxRelay {
+            outbox,
+            event_pub,
+            running: Mutex::new(()),
+        }
+    }
+
+    pub async fn relay(&self) -> Result<PublicationResult> {
+        let _running = self.running.lock().await;
+        let mut result = PublicationResult::default();
+
+        for event in self.outbox.pending().await?.into_iter() {
+            let id = event.id().clone();
+
+            let res = self.event_pub.publish(event).await?.await.map_err(|err| {
+                Error::internal("outbox_relay", "publication_result")
+                    .add_context("event_id", id.value())
+                    .wrap_raw(err)
+                    .build()
+            })?;
+
+            self.outbox.mark_as_published(&id).await?;
+
+            result.published_events += res.published_events();
+            result.ok_handlers += res.ok_handlers();
+            result.err_handlers += res.err_handlers();
+        }
+
+        Ok(result)
+    }
+
+    // Relays periodically the events that could not be published before, like the ones left
+    // by a previous execution.
+    pub fn start(self: Arc<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            loop {
+                if let Err(err) = self.relay().await {
+                    println!("{:?}", err);
+                }
+
+                time::delay_for(interval).await;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+
+    use crate::event::{Event, EventHandler, EventSubscriber};
+    use crate::infrastructure::event::{InMemEventBus, InMemOutbox};
+    use crate::mocks::Counter;
+
+    struct CounterHandler {
+        counter: Arc<Counter>,
+    }
+
+    #[async_trait]
+    impl EventHandler for CounterHandler {
+        fn topic(&self) -> &str {
+            ".*"
+        }
+
+        async fn handle(&mut self, event: &Event) -> Result<bool> {
+            self.counter.inc(event.code());
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn relay() {
+        let counter = Arc::new(Counter::new());
+        let event_bus = Arc::new(InMemEventBus::new());
+        event_bus
+            .subscribe(Box::new(CounterHandler {
+                counter: Arc::clone(&counter),
+            }))
+            .await
+            .unwrap();
+
+        let outbox = Arc::new(InMemOutbox::new());
+        let relay = OutboxRelay::new(outbox.clone(), event_bus);
+
+        outbox
+            .add_all(&[
+                Event::new("topic", "code1", Vec::new()),
+                Event::new("topic", "code2", Vec::new()),
+            ])
+            .await
+            .unwrap();
+
+        let res = relay.relay().await.unwrap();
+        assert_eq!(res.published_events(), 2);
+        assert_eq!(res.ok_handlers(), 2);
+        assert!(outbox.pending().await.unwrap().is_empty());
+        assert_eq!(counter.count("code1"), 1);
+
+        let res = relay.relay().await.unwrap();
+        assert_eq!(res.published_events(), 0);
+        assert_eq!(counter.count("code1"), 1);
+    }
+}
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use regex::Regex;
+use tokio::sync::Mutex;
+
+use crate::error::Error;
+use crate::event::{EventRepository, Projection, RebuildResult, UpcasterRegistry};
+use crate::result::Result;
+
+// Rebuilds projections replaying the stored events in the order they happened.
+pub struct ProjectionRunner {
+    event_repo: Arc<dyn EventRepository>,
+    upcasters: UpcasterRegistry,
+    // Only one projection is rebuilt at a time.
+    lock: Mutex<()>,
+}
+
+impl ProjectionRunner {
+    pub fn new(event_repo: Arc<dyn EventRepository>) -> Self {
+        ProjectionRunner {
+            event_repo,
+            upcasters: UpcasterRegistry::new(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn set_upcasters(&mut self, upcasters: UpcasterRegistry) {
+        self.upcasters = upcasters;
+    }
+
+    // Resets the projection and sends every stored event, sorted by timestamp, to the handlers
+    // of the projection whose topic matches. Failing handlers are counted and the rebuild goes
+    // on with the next event.
+    pub async fn rebuild(&self, projection: &dyn Projection) -> Result<RebuildResult> {
+        let _lock = self.lock.lock().await;
+
+        let mut handlers = Vec::new();
+        for handler in projection.handlers().into_iter() {
+            let re = Regex::new(handler.topic()).map_err(|err| {
+                Error::internal("projection", "invalid_topic_regex")
+                    .add_context("projection", projection.name())
+                    .add_context("handler", handler.name())
+                    .wrap_raw(err)
+                    .build()
+            })?;
+            handlers.push((re, handler));
+        }
+
+        let mut events = self.event_repo.find_all().await?;
+        // Stable, so events with the same timestamp keep the order they were stored in.
+        events.sort_by(|a, b| a.timestamp().cmp(b.timestamp()));
+
+        projection.reset().await?;
+
+        let mut result = RebuildResult::default();
+        for event in events.into_iter() {
+            let event = self.upcasters.upcast(&event)?;
+
+            for (re, handler) in handlers.iter_mut() {
+                if !re.is_match(event.topic()) {
+                    continue;
+                }
+
+                if let Err(err) = handler.handle(&event).await {
+                    let err = Error::internal("projection", "handler_error")
+                        .add_context("projection", projection.name())
+                        .add_context("event_id", event.id().value())
+                        .wrap(err)
+                        .build();
+                    println!("{:?}", err);
+
+                    result.err_handlers += 1;
+                } else {
+                    result.ok_handlers += 1;
+                }
+            }
+
+            result.events += 1;
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex as StdMutex;
+
+    use async_trait::async_trait;
+    use chrono::{Duration, Utc};
+
+    use crate::event::{Event, EventHandler, EventId};
+    use crate::infrastructure::event::InMemEventRepository;
+
+    type Codes = Arc<StdMutex<Vec<String>>>;
+
+    struct CodeHandler {
+        codes: Codes,
+    }
+
+    #[async_trait]
+    impl EventHandler for CodeHandler {
+        fn topic(&self) -> &str {
+            "publication|collection"
+        }
+
+        async fn handle(&mut self, event: &Event) -> Result<bool> {
+            if event.code() == "invalid" {
+                return Err(Error::new("code_handler", "invalid"));
+            }
+
+            self.codes.lock().unwrap().push(event.code().to_owned());
+            Ok(true)
+        }
+    }
+
+    // Keeps the codes of the handled events.
+    struct CodeProjection {
+        codes: Codes,
+    }
+
+    #[async_trait]
+    impl Projection for CodeProjection {
+        fn name(&self) -> &str {
+            "codes"
+        }
+
+        async fn reset(&self) -> Result<()> {
+            self.codes.lock().unwrap().clear();
+            Ok(())
+        }
+
+        fn handlers(&self) -> Vec<Box<dyn EventHandler>> {
+            vec![Box::new(CodeHandler {
+                codes: Arc::clone(&self.codes),
+            })]
+        }
+    }
+
+    fn event(topic: &str, code: &str, minutes: i64) -> Event {
+        Event::build(
+            EventId::new(format!("#{}.{}", topic, code)).unwrap(),
+            topic.to_owned(),
+            code.to_owned(),
+            1,
+            Utc::now() + Duration::minutes(minutes),
+            Vec::new(),
+        )
+    }
+
+    #[tokio::test]
+    async fn rebuild() {
+        let event_repo = Arc::new(InMemEventRepository::new());
+        event_repo
+            .save(&event("publication", "published", 2))
+            .await
+            .unwrap();
+        event_repo
+            .save(&event("publication", "created", 0))
+            .await
+            .unwrap();
+        event_repo
+            .save(&event("user", "registered", 1))
+            .await
+            .unwrap();
+        event_repo
+            .save(&event("collection", "invalid", 3))
+            .await
+            .unwrap();
+        event_repo
+            .save(&event("collection", "created", 4))
+            .await
+            .unwrap();
+
+        let runner = ProjectionRunner::new(event_repo);
+        let projection = CodeProjection {
+            codes: Arc::new(StdMutex::new(vec!["stale".to_owned()])),
+        };
+
+        let res = runner.rebuild(&projection).await.unwrap();
+        assert_eq!(res.events(), 5);
+        assert_eq!(res.ok_handlers(), 3);
+        assert_eq!(res.err_handlers(), 1);
+        assert_eq!(
+            *projection.codes.lock().unwrap(),
+            vec!["created", "published", "created"]
+        );
+
+        // Rebuilding again gives the same projection.
+        runner.rebuild(&projection).await.unwrap();
+        assert_eq!(projection.codes.lock().unwrap().len(), 3);
+    }
+}
@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
+use uuid::Uuid;
 
-use crate::event::{Event, ToEvent};
+use crate::error::Error;
+use crate::event::{Event, EventId, ToEvent};
 use crate::result::Result;
 
 #[derive(Debug)]
@@ -9,7 +11,13 @@ pub struct AggregateRoot<ID, E> {
     created_at: DateTime<Utc>,
     updated_at: Option<DateTime<Utc>>,
     deleted_at: Option<DateTime<Utc>>,
-    events: Vec<E>,
+    // Number of events recorded since the aggregate was created.
+    version: u64,
+    // Version of the aggregate when it was loaded or saved for the last time.
+    original_version: u64,
+    // Each recorded event keeps the id and date it got when it was recorded, so it is always
+    // converted to the same `Event`.
+    events: Vec<(EventId, DateTime<Utc>, E)>,
 }
 
 impl<ID, E> AggregateRoot<ID, E> {
@@ -19,6 +27,8 @@ impl<ID, E> AggregateRoot<ID, E> {
             created_at: Utc::now(),
             updated_at: None,
             deleted_at: None,
+            version: 0,
+            original_version: 0,
             events: Vec::new(),
         }
     }
@@ -28,12 +38,15 @@ impl<ID, E> AggregateRoot<ID, E> {
         created_at: DateTime<Utc>,
         updated_at: Option<DateTime<Utc>>,
         deleted_at: Option<DateTime<Utc>>,
+        version: u64,
     ) -> Self {
         AggregateRoot {
             id,
             created_at,
             updated_at,
             deleted_at,
+            version,
+            original_version: version,
             events: Vec::new(),
         }
     }
@@ -54,6 +67,39 @@ impl<ID, E> AggregateRoot<ID, E> {
         self.deleted_at.as_ref()
     }
 
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn original_version(&self) -> u64 {
+        self.original_version
+    }
+
+    // Checks that nobody saved the aggregate since it was loaded. The stored version is the
+    // version of the aggregate currently persisted, if any.
+    pub fn check_version(&self, stored_version: Option<u64>) -> Result<()> {
+        match stored_version {
+            Some(stored_version) if stored_version != self.original_version => {
+                Err(Error::conflict("aggregate_root")
+                    .add_context("version", &self.original_version.to_string())
+                    .add_context("stored_version", &stored_version.to_string())
+                    .build())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Must be called by repositories once the aggregate has been persisted.
+    pub fn commit_version(&mut self) {
+        self.original_version = self.version;
+    }
+
+    // Counts an event that was already persisted, like the ones replayed from an event stream.
+    pub fn replay_event(&mut self) {
+        self.version += 1;
+        self.original_version = self.version;
+    }
+
     pub fn update(&mut self) {
         self.updated_at = Some(Utc::now());
     }
@@ -68,13 +114,26 @@ where
     E: ToEvent,
 {
     pub fn record_event(&mut self, event: E) {
-        self.events.push(event);
+        self.version += 1;
+        self.events.push((
+            EventId::new(Uuid::new_v4().to_string()).unwrap(),
+            Utc::now(),
+            event,
+        ));
     }
 
     pub fn events(&self) -> Result<Vec<Event>> {
         let mut events = Vec::new();
-        for event in self.events.iter() {
-            events.push(event.to_event()?);
+        for (id, timestamp, event) in self.events.iter() {
+            let event = event.to_event()?;
+            events.push(Event::build(
+                id.clone(),
+                event.topic().to_owned(),
+                event.code().to_owned(),
+                event.version(),
+                *timestamp,
+                event.payload().to_vec(),
+            ));
         }
         Ok(events)
     }
@@ -93,6 +152,8 @@ impl<ID: Clone, E> Clone for AggregateRoot<ID, E> {
             created_at: self.created_at,
             updated_at: self.updated_at,
             deleted_at: self.deleted_at,
+            version: self.version,
+            original_version: self.version,
             events: Vec::new(),
         }
     }
@@ -195,5 +256,41 @@ mod tests {
         assert_eq!(events[0].payload(), "agg_root.created".as_bytes());
         assert_eq!(events[1].topic(), "agg_root.updated");
         assert_eq!(events[2].topic(), "agg_root.deleted");
+
+        let again = ag.base().events().unwrap();
+        assert_eq!(again[0].id(), events[0].id());
+        assert_eq!(again[2].timestamp(), events[2].timestamp());
+    }
+
+    #[test]
+    fn versions() {
+        let mut ag = AggRoot::new(AggRootID::from("AR_08"));
+        assert_eq!(ag.base().version(), 0);
+        assert!(ag.base().check_version(None).is_ok());
+
+        ag.base_mut().record_event(AggRootEvent::Updated { num: 1 });
+        ag.base_mut().record_event(AggRootEvent::Updated { num: 2 });
+        assert_eq!(ag.base().version(), 2);
+        assert_eq!(ag.base().original_version(), 0);
+        assert!(ag.base().check_version(Some(0)).is_ok());
+
+        let err = ag.base().check_version(Some(1)).unwrap_err();
+        assert_eq!(err.code(), "conflict");
+        assert_eq!(err.status(), Some(409));
+
+        ag.base_mut().commit_version();
+        assert!(ag.base().check_version(Some(2)).is_ok());
+        assert!(ag.base().check_version(Some(0)).is_err());
+
+        let cloned = ag.base().clone();
+        assert_eq!(cloned.version(), 2);
+        assert_eq!(cloned.original_version(), 2);
+
+        let mut built: AggregateRoot<AggRootID, AggRootEvent> =
+            AggregateRoot::build("AR_09".to_owned(), Utc::now(), None, None, 5);
+        assert_eq!(built.original_version(), 5);
+        built.replay_event();
+        assert_eq!(built.version(), 6);
+        assert_eq!(built.original_version(), 6);
     }
 }
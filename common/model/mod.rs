@@ -1,6 +1,8 @@
 mod aggregate_root;
 mod id;
+mod pagination;
 mod status_history;
 pub use aggregate_root::*;
 pub use id::*;
+pub use pagination::*;
 pub use status_history::*;
@@ -0,0 +1,343 @@
+use std::cmp::Ordering;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::result::Result;
+
+const MAX_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+// Query string of a paginated list: ?cursor=<next_cursor>&limit=20&sort=created_at&direction=desc
+#[derive(Debug, Default, Deserialize)]
+pub struct PaginationQuery {
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+    pub sort: Option<String>,
+    pub direction: Option<String>,
+}
+
+// Items are sorted by a field and then by id. The cursor encodes the sort key and the id of the
+// last item of the previous page (keyset), so the next page starts after it even if that item
+// was removed or other items were added before it. Without a limit all the items are returned.
+#[derive(Debug, Clone)]
+pub struct Pagination {
+    cursor: Option<String>,
+    limit: Option<usize>,
+    sort: String,
+    direction: SortDirection,
+}
+
+impl Pagination {
+    pub fn new<S: Into<String>>(
+        cursor: Option<String>,
+        limit: Option<usize>,
+        sort: S,
+        direction: SortDirection,
+    ) -> Result<Self> {
+        if let Some(limit) = limit {
+            if limit == 0 || limit > MAX_LIMIT {
+                return Err(Error::new("pagination", "invalid_limit")
+                    .add_context("limit", &limit.to_string())
+                    .add_context("max", &MAX_LIMIT.to_string())
+                    .build());
+            }
+        }
+
+        Ok(Pagination {
+            cursor,
+            limit,
+            sort: sort.into(),
+            direction,
+        })
+    }
+
+    // Items are sorted by the first of the supported fields if the query does not specify one.
+    pub fn from_query(query: PaginationQuery, sort_fields: &[&str]) -> Result<Self> {
+        let sort = match query.sort {
+            Some(sort) => {
+                if !sort_fields.contains(&sort.as_str()) {
+                    return Err(Error::new("pagination", "invalid_sort")
+                        .add_context("sort", &sort)
+                        .add_context("supported", &sort_fields.join(","))
+                        .build());
+                }
+                sort
+            }
+            None => sort_fields.first().copied().unwrap_or("id").to_owned(),
+        };
+
+        let direction = match query.direction.as_deref() {
+            None | Some("asc") => SortDirection::Asc,
+            Some("desc") => SortDirection::Desc,
+            Some(direction) => {
+                return Err(Error::new("pagination", "invalid_direction")
+                    .add_context("direction", direction)
+                    .build())
+            }
+        };
+
+        Self::new(query.cursor, query.limit, sort, direction)
+    }
+
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    // Sort key and id of the last item of the previous page.
+    pub fn cursor_key<K: DeserializeOwned>(&self) -> Result<Option<(K, String)>> {
+        let cursor = match &self.cursor {
+            Some(cursor) => cursor,
+            None => return Ok(None),
+        };
+
+        base64::decode_config(cursor, base64::URL_SAFE_NO_PAD)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .map(Some)
+            .ok_or_else(|| self.invalid_cursor())
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    pub fn sort(&self) -> &str {
+        &self.sort
+    }
+
+    pub fn direction(&self) -> SortDirection {
+        self.direction
+    }
+
+    pub fn unsupported_sort(&self) -> Error {
+        Error::new("pagination", "invalid_sort")
+            .add_context("sort", &self.sort)
+            .build()
+    }
+
+    pub fn invalid_cursor(&self) -> Error {
+        Error::new("pagination", "invalid_cursor")
+            .add_context("cursor", self.cursor.as_deref().unwrap_or(""))
+            .build()
+    }
+
+    // Sorts all the items by the given key and returns the page after the cursor.
+    pub fn paginate<T, FI, FK, K>(&self, mut items: Vec<T>, id: FI, key: FK) -> Result<Paginated<T>>
+    where
+        FI: Fn(&T) -> &str,
+        FK: Fn(&T) -> K,
+        K: Ord + Serialize + DeserializeOwned,
+    {
+        let total = items.len();
+
+        items.sort_by(|a, b| key(a).cmp(&key(b)).then_with(|| id(a).cmp(id(b))));
+        if self.direction == SortDirection::Desc {
+            items.reverse();
+        }
+
+        if let Some((cursor_key, cursor_id)) = self.cursor_key::<K>()? {
+            items.retain(|item| {
+                let ordering = key(item)
+                    .cmp(&cursor_key)
+                    .then_with(|| id(item).cmp(&cursor_id));
+                match self.direction {
+                    SortDirection::Asc => ordering == Ordering::Greater,
+                    SortDirection::Desc => ordering == Ordering::Less,
+                }
+            });
+        }
+
+        self.page(items, total, id, key)
+    }
+
+    // Builds the page from the sorted items following the cursor. One item more than the limit
+    // can be given to know if there is a next page.
+    pub fn page<T, FI, FK, K>(
+        &self,
+        mut items: Vec<T>,
+        total: usize,
+        id: FI,
+        key: FK,
+    ) -> Result<Paginated<T>>
+    where
+        FI: Fn(&T) -> &str,
+        FK: Fn(&T) -> K,
+        K: Serialize,
+    {
+        let mut next_cursor = None;
+        if let Some(limit) = self.limit {
+            if items.len() > limit {
+                items.truncate(limit);
+                if let Some(last) = items.last() {
+                    let cursor = serde_json::to_vec(&(key(last), id(last))).map_err(|err| {
+                        Error::internal("pagination", "cursor")
+                            .wrap_raw(err)
+                            .build()
+                    })?;
+                    next_cursor = Some(base64::encode_config(cursor, base64::URL_SAFE_NO_PAD));
+                }
+            }
+        }
+
+        Ok(Paginated::new(items, next_cursor, total))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Paginated<T> {
+    items: Vec<T>,
+    next_cursor: Option<String>,
+    total: usize,
+}
+
+impl<T> Paginated<T> {
+    pub fn new(items: Vec<T>, next_cursor: Option<String>, total: usize) -> Self {
+        Paginated {
+            items,
+            next_cursor,
+            total,
+        }
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.next_cursor.as_deref()
+    }
+
+    // Number of items in all the pages.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pagination(cursor: Option<&str>, limit: usize, direction: SortDirection) -> Pagination {
+        Pagination::new(cursor.map(str::to_owned), Some(limit), "value", direction).unwrap()
+    }
+
+    fn id(item: &(String, u32)) -> &str {
+        &item.0
+    }
+
+    fn key(item: &(String, u32)) -> u32 {
+        item.1
+    }
+
+    fn ids(page: &Paginated<(String, u32)>) -> Vec<&str> {
+        page.items().iter().map(|(id, _)| id.as_str()).collect()
+    }
+
+    #[test]
+    fn query() {
+        let pagination =
+            Pagination::from_query(PaginationQuery::default(), &["created_at", "name"]).unwrap();
+        assert!(pagination.cursor().is_none());
+        assert!(pagination.limit().is_none());
+        assert_eq!(pagination.sort(), "created_at");
+        assert_eq!(pagination.direction(), SortDirection::Asc);
+
+        let pagination = Pagination::from_query(
+            PaginationQuery {
+                cursor: Some("#id01".to_owned()),
+                limit: Some(5),
+                sort: Some("name".to_owned()),
+                direction: Some("desc".to_owned()),
+            },
+            &["created_at", "name"],
+        )
+        .unwrap();
+        assert_eq!(pagination.cursor(), Some("#id01"));
+        assert_eq!(pagination.limit(), Some(5));
+        assert_eq!(pagination.sort(), "name");
+        assert_eq!(pagination.direction(), SortDirection::Desc);
+
+        let invalid = vec![
+            PaginationQuery {
+                limit: Some(0),
+                ..PaginationQuery::default()
+            },
+            PaginationQuery {
+                limit: Some(MAX_LIMIT + 1),
+                ..PaginationQuery::default()
+            },
+            PaginationQuery {
+                sort: Some("password".to_owned()),
+                ..PaginationQuery::default()
+            },
+            PaginationQuery {
+                direction: Some("up".to_owned()),
+                ..PaginationQuery::default()
+            },
+        ];
+        for query in invalid.into_iter() {
+            assert!(Pagination::from_query(query, &["created_at"]).is_err());
+        }
+    }
+
+    #[test]
+    fn paginate() {
+        let mut items: Vec<(String, u32)> = vec![
+            ("#id03".to_owned(), 1),
+            ("#id01".to_owned(), 2),
+            ("#id02".to_owned(), 1),
+            ("#id04".to_owned(), 3),
+            ("#id05".to_owned(), 2),
+        ];
+
+        let page = pagination(None, 2, SortDirection::Asc)
+            .paginate(items.clone(), id, key)
+            .unwrap();
+        assert_eq!(ids(&page), vec!["#id02", "#id03"]);
+        assert!(page.next_cursor().is_some());
+        assert_eq!(page.total(), 5);
+
+        let page = pagination(page.next_cursor(), 2, SortDirection::Asc)
+            .paginate(items.clone(), id, key)
+            .unwrap();
+        assert_eq!(ids(&page), vec!["#id01", "#id05"]);
+        let cursor = page.next_cursor().unwrap().to_owned();
+
+        let page = pagination(Some(&cursor), 2, SortDirection::Asc)
+            .paginate(items.clone(), id, key)
+            .unwrap();
+        assert_eq!(ids(&page), vec!["#id04"]);
+        assert!(page.next_cursor().is_none());
+
+        let page = pagination(None, 3, SortDirection::Desc)
+            .paginate(items.clone(), id, key)
+            .unwrap();
+        assert_eq!(ids(&page), vec!["#id04", "#id05", "#id01"]);
+        assert!(page.next_cursor().is_some());
+
+        // The item of the cursor was removed.
+        items.retain(|(id, _)| id != "#id05");
+        let page = pagination(Some(&cursor), 2, SortDirection::Asc)
+            .paginate(items.clone(), id, key)
+            .unwrap();
+        assert_eq!(ids(&page), vec!["#id04"]);
+
+        let page = Pagination::new(None, None, "value", SortDirection::Asc)
+            .unwrap()
+            .paginate(items.clone(), id, key)
+            .unwrap();
+        assert_eq!(page.items().len(), 4);
+        assert!(page.next_cursor().is_none());
+
+        assert!(pagination(Some("#id05"), 2, SortDirection::Asc)
+            .paginate(items, id, key)
+            .is_err());
+    }
+}
@@ -1,5 +1,8 @@
 use chrono::{DateTime, Utc};
 
+use crate::error::Error;
+use crate::result::Result;
+
 #[derive(Debug, Clone)]
 pub struct StatusItem<S> {
     date: DateTime<Utc>,
@@ -14,6 +17,10 @@ impl<S> StatusItem<S> {
         }
     }
 
+    pub fn build(status: S, date: DateTime<Utc>) -> Self {
+        StatusItem { date, status }
+    }
+
     pub fn date(&self) -> &DateTime<Utc> {
         &self.date
     }
@@ -35,10 +42,22 @@ impl<S> StatusHistory<S> {
         }
     }
 
+    pub fn build(history: Vec<StatusItem<S>>) -> Result<Self> {
+        if history.is_empty() {
+            return Err(Error::new("status_history", "empty"));
+        }
+
+        Ok(StatusHistory { history })
+    }
+
     pub fn add_status(&mut self, status: S) {
         self.history.push(StatusItem::new(status));
     }
 
+    pub fn add_item(&mut self, item: StatusItem<S>) {
+        self.history.push(item);
+    }
+
     pub fn history(&self) -> &[StatusItem<S>] {
         &self.history
     }
@@ -48,6 +67,11 @@ impl<S> StatusHistory<S> {
         self.history.last().unwrap()
     }
 
+    // Last status set before the given date, if any.
+    pub fn at(&self, date: &DateTime<Utc>) -> Option<&StatusItem<S>> {
+        self.history.iter().rev().find(|item| item.date() < date)
+    }
+
     pub fn is_current<P>(&self, predicate: P) -> bool
     where
         P: Fn(&S) -> bool,
@@ -84,6 +108,21 @@ mod tests {
         assert_eq!(sh.current().status(), &Status::Open);
     }
 
+    #[test]
+    fn build() {
+        assert!(StatusHistory::<Status>::build(Vec::new()).is_err());
+
+        let date = Utc::now();
+        let sh = StatusHistory::build(vec![
+            StatusItem::build(Status::Init, date),
+            StatusItem::build(Status::Open, date),
+        ])
+        .unwrap();
+        assert_eq!(sh.history().len(), 2);
+        assert_eq!(sh.current().status(), &Status::Open);
+        assert_eq!(sh.current().date(), &date);
+    }
+
     #[test]
     fn history() {
         let mut sh = StatusHistory::new(Status::Init);
@@ -94,6 +133,26 @@ mod tests {
         assert_eq!(sh.current().status(), &Status::Closed);
     }
 
+    #[test]
+    fn at() {
+        let date = Utc::now();
+        let sh = StatusHistory::build(vec![
+            StatusItem::build(Status::Init, date),
+            StatusItem::build(Status::Open, date + chrono::Duration::days(1)),
+            StatusItem::build(Status::Closed, date + chrono::Duration::days(2)),
+        ])
+        .unwrap();
+        assert!(sh.at(&date).is_none());
+        assert_eq!(
+            sh.at(&(date + chrono::Duration::hours(36))).unwrap().status(),
+            &Status::Open
+        );
+        assert_eq!(
+            sh.at(&(date + chrono::Duration::days(3))).unwrap().status(),
+            &Status::Closed
+        );
+    }
+
     #[test]
     fn compare() {
         let mut sh = StatusHistory::new(Status::Init);
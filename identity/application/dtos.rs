@@ -1,6 +1,7 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::domain::role::Role;
+use crate::domain::role::{Permission, Role};
+use crate::domain::token::{PersonalAccessToken, Session};
 use crate::domain::user::User;
 
 #[derive(Serialize)]
@@ -11,6 +12,7 @@ pub struct UserDto {
     pub name: Option<String>,
     pub lastname: Option<String>,
     pub validated: bool,
+    pub two_factor: bool,
     pub role: String,
 }
 
@@ -23,15 +25,34 @@ impl From<&User> for UserDto {
             name: user.person().map(|p| p.fullname().name().to_string()),
             lastname: user.person().map(|p| p.fullname().lastname().to_string()),
             validated: user.is_validated(),
+            two_factor: user.has_two_factor(),
             role: user.role().base().id().to_string(),
         }
     }
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct PermissionDto {
+    pub module: String,
+    // CRUD letters
+    pub permissions: String,
+}
+
+impl From<&Permission> for PermissionDto {
+    fn from(permission: &Permission) -> Self {
+        PermissionDto {
+            module: permission.module().to_string(),
+            permissions: permission.permissions().to_string(),
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct RoleDto {
     pub id: String,
     pub name: String,
+    pub permissions: Vec<PermissionDto>,
+    pub two_factor_required: bool,
 }
 
 impl From<&Role> for RoleDto {
@@ -39,6 +60,52 @@ impl From<&Role> for RoleDto {
         RoleDto {
             id: role.base().id().to_string(),
             name: role.name().to_string(),
+            permissions: role.permissions().iter().map(PermissionDto::from).collect(),
+            two_factor_required: role.two_factor_required(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SessionDto {
+    pub id: String,
+    pub device: String,
+    pub created_at: String,
+    pub last_used_at: String,
+}
+
+impl From<&Session> for SessionDto {
+    fn from(session: &Session) -> Self {
+        SessionDto {
+            id: session.id().to_string(),
+            device: session.device().to_owned(),
+            created_at: session.created_at().to_rfc3339(),
+            last_used_at: session.last_used_at().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PersonalAccessTokenDto {
+    pub id: String,
+    pub name: String,
+    pub scopes: Vec<PermissionDto>,
+    pub created_at: String,
+    pub expires_at: Option<String>,
+    pub last_used_at: Option<String>,
+}
+
+impl From<&PersonalAccessToken> for PersonalAccessTokenDto {
+    fn from(token: &PersonalAccessToken) -> Self {
+        PersonalAccessTokenDto {
+            id: token.id().to_string(),
+            name: token.name().to_owned(),
+            scopes: token.scopes().iter().map(PermissionDto::from).collect(),
+            created_at: token.created_at().to_rfc3339(),
+            expires_at: token.expires_at().map(|expires_at| expires_at.to_rfc3339()),
+            last_used_at: token
+                .last_used_at()
+                .map(|last_used_at| last_used_at.to_rfc3339()),
         }
     }
 }
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::application::dtos::PermissionDto;
+use crate::domain::role::{Permission, Role, RoleId, RoleRepository};
+use crate::domain::user::{UserId, UserRepository};
+
+#[derive(Deserialize)]
+pub struct CreateCommand {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub permissions: Vec<PermissionDto>,
+}
+
+#[derive(Serialize)]
+pub struct CreateResponse {
+    pub id: String,
+}
+
+pub struct Create<'a> {
+    role_repo: &'a dyn RoleRepository,
+    user_repo: &'a dyn UserRepository,
+}
+
+impl<'a> Create<'a> {
+    pub fn new(role_repo: &'a dyn RoleRepository, user_repo: &'a dyn UserRepository) -> Self {
+        Create {
+            role_repo,
+            user_repo,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, cmd: CreateCommand) -> Result<CreateResponse> {
+        let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
+        if !auth_user.role().has_permissions("roles", "C") {
+            return Err(Error::unauthorized());
+        }
+
+        let role_id = RoleId::new(cmd.id)?;
+        if self.role_repo.find_by_id(&role_id).await.is_ok() {
+            return Err(Error::new("role", "already_exists"));
+        }
+
+        let permissions = cmd
+            .permissions
+            .into_iter()
+            .map(|p| Permission::new(p.module, p.permissions))
+            .collect::<Result<Vec<Permission>>>()?;
+
+        let mut role = Role::new(role_id, cmd.name)?;
+        role.set_permissions(permissions);
+
+        self.role_repo.save(&mut role).await?;
+
+        Ok(CreateResponse {
+            id: role.base().id().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    fn cmd(permissions: &str) -> CreateCommand {
+        CreateCommand {
+            id: "moderator".to_owned(),
+            name: "Moderator".to_owned(),
+            permissions: vec![PermissionDto {
+                module: "users".to_owned(),
+                permissions: permissions.to_owned(),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn create() {
+        let c = mocks::container();
+        let uc = Create::new(c.role_repo(), c.user_repo());
+
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let mut admin = mocks::admin1();
+        c.user_repo().save(&mut admin).await.unwrap();
+
+        assert!(uc
+            .exec(user.base().id().to_string(), cmd("RU"))
+            .await
+            .is_err());
+        assert!(uc
+            .exec(admin.base().id().to_string(), cmd("RUX"))
+            .await
+            .is_err());
+
+        let res = uc
+            .exec(admin.base().id().to_string(), cmd("ru"))
+            .await
+            .unwrap();
+        assert_eq!(res.id, "moderator");
+
+        let role = c
+            .role_repo()
+            .find_by_id(&RoleId::new("moderator").unwrap())
+            .await
+            .unwrap();
+        assert_eq!(role.name(), "Moderator");
+        assert!(role.has_permissions("users", "RU"));
+
+        assert!(uc
+            .exec(admin.base().id().to_string(), cmd("R"))
+            .await
+            .is_err());
+    }
+}
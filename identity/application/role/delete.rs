@@ -0,0 +1,89 @@
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::role::{RoleId, RoleRepository};
+use crate::domain::user::{UserId, UserRepository};
+
+pub struct Delete<'a> {
+    role_repo: &'a dyn RoleRepository,
+    user_repo: &'a dyn UserRepository,
+}
+
+impl<'a> Delete<'a> {
+    pub fn new(role_repo: &'a dyn RoleRepository, user_repo: &'a dyn UserRepository) -> Self {
+        Delete {
+            role_repo,
+            user_repo,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, role_id: String) -> Result<()> {
+        let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
+        if !auth_user.role().has_permissions("roles", "D") {
+            return Err(Error::unauthorized());
+        }
+
+        let mut role = self.role_repo.find_by_id(&RoleId::new(role_id)?).await?;
+
+        let users = self.user_repo.find_by_role(role.base().id()).await?;
+        if users.iter().any(|user| user.base().deleted_at().is_none()) {
+            return Err(Error::new("role", "in_use")
+                .set_message("The role is assigned to users")
+                .build());
+        }
+
+        role.delete()?;
+
+        self.role_repo.save(&mut role).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::role::Role;
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn delete() {
+        let c = mocks::container();
+        let uc = Delete::new(c.role_repo(), c.user_repo());
+
+        let mut role = Role::new(RoleId::new("moderator").unwrap(), "Moderator").unwrap();
+        c.role_repo().save(&mut role).await.unwrap();
+        let mut user_role = mocks::user_role();
+        c.role_repo().save(&mut user_role).await.unwrap();
+        let mut admin = mocks::admin1();
+        c.user_repo().save(&mut admin).await.unwrap();
+
+        let mut user = mocks::validated_user1();
+        user.change_role(role.clone(), &admin).unwrap();
+        c.user_repo().save(&mut user).await.unwrap();
+
+        assert!(uc
+            .exec(user.base().id().to_string(), "moderator".to_owned())
+            .await
+            .is_err());
+        // In use
+        assert!(uc
+            .exec(admin.base().id().to_string(), "moderator".to_owned())
+            .await
+            .is_err());
+        // Default
+        assert!(uc
+            .exec(admin.base().id().to_string(), "user".to_owned())
+            .await
+            .is_err());
+
+        user.delete().unwrap();
+        c.user_repo().save(&mut user).await.unwrap();
+
+        uc.exec(admin.base().id().to_string(), "moderator".to_owned())
+            .await
+            .unwrap();
+        assert!(c.role_repo().find_by_id(role.base().id()).await.is_err());
+    }
+}
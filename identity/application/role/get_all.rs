@@ -27,7 +27,7 @@ impl<'a> GetAll<'a> {
 
     pub async fn exec(&self, auth_id: String) -> Result<GetAllResponse> {
         let admin = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
-        if !admin.role().is("admin") {
+        if !admin.role().has_permissions("roles", "R") {
             return Err(Error::unauthorized());
         }
 
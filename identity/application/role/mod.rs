@@ -1,2 +1,12 @@
+mod create;
+mod delete;
 mod get_all;
+mod get_by_id;
+mod require_two_factor;
+mod update;
+pub use create::*;
+pub use delete::*;
 pub use get_all::*;
+pub use get_by_id::*;
+pub use require_two_factor::*;
+pub use update::*;
@@ -0,0 +1,86 @@
+use serde::Deserialize;
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::role::{RoleId, RoleRepository};
+use crate::domain::user::{UserId, UserRepository};
+
+#[derive(Deserialize)]
+pub struct RequireTwoFactorCommand {
+    pub required: bool,
+}
+
+pub struct RequireTwoFactor<'a> {
+    role_repo: &'a dyn RoleRepository,
+    user_repo: &'a dyn UserRepository,
+}
+
+impl<'a> RequireTwoFactor<'a> {
+    pub fn new(role_repo: &'a dyn RoleRepository, user_repo: &'a dyn UserRepository) -> Self {
+        RequireTwoFactor {
+            role_repo,
+            user_repo,
+        }
+    }
+
+    pub async fn exec(
+        &self,
+        auth_id: String,
+        role_id: String,
+        cmd: RequireTwoFactorCommand,
+    ) -> Result<()> {
+        let admin = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
+        if !admin.role().has_permissions("roles", "U") {
+            return Err(Error::unauthorized());
+        }
+
+        let mut role = self.role_repo.find_by_id(&RoleId::new(role_id)?).await?;
+        role.set_two_factor_required(cmd.required);
+
+        self.role_repo.save(&mut role).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn require() {
+        let c = mocks::container();
+        let uc = RequireTwoFactor::new(c.role_repo(), c.user_repo());
+
+        let mut role = mocks::user_role();
+        c.role_repo().save(&mut role).await.unwrap();
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let mut admin = mocks::admin1();
+        c.user_repo().save(&mut admin).await.unwrap();
+
+        assert!(uc
+            .exec(
+                user.base().id().to_string(),
+                "user".to_owned(),
+                RequireTwoFactorCommand { required: true },
+            )
+            .await
+            .is_err());
+
+        uc.exec(
+            admin.base().id().to_string(),
+            "user".to_owned(),
+            RequireTwoFactorCommand { required: true },
+        )
+        .await
+        .unwrap();
+
+        let role = c.role_repo().find_by_id(role.base().id()).await.unwrap();
+        assert!(role.two_factor_required());
+        assert!(c.authentication_serv().two_factor_required(&user).await);
+    }
+}
@@ -0,0 +1,161 @@
+use serde::Deserialize;
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::application::dtos::PermissionDto;
+use crate::domain::role::{Permission, RoleId, RoleRepository};
+use crate::domain::user::{UserId, UserRepository};
+
+#[derive(Deserialize)]
+pub struct UpdateCommand {
+    pub name: String,
+    pub permissions: Vec<PermissionDto>,
+}
+
+pub struct Update<'a> {
+    role_repo: &'a dyn RoleRepository,
+    user_repo: &'a dyn UserRepository,
+}
+
+impl<'a> Update<'a> {
+    pub fn new(role_repo: &'a dyn RoleRepository, user_repo: &'a dyn UserRepository) -> Self {
+        Update {
+            role_repo,
+            user_repo,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, role_id: String, cmd: UpdateCommand) -> Result<()> {
+        let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
+        if !auth_user.role().has_permissions("roles", "U") {
+            return Err(Error::unauthorized());
+        }
+
+        let mut role = self.role_repo.find_by_id(&RoleId::new(role_id)?).await?;
+
+        let permissions = cmd
+            .permissions
+            .into_iter()
+            .map(|p| Permission::new(p.module, p.permissions))
+            .collect::<Result<Vec<Permission>>>()?;
+
+        role.set_name(cmd.name)?;
+        let could_manage_roles = role.has_permissions("roles", "U");
+        role.set_permissions(permissions);
+
+        // Some active user with another role has to be able to manage roles, otherwise nobody
+        // could give the permission back.
+        if could_manage_roles && !role.has_permissions("roles", "U") {
+            let users = self.user_repo.find_all().await?;
+            if !users.iter().any(|user| {
+                user.is_active()
+                    && user.role().base().id() != role.base().id()
+                    && user.role().has_permissions("roles", "U")
+            }) {
+                return Err(Error::new("role", "last_role_admin")
+                    .set_status(409)
+                    .set_message("No other active user would be able to manage roles")
+                    .build());
+            }
+        }
+
+        self.role_repo.save(&mut role).await?;
+
+        // Users keep a copy of their role.
+        for mut user in self.user_repo.find_by_role(role.base().id()).await? {
+            user.refresh_role(role.clone())?;
+            self.user_repo.save(&mut user).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::role::Role;
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn update() {
+        let c = mocks::container();
+        let uc = Update::new(c.role_repo(), c.user_repo());
+
+        let mut role = mocks::user_role();
+        c.role_repo().save(&mut role).await.unwrap();
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let mut admin = mocks::admin1();
+        c.user_repo().save(&mut admin).await.unwrap();
+
+        let cmd = || UpdateCommand {
+            name: "Moderator".to_owned(),
+            permissions: vec![PermissionDto {
+                module: "users".to_owned(),
+                permissions: "R".to_owned(),
+            }],
+        };
+
+        assert!(uc
+            .exec(user.base().id().to_string(), "user".to_owned(), cmd())
+            .await
+            .is_err());
+
+        uc.exec(admin.base().id().to_string(), "user".to_owned(), cmd())
+            .await
+            .unwrap();
+
+        let role = c.role_repo().find_by_id(role.base().id()).await.unwrap();
+        assert_eq!(role.name(), "Moderator");
+        assert!(role.has_permissions("users", "R"));
+
+        // The user can read other users now.
+        let user = c.user_repo().find_by_id(user.base().id()).await.unwrap();
+        assert!(user.role().has_permissions("users", "R"));
+    }
+
+    #[tokio::test]
+    async fn last_role_admin() {
+        let c = mocks::container();
+        let uc = Update::new(c.role_repo(), c.user_repo());
+
+        let mut role = mocks::admin_role();
+        c.role_repo().save(&mut role).await.unwrap();
+        let mut admin = mocks::admin1();
+        admin.validate().unwrap();
+        c.user_repo().save(&mut admin).await.unwrap();
+
+        let cmd = || UpdateCommand {
+            name: "Administrator".to_owned(),
+            permissions: vec![PermissionDto {
+                module: "users".to_owned(),
+                permissions: "CRUD".to_owned(),
+            }],
+        };
+
+        let err = uc
+            .exec(admin.base().id().to_string(), "admin".to_owned(), cmd())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "last_role_admin");
+        let role = c.role_repo().find_by_id(role.base().id()).await.unwrap();
+        assert!(role.has_permissions("roles", "U"));
+
+        // Another user can manage roles.
+        let mut moderator_role = Role::new(RoleId::new("moderator").unwrap(), "Moderator").unwrap();
+        moderator_role.add_permissions(Permission::new("roles", "RU").unwrap());
+        c.role_repo().save(&mut moderator_role).await.unwrap();
+        let mut moderator = mocks::validated_user1();
+        moderator.change_role(moderator_role, &admin).unwrap();
+        c.user_repo().save(&mut moderator).await.unwrap();
+
+        uc.exec(admin.base().id().to_string(), "admin".to_owned(), cmd())
+            .await
+            .unwrap();
+        let admin = c.user_repo().find_by_id(admin.base().id()).await.unwrap();
+        assert!(!admin.role().has_permissions("roles", "U"));
+    }
+}
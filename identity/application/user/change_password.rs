@@ -3,6 +3,7 @@ use serde::Deserialize;
 use common::error::Error;
 use common::result::Result;
 
+use crate::domain::token::TokenService;
 use crate::domain::user::{UserId, UserRepository, UserService};
 
 #[derive(Deserialize)]
@@ -15,13 +16,19 @@ pub struct ChangePassword<'a> {
     user_repo: &'a dyn UserRepository,
 
     user_serv: &'a UserService,
+    token_serv: &'a TokenService,
 }
 
 impl<'a> ChangePassword<'a> {
-    pub fn new(user_repo: &'a dyn UserRepository, user_serv: &'a UserService) -> Self {
+    pub fn new(
+        user_repo: &'a dyn UserRepository,
+        user_serv: &'a UserService,
+        token_serv: &'a TokenService,
+    ) -> Self {
         ChangePassword {
             user_repo,
             user_serv,
+            token_serv,
         }
     }
 
@@ -33,14 +40,18 @@ impl<'a> ChangePassword<'a> {
     ) -> Result<()> {
         if auth_id != user_id {
             let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
-            if !auth_user.role().is("admin") {
+            if !auth_user.role().has_permissions("users", "U") {
                 return Err(Error::unauthorized());
             }
         }
 
+        let user_id = UserId::new(user_id)?;
         self.user_serv
-            .change_password(&UserId::new(user_id)?, &cmd.old_password, &cmd.new_password)
-            .await
+            .change_password(&user_id, &cmd.old_password, &cmd.new_password)
+            .await?;
+
+        // Sessions opened with the old password are closed.
+        self.token_serv.revoke_all(&user_id).await
     }
 }
 
@@ -53,11 +64,16 @@ mod tests {
     #[tokio::test]
     async fn success() {
         let c = mocks::container();
-        let uc = ChangePassword::new(c.user_repo(), c.user_serv());
+        let uc = ChangePassword::new(c.user_repo(), c.user_serv(), c.token_serv());
 
         let mut user = mocks::user1();
         let old_password = user.identity().password().unwrap().to_string();
         c.user_repo().save(&mut user).await.unwrap();
+        let tokens = c
+            .token_serv()
+            .create_session(user.base().id(), "Firefox")
+            .await
+            .unwrap();
 
         assert!(uc
             .exec(
@@ -73,12 +89,17 @@ mod tests {
 
         let user = c.user_repo().find_by_id(&user.base().id()).await.unwrap();
         assert_ne!(user.identity().password().unwrap().value(), old_password);
+        assert!(c
+            .token_serv()
+            .validate(tokens.access_token())
+            .await
+            .is_err());
     }
 
     #[tokio::test]
     async fn invalid_password() {
         let c = mocks::container();
-        let uc = ChangePassword::new(c.user_repo(), c.user_serv());
+        let uc = ChangePassword::new(c.user_repo(), c.user_serv(), c.token_serv());
 
         let mut user = mocks::user1();
         c.user_repo().save(&mut user).await.unwrap();
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use common::error::Error;
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::domain::user::{UserId, UserRepository};
+
+#[derive(Deserialize)]
+pub struct ConfirmTwoFactorCommand {
+    pub code: String,
+}
+
+#[derive(Serialize)]
+pub struct ConfirmTwoFactorResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+pub struct ConfirmTwoFactor<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    user_repo: &'a dyn UserRepository,
+}
+
+impl<'a> ConfirmTwoFactor<'a> {
+    pub fn new(event_pub: &'a dyn EventPublisher, user_repo: &'a dyn UserRepository) -> Self {
+        ConfirmTwoFactor {
+            event_pub,
+            user_repo,
+        }
+    }
+
+    pub async fn exec(
+        &self,
+        auth_id: String,
+        user_id: String,
+        cmd: ConfirmTwoFactorCommand,
+    ) -> Result<ConfirmTwoFactorResponse> {
+        if auth_id != user_id {
+            return Err(Error::unauthorized());
+        }
+
+        let mut user = self.user_repo.find_by_id(&UserId::new(user_id)?).await?;
+        let recovery_codes = user.confirm_two_factor(&cmd.code)?;
+
+        self.user_repo.save(&mut user).await?;
+
+        self.event_pub.publish_all(user.base().events()?).await?;
+
+        Ok(ConfirmTwoFactorResponse { recovery_codes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use crate::application::user::EnableTwoFactor;
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn confirm() {
+        let c = mocks::container();
+        let uc = ConfirmTwoFactor::new(c.event_pub(), c.user_repo());
+
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let user_id = user.base().id().to_string();
+
+        assert!(uc
+            .exec(
+                user_id.clone(),
+                user_id.clone(),
+                ConfirmTwoFactorCommand {
+                    code: "123456".to_owned(),
+                },
+            )
+            .await
+            .is_err());
+
+        EnableTwoFactor::new(c.user_repo())
+            .exec(user_id.clone(), user_id.clone())
+            .await
+            .unwrap();
+        let user = c.user_repo().find_by_id(user.base().id()).await.unwrap();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let code = user.two_factor().unwrap().code_at(now).unwrap();
+
+        let res = uc
+            .exec(
+                user_id.clone(),
+                user_id.clone(),
+                ConfirmTwoFactorCommand { code },
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.recovery_codes.len(), 10);
+
+        let user = c.user_repo().find_by_id(user.base().id()).await.unwrap();
+        assert!(user.has_two_factor());
+        assert_eq!(c.event_pub().events().await.len(), 1);
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::application::dtos::PermissionDto;
+use crate::domain::role::Permission;
+use crate::domain::token::TokenService;
+use crate::domain::user::{Authorization, UserId, UserRepository};
+
+const MAX_EXPIRATION_DAYS: i64 = 365;
+
+#[derive(Deserialize)]
+pub struct CreatePersonalAccessTokenCommand {
+    pub name: String,
+    pub scopes: Vec<PermissionDto>,
+    // It does not expire if not present.
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct CreatePersonalAccessTokenResponse {
+    pub id: String,
+    // Only shown once.
+    pub token: String,
+}
+
+pub struct CreatePersonalAccessToken<'a> {
+    user_repo: &'a dyn UserRepository,
+
+    token_serv: &'a TokenService,
+}
+
+impl<'a> CreatePersonalAccessToken<'a> {
+    pub fn new(user_repo: &'a dyn UserRepository, token_serv: &'a TokenService) -> Self {
+        CreatePersonalAccessToken {
+            user_repo,
+            token_serv,
+        }
+    }
+
+    pub async fn exec(
+        &self,
+        auth: &Authorization,
+        user_id: String,
+        cmd: CreatePersonalAccessTokenCommand,
+    ) -> Result<CreatePersonalAccessTokenResponse> {
+        if auth.user_id() != user_id {
+            return Err(Error::unauthorized());
+        }
+
+        // Otherwise a leaked token could get every scope allowed by the role of the user.
+        if auth.scopes().is_some() {
+            return Err(Error::new("personal_access_token", "forbidden")
+                .set_status(403)
+                .set_message("Personal access tokens cannot create other tokens")
+                .build());
+        }
+
+        let user = self.user_repo.find_by_id(&UserId::new(user_id)?).await?;
+        if !user.is_active() {
+            return Err(Error::new("user", "not_active"));
+        }
+
+        let scopes = cmd
+            .scopes
+            .into_iter()
+            .map(|p| Permission::new(p.module, p.permissions))
+            .collect::<Result<Vec<Permission>>>()?;
+        let expires_at = match cmd.expires_in_days {
+            Some(days) if (1..=MAX_EXPIRATION_DAYS).contains(&days) => {
+                Some(Utc::now() + Duration::days(days))
+            }
+            Some(_) => {
+                return Err(Error::new("personal_access_token", "invalid")
+                    .set_status(400)
+                    .add_context("expires_in_days", "out_of_range")
+                    .build())
+            }
+            None => None,
+        };
+
+        let (personal_access_token, token) = self
+            .token_serv
+            .create_personal_access_token(user.base().id(), &cmd.name, scopes, expires_at)
+            .await?;
+
+        Ok(CreatePersonalAccessTokenResponse {
+            id: personal_access_token.id().to_string(),
+            token: token.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::token::Token;
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn create() {
+        let c = mocks::container();
+        let uc = CreatePersonalAccessToken::new(c.user_repo(), c.token_serv());
+
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let mut other = mocks::validated_user2();
+        c.user_repo().save(&mut other).await.unwrap();
+
+        let id = user.base().id().to_string();
+        let cmd = || CreatePersonalAccessTokenCommand {
+            name: "Uploads".to_owned(),
+            scopes: vec![PermissionDto {
+                module: "publications".to_owned(),
+                permissions: "CU".to_owned(),
+            }],
+            expires_in_days: Some(30),
+        };
+
+        let auth = Authorization::new(id.clone(), None);
+
+        assert!(uc
+            .exec(
+                &Authorization::new(other.base().id().to_string(), None),
+                id.clone(),
+                cmd()
+            )
+            .await
+            .is_err());
+
+        let res = uc.exec(&auth, id.clone(), cmd()).await.unwrap();
+        let authorization = c
+            .authorization_serv()
+            .authorize(&Token::new(res.token))
+            .await
+            .unwrap();
+        assert_eq!(authorization.user_id(), id);
+        assert!(authorization.allows("publications", "C"));
+
+        // Not with another token.
+        match uc.exec(&authorization, id.clone(), cmd()).await {
+            Err(err) => assert_eq!(err.status(), Some(403)),
+            Ok(_) => panic!("created with a personal access token"),
+        }
+
+        let mut invalid = cmd();
+        invalid.scopes[0].permissions = "X".to_owned();
+        assert!(uc.exec(&auth, id.clone(), invalid).await.is_err());
+
+        for days in [0, -1, 366, i64::MAX].iter() {
+            let mut invalid = cmd();
+            invalid.expires_in_days = Some(*days);
+            match uc.exec(&auth, id.clone(), invalid).await {
+                Err(err) => assert_eq!(err.status(), Some(400)),
+                Ok(_) => panic!("created with {} days", days),
+            }
+        }
+    }
+}
@@ -21,7 +21,7 @@ impl<'a> Delete<'a> {
     pub async fn exec(&self, auth_id: String, user_id: String) -> Result<()> {
         if auth_id != user_id {
             let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
-            if !auth_user.role().is("admin") {
+            if !auth_user.role().has_permissions("users", "D") {
                 return Err(Error::unauthorized());
             }
         }
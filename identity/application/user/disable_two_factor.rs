@@ -0,0 +1,140 @@
+use serde::Deserialize;
+
+use common::error::Error;
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::domain::user::{AuthenticationService, UserId, UserRepository};
+
+#[derive(Deserialize)]
+pub struct DisableTwoFactorCommand {
+    pub code: String,
+}
+
+pub struct DisableTwoFactor<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    user_repo: &'a dyn UserRepository,
+
+    authentication_serv: &'a AuthenticationService,
+}
+
+impl<'a> DisableTwoFactor<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        user_repo: &'a dyn UserRepository,
+        authentication_serv: &'a AuthenticationService,
+    ) -> Self {
+        DisableTwoFactor {
+            event_pub,
+            user_repo,
+            authentication_serv,
+        }
+    }
+
+    pub async fn exec(
+        &self,
+        auth_id: String,
+        user_id: String,
+        cmd: DisableTwoFactorCommand,
+    ) -> Result<()> {
+        if auth_id != user_id {
+            return Err(Error::unauthorized());
+        }
+
+        let mut user = self.user_repo.find_by_id(&UserId::new(user_id)?).await?;
+        if self.authentication_serv.two_factor_required(&user).await {
+            return Err(Error::new("two_factor", "required")
+                .set_message("The role of the user requires a second factor")
+                .build());
+        }
+
+        user.disable_two_factor(&cmd.code)?;
+
+        self.user_repo.save(&mut user).await?;
+
+        self.event_pub.publish_all(user.base().events()?).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use crate::domain::user::User;
+    use crate::mocks;
+
+    fn enable(user: &mut User) -> Vec<String> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let code = user.start_two_factor().unwrap().code_at(now).unwrap();
+        user.confirm_two_factor(&code).unwrap()
+    }
+
+    #[tokio::test]
+    async fn disable() {
+        let c = mocks::container();
+        let uc = DisableTwoFactor::new(c.event_pub(), c.user_repo(), c.authentication_serv());
+
+        let mut user = mocks::validated_user1();
+        let recovery_codes = enable(&mut user);
+        c.user_repo().save(&mut user).await.unwrap();
+        let user_id = user.base().id().to_string();
+
+        assert!(uc
+            .exec(
+                user_id.clone(),
+                user_id.clone(),
+                DisableTwoFactorCommand {
+                    code: "invalid".to_owned(),
+                },
+            )
+            .await
+            .is_err());
+
+        uc.exec(
+            user_id.clone(),
+            user_id.clone(),
+            DisableTwoFactorCommand {
+                code: recovery_codes[0].clone(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let user = c.user_repo().find_by_id(user.base().id()).await.unwrap();
+        assert!(user.two_factor().is_none());
+        assert_eq!(c.event_pub().events().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn required_by_role() {
+        let c = mocks::container();
+        let uc = DisableTwoFactor::new(c.event_pub(), c.user_repo(), c.authentication_serv());
+
+        let mut role = mocks::user_role();
+        role.set_two_factor_required(true);
+        c.role_repo().save(&mut role).await.unwrap();
+
+        let mut user = mocks::validated_user1();
+        let recovery_codes = enable(&mut user);
+        c.user_repo().save(&mut user).await.unwrap();
+
+        assert!(uc
+            .exec(
+                user.base().id().to_string(),
+                user.base().id().to_string(),
+                DisableTwoFactorCommand {
+                    code: recovery_codes[0].clone(),
+                },
+            )
+            .await
+            .is_err());
+    }
+}
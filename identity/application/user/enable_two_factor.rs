@@ -0,0 +1,86 @@
+use serde::Serialize;
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::user::{User, UserId, UserRepository};
+
+// Name shown by the authenticator apps.
+const ISSUER: &str = "Omics";
+
+#[derive(Serialize)]
+pub struct TwoFactorSetupResponse {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+impl TwoFactorSetupResponse {
+    // Starts the enrollment of the user, confirmed with a first code.
+    pub(crate) fn start(user: &mut User) -> Result<Self> {
+        let email = user.identity().email().to_string();
+        let two_factor = user.start_two_factor()?;
+
+        Ok(TwoFactorSetupResponse {
+            secret: two_factor.secret().to_owned(),
+            otpauth_uri: two_factor.otpauth_uri(ISSUER, &email),
+        })
+    }
+}
+
+pub struct EnableTwoFactor<'a> {
+    user_repo: &'a dyn UserRepository,
+}
+
+impl<'a> EnableTwoFactor<'a> {
+    pub fn new(user_repo: &'a dyn UserRepository) -> Self {
+        EnableTwoFactor { user_repo }
+    }
+
+    pub async fn exec(&self, auth_id: String, user_id: String) -> Result<TwoFactorSetupResponse> {
+        if auth_id != user_id {
+            return Err(Error::unauthorized());
+        }
+
+        let mut user = self.user_repo.find_by_id(&UserId::new(user_id)?).await?;
+        let res = TwoFactorSetupResponse::start(&mut user)?;
+
+        self.user_repo.save(&mut user).await?;
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn enable() {
+        let c = mocks::container();
+        let uc = EnableTwoFactor::new(c.user_repo());
+
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let mut other = mocks::validated_user2();
+        c.user_repo().save(&mut other).await.unwrap();
+
+        assert!(uc
+            .exec(other.base().id().to_string(), user.base().id().to_string())
+            .await
+            .is_err());
+
+        let res = uc
+            .exec(user.base().id().to_string(), user.base().id().to_string())
+            .await
+            .unwrap();
+        assert!(res.otpauth_uri.starts_with("otpauth://totp/Omics"));
+        assert!(res.otpauth_uri.contains(&res.secret));
+
+        // Not enabled until it is confirmed.
+        let user = c.user_repo().find_by_id(user.base().id()).await.unwrap();
+        assert_eq!(user.two_factor().unwrap().secret(), res.secret);
+        assert!(!user.has_two_factor());
+    }
+}
@@ -1,14 +1,19 @@
 use serde::Serialize;
 
 use common::error::Error;
+use common::model::{Pagination, PaginationQuery};
 use common::result::Result;
 
 use crate::application::dtos::UserDto;
 use crate::domain::user::{UserId, UserRepository};
 
+const SORT_FIELDS: &[&str] = &["created_at", "username"];
+
 #[derive(Serialize)]
 pub struct GetAllResponse {
     pub users: Vec<UserDto>,
+    pub next_cursor: Option<String>,
+    pub total: usize,
 }
 
 pub struct GetAll<'a> {
@@ -20,16 +25,27 @@ impl<'a> GetAll<'a> {
         GetAll { user_repo }
     }
 
-    pub async fn exec(&self, auth_id: String) -> Result<GetAllResponse> {
+    pub async fn exec(
+        &self,
+        auth_id: String,
+        pagination: PaginationQuery,
+    ) -> Result<GetAllResponse> {
         let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
-        if !auth_user.role().is("admin") {
+        if !auth_user.role().has_permissions("users", "R") {
             return Err(Error::unauthorized());
         }
 
-        let users = self.user_repo.find_all().await?;
+        let pagination = Pagination::from_query(pagination, SORT_FIELDS)?;
+        let users = self.user_repo.find_page(&pagination).await?;
 
         Ok(GetAllResponse {
-            users: users.iter().map(|user| UserDto::from(user)).collect(),
+            users: users
+                .items()
+                .iter()
+                .map(|user| UserDto::from(user))
+                .collect(),
+            next_cursor: users.next_cursor().map(str::to_owned),
+            total: users.total(),
         })
     }
 }
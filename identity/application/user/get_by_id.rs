@@ -16,7 +16,7 @@ impl<'a> GetById<'a> {
     pub async fn exec(&self, auth_id: String, user_id: String) -> Result<UserDto> {
         if auth_id != user_id {
             let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
-            if !auth_user.role().is("admin") {
+            if !auth_user.role().has_permissions("users", "R") {
                 return Err(Error::unauthorized());
             }
         }
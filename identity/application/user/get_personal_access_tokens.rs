@@ -0,0 +1,53 @@
+use serde::Serialize;
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::application::dtos::PersonalAccessTokenDto;
+use crate::domain::token::TokenService;
+use crate::domain::user::{UserId, UserRepository};
+
+#[derive(Serialize)]
+pub struct GetPersonalAccessTokensResponse {
+    personal_access_tokens: Vec<PersonalAccessTokenDto>,
+}
+
+pub struct GetPersonalAccessTokens<'a> {
+    user_repo: &'a dyn UserRepository,
+
+    token_serv: &'a TokenService,
+}
+
+impl<'a> GetPersonalAccessTokens<'a> {
+    pub fn new(user_repo: &'a dyn UserRepository, token_serv: &'a TokenService) -> Self {
+        GetPersonalAccessTokens {
+            user_repo,
+            token_serv,
+        }
+    }
+
+    pub async fn exec(
+        &self,
+        auth_id: String,
+        user_id: String,
+    ) -> Result<GetPersonalAccessTokensResponse> {
+        if auth_id != user_id {
+            let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
+            if !auth_user.role().has_permissions("users", "R") {
+                return Err(Error::unauthorized());
+            }
+        }
+
+        let personal_access_tokens = self
+            .token_serv
+            .personal_access_tokens(&UserId::new(user_id)?)
+            .await?;
+
+        Ok(GetPersonalAccessTokensResponse {
+            personal_access_tokens: personal_access_tokens
+                .iter()
+                .map(PersonalAccessTokenDto::from)
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,88 @@
+use serde::Serialize;
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::application::dtos::SessionDto;
+use crate::domain::token::TokenService;
+use crate::domain::user::{UserId, UserRepository};
+
+#[derive(Serialize)]
+pub struct GetSessionsResponse {
+    sessions: Vec<SessionDto>,
+}
+
+pub struct GetSessions<'a> {
+    user_repo: &'a dyn UserRepository,
+
+    token_serv: &'a TokenService,
+}
+
+impl<'a> GetSessions<'a> {
+    pub fn new(user_repo: &'a dyn UserRepository, token_serv: &'a TokenService) -> Self {
+        GetSessions {
+            user_repo,
+            token_serv,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, user_id: String) -> Result<GetSessionsResponse> {
+        if auth_id != user_id {
+            let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
+            if !auth_user.role().has_permissions("users", "R") {
+                return Err(Error::unauthorized());
+            }
+        }
+
+        let sessions = self.token_serv.sessions(&UserId::new(user_id)?).await?;
+
+        Ok(GetSessionsResponse {
+            sessions: sessions.iter().map(SessionDto::from).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn owner() {
+        let c = mocks::container();
+        let uc = GetSessions::new(c.user_repo(), c.token_serv());
+
+        let mut user = mocks::user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        c.token_serv()
+            .create_session(user.base().id(), "Firefox")
+            .await
+            .unwrap();
+        c.token_serv()
+            .create_session(user.base().id(), "Chrome")
+            .await
+            .unwrap();
+
+        let id = user.base().id().to_string();
+        let res = uc.exec(id.clone(), id).await.unwrap();
+        assert_eq!(res.sessions.len(), 2);
+        assert_eq!(res.sessions[0].device, "Firefox");
+    }
+
+    #[tokio::test]
+    async fn not_owner() {
+        let c = mocks::container();
+        let uc = GetSessions::new(c.user_repo(), c.token_serv());
+
+        let mut user = mocks::user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let mut other = mocks::user2();
+        c.user_repo().save(&mut other).await.unwrap();
+
+        assert!(uc
+            .exec(other.base().id().to_string(), user.base().id().to_string())
+            .await
+            .is_err());
+    }
+}
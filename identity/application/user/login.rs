@@ -3,18 +3,34 @@ use serde::{Deserialize, Serialize};
 use common::event::EventPublisher;
 use common::result::Result;
 
-use crate::domain::user::AuthenticationService;
+use crate::domain::user::{Authentication, AuthenticationService};
 
 #[derive(Deserialize)]
 pub struct LoginCommand {
     pub username: String,
     pub password: String,
+    // Taken from the User-Agent header if it is not given.
+    #[serde(default)]
+    pub device: Option<String>,
+    // Address of the client, set by the server.
+    #[serde(skip)]
+    pub client: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct LoginResponse {
     user_id: String,
-    auth_token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auth_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<String>,
+    // Sent instead of the session when a second factor is required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    two_factor_token: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    two_factor_setup_required: bool,
 }
 
 pub struct Login<'a> {
@@ -37,17 +53,40 @@ impl<'a> Login<'a> {
     pub async fn exec(&self, cmd: LoginCommand) -> Result<LoginResponse> {
         match self
             .authentication_serv
-            .authenticate(&cmd.username, &cmd.password)
+            .authenticate(
+                &cmd.username,
+                &cmd.password,
+                cmd.device.as_deref().unwrap_or("unknown"),
+                cmd.client.as_deref().unwrap_or("unknown"),
+            )
             .await
         {
-            Ok((user, token)) => {
+            Ok((user, Authentication::Session(tokens))) => {
                 self.event_pub.publish_all(user.base().events()?).await?;
 
                 Ok(LoginResponse {
                     user_id: user.base().id().to_string(),
-                    auth_token: token.to_string(),
+                    session_id: Some(tokens.session_id().to_string()),
+                    auth_token: Some(tokens.access_token().to_string()),
+                    refresh_token: Some(tokens.refresh_token().to_string()),
+                    two_factor_token: None,
+                    two_factor_setup_required: false,
                 })
             }
+            Ok((
+                user,
+                Authentication::TwoFactor {
+                    token,
+                    setup_required,
+                },
+            )) => Ok(LoginResponse {
+                user_id: user.base().id().to_string(),
+                session_id: None,
+                auth_token: None,
+                refresh_token: None,
+                two_factor_token: Some(token.to_string()),
+                two_factor_setup_required: setup_required,
+            }),
             Err(e) => Err(e),
         }
     }
@@ -71,6 +110,8 @@ mod tests {
             .exec(LoginCommand {
                 username: user.identity().username().to_string(),
                 password: "P@asswd!".to_owned(),
+                device: None,
+                client: None,
             })
             .await
             .is_err());
@@ -88,16 +129,22 @@ mod tests {
             .exec(LoginCommand {
                 username: user.identity().username().to_string(),
                 password: "P@asswd!".to_owned(),
+                device: None,
+                client: None,
             })
             .await
             .unwrap();
-        assert!(!res.auth_token.is_empty());
+        assert!(!res.auth_token.unwrap().is_empty());
+        assert!(!res.refresh_token.unwrap().is_empty());
+        assert!(res.two_factor_token.is_none());
         assert_eq!(c.event_pub().events().await.len(), 1);
 
         assert!(uc
             .exec(LoginCommand {
                 username: "non-existing".to_owned(),
                 password: "P@asswd!".to_owned(),
+                device: None,
+                client: None,
             })
             .await
             .is_err());
@@ -106,6 +153,8 @@ mod tests {
             .exec(LoginCommand {
                 username: user.identity().username().to_string(),
                 password: "invalid".to_owned(),
+                device: None,
+                client: None,
             })
             .await
             .is_err());
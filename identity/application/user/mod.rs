@@ -1,20 +1,52 @@
 mod change_password;
 mod change_role;
+mod confirm_two_factor;
+mod create_personal_access_token;
 mod delete;
+mod disable_two_factor;
+mod enable_two_factor;
 mod get_all;
 mod get_by_id;
+mod get_personal_access_tokens;
+mod get_sessions;
 mod login;
+mod oidc_login;
 mod recover_password;
+mod refresh_token;
 mod register;
+mod reset_password;
+mod revoke_personal_access_token;
+mod revoke_session;
+mod revoke_sessions;
+mod start_oidc_login;
+mod start_two_factor_setup;
+mod two_factor_login;
+mod unlock;
 mod update;
 mod validate;
 pub use change_password::*;
 pub use change_role::*;
+pub use confirm_two_factor::*;
+pub use create_personal_access_token::*;
 pub use delete::*;
+pub use disable_two_factor::*;
+pub use enable_two_factor::*;
 pub use get_all::*;
 pub use get_by_id::*;
+pub use get_personal_access_tokens::*;
+pub use get_sessions::*;
 pub use login::*;
+pub use oidc_login::*;
 pub use recover_password::*;
+pub use refresh_token::*;
 pub use register::*;
+pub use reset_password::*;
+pub use revoke_personal_access_token::*;
+pub use revoke_session::*;
+pub use revoke_sessions::*;
+pub use start_oidc_login::*;
+pub use start_two_factor_setup::*;
+pub use two_factor_login::*;
+pub use unlock::*;
 pub use update::*;
 pub use validate::*;
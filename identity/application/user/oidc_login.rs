@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::domain::oidc::OidcService;
+use crate::domain::user::{Authentication, Provider};
+
+// Parameters of the redirection from the provider.
+#[derive(Deserialize)]
+pub struct OidcLoginCommand {
+    pub state: String,
+    pub code: String,
+    #[serde(default)]
+    pub device: Option<String>,
+}
+
+// Same as the response of the login with a password.
+#[derive(Serialize)]
+pub struct OidcLoginResponse {
+    user_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auth_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    two_factor_token: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    two_factor_setup_required: bool,
+}
+
+pub struct OidcLogin<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    oidc_serv: &'a OidcService,
+}
+
+impl<'a> OidcLogin<'a> {
+    pub fn new(event_pub: &'a dyn EventPublisher, oidc_serv: &'a OidcService) -> Self {
+        OidcLogin {
+            event_pub,
+            oidc_serv,
+        }
+    }
+
+    pub async fn exec(&self, provider: String, cmd: OidcLoginCommand) -> Result<OidcLoginResponse> {
+        let (user, auth) = self
+            .oidc_serv
+            .authenticate(
+                &Provider::new(provider)?,
+                &cmd.state,
+                &cmd.code,
+                cmd.device.as_deref().unwrap_or("unknown"),
+            )
+            .await?;
+
+        // A new user is registered even if the login is not finished.
+        self.event_pub.publish_all(user.base().events()?).await?;
+
+        match auth {
+            Authentication::Session(tokens) => Ok(OidcLoginResponse {
+                user_id: user.base().id().to_string(),
+                session_id: Some(tokens.session_id().to_string()),
+                auth_token: Some(tokens.access_token().to_string()),
+                refresh_token: Some(tokens.refresh_token().to_string()),
+                two_factor_token: None,
+                two_factor_setup_required: false,
+            }),
+            Authentication::TwoFactor {
+                token,
+                setup_required,
+            } => Ok(OidcLoginResponse {
+                user_id: user.base().id().to_string(),
+                session_id: None,
+                auth_token: None,
+                refresh_token: None,
+                two_factor_token: Some(token.to_string()),
+                two_factor_setup_required: setup_required,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::application::user::StartOidcLogin;
+    use crate::domain::user::{Email, UserId};
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn new_user() {
+        let c = mocks::container();
+        let uc = OidcLogin::new(c.event_pub(), c.oidc_serv());
+
+        let url = StartOidcLogin::new(c.oidc_serv())
+            .exec("google".to_owned())
+            .await
+            .unwrap()
+            .authorization_url;
+        let state = url.split("state=").nth(1).unwrap().to_owned();
+
+        let res = uc
+            .exec(
+                "google".to_owned(),
+                OidcLoginCommand {
+                    state,
+                    code: "new.user@gmail.com".to_owned(),
+                    device: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(!res.auth_token.unwrap().is_empty());
+        assert!(!res.refresh_token.unwrap().is_empty());
+        assert!(res.two_factor_token.is_none());
+
+        let user = c
+            .user_repo()
+            .find_by_email(&Email::new("new.user@gmail.com").unwrap())
+            .await
+            .unwrap();
+        assert_eq!(user.base().id(), &UserId::new(res.user_id).unwrap());
+
+        // Registered, Validated, AccountLinked and LoggedIn
+        assert_eq!(c.event_pub().events().await.len(), 4);
+
+        assert!(uc
+            .exec(
+                "google".to_owned(),
+                OidcLoginCommand {
+                    state: "invalid".to_owned(),
+                    code: "new.user@gmail.com".to_owned(),
+                    device: None,
+                },
+            )
+            .await
+            .is_err());
+    }
+}
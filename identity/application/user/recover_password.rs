@@ -1,10 +1,10 @@
 use serde::Deserialize;
-use uuid::Uuid;
 
 use common::event::EventPublisher;
 use common::result::Result;
 
-use crate::domain::user::{Email, Password, UserRepository, UserService};
+use crate::domain::user::{Email, UserRepository};
+use crate::domain::verification::{VerificationKind, VerificationService};
 
 #[derive(Deserialize)]
 pub struct RecoverPasswordCommand {
@@ -16,19 +16,19 @@ pub struct RecoverPassword<'a> {
 
     user_repo: &'a dyn UserRepository,
 
-    user_serv: &'a UserService,
+    verification_serv: &'a VerificationService,
 }
 
 impl<'a> RecoverPassword<'a> {
     pub fn new(
         event_pub: &'a dyn EventPublisher,
         user_repo: &'a dyn UserRepository,
-        user_serv: &'a UserService,
+        verification_serv: &'a VerificationService,
     ) -> Self {
         RecoverPassword {
-            user_repo,
-            user_serv,
             event_pub,
+            user_repo,
+            verification_serv,
         }
     }
 
@@ -36,11 +36,11 @@ impl<'a> RecoverPassword<'a> {
         let email = Email::new(cmd.email)?;
         let mut user = self.user_repo.find_by_email(&email).await?;
 
-        let tmp_password = Uuid::new_v4().to_string();
-        let hashed_password = self.user_serv.generate_password(&tmp_password)?;
-        let password = Password::new(hashed_password)?;
+        user.recover_password()?;
 
-        user.recover_password(password, &tmp_password)?;
+        self.verification_serv
+            .issue(&user, VerificationKind::PasswordReset)
+            .await?;
 
         self.user_repo.save(&mut user).await?;
 
@@ -54,12 +54,14 @@ impl<'a> RecoverPassword<'a> {
 mod tests {
     use super::*;
 
-    use crate::mocks;
+    use std::sync::Arc;
+
+    use crate::mocks::{self, FakeVerificationSender};
 
     #[tokio::test]
     async fn non_existing_user() {
         let c = mocks::container();
-        let uc = RecoverPassword::new(c.event_pub(), c.user_repo(), c.user_serv());
+        let uc = RecoverPassword::new(c.event_pub(), c.user_repo(), c.verification_serv());
 
         let user = mocks::user1();
         assert!(uc
@@ -71,9 +73,10 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn password_recovery_code_generated() {
-        let c = mocks::container();
-        let uc = RecoverPassword::new(c.event_pub(), c.user_repo(), c.user_serv());
+    async fn reset_link_sent() {
+        let sender = Arc::new(FakeVerificationSender::new());
+        let c = mocks::container_with_verification_sender(sender.clone());
+        let uc = RecoverPassword::new(c.event_pub(), c.user_repo(), c.verification_serv());
 
         let mut user = mocks::user1();
         let old_password = user.identity().password().unwrap().to_string();
@@ -86,9 +89,15 @@ mod tests {
             .await
             .is_ok());
 
+        // The password does not change until the link is followed.
         let user = c.user_repo().find_by_id(&user.base().id()).await.unwrap();
-        assert_ne!(user.identity().password().unwrap().value(), old_password);
-
-        assert_eq!(c.event_pub().events().await.len(), 1);
+        assert_eq!(user.identity().password().unwrap().value(), old_password);
+        let token = sender
+            .token(user.base().id(), &VerificationKind::PasswordReset)
+            .unwrap();
+
+        let events = c.event_pub().events().await;
+        assert_eq!(events.len(), 1);
+        assert!(!String::from_utf8_lossy(events[0].payload()).contains(&token));
     }
 }
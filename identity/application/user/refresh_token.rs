@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+
+use common::result::Result;
+
+use crate::domain::token::Token;
+use crate::domain::user::AuthenticationService;
+
+#[derive(Deserialize)]
+pub struct RefreshTokenCommand {
+    pub refresh_token: String,
+}
+
+#[derive(Serialize)]
+pub struct RefreshTokenResponse {
+    session_id: String,
+    auth_token: String,
+    refresh_token: String,
+}
+
+pub struct RefreshToken<'a> {
+    authentication_serv: &'a AuthenticationService,
+}
+
+impl<'a> RefreshToken<'a> {
+    pub fn new(authentication_serv: &'a AuthenticationService) -> Self {
+        RefreshToken {
+            authentication_serv,
+        }
+    }
+
+    pub async fn exec(&self, cmd: RefreshTokenCommand) -> Result<RefreshTokenResponse> {
+        let tokens = self
+            .authentication_serv
+            .refresh(&Token::new(cmd.refresh_token))
+            .await?;
+
+        Ok(RefreshTokenResponse {
+            session_id: tokens.session_id().to_string(),
+            auth_token: tokens.access_token().to_string(),
+            refresh_token: tokens.refresh_token().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::token::SessionTokens;
+    use crate::domain::user::LoginAttempts;
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn rotate() {
+        let c = mocks::container();
+        let uc = RefreshToken::new(c.authentication_serv());
+
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let tokens = c
+            .token_serv()
+            .create_session(user.base().id(), "Firefox")
+            .await
+            .unwrap();
+
+        let res = uc
+            .exec(RefreshTokenCommand {
+                refresh_token: tokens.refresh_token().to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(res.session_id, tokens.session_id().value());
+        assert_ne!(res.refresh_token, tokens.refresh_token().value());
+
+        let res = uc
+            .exec(RefreshTokenCommand {
+                refresh_token: res.refresh_token,
+            })
+            .await
+            .unwrap();
+        assert!(!res.auth_token.is_empty());
+
+        assert!(uc
+            .exec(RefreshTokenCommand {
+                refresh_token: tokens.refresh_token().to_string(),
+            })
+            .await
+            .is_err());
+        assert!(c
+            .token_serv()
+            .validate(&Token::new(res.auth_token))
+            .await
+            .is_err());
+    }
+    #[tokio::test]
+    async fn inactive_user() {
+        let c = mocks::container();
+        let uc = RefreshToken::new(c.authentication_serv());
+        let cmd = |tokens: &SessionTokens| RefreshTokenCommand {
+            refresh_token: tokens.refresh_token().to_string(),
+        };
+
+        // Locked out
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let tokens = c
+            .token_serv()
+            .create_session(user.base().id(), "Firefox")
+            .await
+            .unwrap();
+        let mut attempts = LoginAttempts::new(format!("account:{}", user.base().id().value()));
+        attempts.lock();
+        c.login_attempt_repo().save(&attempts).await.unwrap();
+        let err = uc.exec(cmd(&tokens)).await.err().unwrap();
+        assert_eq!(err.code(), "too_many_attempts");
+        c.login_attempt_repo().delete(attempts.key()).await.unwrap();
+        assert!(uc.exec(cmd(&tokens)).await.is_ok());
+
+        // Deleted
+        let tokens = c
+            .token_serv()
+            .create_session(user.base().id(), "Firefox")
+            .await
+            .unwrap();
+        user.delete().unwrap();
+        c.user_repo().save(&mut user).await.unwrap();
+        let err = uc.exec(cmd(&tokens)).await.err().unwrap();
+        assert_eq!(err.code(), "inactive");
+        assert!(c
+            .token_serv()
+            .validate(tokens.access_token())
+            .await
+            .is_err());
+
+        // Not validated
+        let mut user = mocks::user2();
+        c.user_repo().save(&mut user).await.unwrap();
+        let tokens = c
+            .token_serv()
+            .create_session(user.base().id(), "Firefox")
+            .await
+            .unwrap();
+        assert!(uc.exec(cmd(&tokens)).await.is_err());
+    }
+}
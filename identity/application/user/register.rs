@@ -8,6 +8,7 @@ use crate::domain::role::{Role, RoleId};
 use crate::domain::user::{
     Email, Identity, Password, Provider, User, UserRepository, UserService, Username,
 };
+use crate::domain::verification::{VerificationKind, VerificationService};
 
 #[derive(Deserialize)]
 pub struct RegisterCommand {
@@ -19,7 +20,6 @@ pub struct RegisterCommand {
 #[derive(Serialize)]
 pub struct RegisterResponse {
     pub id: String,
-    pub validation_code: String, // TODO: remove, only for testing
 }
 
 pub struct Register<'a> {
@@ -28,6 +28,7 @@ pub struct Register<'a> {
     user_repo: &'a dyn UserRepository,
 
     user_serv: &'a UserService,
+    verification_serv: &'a VerificationService,
 }
 
 impl<'a> Register<'a> {
@@ -35,11 +36,13 @@ impl<'a> Register<'a> {
         event_pub: &'a dyn EventPublisher,
         user_repo: &'a dyn UserRepository,
         user_serv: &'a UserService,
+        verification_serv: &'a VerificationService,
     ) -> Self {
         Register {
             event_pub,
             user_repo,
             user_serv,
+            verification_serv,
         }
     }
 
@@ -61,11 +64,14 @@ impl<'a> Register<'a> {
 
         self.user_repo.save(&mut user).await?;
 
+        self.verification_serv
+            .issue(&user, VerificationKind::Email)
+            .await?;
+
         self.event_pub.publish_all(user.base().events()?).await?;
 
         Ok(RegisterResponse {
             id: user.base().id().to_string(),
-            validation_code: user.validation().unwrap().code().to_string(),
         })
     }
 }
@@ -74,13 +80,21 @@ impl<'a> Register<'a> {
 mod tests {
     use super::*;
 
+    use std::sync::Arc;
+
     use crate::domain::user::UserId;
-    use crate::mocks;
+    use crate::mocks::{self, FakeVerificationSender};
 
     #[tokio::test]
     async fn new_user() {
-        let c = mocks::container();
-        let uc = Register::new(c.event_pub(), c.user_repo(), c.user_serv());
+        let sender = Arc::new(FakeVerificationSender::new());
+        let c = mocks::container_with_verification_sender(sender.clone());
+        let uc = Register::new(
+            c.event_pub(),
+            c.user_repo(),
+            c.user_serv(),
+            c.verification_serv(),
+        );
 
         let cmd = RegisterCommand {
             username: "new-user".to_owned(),
@@ -102,13 +116,22 @@ mod tests {
             "P@asswd!"
         );
 
+        assert!(sender
+            .token(saved_user.base().id(), &VerificationKind::Email)
+            .is_some());
+
         assert_eq!(c.event_pub().events().await.len(), 1);
     }
 
     #[tokio::test]
     async fn invalid_data() {
         let c = mocks::container();
-        let uc = Register::new(c.event_pub(), c.user_repo(), c.user_serv());
+        let uc = Register::new(
+            c.event_pub(),
+            c.user_repo(),
+            c.user_serv(),
+            c.verification_serv(),
+        );
 
         let mut user = mocks::user1();
         c.user_repo().save(&mut user).await.unwrap();
@@ -144,7 +167,12 @@ mod tests {
     #[tokio::test]
     async fn existing_user() {
         let c = mocks::container();
-        let uc = Register::new(c.event_pub(), c.user_repo(), c.user_serv());
+        let uc = Register::new(
+            c.event_pub(),
+            c.user_repo(),
+            c.user_serv(),
+            c.verification_serv(),
+        );
 
         let mut user = mocks::user1();
         c.user_repo().save(&mut user).await.unwrap();
@@ -0,0 +1,168 @@
+use serde::Deserialize;
+
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::domain::token::TokenService;
+use crate::domain::user::{Password, UserRepository, UserService};
+use crate::domain::verification::{VerificationKind, VerificationService};
+
+#[derive(Deserialize)]
+pub struct ResetPasswordCommand {
+    pub token: String,
+    pub password: String,
+}
+
+pub struct ResetPassword<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    user_repo: &'a dyn UserRepository,
+
+    token_serv: &'a TokenService,
+    user_serv: &'a UserService,
+    verification_serv: &'a VerificationService,
+}
+
+impl<'a> ResetPassword<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        user_repo: &'a dyn UserRepository,
+        token_serv: &'a TokenService,
+        user_serv: &'a UserService,
+        verification_serv: &'a VerificationService,
+    ) -> Self {
+        ResetPassword {
+            event_pub,
+            user_repo,
+            token_serv,
+            user_serv,
+            verification_serv,
+        }
+    }
+
+    pub async fn exec(&self, cmd: ResetPasswordCommand) -> Result<()> {
+        // Checked before the token is used.
+        let hashed_password = self.user_serv.generate_password(&cmd.password)?;
+
+        let user_id = self
+            .verification_serv
+            .verify(&cmd.token, &VerificationKind::PasswordReset)
+            .await?;
+        let mut user = self.user_repo.find_by_id(&user_id).await?;
+
+        user.reset_password(Password::new(hashed_password)?)?;
+
+        self.user_repo.save(&mut user).await?;
+
+        // Sessions opened with the old password are closed.
+        self.token_serv.revoke_all(&user_id).await?;
+
+        self.event_pub.publish_all(user.base().events()?).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::mocks::{self, FakeVerificationSender};
+
+    #[tokio::test]
+    async fn reset() {
+        let sender = Arc::new(FakeVerificationSender::new());
+        let c = mocks::container_with_verification_sender(sender.clone());
+        let uc = ResetPassword::new(
+            c.event_pub(),
+            c.user_repo(),
+            c.token_serv(),
+            c.user_serv(),
+            c.verification_serv(),
+        );
+
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let session = c
+            .token_serv()
+            .create_session(user.base().id(), "Firefox")
+            .await
+            .unwrap();
+
+        c.verification_serv()
+            .issue(&user, VerificationKind::PasswordReset)
+            .await
+            .unwrap();
+        let token = sender
+            .token(user.base().id(), &VerificationKind::PasswordReset)
+            .unwrap();
+
+        // Invalid password, the token can still be used
+        assert!(uc
+            .exec(ResetPasswordCommand {
+                token: token.clone(),
+                password: "1234".to_owned(),
+            })
+            .await
+            .is_err());
+
+        uc.exec(ResetPasswordCommand {
+            token: token.clone(),
+            password: "new-P@asswd!".to_owned(),
+        })
+        .await
+        .unwrap();
+
+        let user = c.user_repo().find_by_id(user.base().id()).await.unwrap();
+        assert!(c
+            .password_hasher()
+            .compare(user.identity().password().unwrap().value(), "new-P@asswd!"));
+        assert!(c
+            .token_serv()
+            .validate(session.access_token())
+            .await
+            .is_err());
+        assert_eq!(c.event_pub().events().await.len(), 1);
+
+        // Used
+        assert!(uc
+            .exec(ResetPasswordCommand {
+                token,
+                password: "other-P@asswd!".to_owned(),
+            })
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn email_token() {
+        let sender = Arc::new(FakeVerificationSender::new());
+        let c = mocks::container_with_verification_sender(sender.clone());
+        let uc = ResetPassword::new(
+            c.event_pub(),
+            c.user_repo(),
+            c.token_serv(),
+            c.user_serv(),
+            c.verification_serv(),
+        );
+
+        let mut user = mocks::user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        c.verification_serv()
+            .issue(&user, VerificationKind::Email)
+            .await
+            .unwrap();
+
+        assert!(uc
+            .exec(ResetPasswordCommand {
+                token: sender
+                    .token(user.base().id(), &VerificationKind::Email)
+                    .unwrap(),
+                password: "new-P@asswd!".to_owned(),
+            })
+            .await
+            .is_err());
+    }
+}
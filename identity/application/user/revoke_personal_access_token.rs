@@ -0,0 +1,90 @@
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::token::{PersonalAccessTokenId, TokenService};
+use crate::domain::user::{UserId, UserRepository};
+
+pub struct RevokePersonalAccessToken<'a> {
+    user_repo: &'a dyn UserRepository,
+
+    token_serv: &'a TokenService,
+}
+
+impl<'a> RevokePersonalAccessToken<'a> {
+    pub fn new(user_repo: &'a dyn UserRepository, token_serv: &'a TokenService) -> Self {
+        RevokePersonalAccessToken {
+            user_repo,
+            token_serv,
+        }
+    }
+
+    pub async fn exec(
+        &self,
+        auth_id: String,
+        user_id: String,
+        personal_access_token_id: String,
+    ) -> Result<()> {
+        if auth_id != user_id {
+            let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
+            if !auth_user.role().has_permissions("users", "U") {
+                return Err(Error::unauthorized());
+            }
+        }
+
+        self.token_serv
+            .revoke_personal_access_token(
+                &UserId::new(user_id)?,
+                &PersonalAccessTokenId::new(personal_access_token_id)?,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::role::Permission;
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn revoke() {
+        let c = mocks::container();
+        let uc = RevokePersonalAccessToken::new(c.user_repo(), c.token_serv());
+
+        let mut user = mocks::user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let mut other = mocks::user2();
+        c.user_repo().save(&mut other).await.unwrap();
+
+        let (personal_access_token, token) = c
+            .token_serv()
+            .create_personal_access_token(
+                user.base().id(),
+                "Uploads",
+                vec![Permission::new("publications", "C").unwrap()],
+                None,
+            )
+            .await
+            .unwrap();
+
+        let id = user.base().id().to_string();
+        assert!(uc
+            .exec(
+                other.base().id().to_string(),
+                id.clone(),
+                personal_access_token.id().to_string()
+            )
+            .await
+            .is_err());
+
+        uc.exec(id.clone(), id, personal_access_token.id().to_string())
+            .await
+            .unwrap();
+        assert!(c
+            .token_serv()
+            .validate_personal_access_token(&token)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::token::{SessionId, TokenService};
+use crate::domain::user::{UserId, UserRepository};
+
+pub struct RevokeSession<'a> {
+    user_repo: &'a dyn UserRepository,
+
+    token_serv: &'a TokenService,
+}
+
+impl<'a> RevokeSession<'a> {
+    pub fn new(user_repo: &'a dyn UserRepository, token_serv: &'a TokenService) -> Self {
+        RevokeSession {
+            user_repo,
+            token_serv,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, user_id: String, session_id: String) -> Result<()> {
+        if auth_id != user_id {
+            let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
+            if !auth_user.role().has_permissions("users", "U") {
+                return Err(Error::unauthorized());
+            }
+        }
+
+        self.token_serv
+            .revoke_session(&UserId::new(user_id)?, &SessionId::new(session_id)?)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn revoke() {
+        let c = mocks::container();
+        let uc = RevokeSession::new(c.user_repo(), c.token_serv());
+
+        let mut user = mocks::user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let mut other = mocks::user2();
+        c.user_repo().save(&mut other).await.unwrap();
+
+        let tokens = c
+            .token_serv()
+            .create_session(user.base().id(), "Firefox")
+            .await
+            .unwrap();
+        let other_tokens = c
+            .token_serv()
+            .create_session(other.base().id(), "Firefox")
+            .await
+            .unwrap();
+
+        let id = user.base().id().to_string();
+        assert!(uc
+            .exec(
+                other.base().id().to_string(),
+                id.clone(),
+                tokens.session_id().to_string()
+            )
+            .await
+            .is_err());
+        assert!(uc
+            .exec(
+                id.clone(),
+                id.clone(),
+                other_tokens.session_id().to_string()
+            )
+            .await
+            .is_err());
+
+        uc.exec(id.clone(), id, tokens.session_id().to_string())
+            .await
+            .unwrap();
+        assert!(c
+            .token_serv()
+            .validate(tokens.access_token())
+            .await
+            .is_err());
+        assert!(c
+            .token_serv()
+            .validate(other_tokens.access_token())
+            .await
+            .is_ok());
+    }
+}
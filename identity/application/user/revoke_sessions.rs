@@ -0,0 +1,79 @@
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::token::TokenService;
+use crate::domain::user::{UserId, UserRepository};
+
+// Logs the user out everywhere.
+pub struct RevokeSessions<'a> {
+    user_repo: &'a dyn UserRepository,
+
+    token_serv: &'a TokenService,
+}
+
+impl<'a> RevokeSessions<'a> {
+    pub fn new(user_repo: &'a dyn UserRepository, token_serv: &'a TokenService) -> Self {
+        RevokeSessions {
+            user_repo,
+            token_serv,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, user_id: String) -> Result<()> {
+        if auth_id != user_id {
+            let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
+            if !auth_user.role().has_permissions("users", "U") {
+                return Err(Error::unauthorized());
+            }
+        }
+
+        self.token_serv.revoke_all(&UserId::new(user_id)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn revoke_all() {
+        let c = mocks::container();
+        let uc = RevokeSessions::new(c.user_repo(), c.token_serv());
+
+        let mut user = mocks::user1();
+        c.user_repo().save(&mut user).await.unwrap();
+
+        let firefox = c
+            .token_serv()
+            .create_session(user.base().id(), "Firefox")
+            .await
+            .unwrap();
+        let chrome = c
+            .token_serv()
+            .create_session(user.base().id(), "Chrome")
+            .await
+            .unwrap();
+
+        let id = user.base().id().to_string();
+        uc.exec(id.clone(), id).await.unwrap();
+
+        assert!(c
+            .token_serv()
+            .validate(firefox.access_token())
+            .await
+            .is_err());
+        assert!(c
+            .token_serv()
+            .refresh(chrome.refresh_token())
+            .await
+            .is_err());
+        assert!(c
+            .token_serv()
+            .sessions(user.base().id())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,50 @@
+use serde::Serialize;
+
+use common::result::Result;
+
+use crate::domain::oidc::OidcService;
+use crate::domain::user::Provider;
+
+#[derive(Serialize)]
+pub struct StartOidcLoginResponse {
+    pub authorization_url: String,
+}
+
+pub struct StartOidcLogin<'a> {
+    oidc_serv: &'a OidcService,
+}
+
+impl<'a> StartOidcLogin<'a> {
+    pub fn new(oidc_serv: &'a OidcService) -> Self {
+        StartOidcLogin { oidc_serv }
+    }
+
+    pub async fn exec(&self, provider: String) -> Result<StartOidcLoginResponse> {
+        let authorization_url = self
+            .oidc_serv
+            .authorization_url(Provider::new(provider)?)
+            .await?;
+
+        Ok(StartOidcLoginResponse { authorization_url })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn providers() {
+        let c = mocks::container();
+        let uc = StartOidcLogin::new(c.oidc_serv());
+
+        let res = uc.exec("google".to_owned()).await.unwrap();
+        assert!(res.authorization_url.starts_with("https://google.test"));
+        assert!(uc.exec("facebook".to_owned()).await.is_ok());
+
+        assert!(uc.exec("local".to_owned()).await.is_err());
+        assert!(uc.exec("twitter".to_owned()).await.is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use serde::Deserialize;
+
+use common::result::Result;
+
+use crate::application::user::TwoFactorSetupResponse;
+use crate::domain::token::Token;
+use crate::domain::user::{AuthenticationService, UserRepository};
+
+// Users whose role requires a second factor set it up during the login, with the token of the
+// first step, and confirm it in the second one.
+#[derive(Deserialize)]
+pub struct StartTwoFactorSetupCommand {
+    pub token: String,
+}
+
+pub struct StartTwoFactorSetup<'a> {
+    user_repo: &'a dyn UserRepository,
+
+    authentication_serv: &'a AuthenticationService,
+}
+
+impl<'a> StartTwoFactorSetup<'a> {
+    pub fn new(
+        user_repo: &'a dyn UserRepository,
+        authentication_serv: &'a AuthenticationService,
+    ) -> Self {
+        StartTwoFactorSetup {
+            user_repo,
+            authentication_serv,
+        }
+    }
+
+    pub async fn exec(&self, cmd: StartTwoFactorSetupCommand) -> Result<TwoFactorSetupResponse> {
+        let mut user = self
+            .authentication_serv
+            .two_factor_user(&Token::new(cmd.token))
+            .await?;
+        let res = TwoFactorSetupResponse::start(&mut user)?;
+
+        self.user_repo.save(&mut user).await?;
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::domain::token::Token;
+use crate::domain::user::AuthenticationService;
+
+#[derive(Deserialize)]
+pub struct TwoFactorLoginCommand {
+    // Token returned by the first step of the login.
+    pub token: String,
+    // Code of the authenticator, or a recovery code.
+    pub code: String,
+    #[serde(default)]
+    pub device: Option<String>,
+    #[serde(skip)]
+    pub client: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct TwoFactorLoginResponse {
+    user_id: String,
+    session_id: String,
+    auth_token: String,
+    refresh_token: String,
+    // Only when the second factor was set up in this login.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recovery_codes: Option<Vec<String>>,
+}
+
+pub struct TwoFactorLogin<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    authentication_serv: &'a AuthenticationService,
+}
+
+impl<'a> TwoFactorLogin<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        authentication_serv: &'a AuthenticationService,
+    ) -> Self {
+        TwoFactorLogin {
+            event_pub,
+            authentication_serv,
+        }
+    }
+
+    pub async fn exec(&self, cmd: TwoFactorLoginCommand) -> Result<TwoFactorLoginResponse> {
+        let (user, tokens, recovery_codes) = self
+            .authentication_serv
+            .authenticate_two_factor(
+                &Token::new(cmd.token),
+                &cmd.code,
+                cmd.device.as_deref().unwrap_or("unknown"),
+                cmd.client.as_deref().unwrap_or("unknown"),
+            )
+            .await?;
+
+        self.event_pub.publish_all(user.base().events()?).await?;
+
+        Ok(TwoFactorLoginResponse {
+            user_id: user.base().id().to_string(),
+            session_id: tokens.session_id().to_string(),
+            auth_token: tokens.access_token().to_string(),
+            refresh_token: tokens.refresh_token().to_string(),
+            recovery_codes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use crate::application::user::{StartTwoFactorSetup, StartTwoFactorSetupCommand};
+    use crate::domain::user::Authentication;
+    use crate::mocks;
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[tokio::test]
+    async fn setup_required() {
+        let c = mocks::container();
+        let uc = TwoFactorLogin::new(c.event_pub(), c.authentication_serv());
+
+        let mut role = mocks::user_role();
+        role.set_two_factor_required(true);
+        c.role_repo().save(&mut role).await.unwrap();
+
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+
+        let (_, auth) = c
+            .authentication_serv()
+            .authenticate(
+                &user.identity().username().to_string(),
+                "P@asswd!",
+                "device",
+                "client",
+            )
+            .await
+            .unwrap();
+        let token = match auth {
+            Authentication::TwoFactor {
+                token,
+                setup_required,
+            } => {
+                assert!(setup_required);
+                token
+            }
+            Authentication::Session(_) => panic!("session without second factor"),
+        };
+
+        let setup = StartTwoFactorSetup::new(c.user_repo(), c.authentication_serv())
+            .exec(StartTwoFactorSetupCommand {
+                token: token.to_string(),
+            })
+            .await
+            .unwrap();
+        let user = c.user_repo().find_by_id(user.base().id()).await.unwrap();
+        assert_eq!(user.two_factor().unwrap().secret(), setup.secret);
+
+        let code = user.two_factor().unwrap().code_at(now()).unwrap();
+        let res = uc
+            .exec(TwoFactorLoginCommand {
+                token: token.to_string(),
+                code,
+                device: None,
+                client: None,
+            })
+            .await
+            .unwrap();
+        assert!(!res.auth_token.is_empty());
+        assert_eq!(res.recovery_codes.unwrap().len(), 10);
+
+        let user = c.user_repo().find_by_id(user.base().id()).await.unwrap();
+        assert!(user.has_two_factor());
+
+        // The token of the first step cannot be used again.
+        assert!(uc
+            .exec(TwoFactorLoginCommand {
+                token: token.to_string(),
+                code: "000000".to_owned(),
+                device: None,
+                client: None,
+            })
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn recovery_code() {
+        let c = mocks::container();
+        let uc = TwoFactorLogin::new(c.event_pub(), c.authentication_serv());
+
+        let mut user = mocks::validated_user1();
+        let code = user.start_two_factor().unwrap().code_at(now()).unwrap();
+        let recovery_codes = user.confirm_two_factor(&code).unwrap();
+        c.user_repo().save(&mut user).await.unwrap();
+
+        let (_, auth) = c
+            .authentication_serv()
+            .authenticate(
+                &user.identity().username().to_string(),
+                "P@asswd!",
+                "device",
+                "client",
+            )
+            .await
+            .unwrap();
+        assert!(auth.session().is_none());
+        let token = match auth {
+            Authentication::TwoFactor { token, .. } => token,
+            Authentication::Session(_) => panic!("session without second factor"),
+        };
+
+        assert!(uc
+            .exec(TwoFactorLoginCommand {
+                token: token.to_string(),
+                code: "invalid".to_owned(),
+                device: None,
+                client: None,
+            })
+            .await
+            .is_err());
+
+        let res = uc
+            .exec(TwoFactorLoginCommand {
+                token: token.to_string(),
+                code: recovery_codes[0].clone(),
+                device: None,
+                client: None,
+            })
+            .await
+            .unwrap();
+        assert!(!res.refresh_token.is_empty());
+        assert!(res.recovery_codes.is_none());
+
+        let user = c.user_repo().find_by_id(user.base().id()).await.unwrap();
+        assert_eq!(user.two_factor().unwrap().recovery_codes_left(), 9);
+    }
+}
@@ -0,0 +1,80 @@
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::user::{AuthenticationService, UserId, UserRepository};
+
+pub struct Unlock<'a> {
+    user_repo: &'a dyn UserRepository,
+
+    authentication_serv: &'a AuthenticationService,
+}
+
+impl<'a> Unlock<'a> {
+    pub fn new(
+        user_repo: &'a dyn UserRepository,
+        authentication_serv: &'a AuthenticationService,
+    ) -> Self {
+        Unlock {
+            user_repo,
+            authentication_serv,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, user_id: String) -> Result<()> {
+        let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
+        if !auth_user.role().has_permissions("users", "U") {
+            return Err(Error::unauthorized());
+        }
+
+        let user = self.user_repo.find_by_id(&UserId::new(user_id)?).await?;
+
+        self.authentication_serv.unlock(user.base().id()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn unlock() {
+        let c = mocks::container();
+        let uc = Unlock::new(c.user_repo(), c.authentication_serv());
+
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let mut admin = mocks::admin1();
+        c.user_repo().save(&mut admin).await.unwrap();
+
+        for _ in 0..3 {
+            assert!(c
+                .authentication_serv()
+                .authenticate(user.identity().username().value(), "invalid", "Firefox", "")
+                .await
+                .is_err());
+        }
+        assert!(c
+            .authentication_serv()
+            .account_attempts(user.base().id())
+            .await
+            .check()
+            .is_err());
+
+        assert!(uc
+            .exec(user.base().id().to_string(), user.base().id().to_string())
+            .await
+            .is_err());
+
+        uc.exec(admin.base().id().to_string(), user.base().id().to_string())
+            .await
+            .unwrap();
+        assert!(c
+            .authentication_serv()
+            .account_attempts(user.base().id())
+            .await
+            .check()
+            .is_ok());
+    }
+}
@@ -29,7 +29,7 @@ impl<'a> Update<'a> {
     pub async fn exec(&self, auth_id: String, user_id: String, cmd: UpdateCommand) -> Result<()> {
         if auth_id != user_id {
             let auth_user = self.user_repo.find_by_id(&UserId::new(auth_id)?).await?;
-            if !auth_user.role().is("admin") {
+            if !auth_user.role().has_permissions("users", "U") {
                 return Err(Error::unauthorized());
             }
         }
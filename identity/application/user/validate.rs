@@ -1,28 +1,46 @@
+use common::error::Error;
 use common::event::EventPublisher;
 use common::result::Result;
 
-use crate::domain::user::{UserId, UserRepository, Validation};
+use crate::domain::user::{UserId, UserRepository};
+use crate::domain::verification::{VerificationKind, VerificationService};
 
 pub struct Validate<'a> {
     event_pub: &'a dyn EventPublisher,
 
     user_repo: &'a dyn UserRepository,
+
+    verification_serv: &'a VerificationService,
 }
 
 impl<'a> Validate<'a> {
-    pub fn new(event_pub: &'a dyn EventPublisher, user_repo: &'a dyn UserRepository) -> Self {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        user_repo: &'a dyn UserRepository,
+        verification_serv: &'a VerificationService,
+    ) -> Self {
         Validate {
             event_pub,
             user_repo,
+            verification_serv,
         }
     }
 
-    pub async fn exec(&self, user_id: String, validation_code: String) -> Result<()> {
+    pub async fn exec(&self, user_id: String, token: String) -> Result<()> {
         let user_id = UserId::new(user_id)?;
-        let mut user = self.user_repo.find_by_id(&user_id).await?;
+        if self
+            .verification_serv
+            .verify(&token, &VerificationKind::Email)
+            .await?
+            != user_id
+        {
+            return Err(Error::new("verification", "invalid")
+                .set_status(400)
+                .build());
+        }
 
-        let validation = Validation::from(validation_code);
-        user.validate(&validation)?;
+        let mut user = self.user_repo.find_by_id(&user_id).await?;
+        user.validate()?;
 
         self.user_repo.save(&mut user).await?;
 
@@ -36,15 +54,21 @@ impl<'a> Validate<'a> {
 mod tests {
     use super::*;
 
-    use crate::mocks;
+    use std::sync::Arc;
+
+    use crate::mocks::{self, FakeVerificationSender};
 
     #[tokio::test]
     async fn invalid_code() {
         let c = mocks::container();
-        let uc = Validate::new(c.event_pub(), c.user_repo());
+        let uc = Validate::new(c.event_pub(), c.user_repo(), c.verification_serv());
 
         let mut user = mocks::user1();
         c.user_repo().save(&mut user).await.unwrap();
+        c.verification_serv()
+            .issue(&user, VerificationKind::Email)
+            .await
+            .unwrap();
 
         assert!(uc
             .exec(user.base().id().to_string(), "invalid-123".to_owned())
@@ -54,31 +78,46 @@ mod tests {
 
     #[tokio::test]
     async fn valid_code() {
-        let c = mocks::container();
-        let uc = Validate::new(c.event_pub(), c.user_repo());
+        let sender = Arc::new(FakeVerificationSender::new());
+        let c = mocks::container_with_verification_sender(sender.clone());
+        let uc = Validate::new(c.event_pub(), c.user_repo(), c.verification_serv());
 
         let mut user = mocks::user1();
         c.user_repo().save(&mut user).await.unwrap();
         assert!(!user.is_validated());
 
+        c.verification_serv()
+            .issue(&user, VerificationKind::Email)
+            .await
+            .unwrap();
+        let token = sender
+            .token(user.base().id(), &VerificationKind::Email)
+            .unwrap();
+
+        // Other user
+        let mut other = mocks::user2();
+        c.user_repo().save(&mut other).await.unwrap();
+        c.verification_serv()
+            .issue(&other, VerificationKind::Email)
+            .await
+            .unwrap();
+        let other_token = sender
+            .token(other.base().id(), &VerificationKind::Email)
+            .unwrap();
+        assert!(uc
+            .exec(user.base().id().to_string(), other_token)
+            .await
+            .is_err());
+
         assert!(uc
-            .exec(
-                user.base().id().to_string(),
-                user.validation().unwrap().code().to_string()
-            )
+            .exec(user.base().id().to_string(), token.clone())
             .await
             .is_ok());
 
         let saved_user = c.user_repo().find_by_id(&user.base().id()).await.unwrap();
         assert!(saved_user.is_validated());
 
-        assert!(uc
-            .exec(
-                user.base().id().to_string(),
-                user.validation().unwrap().code().to_string()
-            )
-            .await
-            .is_err());
+        assert!(uc.exec(user.base().id().to_string(), token).await.is_err());
 
         assert_eq!(c.event_pub().events().await.len(), 1);
     }
@@ -3,65 +3,102 @@ use std::sync::Arc;
 use common::event::{EventPublisher, EventSubscriber};
 use common::result::Result;
 
+use crate::domain::oidc::{AuthorizationRequestRepository, OidcClient, OidcService};
 use crate::domain::role::RoleRepository;
 use crate::domain::token::{TokenEncoder, TokenRepository, TokenService};
 use crate::domain::user::{
-    AuthenticationService, AuthorizationService, PasswordHasher, UserRepository, UserService,
+    AuthenticationService, AuthorizationService, LoginAttemptRepository, PasswordHasher,
+    UserRepository, UserService,
+};
+use crate::domain::verification::{
+    VerificationRepository, VerificationSender, VerificationService,
 };
 
 pub struct Container<EPub> {
     event_pub: Arc<EPub>,
 
+    authorization_request_repo: Arc<dyn AuthorizationRequestRepository>,
+    login_attempt_repo: Arc<dyn LoginAttemptRepository>,
     role_repo: Arc<dyn RoleRepository>,
     token_repo: Arc<dyn TokenRepository>,
     user_repo: Arc<dyn UserRepository>,
+    verification_repo: Arc<dyn VerificationRepository>,
 
     password_hasher: Arc<dyn PasswordHasher>,
     token_enc: Arc<dyn TokenEncoder>,
+    oidc_client: Arc<dyn OidcClient>,
+    verification_sender: Arc<dyn VerificationSender>,
 
     token_serv: Arc<TokenService>,
     user_serv: Arc<UserService>,
     authentication_serv: Arc<AuthenticationService>,
     authorization_serv: Arc<AuthorizationService>,
+    oidc_serv: Arc<OidcService>,
+    verification_serv: Arc<VerificationService>,
 }
 
 impl<EPub> Container<EPub>
 where
-    EPub: EventPublisher,
+    EPub: EventPublisher + 'static,
 {
     pub fn new(
         event_pub: Arc<EPub>,
 
+        authorization_request_repo: Arc<dyn AuthorizationRequestRepository>,
+        login_attempt_repo: Arc<dyn LoginAttemptRepository>,
         role_repo: Arc<dyn RoleRepository>,
         token_repo: Arc<dyn TokenRepository>,
         user_repo: Arc<dyn UserRepository>,
+        verification_repo: Arc<dyn VerificationRepository>,
 
         password_hasher: Arc<dyn PasswordHasher>,
         token_enc: Arc<dyn TokenEncoder>,
+        oidc_client: Arc<dyn OidcClient>,
+        verification_sender: Arc<dyn VerificationSender>,
     ) -> Self {
         let token_serv = Arc::new(TokenService::new(token_repo.clone(), token_enc.clone()));
         let user_serv = Arc::new(UserService::new(user_repo.clone(), password_hasher.clone()));
         let authentication_serv = Arc::new(AuthenticationService::new(
+            event_pub.clone(),
+            login_attempt_repo.clone(),
+            role_repo.clone(),
             user_repo.clone(),
             password_hasher.clone(),
             token_serv.clone(),
         ));
         let authorization_serv = Arc::new(AuthorizationService::new(token_serv.clone()));
+        let oidc_serv = Arc::new(OidcService::new(
+            authorization_request_repo.clone(),
+            user_repo.clone(),
+            oidc_client.clone(),
+            authentication_serv.clone(),
+        ));
+        let verification_serv = Arc::new(VerificationService::new(
+            verification_repo.clone(),
+            verification_sender.clone(),
+        ));
 
         Container {
             event_pub,
 
+            authorization_request_repo,
+            login_attempt_repo,
             role_repo,
             token_repo,
             user_repo,
+            verification_repo,
 
             password_hasher,
             token_enc,
+            oidc_client,
+            verification_sender,
 
             token_serv,
             user_serv,
             authentication_serv,
             authorization_serv,
+            oidc_serv,
+            verification_serv,
         }
     }
 
@@ -76,6 +113,14 @@ where
         &self.event_pub
     }
 
+    pub fn authorization_request_repo(&self) -> &dyn AuthorizationRequestRepository {
+        self.authorization_request_repo.as_ref()
+    }
+
+    pub fn login_attempt_repo(&self) -> &dyn LoginAttemptRepository {
+        self.login_attempt_repo.as_ref()
+    }
+
     pub fn role_repo(&self) -> &dyn RoleRepository {
         self.role_repo.as_ref()
     }
@@ -88,6 +133,10 @@ where
         self.user_repo.as_ref()
     }
 
+    pub fn verification_repo(&self) -> &dyn VerificationRepository {
+        self.verification_repo.as_ref()
+    }
+
     // Services
     pub fn password_hasher(&self) -> &dyn PasswordHasher {
         self.password_hasher.as_ref()
@@ -97,6 +146,14 @@ where
         self.token_enc.as_ref()
     }
 
+    pub fn oidc_client(&self) -> &dyn OidcClient {
+        self.oidc_client.as_ref()
+    }
+
+    pub fn verification_sender(&self) -> &dyn VerificationSender {
+        self.verification_sender.as_ref()
+    }
+
     pub fn token_serv(&self) -> &TokenService {
         &self.token_serv
     }
@@ -112,4 +169,12 @@ where
     pub fn authorization_serv(&self) -> &AuthorizationService {
         &self.authorization_serv
     }
+
+    pub fn oidc_serv(&self) -> &OidcService {
+        &self.oidc_serv
+    }
+
+    pub fn verification_serv(&self) -> &VerificationService {
+        &self.verification_serv
+    }
 }
@@ -1,3 +1,5 @@
+pub mod oidc;
 pub mod role;
 pub mod token;
 pub mod user;
+pub mod verification;
@@ -0,0 +1,151 @@
+mod client;
+mod repository;
+mod service;
+pub use client::*;
+pub use repository::*;
+pub use service::*;
+
+use chrono::{DateTime, Duration, Utc};
+use ring::digest;
+
+use common::result::Result;
+
+use crate::domain::token::random_string;
+use crate::domain::user::Provider;
+
+// Minutes the user has to authenticate in the provider.
+const REQUEST_MINUTES: i64 = 10;
+
+// AuthorizationRequest
+// Login started with a provider, kept until the provider redirects the user back with the
+// state. The nonce is checked against the ID token and the code verifier is sent when the code
+// is exchanged (PKCE), so a stolen code cannot be used.
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+    provider: Provider,
+    state: String,
+    nonce: String,
+    code_verifier: String,
+    created_at: DateTime<Utc>,
+}
+
+impl AuthorizationRequest {
+    pub fn new(provider: Provider) -> Result<Self> {
+        Ok(AuthorizationRequest {
+            provider,
+            state: random_string()?,
+            nonce: random_string()?,
+            code_verifier: random_string()?,
+            created_at: Utc::now(),
+        })
+    }
+
+    pub fn provider(&self) -> &Provider {
+        &self.provider
+    }
+
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    pub fn code_verifier(&self) -> &str {
+        &self.code_verifier
+    }
+
+    // S256 code challenge.
+    pub fn code_challenge(&self) -> String {
+        let hash = digest::digest(&digest::SHA256, self.code_verifier.as_bytes());
+        base64::encode_config(hash.as_ref(), base64::URL_SAFE_NO_PAD)
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.created_at + Duration::minutes(REQUEST_MINUTES) < Utc::now()
+    }
+}
+
+// IdentityClaims
+// Claims of an ID token whose signature, issuer, audience and expiration were verified.
+#[derive(Debug, Clone)]
+pub struct IdentityClaims {
+    subject: String,
+    email: Option<String>,
+    email_verified: bool,
+    name: Option<String>,
+    nonce: Option<String>,
+}
+
+impl IdentityClaims {
+    pub fn new<S: Into<String>>(
+        subject: S,
+        email: Option<String>,
+        email_verified: bool,
+        name: Option<String>,
+        nonce: Option<String>,
+    ) -> Self {
+        IdentityClaims {
+            subject: subject.into(),
+            email,
+            email_verified,
+            name,
+            nonce,
+        }
+    }
+
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn email(&self) -> Option<&str> {
+        self.email.as_deref()
+    }
+
+    pub fn email_verified(&self) -> bool {
+        self.email_verified
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn nonce(&self) -> Option<&str> {
+        self.nonce.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn authorization_request() {
+        let req = AuthorizationRequest::new(Provider::Google).unwrap();
+        assert_eq!(req.state().len(), 43);
+        assert_ne!(req.state(), req.nonce());
+        assert_ne!(req.nonce(), req.code_verifier());
+        assert!(!req.is_expired());
+
+        // RFC 7636, appendix B
+        let req = AuthorizationRequest {
+            code_verifier: "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk".to_owned(),
+            ..req
+        };
+        assert_eq!(
+            req.code_challenge(),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+
+        let req = AuthorizationRequest {
+            created_at: Utc::now() - Duration::minutes(REQUEST_MINUTES + 1),
+            ..req
+        };
+        assert!(req.is_expired());
+    }
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use common::result::Result;
+
+use crate::domain::oidc::{AuthorizationRequest, IdentityClaims};
+
+// Client of the OpenID Connect providers. Requests are not sent from other threads, so the
+// returned futures are not required to be Send.
+#[async_trait(?Send)]
+pub trait OidcClient: Sync + Send {
+    // URL of the provider where the user is redirected to authenticate.
+    async fn authorization_url(&self, req: &AuthorizationRequest) -> Result<String>;
+
+    // Exchanges the authorization code for an ID token and verifies it.
+    async fn exchange_code(&self, req: &AuthorizationRequest, code: &str)
+        -> Result<IdentityClaims>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+
+use common::cache::Cache;
+
+use crate::domain::oidc::AuthorizationRequest;
+
+// Pending authorization requests by state.
+#[async_trait]
+pub trait AuthorizationRequestRepository:
+    Cache<String, AuthorizationRequest> + Sync + Send
+{
+}
@@ -0,0 +1,370 @@
+use std::sync::Arc;
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::oidc::{AuthorizationRequest, AuthorizationRequestRepository, OidcClient};
+use crate::domain::role::{Role, RoleId};
+use crate::domain::user::{
+    Authentication, AuthenticationService, Email, Identity, LinkedAccount, Provider, User,
+    UserRepository, Username,
+};
+
+pub struct OidcService {
+    authorization_request_repo: Arc<dyn AuthorizationRequestRepository>,
+    user_repo: Arc<dyn UserRepository>,
+
+    oidc_client: Arc<dyn OidcClient>,
+    authentication_serv: Arc<AuthenticationService>,
+}
+
+impl OidcService {
+    pub fn new(
+        authorization_request_repo: Arc<dyn AuthorizationRequestRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        oidc_client: Arc<dyn OidcClient>,
+        authentication_serv: Arc<AuthenticationService>,
+    ) -> Self {
+        OidcService {
+            authorization_request_repo,
+            user_repo,
+            oidc_client,
+            authentication_serv,
+        }
+    }
+
+    // Starts the login and returns the URL of the provider.
+    pub async fn authorization_url(&self, provider: Provider) -> Result<String> {
+        if provider == Provider::Local {
+            return Err(Error::new("provider", "invalid"));
+        }
+
+        let req = AuthorizationRequest::new(provider)?;
+        let url = self.oidc_client.authorization_url(&req).await?;
+        self.authorization_request_repo
+            .set(req.state().to_owned(), req)
+            .await?;
+
+        Ok(url)
+    }
+
+    // Finishes the login started with the given state. The user linked to the provider account is
+    // authenticated. The first time, the account is linked to the validated user with its verified
+    // email, or to a new user if there is none. As with passwords, users with a second factor have
+    // to finish the login with a code.
+    pub async fn authenticate(
+        &self,
+        provider: &Provider,
+        state: &str,
+        code: &str,
+        device: &str,
+    ) -> Result<(User, Authentication)> {
+        let state = state.to_owned();
+        let req = match self.authorization_request_repo.get(&state).await {
+            Some(req) => req,
+            None => return Err(Error::new("oidc", "invalid_state").set_status(401).build()),
+        };
+        // States are used once.
+        self.authorization_request_repo.delete(&state).await?;
+
+        if req.is_expired() || req.provider() != provider {
+            return Err(Error::new("oidc", "invalid_state").set_status(401).build());
+        }
+
+        let claims = self.oidc_client.exchange_code(&req, code).await?;
+        if claims.nonce() != Some(req.nonce()) {
+            return Err(Error::new("oidc", "invalid_nonce").set_status(401).build());
+        }
+
+        let email = match claims.email() {
+            Some(email) if claims.email_verified() => Email::new(email)?,
+            _ => {
+                return Err(Error::new("oidc", "unverified_email")
+                    .set_status(401)
+                    .set_message("The email of the account has not been verified")
+                    .build())
+            }
+        };
+
+        let account = LinkedAccount::new(provider.clone(), claims.subject())?;
+        let mut user = match self.user_repo.find_by_linked_account(&account).await {
+            Ok(user) => user,
+            Err(_) => match self.user_repo.find_by_email(&email).await {
+                // Anybody could have registered an account with the email without validating it,
+                // so it is not linked until its owner does.
+                Ok(user) if !user.is_validated() => {
+                    return Err(Error::new("oidc", "account_not_validated")
+                        .set_status(409)
+                        .set_message(
+                            "There is an account with this email that has not been validated",
+                        )
+                        .build())
+                }
+                Ok(user) => user,
+                Err(_) => self.register(provider, email).await?,
+            },
+        };
+        user.link_account(account)?;
+
+        let (mut user, authentication) = self.authentication_serv.identified(user, device).await?;
+        self.user_repo.save(&mut user).await?;
+
+        Ok((user, authentication))
+    }
+
+    // Registers a validated user, since the provider has verified the email. The username is
+    // taken from the email.
+    async fn register(&self, provider: &Provider, email: Email) -> Result<User> {
+        let username = self.available_username(email.value()).await?;
+
+        let mut user = User::new(
+            self.user_repo.next_id().await?,
+            Identity::new(provider.clone(), username, email, None)?,
+            Role::new(RoleId::new("user")?, "User")?,
+        )?;
+        user.validate()?;
+
+        self.user_repo.save(&mut user).await?;
+
+        Ok(user)
+    }
+
+    async fn available_username(&self, email: &str) -> Result<Username> {
+        let mut base: String = email
+            .split('@')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_' || *c == '.')
+            .collect::<String>()
+            .trim_matches(|c: char| !c.is_ascii_alphanumeric())
+            .chars()
+            .take(20)
+            .collect();
+        if base.len() < 4 {
+            base = format!("user{}", base);
+        }
+
+        for i in 0..100 {
+            let candidate = if i == 0 {
+                base.clone()
+            } else {
+                format!("{}{}", base, i)
+            };
+
+            let username = Username::new(candidate)?;
+            if self.user_repo.find_by_username(&username).await.is_err() {
+                return Ok(username);
+            }
+        }
+
+        Err(Error::new("username", "not_available"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::mocks::FakeEventPublisher;
+
+    use crate::domain::user::LoginAttempts;
+
+    use crate::container::Container;
+    use crate::mocks;
+
+    async fn start(c: &Container<FakeEventPublisher>, provider: Provider) -> String {
+        let url = c.oidc_serv().authorization_url(provider).await.unwrap();
+        url.split("state=").nth(1).unwrap().to_owned()
+    }
+
+    #[tokio::test]
+    async fn register_and_link() {
+        let c = mocks::container();
+
+        let state = start(&c, Provider::Google).await;
+        let (user, auth) = c
+            .oidc_serv()
+            .authenticate(&Provider::Google, &state, "new.user@gmail.com", "Firefox")
+            .await
+            .unwrap();
+        assert_eq!(user.identity().provider(), &Provider::Google);
+        assert_eq!(user.identity().username().value(), "new.user");
+        assert!(user.identity().password().is_none());
+        assert!(user.is_validated());
+        assert!(c
+            .token_serv()
+            .validate(auth.session().unwrap().access_token())
+            .await
+            .is_ok());
+
+        // States are used once.
+        assert!(c
+            .oidc_serv()
+            .authenticate(&Provider::Google, &state, "new.user@gmail.com", "Firefox")
+            .await
+            .is_err());
+
+        // Linked by email
+        let state = start(&c, Provider::Facebook).await;
+        let (linked, _) = c
+            .oidc_serv()
+            .authenticate(&Provider::Facebook, &state, "new.user@gmail.com", "Firefox")
+            .await
+            .unwrap();
+        assert_eq!(linked.base().id(), user.base().id());
+        assert_eq!(
+            linked
+                .linked_account(&Provider::Facebook)
+                .unwrap()
+                .subject(),
+            "facebook|new.user@gmail.com"
+        );
+
+        // Only the linked account of the provider can log in.
+        let state = start(&c, Provider::Facebook).await;
+        let err = c
+            .oidc_serv()
+            .authenticate(
+                &Provider::Facebook,
+                &state,
+                "other-account:new.user@gmail.com",
+                "Firefox",
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "account_already_linked");
+
+        let mut local = mocks::validated_user1();
+        c.user_repo().save(&mut local).await.unwrap();
+        let state = start(&c, Provider::Google).await;
+        let (linked, _) = c
+            .oidc_serv()
+            .authenticate(
+                &Provider::Google,
+                &state,
+                local.identity().email().value(),
+                "Firefox",
+            )
+            .await
+            .unwrap();
+        assert_eq!(linked.base().id(), local.base().id());
+
+        // Not validated accounts are not linked.
+        let mut local = mocks::user2();
+        c.user_repo().save(&mut local).await.unwrap();
+        let state = start(&c, Provider::Google).await;
+        let err = c
+            .oidc_serv()
+            .authenticate(
+                &Provider::Google,
+                &state,
+                local.identity().email().value(),
+                "Firefox",
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "account_not_validated");
+
+        // Username taken
+        let state = start(&c, Provider::Google).await;
+        let (user, _) = c
+            .oidc_serv()
+            .authenticate(&Provider::Google, &state, "new.user@outlook.com", "Firefox")
+            .await
+            .unwrap();
+        assert_eq!(user.identity().username().value(), "new.user1");
+    }
+
+    #[tokio::test]
+    async fn two_factor_and_lockout() {
+        let c = mocks::container();
+
+        let mut user = mocks::validated_user1();
+        let now = chrono::Utc::now().timestamp() as u64;
+        let code = user.start_two_factor().unwrap().code_at(now).unwrap();
+        user.confirm_two_factor(&code).unwrap();
+        c.user_repo().save(&mut user).await.unwrap();
+        let email = user.identity().email().value().to_owned();
+
+        let state = start(&c, Provider::Google).await;
+        let (_, auth) = c
+            .oidc_serv()
+            .authenticate(&Provider::Google, &state, &email, "Firefox")
+            .await
+            .unwrap();
+        assert!(auth.session().is_none());
+        match auth {
+            Authentication::TwoFactor { setup_required, .. } => assert!(!setup_required),
+            Authentication::Session(_) => panic!("session without second factor"),
+        }
+
+        let mut attempts = LoginAttempts::new(format!("account:{}", user.base().id().value()));
+        attempts.lock();
+        c.login_attempt_repo().save(&attempts).await.unwrap();
+
+        let state = start(&c, Provider::Google).await;
+        let err = c
+            .oidc_serv()
+            .authenticate(&Provider::Google, &state, &email, "Firefox")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "too_many_attempts");
+    }
+
+    #[tokio::test]
+    async fn invalid() {
+        let c = mocks::container();
+
+        assert!(c
+            .oidc_serv()
+            .authorization_url(Provider::Local)
+            .await
+            .is_err());
+
+        // Unknown state
+        assert!(c
+            .oidc_serv()
+            .authenticate(&Provider::Google, "state", "new.user@gmail.com", "Firefox")
+            .await
+            .is_err());
+
+        // Other provider
+        let state = start(&c, Provider::Google).await;
+        assert!(c
+            .oidc_serv()
+            .authenticate(&Provider::Facebook, &state, "new.user@gmail.com", "Firefox")
+            .await
+            .is_err());
+
+        let state = start(&c, Provider::Google).await;
+        assert!(c
+            .oidc_serv()
+            .authenticate(
+                &Provider::Google,
+                &state,
+                "unverified:new.user@gmail.com",
+                "Firefox"
+            )
+            .await
+            .is_err());
+
+        let state = start(&c, Provider::Google).await;
+        assert!(c
+            .oidc_serv()
+            .authenticate(
+                &Provider::Google,
+                &state,
+                "invalid-nonce:new.user@gmail.com",
+                "Firefox"
+            )
+            .await
+            .is_err());
+
+        assert!(c
+            .user_repo()
+            .find_by_email(&Email::new("new.user@gmail.com").unwrap())
+            .await
+            .is_err());
+    }
+}
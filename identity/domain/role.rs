@@ -3,6 +3,7 @@ mod repository;
 pub use permission::*;
 pub use repository::*;
 
+use common::error::Error;
 use common::event::Event;
 use common::model::{AggregateRoot, StringId};
 use common::result::Result;
@@ -14,16 +15,23 @@ pub struct Role {
     base: AggregateRoot<RoleId, Event>,
     name: String,
     permissions: Vec<Permission>,
+    two_factor_required: bool,
 }
 
 impl Role {
     pub fn new<S: Into<String>>(code: RoleId, name: S) -> Result<Self> {
         let name = name.into();
+        if name.is_empty() {
+            return Err(Error::new("role", "invalid")
+                .add_context("name", "empty")
+                .build());
+        }
 
         Ok(Role {
             base: AggregateRoot::new(code),
             name,
             permissions: Vec::new(),
+            two_factor_required: false,
         })
     }
 
@@ -39,6 +47,15 @@ impl Role {
         &self.name
     }
 
+    pub fn permissions(&self) -> &[Permission] {
+        &self.permissions
+    }
+
+    // Roles the application relies on, which cannot be deleted.
+    pub fn is_default(&self) -> bool {
+        self.is("admin") || self.is("user")
+    }
+
     pub fn has_permissions(&self, module: &str, permissions: &str) -> bool {
         for p in self.permissions.iter() {
             if p.module() == module && p.contains(permissions) {
@@ -51,6 +68,47 @@ impl Role {
     pub fn add_permissions(&mut self, permission: Permission) {
         self.permissions.push(permission);
     }
+
+    pub fn set_name<S: Into<String>>(&mut self, name: S) -> Result<()> {
+        let name = name.into();
+        if name.is_empty() {
+            return Err(Error::new("role", "invalid")
+                .add_context("name", "empty")
+                .build());
+        }
+
+        self.name = name;
+        self.base.update();
+
+        Ok(())
+    }
+
+    pub fn set_permissions(&mut self, permissions: Vec<Permission>) {
+        self.permissions = permissions;
+        self.base.update();
+    }
+
+    pub fn delete(&mut self) -> Result<()> {
+        if self.is_default() {
+            return Err(Error::new("role", "default")
+                .set_message("Default roles cannot be deleted")
+                .build());
+        }
+
+        self.base.delete();
+
+        Ok(())
+    }
+
+    // Users with this role cannot log in without a second factor.
+    pub fn two_factor_required(&self) -> bool {
+        self.two_factor_required
+    }
+
+    pub fn set_two_factor_required(&mut self, required: bool) {
+        self.two_factor_required = required;
+        self.base.update();
+    }
 }
 
 #[cfg(test)]
@@ -92,4 +150,29 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn update() -> Result<()> {
+        let mut r = Role::new(RoleId::new("moderator").unwrap(), "Moderator")?;
+        assert!(Role::new(RoleId::new("moderator").unwrap(), "").is_err());
+        assert!(r.set_name("").is_err());
+
+        r.set_name("Mod")?;
+        r.set_permissions(vec![Permission::new("users", "RU")?]);
+        assert_eq!(r.name(), "Mod");
+        assert!(r.has_permissions("users", "U"));
+        assert!(!r.has_permissions("users", "D"));
+
+        r.set_permissions(Vec::new());
+        assert!(r.permissions().is_empty());
+        assert!(!r.has_permissions("users", "R"));
+
+        r.delete()?;
+        assert!(r.base().deleted_at().is_some());
+        assert!(Role::new(RoleId::new("admin").unwrap(), "Administrator")?
+            .delete()
+            .is_err());
+
+        Ok(())
+    }
 }
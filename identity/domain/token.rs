@@ -1,15 +1,24 @@
 mod encoder;
+mod personal_access_token;
 mod repository;
 mod service;
+mod session;
 pub use encoder::*;
+pub use personal_access_token::*;
 pub use repository::*;
 pub use service::*;
+pub use session::*;
 
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
+use ring::digest;
+use ring::rand::{SecureRandom, SystemRandom};
 use uuid::Uuid;
 
+use common::error::Error;
+use common::result::Result;
+
 // TokenId
 #[derive(Default, Debug, Clone, Eq)]
 pub struct TokenId {
@@ -71,6 +80,12 @@ impl Token {
     pub fn value(&self) -> &str {
         &self.token
     }
+
+    // Tokens that are stored to be checked later are stored hashed.
+    pub fn hash(&self) -> String {
+        let hash = digest::digest(&digest::SHA256, self.token.as_bytes());
+        base64::encode_config(hash.as_ref(), base64::URL_SAFE_NO_PAD)
+    }
 }
 
 impl PartialEq for Token {
@@ -106,3 +121,12 @@ impl Data {
         self.data.get(&k.into())
     }
 }
+
+// 32 random bytes, URL safe encoded.
+pub(crate) fn random_string() -> Result<String> {
+    let mut bytes = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| Error::internal("token", "random"))?;
+    Ok(base64::encode_config(bytes, base64::URL_SAFE_NO_PAD))
+}
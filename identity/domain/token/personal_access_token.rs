@@ -0,0 +1,205 @@
+use chrono::{DateTime, Utc};
+
+use common::error::Error;
+use common::model::StringId;
+use common::result::Result;
+
+use crate::domain::role::Permission;
+use crate::domain::token::{random_string, Token};
+use crate::domain::user::UserId;
+
+pub type PersonalAccessTokenId = StringId;
+
+// Tokens are recognized by their prefix, so they are not mistaken for session tokens.
+const PREFIX: &str = "omics_pat_";
+
+// A named, long-lived token a user creates to call the API from scripts. It is limited to its
+// scopes, which are permissions like those of the roles, and only the hash of the token is stored.
+#[derive(Debug, Clone)]
+pub struct PersonalAccessToken {
+    id: PersonalAccessTokenId,
+    user_id: UserId,
+    name: String,
+    token_hash: String,
+    scopes: Vec<Permission>,
+    created_at: DateTime<Utc>,
+    expires_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl PersonalAccessToken {
+    // Returns the personal access token and the token to give to the user.
+    pub fn new<S: Into<String>>(
+        id: PersonalAccessTokenId,
+        user_id: UserId,
+        name: S,
+        scopes: Vec<Permission>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(Self, Token)> {
+        let name = name.into();
+
+        let mut err = Error::new("personal_access_token", "invalid");
+        if name.trim().is_empty() {
+            err.add_context("name", "empty");
+        }
+
+        if scopes.is_empty() {
+            err.add_context("scopes", "empty");
+        }
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= Utc::now() {
+                err.add_context("expires_at", "past");
+            }
+        }
+
+        if err.has_context() {
+            return Err(err);
+        }
+
+        let token = Token::new(format!("{}{}", PREFIX, random_string()?));
+
+        Ok((
+            PersonalAccessToken {
+                id,
+                user_id,
+                name: name.trim().to_owned(),
+                token_hash: Self::hash(&token),
+                scopes,
+                created_at: Utc::now(),
+                expires_at,
+                last_used_at: None,
+                revoked_at: None,
+            },
+            token,
+        ))
+    }
+
+    pub fn is_personal_access_token(token: &Token) -> bool {
+        token.value().starts_with(PREFIX)
+    }
+
+    pub fn hash(token: &Token) -> String {
+        token.hash()
+    }
+
+    pub fn id(&self) -> &PersonalAccessTokenId {
+        &self.id
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn token_hash(&self) -> &str {
+        &self.token_hash
+    }
+
+    pub fn scopes(&self) -> &[Permission] {
+        &self.scopes
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn expires_at(&self) -> Option<&DateTime<Utc>> {
+        self.expires_at.as_ref()
+    }
+
+    pub fn last_used_at(&self) -> Option<&DateTime<Utc>> {
+        self.last_used_at.as_ref()
+    }
+
+    pub fn revoked_at(&self) -> Option<&DateTime<Utc>> {
+        self.revoked_at.as_ref()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+            && self
+                .expires_at
+                .map(|expires_at| expires_at > Utc::now())
+                .unwrap_or(true)
+    }
+
+    pub fn touch(&mut self) {
+        self.last_used_at = Some(Utc::now());
+    }
+
+    pub fn revoke(&mut self) {
+        if self.revoked_at.is_none() {
+            self.revoked_at = Some(Utc::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+
+    fn scopes() -> Vec<Permission> {
+        vec![Permission::new("publications", "CR").unwrap()]
+    }
+
+    #[test]
+    fn create() {
+        let (pat, token) = PersonalAccessToken::new(
+            PersonalAccessTokenId::new("#pat01").unwrap(),
+            UserId::new("#user01").unwrap(),
+            " Uploads ",
+            scopes(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(pat.name(), "Uploads");
+        assert!(PersonalAccessToken::is_personal_access_token(&token));
+        assert_ne!(pat.token_hash(), token.value());
+        assert_eq!(pat.token_hash(), PersonalAccessToken::hash(&token));
+        assert!(pat.is_active());
+
+        assert!(PersonalAccessToken::new(
+            PersonalAccessTokenId::new("#pat01").unwrap(),
+            UserId::new("#user01").unwrap(),
+            "",
+            Vec::new(),
+            None,
+        )
+        .is_err());
+        assert!(PersonalAccessToken::new(
+            PersonalAccessTokenId::new("#pat01").unwrap(),
+            UserId::new("#user01").unwrap(),
+            "Uploads",
+            scopes(),
+            Some(Utc::now() - Duration::days(1)),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn expire_and_revoke() {
+        let (mut pat, _) = PersonalAccessToken::new(
+            PersonalAccessTokenId::new("#pat01").unwrap(),
+            UserId::new("#user01").unwrap(),
+            "Uploads",
+            scopes(),
+            Some(Utc::now() + Duration::days(1)),
+        )
+        .unwrap();
+        assert!(pat.is_active());
+
+        pat.expires_at = Some(Utc::now() - Duration::seconds(1));
+        assert!(!pat.is_active());
+
+        pat.expires_at = None;
+        pat.revoke();
+        assert!(!pat.is_active());
+    }
+}
@@ -1,8 +1,35 @@
 use async_trait::async_trait;
 
 use common::cache::Cache;
+use common::result::Result;
 
-use crate::domain::token::{Data, TokenId};
+use crate::domain::token::{
+    Data, PersonalAccessToken, PersonalAccessTokenId, Session, SessionId, TokenId,
+};
+use crate::domain::user::UserId;
 
 #[async_trait]
-pub trait TokenRepository: Cache<TokenId, Data> + Sync + Send {}
+pub trait TokenRepository: Cache<TokenId, Data> + Sync + Send {
+    async fn find_session_by_id(&self, id: &SessionId) -> Result<Session>;
+    // Finds the session by the hash of its current refresh token or of any of the already used
+    // ones.
+    async fn find_session_by_refresh_token_hash(&self, token_hash: &str) -> Result<Session>;
+    async fn find_sessions_by_user_id(&self, user_id: &UserId) -> Result<Vec<Session>>;
+
+    async fn save_session(&self, session: &Session) -> Result<()>;
+
+    async fn find_personal_access_token_by_id(
+        &self,
+        id: &PersonalAccessTokenId,
+    ) -> Result<PersonalAccessToken>;
+    async fn find_personal_access_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<PersonalAccessToken>;
+    async fn find_personal_access_tokens_by_user_id(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Vec<PersonalAccessToken>>;
+
+    async fn save_personal_access_token(&self, token: &PersonalAccessToken) -> Result<()>;
+}
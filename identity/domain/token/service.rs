@@ -1,9 +1,43 @@
 use std::sync::Arc;
 
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
 use common::error::Error;
 use common::result::Result;
 
-use crate::domain::token::{Data, Token, TokenEncoder, TokenId, TokenRepository};
+use crate::domain::role::Permission;
+use crate::domain::token::{
+    random_string, Data, PersonalAccessToken, PersonalAccessTokenId, Session, SessionId, Token,
+    TokenEncoder, TokenId, TokenRepository,
+};
+use crate::domain::user::UserId;
+
+// Refresh tokens, and so sessions, are valid for 30 days since they are created.
+const SESSION_DAYS: i64 = 30;
+
+// Tokens issued when a session is created or refreshed. The access token is short-lived and the
+// refresh token is used once to get a new pair.
+#[derive(Debug, Clone)]
+pub struct SessionTokens {
+    session_id: SessionId,
+    access_token: Token,
+    refresh_token: Token,
+}
+
+impl SessionTokens {
+    pub fn session_id(&self) -> &SessionId {
+        &self.session_id
+    }
+
+    pub fn access_token(&self) -> &Token {
+        &self.access_token
+    }
+
+    pub fn refresh_token(&self) -> &Token {
+        &self.refresh_token
+    }
+}
 
 pub struct TokenService {
     token_repo: Arc<dyn TokenRepository>,
@@ -29,17 +63,221 @@ impl TokenService {
 
     pub async fn validate(&self, token: &Token) -> Result<Data> {
         let token_id = self.token_enc.decode(token)?;
-        if let Some(data) = self.token_repo.get(&token_id).await {
-            return Ok(data);
+        let data = match self.token_repo.get(&token_id).await {
+            Some(data) => data,
+            None => return Err(Error::new("token", "not_found")),
+        };
+
+        // Access tokens of a revoked session are not valid anymore.
+        if let Some(session_id) = data.get("session_id") {
+            let mut session = self
+                .token_repo
+                .find_session_by_id(&SessionId::new(session_id.as_str())?)
+                .await?;
+            if !session.is_active() {
+                return Err(Error::new("session", "inactive"));
+            }
+
+            session.touch();
+            self.token_repo.save_session(&session).await?;
         }
-        Err(Error::new("token", "not_found"))
+
+        Ok(data)
     }
 
     pub async fn invalidate(&self, token: &Token) -> Result<()> {
         let token_id = self.token_enc.decode(token)?;
+
+        if let Some(session_id) = self
+            .token_repo
+            .get(&token_id)
+            .await
+            .and_then(|data| data.get("session_id").cloned())
+        {
+            let mut session = self
+                .token_repo
+                .find_session_by_id(&SessionId::new(session_id)?)
+                .await?;
+            session.revoke();
+            self.token_repo.save_session(&session).await?;
+        }
+
         self.token_repo.delete(&token_id).await?;
         Ok(())
     }
+
+    pub async fn create_session(&self, user_id: &UserId, device: &str) -> Result<SessionTokens> {
+        let refresh_token = Token::new(random_string()?);
+        let session = Session::new(
+            SessionId::new(Uuid::new_v4().to_string())?,
+            user_id.clone(),
+            device,
+            &refresh_token,
+            Duration::days(SESSION_DAYS),
+        );
+        self.token_repo.save_session(&session).await?;
+
+        self.session_tokens(&session, refresh_token).await
+    }
+
+    // Session the refresh token was issued for, even if it was already used.
+    pub async fn session_by_refresh_token(&self, refresh_token: &Token) -> Result<Session> {
+        self.token_repo
+            .find_session_by_refresh_token_hash(&refresh_token.hash())
+            .await
+            .map_err(|err| {
+                Error::new("refresh_token", "invalid")
+                    .set_status(401)
+                    .wrap(err)
+                    .build()
+            })
+    }
+
+    // Rotates the refresh token. Using a refresh token twice revokes its session.
+    pub async fn refresh(&self, refresh_token: &Token) -> Result<SessionTokens> {
+        let mut session = self.session_by_refresh_token(refresh_token).await?;
+
+        let new_refresh_token = Token::new(random_string()?);
+        let res = session.rotate(refresh_token, &new_refresh_token);
+        self.token_repo.save_session(&session).await?;
+        res?;
+
+        self.session_tokens(&session, new_refresh_token).await
+    }
+
+    // Active sessions of the user, the oldest first.
+    pub async fn sessions(&self, user_id: &UserId) -> Result<Vec<Session>> {
+        let mut sessions: Vec<Session> = self
+            .token_repo
+            .find_sessions_by_user_id(user_id)
+            .await?
+            .into_iter()
+            .filter(|session| session.is_active())
+            .collect();
+        sessions.sort_by(|a, b| a.created_at().cmp(b.created_at()));
+
+        Ok(sessions)
+    }
+
+    pub async fn revoke_session(&self, user_id: &UserId, session_id: &SessionId) -> Result<()> {
+        let mut session = self.token_repo.find_session_by_id(session_id).await?;
+        if session.user_id() != user_id {
+            return Err(Error::new("session", "not_found"));
+        }
+
+        session.revoke();
+        self.token_repo.save_session(&session).await
+    }
+
+    // Logs the user out everywhere.
+    pub async fn revoke_all(&self, user_id: &UserId) -> Result<()> {
+        for mut session in self
+            .token_repo
+            .find_sessions_by_user_id(user_id)
+            .await?
+            .into_iter()
+        {
+            if session.revoked_at().is_none() {
+                session.revoke();
+                self.token_repo.save_session(&session).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Returns the personal access token and the token, which is only shown once.
+    pub async fn create_personal_access_token(
+        &self,
+        user_id: &UserId,
+        name: &str,
+        scopes: Vec<Permission>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(PersonalAccessToken, Token)> {
+        let (personal_access_token, token) = PersonalAccessToken::new(
+            PersonalAccessTokenId::new(Uuid::new_v4().to_string())?,
+            user_id.clone(),
+            name,
+            scopes,
+            expires_at,
+        )?;
+        self.token_repo
+            .save_personal_access_token(&personal_access_token)
+            .await?;
+
+        Ok((personal_access_token, token))
+    }
+
+    // Checks a personal access token and records it has been used.
+    pub async fn validate_personal_access_token(
+        &self,
+        token: &Token,
+    ) -> Result<PersonalAccessToken> {
+        let mut personal_access_token = self
+            .token_repo
+            .find_personal_access_token_by_hash(&PersonalAccessToken::hash(token))
+            .await?;
+        if !personal_access_token.is_active() {
+            return Err(Error::new("personal_access_token", "inactive"));
+        }
+
+        personal_access_token.touch();
+        self.token_repo
+            .save_personal_access_token(&personal_access_token)
+            .await?;
+
+        Ok(personal_access_token)
+    }
+
+    // Active personal access tokens of the user, the oldest first.
+    pub async fn personal_access_tokens(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Vec<PersonalAccessToken>> {
+        let mut tokens: Vec<PersonalAccessToken> = self
+            .token_repo
+            .find_personal_access_tokens_by_user_id(user_id)
+            .await?
+            .into_iter()
+            .filter(|token| token.is_active())
+            .collect();
+        tokens.sort_by(|a, b| a.created_at().cmp(b.created_at()));
+
+        Ok(tokens)
+    }
+
+    pub async fn revoke_personal_access_token(
+        &self,
+        user_id: &UserId,
+        id: &PersonalAccessTokenId,
+    ) -> Result<()> {
+        let mut personal_access_token =
+            self.token_repo.find_personal_access_token_by_id(id).await?;
+        if personal_access_token.user_id() != user_id {
+            return Err(Error::new("personal_access_token", "not_found"));
+        }
+
+        personal_access_token.revoke();
+        self.token_repo
+            .save_personal_access_token(&personal_access_token)
+            .await
+    }
+
+    async fn session_tokens(
+        &self,
+        session: &Session,
+        refresh_token: Token,
+    ) -> Result<SessionTokens> {
+        let mut data = Data::new();
+        data.add("user_id", session.user_id().value());
+        data.add("session_id", session.id().value());
+
+        Ok(SessionTokens {
+            session_id: session.id().clone(),
+            access_token: self.create(data).await?,
+            refresh_token,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -68,4 +306,129 @@ mod tests {
 
         assert!(serv.validate(&token).await.is_err());
     }
+
+    #[tokio::test]
+    async fn refresh() {
+        let c = mocks::container();
+        let serv = c.token_serv();
+        let user_id = UserId::new("#user01").unwrap();
+
+        let tokens = serv.create_session(&user_id, "Firefox").await.unwrap();
+        let data = serv.validate(tokens.access_token()).await.unwrap();
+        assert_eq!(data.get("user_id"), Some(&"#user01".to_owned()));
+
+        let refreshed = serv.refresh(tokens.refresh_token()).await.unwrap();
+        assert_eq!(refreshed.session_id(), tokens.session_id());
+        assert_ne!(refreshed.refresh_token(), tokens.refresh_token());
+        assert!(serv.validate(refreshed.access_token()).await.is_ok());
+        assert!(serv.refresh(&Token::new("invalid")).await.is_err());
+
+        // Reusing a refresh token revokes the whole family.
+        assert!(serv.refresh(tokens.refresh_token()).await.is_err());
+        assert!(serv.validate(tokens.access_token()).await.is_err());
+        assert!(serv.validate(refreshed.access_token()).await.is_err());
+        assert!(serv.refresh(refreshed.refresh_token()).await.is_err());
+        assert!(serv.sessions(&user_id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sessions() {
+        let c = mocks::container();
+        let serv = c.token_serv();
+        let user_id = UserId::new("#user01").unwrap();
+        let other_user_id = UserId::new("#user02").unwrap();
+
+        let firefox = serv.create_session(&user_id, "Firefox").await.unwrap();
+        let chrome = serv.create_session(&user_id, "Chrome").await.unwrap();
+        let other = serv.create_session(&other_user_id, "Safari").await.unwrap();
+
+        let sessions = serv.sessions(&user_id).await.unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].device(), "Firefox");
+        assert_eq!(sessions[1].device(), "Chrome");
+
+        assert!(serv
+            .revoke_session(&other_user_id, firefox.session_id())
+            .await
+            .is_err());
+        serv.revoke_session(&user_id, firefox.session_id())
+            .await
+            .unwrap();
+        assert!(serv.validate(firefox.access_token()).await.is_err());
+        assert!(serv.validate(chrome.access_token()).await.is_ok());
+        assert_eq!(serv.sessions(&user_id).await.unwrap().len(), 1);
+
+        serv.revoke_all(&user_id).await.unwrap();
+        assert!(serv.validate(chrome.access_token()).await.is_err());
+        assert!(serv.refresh(chrome.refresh_token()).await.is_err());
+        assert!(serv.sessions(&user_id).await.unwrap().is_empty());
+        assert!(serv.validate(other.access_token()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn personal_access_tokens() {
+        let c = mocks::container();
+        let serv = c.token_serv();
+        let user_id = UserId::new("#user01").unwrap();
+        let other_user_id = UserId::new("#user02").unwrap();
+
+        let (uploads, uploads_token) = serv
+            .create_personal_access_token(
+                &user_id,
+                "Uploads",
+                vec![Permission::new("publications", "C").unwrap()],
+                None,
+            )
+            .await
+            .unwrap();
+        let (_, moderation_token) = serv
+            .create_personal_access_token(
+                &user_id,
+                "Moderation",
+                vec![Permission::new("publications", "RU").unwrap()],
+                Some(Utc::now() + Duration::days(7)),
+            )
+            .await
+            .unwrap();
+
+        // They are not session tokens.
+        assert!(serv.validate(&uploads_token).await.is_err());
+
+        let validated = serv
+            .validate_personal_access_token(&uploads_token)
+            .await
+            .unwrap();
+        assert_eq!(validated.id(), uploads.id());
+        assert!(validated.last_used_at().is_some());
+        assert!(serv
+            .validate_personal_access_token(&Token::new("omics_pat_invalid"))
+            .await
+            .is_err());
+
+        let tokens = serv.personal_access_tokens(&user_id).await.unwrap();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].name(), "Uploads");
+        assert!(tokens[0].last_used_at().is_some());
+        assert!(tokens[1].last_used_at().is_none());
+
+        assert!(serv
+            .revoke_personal_access_token(&other_user_id, uploads.id())
+            .await
+            .is_err());
+        serv.revoke_personal_access_token(&user_id, uploads.id())
+            .await
+            .unwrap();
+        assert!(serv
+            .validate_personal_access_token(&uploads_token)
+            .await
+            .is_err());
+        assert!(serv
+            .validate_personal_access_token(&moderation_token)
+            .await
+            .is_ok());
+        assert_eq!(
+            serv.personal_access_tokens(&user_id).await.unwrap().len(),
+            1
+        );
+    }
 }
@@ -0,0 +1,184 @@
+use chrono::{DateTime, Duration, Utc};
+
+use common::error::Error;
+use common::model::StringId;
+use common::result::Result;
+
+use crate::domain::token::Token;
+use crate::domain::user::UserId;
+
+pub type SessionId = StringId;
+
+// A login of a user in a device. Every refresh token issued for the session belongs to the same
+// family: refreshing rotates the current refresh token, and presenting one that was already used
+// revokes the whole session. Only the hashes of the refresh tokens are stored.
+#[derive(Debug, Clone)]
+pub struct Session {
+    id: SessionId,
+    user_id: UserId,
+    device: String,
+    refresh_token_hash: String,
+    used_refresh_token_hashes: Vec<String>,
+    created_at: DateTime<Utc>,
+    last_used_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl Session {
+    pub fn new<S: Into<String>>(
+        id: SessionId,
+        user_id: UserId,
+        device: S,
+        refresh_token: &Token,
+        duration: Duration,
+    ) -> Self {
+        let now = Utc::now();
+
+        Session {
+            id,
+            user_id,
+            device: device.into(),
+            refresh_token_hash: refresh_token.hash(),
+            used_refresh_token_hashes: Vec::new(),
+            created_at: now,
+            last_used_at: now,
+            expires_at: now + duration,
+            revoked_at: None,
+        }
+    }
+
+    pub fn id(&self) -> &SessionId {
+        &self.id
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    pub fn refresh_token_hash(&self) -> &str {
+        &self.refresh_token_hash
+    }
+
+    pub fn used_refresh_token_hashes(&self) -> &[String] {
+        &self.used_refresh_token_hashes
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn last_used_at(&self) -> &DateTime<Utc> {
+        &self.last_used_at
+    }
+
+    pub fn expires_at(&self) -> &DateTime<Utc> {
+        &self.expires_at
+    }
+
+    pub fn revoked_at(&self) -> Option<&DateTime<Utc>> {
+        self.revoked_at.as_ref()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && self.expires_at > Utc::now()
+    }
+
+    pub fn touch(&mut self) {
+        self.last_used_at = Utc::now();
+    }
+
+    // Replaces the current refresh token. A token that was already used means it has been stolen,
+    // so the session is revoked.
+    pub fn rotate(&mut self, used: &Token, refresh_token: &Token) -> Result<()> {
+        if !self.is_active() {
+            return Err(Error::new("session", "inactive").set_status(401).build());
+        }
+
+        if used.hash() != self.refresh_token_hash {
+            self.revoke();
+            return Err(Error::new("refresh_token", "reused")
+                .set_status(401)
+                .set_message("Refresh token has already been used, session revoked")
+                .build());
+        }
+
+        let used = std::mem::replace(&mut self.refresh_token_hash, refresh_token.hash());
+        self.used_refresh_token_hashes.push(used);
+        self.touch();
+
+        Ok(())
+    }
+
+    pub fn revoke(&mut self) {
+        if self.revoked_at.is_none() {
+            self.revoked_at = Some(Utc::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> Session {
+        Session::new(
+            SessionId::new("#session01").unwrap(),
+            UserId::new("#user01").unwrap(),
+            "Firefox",
+            &Token::new("#refresh01"),
+            Duration::days(1),
+        )
+    }
+
+    #[test]
+    fn rotate() {
+        let mut session = session();
+        assert!(session.is_active());
+
+        session
+            .rotate(&Token::new("#refresh01"), &Token::new("#refresh02"))
+            .unwrap();
+        assert_eq!(
+            session.refresh_token_hash(),
+            Token::new("#refresh02").hash()
+        );
+        assert_ne!(session.refresh_token_hash(), "#refresh02");
+        assert_eq!(session.used_refresh_token_hashes().len(), 1);
+
+        session
+            .rotate(&Token::new("#refresh02"), &Token::new("#refresh03"))
+            .unwrap();
+        assert!(session.is_active());
+
+        // Reused
+        assert!(session
+            .rotate(&Token::new("#refresh01"), &Token::new("#refresh04"))
+            .is_err());
+        assert!(!session.is_active());
+        assert!(session.revoked_at().is_some());
+
+        assert!(session
+            .rotate(&Token::new("#refresh03"), &Token::new("#refresh04"))
+            .is_err());
+    }
+
+    #[test]
+    fn expired() {
+        let mut session = Session::new(
+            SessionId::new("#session01").unwrap(),
+            UserId::new("#user01").unwrap(),
+            "Firefox",
+            &Token::new("#refresh01"),
+            Duration::seconds(-1),
+        );
+        assert!(!session.is_active());
+        assert!(session
+            .rotate(&Token::new("#refresh01"), &Token::new("#refresh02"))
+            .is_err());
+    }
+}
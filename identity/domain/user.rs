@@ -3,27 +3,35 @@ mod authorization_service;
 mod email;
 mod fullname;
 mod identity;
+mod linked_account;
+mod login_attempt_repository;
+mod login_attempts;
 mod password;
 mod password_hasher;
 mod person;
 mod provider;
 mod repository;
 mod service;
+mod two_factor;
 mod username;
-mod validation;
 pub use self::identity::*;
 pub use authentication_service::*;
 pub use authorization_service::*;
 pub use email::*;
 pub use fullname::*;
+pub use linked_account::*;
+pub use login_attempt_repository::*;
+pub use login_attempts::*;
 pub use password::*;
 pub use password_hasher::*;
 pub use person::*;
 pub use provider::*;
 pub use repository::*;
 pub use service::*;
+pub use two_factor::*;
 pub use username::*;
-pub use validation::*;
+
+use chrono::{DateTime, Utc};
 
 use common::error::Error;
 use common::model::{AggregateRoot, StringId};
@@ -31,7 +39,6 @@ use common::result::Result;
 use shared::event::UserEvent;
 
 use crate::domain::role::Role;
-use crate::domain::token::Token;
 
 pub type UserId = StringId;
 
@@ -41,7 +48,9 @@ pub struct User {
     identity: Identity,
     person: Option<Person>,
     role: Role,
-    validation: Option<Validation>,
+    validated: bool,
+    two_factor: Option<TwoFactor>,
+    linked_accounts: Vec<LinkedAccount>,
 }
 
 impl User {
@@ -51,14 +60,15 @@ impl User {
             identity,
             person: None,
             role,
-            validation: Some(Validation::new()),
+            validated: false,
+            two_factor: None,
+            linked_accounts: Vec::new(),
         };
 
         user.base.record_event(UserEvent::Registered {
             id: user.base().id().to_string(),
             username: user.identity().username().to_string(),
-            email: user.identity().username().to_string(),
-            validation_code: user.validation().unwrap().code().to_string(),
+            email: user.identity().email().to_string(),
         });
 
         Ok(user)
@@ -69,14 +79,18 @@ impl User {
         identity: Identity,
         person: Option<Person>,
         role: Role,
-        validation: Option<Validation>,
+        validated: bool,
+        two_factor: Option<TwoFactor>,
+        linked_accounts: Vec<LinkedAccount>,
     ) -> Self {
         User {
             base,
             identity,
             person,
             role,
-            validation,
+            validated,
+            two_factor,
+            linked_accounts,
         }
     }
 
@@ -84,6 +98,10 @@ impl User {
         &self.base
     }
 
+    pub fn base_mut(&mut self) -> &mut AggregateRoot<UserId, UserEvent> {
+        &mut self.base
+    }
+
     pub fn identity(&self) -> &Identity {
         &self.identity
     }
@@ -96,12 +114,29 @@ impl User {
         &self.role
     }
 
-    pub fn validation(&self) -> Option<&Validation> {
-        self.validation.as_ref()
+    pub fn is_validated(&self) -> bool {
+        self.validated
     }
 
-    pub fn is_validated(&self) -> bool {
-        self.validation.is_none()
+    pub fn two_factor(&self) -> Option<&TwoFactor> {
+        self.two_factor.as_ref()
+    }
+
+    pub fn has_two_factor(&self) -> bool {
+        self.two_factor
+            .as_ref()
+            .map(|two_factor| two_factor.is_enabled())
+            .unwrap_or(false)
+    }
+
+    pub fn linked_accounts(&self) -> &[LinkedAccount] {
+        &self.linked_accounts
+    }
+
+    pub fn linked_account(&self, provider: &Provider) -> Option<&LinkedAccount> {
+        self.linked_accounts
+            .iter()
+            .find(|account| account.provider() == provider)
     }
 
     pub fn is_active(&self) -> bool {
@@ -126,7 +161,7 @@ impl User {
     }
 
     pub fn change_role(&mut self, role: Role, admin: &User) -> Result<()> {
-        if !admin.role().is("admin") {
+        if !admin.role().has_permissions("roles", "U") {
             return Err(Error::unauthorized());
         }
 
@@ -135,34 +170,56 @@ impl User {
         Ok(())
     }
 
-    pub fn validate(&mut self, val: &Validation) -> Result<()> {
+    // Keeps the role of the user up to date when it is changed.
+    pub fn refresh_role(&mut self, role: Role) -> Result<()> {
+        if role.base().id() != self.role.base().id() {
+            return Err(Error::new("user", "invalid_role"));
+        }
+
+        self.role = role;
+
+        Ok(())
+    }
+
+    // The email has been verified.
+    pub fn validate(&mut self) -> Result<()> {
         if self.is_validated() {
             return Err(Error::new("user", "already_validated"));
         }
 
-        self.validation = match self.validation.take() {
-            Some(validation) => {
-                if &validation == val {
-                    None
-                } else {
-                    Some(validation)
-                }
-            }
-            None => None,
-        };
+        self.validated = true;
 
-        if !self.is_validated() {
-            return Err(Error::new("user", "not_validated"));
+        self.base.record_event(UserEvent::Validated {
+            id: self.base().id().to_string(),
+        });
+
+        Ok(())
+    }
+
+    // Only one account of each provider can be linked, so it cannot be replaced by another one
+    // with the same email.
+    pub fn link_account(&mut self, account: LinkedAccount) -> Result<()> {
+        match self.linked_account(account.provider()) {
+            Some(linked) if linked == &account => return Ok(()),
+            Some(_) => {
+                return Err(Error::new("user", "account_already_linked")
+                    .set_status(409)
+                    .build())
+            }
+            None => {}
         }
 
-        self.base.record_event(UserEvent::Validated {
+        self.base.record_event(UserEvent::AccountLinked {
             id: self.base().id().to_string(),
+            provider: account.provider().to_string(),
         });
 
+        self.linked_accounts.push(account);
+
         Ok(())
     }
 
-    pub fn login(&mut self, token: &Token) -> Result<()> {
+    pub fn login(&mut self) -> Result<()> {
         if !self.is_validated() {
             return Err(Error::new("user", "not_validated"));
         }
@@ -173,24 +230,122 @@ impl User {
 
         self.base.record_event(UserEvent::LoggedIn {
             id: self.base().id().to_string(),
-            auth_token: token.to_string(),
         });
 
         Ok(())
     }
 
-    pub fn recover_password(&mut self, password: Password, temp_password: &str) -> Result<()> {
-        self.identity.set_password(password)?;
+    // Generates a new secret, replacing any other that has not been confirmed.
+    pub fn start_two_factor(&mut self) -> Result<&TwoFactor> {
+        if self.has_two_factor() {
+            return Err(Error::new("two_factor", "already_enabled"));
+        }
+
+        Ok(self.two_factor.insert(TwoFactor::new()?))
+    }
+
+    // Enables the second factor with a first code and returns the recovery codes.
+    pub fn confirm_two_factor(&mut self, code: &str) -> Result<Vec<String>> {
+        let two_factor = match self.two_factor.as_mut() {
+            Some(two_factor) if !two_factor.is_enabled() => two_factor,
+            Some(_) => return Err(Error::new("two_factor", "already_enabled")),
+            None => return Err(Error::new("two_factor", "not_started")),
+        };
+
+        if !two_factor.verify(code)? {
+            return Err(Error::new("two_factor", "invalid_code")
+                .set_status(401)
+                .build());
+        }
+        let recovery_codes = two_factor.enable()?;
+
+        self.base.record_event(UserEvent::TwoFactorEnabled {
+            id: self.base().id().to_string(),
+        });
+
+        Ok(recovery_codes)
+    }
+
+    // Checks a code of the authenticator or a recovery code.
+    pub fn verify_two_factor(&mut self, code: &str) -> Result<()> {
+        let two_factor = match self.two_factor.as_mut() {
+            Some(two_factor) if two_factor.is_enabled() => two_factor,
+            _ => return Err(Error::new("two_factor", "not_enabled")),
+        };
+
+        if two_factor.verify(code)? || two_factor.use_recovery_code(code) {
+            return Ok(());
+        }
+
+        Err(Error::new("two_factor", "invalid_code")
+            .set_status(401)
+            .build())
+    }
+
+    pub fn disable_two_factor(&mut self, code: &str) -> Result<()> {
+        self.verify_two_factor(code)?;
+        self.two_factor = None;
+
+        self.base.record_event(UserEvent::TwoFactorDisabled {
+            id: self.base().id().to_string(),
+        });
+
+        Ok(())
+    }
+
+    pub fn login_failed(&mut self, failures: u32) -> Result<()> {
+        self.base.record_event(UserEvent::LoginFailed {
+            id: self.base().id().to_string(),
+            failures,
+        });
+
+        Ok(())
+    }
+
+    pub fn lock_out(&mut self, until: &DateTime<Utc>) -> Result<()> {
+        self.base.record_event(UserEvent::LockedOut {
+            id: self.base().id().to_string(),
+            until: until.to_rfc3339(),
+        });
+
+        Ok(())
+    }
+
+    // A link to reset the password is sent to the email.
+    pub fn recover_password(&mut self) -> Result<()> {
+        if self.base.deleted_at().is_some() {
+            return Err(Error::new("user", "not_active"));
+        }
+
+        if self.identity.provider() != &Provider::Local {
+            return Err(Error::new("password", "not_required"));
+        }
+
         self.base
             .record_event(UserEvent::PasswordRecoveryRequested {
                 id: self.base().id().to_string(),
-                temp_password: temp_password.to_owned(),
                 email: self.identity().email().to_string(),
             });
 
         Ok(())
     }
 
+    // Following the link also proves the email belongs to the user.
+    pub fn reset_password(&mut self, password: Password) -> Result<()> {
+        if self.base.deleted_at().is_some() {
+            return Err(Error::new("user", "not_active"));
+        }
+
+        self.identity.set_password(password)?;
+        self.validated = true;
+
+        self.base.record_event(UserEvent::PasswordReset {
+            id: self.base().id().to_string(),
+        });
+
+        Ok(())
+    }
+
     pub fn delete(&mut self) -> Result<()> {
         if !self.is_active() {
             return Err(Error::new("user", "not_active"));
@@ -249,17 +404,14 @@ mod tests {
 
         assert!(!user.is_validated());
         assert!(!user.is_active());
-        assert!(user.validation().is_some());
 
-        let code = user.validation().unwrap().clone();
-        assert!(user.validate(&code).is_ok());
+        assert!(user.validate().is_ok());
 
         assert!(user.is_validated());
         assert!(user.is_active());
-        assert!(user.validation().is_none());
 
-        assert!(user.validate(&code).is_err());
-        assert!(user.validation().is_none());
+        assert!(user.validate().is_err());
+        assert!(user.is_validated());
     }
 
     #[test]
@@ -279,10 +431,36 @@ mod tests {
 
         assert!(user.delete().is_err());
 
-        let code = user.validation().unwrap().clone();
-        assert!(user.validate(&code).is_ok());
+        assert!(user.validate().is_ok());
 
         assert!(user.delete().is_ok());
         assert!(user.delete().is_err());
     }
+
+    #[test]
+    fn reset_password() {
+        let mut user = User::new(
+            UserId::new("user123").unwrap(),
+            Identity::new(
+                Provider::Local,
+                Username::new("user1").unwrap(),
+                Email::new("email@user.com").unwrap(),
+                Some(Password::new(&format!("{:X>50}", "2")).unwrap()),
+            )
+            .unwrap(),
+            Role::new(RoleId::new("user").unwrap(), "User").unwrap(),
+        )
+        .unwrap();
+
+        assert!(user.recover_password().is_ok());
+        assert!(user
+            .reset_password(Password::new(&format!("{:X>50}", "3")).unwrap())
+            .is_ok());
+        assert_eq!(
+            user.identity().password().unwrap().value(),
+            format!("{:X>50}", "3")
+        );
+        assert!(user.is_validated());
+        assert_eq!(user.base().events().unwrap().len(), 3);
+    }
 }
@@ -1,12 +1,38 @@
 use std::sync::Arc;
 
 use common::error::Error;
+use common::event::EventPublisher;
 use common::result::Result;
 
-use crate::domain::token::{Data, Token, TokenService};
-use crate::domain::user::{Email, PasswordHasher, User, UserRepository, Username};
+use crate::domain::role::RoleRepository;
+use crate::domain::token::{Data, SessionTokens, Token, TokenService};
+use crate::domain::user::{
+    Email, LoginAttemptRepository, LoginAttempts, Password, PasswordHasher, User, UserId,
+    UserRepository, Username, LOCKOUT_FAILURES,
+};
+
+// Result of checking the password. Users with a second factor, or whose role requires one, get
+// a token to finish the login with a code instead of a session.
+#[derive(Debug)]
+pub enum Authentication {
+    Session(SessionTokens),
+    TwoFactor { token: Token, setup_required: bool },
+}
+
+impl Authentication {
+    pub fn session(&self) -> Option<&SessionTokens> {
+        match self {
+            Authentication::Session(tokens) => Some(tokens),
+            _ => None,
+        }
+    }
+}
 
 pub struct AuthenticationService {
+    event_pub: Arc<dyn EventPublisher>,
+
+    login_attempt_repo: Arc<dyn LoginAttemptRepository>,
+    role_repo: Arc<dyn RoleRepository>,
     user_repo: Arc<dyn UserRepository>,
 
     password_hasher: Arc<dyn PasswordHasher>,
@@ -14,14 +40,22 @@ pub struct AuthenticationService {
     token_serv: Arc<TokenService>,
 }
 
-/// AutenticationService authenticate any user, validated or not.
+/// AutenticationService authenticate any user, validated or not. Failed attempts are counted
+/// by account and by client: they have to wait longer after each failure, and accounts are
+/// locked for a while after too many.
 impl AuthenticationService {
     pub fn new(
+        event_pub: Arc<dyn EventPublisher>,
+        login_attempt_repo: Arc<dyn LoginAttemptRepository>,
+        role_repo: Arc<dyn RoleRepository>,
         user_repo: Arc<dyn UserRepository>,
         password_hasher: Arc<dyn PasswordHasher>,
         token_serv: Arc<TokenService>,
     ) -> Self {
         AuthenticationService {
+            event_pub,
+            login_attempt_repo,
+            role_repo,
             user_repo,
             password_hasher,
             token_serv,
@@ -32,44 +66,252 @@ impl AuthenticationService {
         &self,
         username_or_email: &str,
         password: &str,
-    ) -> Result<(User, Token)> {
-        let mut err = Error::new("credentials", "invalid");
+        device: &str,
+        client: &str,
+    ) -> Result<(User, Authentication)> {
+        let err = Error::new("credentials", "invalid");
+
+        let mut client_attempts = self.attempts(&client_key(client)).await;
+        client_attempts.check()?;
 
-        let mut user = match (
+        let user = match (
             Username::new(username_or_email),
             Email::new(username_or_email),
         ) {
             (Ok(username), Err(_)) => self.user_repo.find_by_username(&username).await,
             (Err(_), Ok(email)) => self.user_repo.find_by_email(&email).await,
             _ => return Err(err),
-        }?;
+        };
+        let mut user = match user {
+            Ok(user) => user,
+            Err(e) => {
+                client_attempts.fail();
+                self.login_attempt_repo.save(&client_attempts).await?;
+                return Err(e);
+            }
+        };
 
-        let user_password = match user.identity().password() {
-            Some(password) => password.value(),
-            None => return Err(err),
+        let mut account_attempts = self.attempts(&account_key(user.base().id())).await;
+        account_attempts.check()?;
+
+        let valid = match user.identity().password() {
+            Some(user_password) => self
+                .password_hasher
+                .compare(user_password.value(), password),
+            None => false,
         };
 
-        if self.password_hasher.compare(user_password, password) {
+        if !valid {
+            self.fail(&mut user, &mut client_attempts, &mut account_attempts)
+                .await?;
+            return Err(err);
+        }
+
+        // Passwords hashed with an older algorithm or parameters are upgraded.
+        let rehash = user
+            .identity()
+            .password()
+            .map(|user_password| self.password_hasher.needs_rehash(user_password.value()))
+            .unwrap_or(false);
+        if rehash {
+            user.set_password(Password::new(self.password_hasher.hash(password)?)?)?;
+            self.user_repo.save(&mut user).await?;
+        }
+
+        self.identified(user, device).await
+    }
+
+    // Last step of a login, once the user has been identified by a password or by a provider.
+    // Locked accounts are rejected, and users with a second factor get a token instead of a
+    // session.
+    pub async fn identified(&self, mut user: User, device: &str) -> Result<(User, Authentication)> {
+        self.attempts(&account_key(user.base().id()))
+            .await
+            .check()?;
+
+        if user.has_two_factor() || self.two_factor_required(&user).await {
             let mut data = Data::new();
-            data.add("user_id", user.base().id().value());
+            data.add("two_factor_user_id", user.base().id().value());
+            let token = self.token_serv.create(data).await?;
+
+            let setup_required = !user.has_two_factor();
+            return Ok((
+                user,
+                Authentication::TwoFactor {
+                    token,
+                    setup_required,
+                },
+            ));
+        }
+
+        let tokens = self.login(&mut user, device).await?;
+
+        Ok((user, Authentication::Session(tokens)))
+    }
 
-            let token = match self.token_serv.create(data).await {
-                Ok(token) => token,
-                Err(e) => return Err(err.wrap(e).build()),
-            };
+    // Rotates the refresh token of a session. Sessions of users that were deleted or are not
+    // validated are revoked, and they cannot be refreshed while the account is locked.
+    pub async fn refresh(&self, refresh_token: &Token) -> Result<SessionTokens> {
+        let session = self
+            .token_serv
+            .session_by_refresh_token(refresh_token)
+            .await?;
 
-            user.login(&token)?;
+        match self.user_repo.find_by_id(session.user_id()).await {
+            Ok(user) if user.is_active() => {}
+            _ => {
+                self.token_serv
+                    .revoke_session(session.user_id(), session.id())
+                    .await?;
+                return Err(Error::new("session", "inactive").set_status(401).build());
+            }
+        }
+
+        self.attempts(&account_key(session.user_id()))
+            .await
+            .check()?;
+
+        self.token_serv.refresh(refresh_token).await
+    }
+
+    // User that has to finish the login with the given token.
+    pub async fn two_factor_user(&self, token: &Token) -> Result<User> {
+        let user_id = match self
+            .token_serv
+            .validate(token)
+            .await
+            .ok()
+            .and_then(|data| data.get("two_factor_user_id").cloned())
+        {
+            Some(user_id) => UserId::new(user_id)?,
+            None => {
+                return Err(Error::new("two_factor", "invalid_token")
+                    .set_status(401)
+                    .build())
+            }
+        };
+
+        self.user_repo.find_by_id(&user_id).await
+    }
+
+    // Second step of the login. If the user has not enrolled yet, the code confirms the
+    // enrollment and the recovery codes are returned.
+    pub async fn authenticate_two_factor(
+        &self,
+        token: &Token,
+        code: &str,
+        device: &str,
+        client: &str,
+    ) -> Result<(User, SessionTokens, Option<Vec<String>>)> {
+        let mut client_attempts = self.attempts(&client_key(client)).await;
+        client_attempts.check()?;
+
+        let mut user = self.two_factor_user(token).await?;
+
+        let mut account_attempts = self.attempts(&account_key(user.base().id())).await;
+        account_attempts.check()?;
+
+        let res = if user.has_two_factor() {
+            user.verify_two_factor(code).map(|_| None)
+        } else {
+            user.confirm_two_factor(code).map(Some)
+        };
+        let recovery_codes = match res {
+            Ok(recovery_codes) => recovery_codes,
+            Err(err) => {
+                self.fail(&mut user, &mut client_attempts, &mut account_attempts)
+                    .await?;
+                return Err(err);
+            }
+        };
+
+        self.token_serv.invalidate(token).await?;
+        self.user_repo.save(&mut user).await?;
+
+        let tokens = self.login(&mut user, device).await?;
+
+        Ok((user, tokens, recovery_codes))
+    }
+
+    pub async fn unlock(&self, user_id: &UserId) -> Result<()> {
+        self.login_attempt_repo.delete(&account_key(user_id)).await
+    }
+
+    pub async fn account_attempts(&self, user_id: &UserId) -> LoginAttempts {
+        self.attempts(&account_key(user_id)).await
+    }
+
+    // The role may have changed since it was assigned to the user.
+    pub async fn two_factor_required(&self, user: &User) -> bool {
+        match self.role_repo.find_by_id(user.role().base().id()).await {
+            Ok(role) => role.two_factor_required(),
+            Err(_) => user.role().two_factor_required(),
+        }
+    }
+
+    async fn login(&self, user: &mut User, device: &str) -> Result<SessionTokens> {
+        let tokens = match self
+            .token_serv
+            .create_session(user.base().id(), device)
+            .await
+        {
+            Ok(tokens) => tokens,
+            Err(e) => return Err(Error::new("credentials", "invalid").wrap(e).build()),
+        };
+
+        user.login()?;
+
+        self.login_attempt_repo
+            .delete(&account_key(user.base().id()))
+            .await?;
+
+        Ok(tokens)
+    }
+
+    async fn fail(
+        &self,
+        user: &mut User,
+        client_attempts: &mut LoginAttempts,
+        account_attempts: &mut LoginAttempts,
+    ) -> Result<()> {
+        client_attempts.fail();
+        account_attempts.fail();
 
-            return Ok((user, token));
+        user.login_failed(account_attempts.failures())?;
+        if account_attempts.failures() >= LOCKOUT_FAILURES {
+            let locked_until = account_attempts.lock();
+            user.lock_out(&locked_until)?;
         }
-        Err(err)
+
+        self.login_attempt_repo.save(client_attempts).await?;
+        self.login_attempt_repo.save(account_attempts).await?;
+        self.event_pub.publish_all(user.base().events()?).await?;
+
+        Ok(())
+    }
+
+    async fn attempts(&self, key: &str) -> LoginAttempts {
+        self.login_attempt_repo
+            .find_by_key(key)
+            .await
+            .unwrap_or_else(|_| LoginAttempts::new(key))
     }
 }
 
+fn account_key(user_id: &UserId) -> String {
+    format!("account:{}", user_id.value())
+}
+
+fn client_key(client: &str) -> String {
+    format!("client:{}", client)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use chrono::{Duration, Utc};
+
     use crate::mocks;
 
     #[tokio::test]
@@ -80,35 +322,175 @@ mod tests {
         let mut user = mocks::validated_user1();
         c.user_repo().save(&mut user).await.unwrap();
 
-        let (_, token) = serv
-            .authenticate(user.identity().username().value(), "P@asswd!")
+        let (_, auth) = serv
+            .authenticate(
+                user.identity().username().value(),
+                "P@asswd!",
+                "Firefox",
+                "127.0.0.1",
+            )
             .await
             .unwrap();
-        assert!(!token.value().is_empty());
+        assert!(!auth.session().unwrap().access_token().value().is_empty());
 
-        let (_, token) = serv
-            .authenticate(user.identity().email().value(), "P@asswd!")
+        let (_, auth) = serv
+            .authenticate(
+                user.identity().email().value(),
+                "P@asswd!",
+                "Firefox",
+                "127.0.0.1",
+            )
             .await
             .unwrap();
-        assert!(!token.value().is_empty());
+        assert!(!auth.session().unwrap().access_token().value().is_empty());
 
-        assert!(serv.authenticate("user2", "user123").await.is_err());
-        assert!(serv.authenticate("user1", "user124").await.is_err());
         assert!(serv
-            .authenticate("user@email.com.ar", "user123")
+            .authenticate("user2", "user123", "Firefox", "127.0.0.1")
+            .await
+            .is_err());
+        assert!(serv
+            .authenticate("user1", "user124", "Firefox", "127.0.0.1")
+            .await
+            .is_err());
+        assert!(serv
+            .authenticate("user@email.com.ar", "user123", "Firefox", "127.0.0.1")
             .await
             .is_err());
         assert!(serv
-            .authenticate("user@email.com", "user124")
+            .authenticate("user@email.com", "user124", "Firefox", "127.0.0.1")
             .await
             .is_err());
         assert!(serv
-            .authenticate(user.identity().username().value(), "invalid")
+            .authenticate(
+                user.identity().username().value(),
+                "invalid",
+                "Firefox",
+                "127.0.0.1"
+            )
             .await
             .is_err());
         assert!(serv
-            .authenticate(user.identity().email().value(), "invalid")
+            .authenticate(
+                user.identity().email().value(),
+                "invalid",
+                "Firefox",
+                "127.0.0.1"
+            )
             .await
             .is_err());
     }
+
+    #[tokio::test]
+    async fn rehash() {
+        let c = mocks::container();
+        let serv = c.authentication_serv();
+
+        let mut user = mocks::validated_user1();
+        user.set_password(
+            Password::new(mocks::FakePasswordHasher::legacy_hash("P@asswd!")).unwrap(),
+        )
+        .unwrap();
+        c.user_repo().save(&mut user).await.unwrap();
+
+        assert!(serv
+            .authenticate(
+                user.identity().username().value(),
+                "invalid",
+                "Firefox",
+                "127.0.0.1"
+            )
+            .await
+            .is_err());
+        let user = c.user_repo().find_by_id(user.base().id()).await.unwrap();
+        assert!(c
+            .password_hasher()
+            .needs_rehash(user.identity().password().unwrap().value()));
+
+        serv.authenticate(
+            user.identity().username().value(),
+            "P@asswd!",
+            "Firefox",
+            "127.0.0.1",
+        )
+        .await
+        .unwrap();
+        let user = c.user_repo().find_by_id(user.base().id()).await.unwrap();
+        let password = user.identity().password().unwrap().value();
+        assert!(!c.password_hasher().needs_rehash(password));
+        assert!(c.password_hasher().compare(password, "P@asswd!"));
+    }
+
+    #[tokio::test]
+    async fn throttling() {
+        let c = mocks::container();
+        let serv = c.authentication_serv();
+
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let username = user.identity().username().value();
+
+        for _ in 0..3 {
+            let err = serv
+                .authenticate(username, "invalid", "Firefox", "10.0.0.1")
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), "invalid");
+        }
+        assert_eq!(c.event_pub().events().await.len(), 3);
+
+        // The account has to wait, even from other clients.
+        let err = serv
+            .authenticate(username, "P@asswd!", "Firefox", "10.0.0.2")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "too_many_attempts");
+        assert_eq!(err.status(), Some(429));
+
+        // So does the client, even for other accounts.
+        let err = serv
+            .authenticate("user-two", "P@asswd!", "Firefox", "10.0.0.1")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "too_many_attempts");
+    }
+
+    #[tokio::test]
+    async fn lockout() {
+        let c = mocks::container();
+        let serv = c.authentication_serv();
+
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+        let username = user.identity().username().value();
+
+        let attempts = LoginAttempts::build(
+            format!("account:{}", user.base().id().value()),
+            LOCKOUT_FAILURES - 1,
+            Some(Utc::now() - Duration::minutes(10)),
+            None,
+        );
+        c.login_attempt_repo().save(&attempts).await.unwrap();
+
+        assert!(serv
+            .authenticate(username, "invalid", "Firefox", "10.0.0.1")
+            .await
+            .is_err());
+        let attempts = serv.account_attempts(user.base().id()).await;
+        assert!(attempts.is_locked());
+        let events = c.event_pub().events().await;
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].code(), "locked-out");
+
+        let err = serv
+            .authenticate(username, "P@asswd!", "Firefox", "10.0.0.2")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "too_many_attempts");
+
+        serv.unlock(user.base().id()).await.unwrap();
+        assert!(serv
+            .authenticate(username, "P@asswd!", "Firefox", "10.0.0.2")
+            .await
+            .is_ok());
+    }
 }
@@ -3,7 +3,42 @@ use std::sync::Arc;
 use common::error::Error;
 use common::result::Result;
 
-use crate::domain::token::{Token, TokenService};
+use crate::domain::role::Permission;
+use crate::domain::token::{PersonalAccessToken, Token, TokenService};
+
+// User a request is made by. Requests with a personal access token are limited to its scopes.
+#[derive(Debug, Clone)]
+pub struct Authorization {
+    user_id: String,
+    scopes: Option<Vec<Permission>>,
+}
+
+impl Authorization {
+    pub fn new<S: Into<String>>(user_id: S, scopes: Option<Vec<Permission>>) -> Self {
+        Authorization {
+            user_id: user_id.into(),
+            scopes,
+        }
+    }
+
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    pub fn scopes(&self) -> Option<&[Permission]> {
+        self.scopes.as_deref()
+    }
+
+    // Whether the scopes allow the permissions. Session tokens are not limited.
+    pub fn allows(&self, module: &str, permissions: &str) -> bool {
+        match &self.scopes {
+            Some(scopes) => scopes
+                .iter()
+                .any(|scope| scope.module() == module && scope.contains(permissions)),
+            None => true,
+        }
+    }
+}
 
 pub struct AuthorizationService {
     token_serv: Arc<TokenService>,
@@ -14,12 +49,22 @@ impl AuthorizationService {
         AuthorizationService { token_serv }
     }
 
-    pub async fn authorize(&self, token: &Token) -> Result<String> {
-        if let Ok(data) = self.token_serv.validate(token).await {
+    pub async fn authorize(&self, token: &Token) -> Result<Authorization> {
+        if PersonalAccessToken::is_personal_access_token(token) {
+            if let Ok(personal_access_token) =
+                self.token_serv.validate_personal_access_token(token).await
+            {
+                return Ok(Authorization::new(
+                    personal_access_token.user_id().to_string(),
+                    Some(personal_access_token.scopes().to_vec()),
+                ));
+            }
+        } else if let Ok(data) = self.token_serv.validate(token).await {
             if let Some(user_id) = data.get("user_id") {
-                return Ok(user_id.to_string());
+                return Ok(Authorization::new(user_id.to_string(), None));
             }
         }
+
         Err(Error::new("authorization", "unauthorized")
             .set_status(401)
             .set_message("User is not logged in")
@@ -47,9 +92,44 @@ mod tests {
 
         let serv = c.authorization_serv();
 
-        let user_id = serv.authorize(&token).await.unwrap();
-        assert_eq!(user_id, user.base().id().to_string());
+        let authorization = serv.authorize(&token).await.unwrap();
+        assert_eq!(authorization.user_id(), user.base().id().value());
+        assert!(authorization.scopes().is_none());
+        assert!(authorization.allows("users", "D"));
 
         assert!(serv.authorize(&Token::new("invalid")).await.is_err());
     }
+
+    #[tokio::test]
+    async fn personal_access_token() {
+        let c = mocks::container();
+
+        let mut user = mocks::validated_user1();
+        c.user_repo().save(&mut user).await.unwrap();
+
+        let (personal_access_token, token) = c
+            .token_serv()
+            .create_personal_access_token(
+                user.base().id(),
+                "Moderation",
+                vec![Permission::new("publications", "RU").unwrap()],
+                None,
+            )
+            .await
+            .unwrap();
+
+        let serv = c.authorization_serv();
+
+        let authorization = serv.authorize(&token).await.unwrap();
+        assert_eq!(authorization.user_id(), user.base().id().value());
+        assert!(authorization.allows("publications", "R"));
+        assert!(!authorization.allows("publications", "D"));
+        assert!(!authorization.allows("users", "R"));
+
+        c.token_serv()
+            .revoke_personal_access_token(user.base().id(), personal_access_token.id())
+            .await
+            .unwrap();
+        assert!(serv.authorize(&token).await.is_err());
+    }
 }
@@ -0,0 +1,31 @@
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::user::Provider;
+
+// Account of an OpenID Connect provider, identified by its subject, used to log in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkedAccount {
+    provider: Provider,
+    subject: String,
+}
+
+impl LinkedAccount {
+    pub fn new<S: Into<String>>(provider: Provider, subject: S) -> Result<Self> {
+        let subject = subject.into();
+
+        if provider == Provider::Local || subject.is_empty() {
+            return Err(Error::new("linked_account", "invalid"));
+        }
+
+        Ok(LinkedAccount { provider, subject })
+    }
+
+    pub fn provider(&self) -> &Provider {
+        &self.provider
+    }
+
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+}
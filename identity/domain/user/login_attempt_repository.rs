@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+
+use common::result::Result;
+
+use crate::domain::user::LoginAttempts;
+
+#[async_trait]
+pub trait LoginAttemptRepository: Sync + Send {
+    async fn find_by_key(&self, key: &str) -> Result<LoginAttempts>;
+
+    async fn save(&self, attempts: &LoginAttempts) -> Result<()>;
+    async fn delete(&self, key: &str) -> Result<()>;
+}
@@ -0,0 +1,178 @@
+use chrono::{DateTime, Duration, Utc};
+
+use common::error::Error;
+use common::result::Result;
+
+// Failures allowed before each attempt has to wait.
+const FREE_FAILURES: u32 = 3;
+const MAX_BACKOFF_SECONDS: i64 = 300;
+// Failures after which an account is locked.
+pub const LOCKOUT_FAILURES: u32 = 10;
+const LOCKOUT_MINUTES: i64 = 15;
+// Failures are forgotten after a while without new ones.
+const RESET_HOURS: i64 = 1;
+
+// Failed logins of an account or a client. After a few failures every attempt has to wait
+// twice as long as the previous one.
+#[derive(Debug, Clone)]
+pub struct LoginAttempts {
+    key: String,
+    failures: u32,
+    last_failure_at: Option<DateTime<Utc>>,
+    locked_until: Option<DateTime<Utc>>,
+}
+
+impl LoginAttempts {
+    pub fn new<S: Into<String>>(key: S) -> Self {
+        LoginAttempts {
+            key: key.into(),
+            failures: 0,
+            last_failure_at: None,
+            locked_until: None,
+        }
+    }
+
+    pub fn build<S: Into<String>>(
+        key: S,
+        failures: u32,
+        last_failure_at: Option<DateTime<Utc>>,
+        locked_until: Option<DateTime<Utc>>,
+    ) -> Self {
+        LoginAttempts {
+            key: key.into(),
+            failures,
+            last_failure_at,
+            locked_until,
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    pub fn last_failure_at(&self) -> Option<&DateTime<Utc>> {
+        self.last_failure_at.as_ref()
+    }
+
+    pub fn locked_until(&self) -> Option<&DateTime<Utc>> {
+        self.locked_until.as_ref()
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked_until
+            .map(|locked_until| locked_until > Utc::now())
+            .unwrap_or(false)
+    }
+
+    pub fn backoff(&self) -> Duration {
+        if self.failures < FREE_FAILURES {
+            return Duration::zero();
+        }
+
+        let exp = (self.failures - FREE_FAILURES).min(16);
+        Duration::seconds((1i64 << exp).min(MAX_BACKOFF_SECONDS))
+    }
+
+    // When the next attempt is allowed.
+    pub fn retry_at(&self) -> Option<DateTime<Utc>> {
+        let backoff_until = self
+            .last_failure_at
+            .map(|last_failure_at| last_failure_at + self.backoff());
+
+        match (backoff_until, self.locked_until) {
+            (Some(backoff_until), Some(locked_until)) => Some(backoff_until.max(locked_until)),
+            (backoff_until, locked_until) => backoff_until.or(locked_until),
+        }
+        .filter(|retry_at| retry_at > &Utc::now())
+    }
+
+    pub fn check(&self) -> Result<()> {
+        match self.retry_at() {
+            Some(retry_at) => Err(Error::new("login", "too_many_attempts")
+                .set_status(429)
+                .add_context("retry_at", &retry_at.to_rfc3339())
+                .set_message("Too many failed attempts, try again later")
+                .build()),
+            None => Ok(()),
+        }
+    }
+
+    pub fn fail(&mut self) {
+        let now = Utc::now();
+        if let Some(last_failure_at) = self.last_failure_at {
+            if last_failure_at + Duration::hours(RESET_HOURS) < now && !self.is_locked() {
+                self.failures = 0;
+            }
+        }
+
+        self.failures += 1;
+        self.last_failure_at = Some(now);
+    }
+
+    // Returns until when the attempts are locked.
+    pub fn lock(&mut self) -> DateTime<Utc> {
+        let locked_until = Utc::now() + Duration::minutes(LOCKOUT_MINUTES);
+        self.locked_until = Some(locked_until);
+        locked_until
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff() {
+        let mut attempts = LoginAttempts::new("account:#user01");
+        assert!(attempts.check().is_ok());
+
+        for _ in 0..FREE_FAILURES - 1 {
+            attempts.fail();
+        }
+        assert_eq!(attempts.backoff(), Duration::zero());
+        assert!(attempts.check().is_ok());
+
+        attempts.fail();
+        assert_eq!(attempts.backoff(), Duration::seconds(1));
+        assert!(attempts.check().is_err());
+
+        attempts.fail();
+        attempts.fail();
+        assert_eq!(attempts.backoff(), Duration::seconds(4));
+
+        for _ in 0..20 {
+            attempts.fail();
+        }
+        assert_eq!(attempts.backoff(), Duration::seconds(MAX_BACKOFF_SECONDS));
+    }
+
+    #[test]
+    fn lock() {
+        let mut attempts = LoginAttempts::new("account:#user01");
+        let locked_until = attempts.lock();
+        assert!(attempts.is_locked());
+        assert_eq!(attempts.retry_at(), Some(locked_until));
+        assert!(attempts.check().is_err());
+
+        attempts.locked_until = Some(Utc::now() - Duration::seconds(1));
+        assert!(!attempts.is_locked());
+        assert!(attempts.check().is_ok());
+    }
+
+    #[test]
+    fn reset() {
+        let mut attempts = LoginAttempts::new("client:127.0.0.1");
+        for _ in 0..5 {
+            attempts.fail();
+        }
+        attempts.last_failure_at = Some(Utc::now() - Duration::hours(RESET_HOURS + 1));
+        assert!(attempts.check().is_ok());
+
+        attempts.fail();
+        assert_eq!(attempts.failures(), 1);
+    }
+}
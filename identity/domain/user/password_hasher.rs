@@ -3,4 +3,10 @@ use common::result::Result;
 pub trait PasswordHasher: Sync + Send {
     fn hash(&self, plain_password: &str) -> Result<String>;
     fn compare(&self, hashed_password: &str, plain_password: &str) -> bool;
+
+    // The hash was computed with an older algorithm or parameters, and should be replaced the
+    // next time the plain password is known.
+    fn needs_rehash(&self, _hashed_password: &str) -> bool {
+        false
+    }
 }
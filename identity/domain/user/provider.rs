@@ -1,10 +1,24 @@
-#[derive(Debug, Clone)]
+use common::error::Error;
+use common::result::Result;
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Provider {
     Local,
     Google,
     Facebook,
 }
 
+impl Provider {
+    pub fn new<S: Into<String>>(provider: S) -> Result<Self> {
+        match provider.into().as_ref() {
+            "local" => Ok(Provider::Local),
+            "google" => Ok(Provider::Google),
+            "facebook" => Ok(Provider::Facebook),
+            _ => Err(Error::new("provider", "invalid")),
+        }
+    }
+}
+
 impl ToString for Provider {
     fn to_string(&self) -> String {
         match self {
@@ -1,8 +1,10 @@
 use async_trait::async_trait;
 
+use common::model::{Paginated, Pagination};
 use common::result::Result;
 
-use crate::domain::user::{Email, User, UserId, Username};
+use crate::domain::role::RoleId;
+use crate::domain::user::{Email, LinkedAccount, User, UserId, Username};
 
 #[async_trait]
 pub trait UserRepository: Sync + Send {
@@ -12,6 +14,10 @@ pub trait UserRepository: Sync + Send {
     async fn find_by_id(&self, id: &UserId) -> Result<User>;
     async fn find_by_username(&self, username: &Username) -> Result<User>;
     async fn find_by_email(&self, email: &Email) -> Result<User>;
+    async fn find_by_linked_account(&self, account: &LinkedAccount) -> Result<User>;
+    async fn find_by_role(&self, role_id: &RoleId) -> Result<Vec<User>>;
+    // Sortable by created_at and username.
+    async fn find_page(&self, pagination: &Pagination) -> Result<Paginated<User>>;
 
     async fn save(&self, user: &mut User) -> Result<()>;
 }
@@ -0,0 +1,285 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ring::digest;
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use url::form_urlencoded::byte_serialize;
+
+use common::error::Error;
+use common::result::Result;
+
+const DIGITS: usize = 6;
+const STEP_SECONDS: u64 = 30;
+// Steps before and after the current one that are accepted, to allow for clock drift.
+const WINDOW: u64 = 1;
+const RECOVERY_CODES: usize = 10;
+
+const BASE32_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+// TOTP (RFC 6238) second factor. The secret is base32 encoded, as authenticator apps expect it,
+// and the one-time recovery codes are stored hashed.
+#[derive(Debug, Clone)]
+pub struct TwoFactor {
+    secret: String,
+    enabled: bool,
+    recovery_codes: Vec<String>,
+    last_used_step: Option<u64>,
+}
+
+impl TwoFactor {
+    // A new secret, enabled once the user confirms a first code.
+    pub fn new() -> Result<Self> {
+        Ok(TwoFactor {
+            secret: base32_encode(&random_bytes(20)?),
+            enabled: false,
+            recovery_codes: Vec::new(),
+            last_used_step: None,
+        })
+    }
+
+    pub fn build<S: Into<String>>(
+        secret: S,
+        enabled: bool,
+        recovery_codes: Vec<String>,
+        last_used_step: Option<u64>,
+    ) -> Self {
+        TwoFactor {
+            secret: secret.into(),
+            enabled,
+            recovery_codes,
+            last_used_step,
+        }
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn recovery_codes_left(&self) -> usize {
+        self.recovery_codes.len()
+    }
+
+    pub fn otpauth_uri(&self, issuer: &str, account: &str) -> String {
+        let label: String = byte_serialize(format!("{}:{}", issuer, account).as_bytes()).collect();
+        let issuer: String = byte_serialize(issuer.as_bytes()).collect();
+        format!(
+            "otpauth://totp/{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            label, self.secret, issuer, DIGITS, STEP_SECONDS
+        )
+    }
+
+    pub fn code_at(&self, timestamp: u64) -> Result<String> {
+        let secret = base32_decode(&self.secret)?;
+        Ok(hotp(&secret, timestamp / STEP_SECONDS))
+    }
+
+    // Checks a code of the authenticator. Codes cannot be used twice.
+    pub fn verify_at(&mut self, code: &str, timestamp: u64) -> Result<bool> {
+        let secret = base32_decode(&self.secret)?;
+        let step = timestamp / STEP_SECONDS;
+
+        for step in step.saturating_sub(WINDOW)..=step + WINDOW {
+            if self
+                .last_used_step
+                .map(|last| step <= last)
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            if constant_time_eq(hotp(&secret, step).as_bytes(), code.trim().as_bytes()) {
+                self.last_used_step = Some(step);
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    pub fn verify(&mut self, code: &str) -> Result<bool> {
+        self.verify_at(code, now())
+    }
+
+    // Checks and removes a recovery code.
+    pub fn use_recovery_code(&mut self, code: &str) -> bool {
+        let hash = hash_recovery_code(code);
+        match self.recovery_codes.iter().position(|c| c == &hash) {
+            Some(i) => {
+                self.recovery_codes.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Enables the second factor and returns the recovery codes, which are only shown once.
+    pub fn enable(&mut self) -> Result<Vec<String>> {
+        let mut codes = Vec::new();
+        for _ in 0..RECOVERY_CODES {
+            let code = base32_encode(&random_bytes(10)?).to_lowercase();
+            codes.push(format!("{}-{}", &code[..8], &code[8..16]));
+        }
+
+        self.recovery_codes = codes.iter().map(|code| hash_recovery_code(code)).collect();
+        self.enabled = true;
+
+        Ok(codes)
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or(0)
+}
+
+// HOTP (RFC 4226) with HMAC-SHA1, the algorithm supported by every authenticator.
+fn hotp(secret: &[u8], counter: u64) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, secret);
+    let tag = hmac::sign(&key, &counter.to_be_bytes());
+    let hash = tag.as_ref();
+
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = (u32::from(hash[offset] & 0x7f) << 24)
+        | (u32::from(hash[offset + 1]) << 16)
+        | (u32::from(hash[offset + 2]) << 8)
+        | u32::from(hash[offset + 3]);
+
+    format!(
+        "{:0width$}",
+        binary % 10u32.pow(DIGITS as u32),
+        width = DIGITS
+    )
+}
+
+fn hash_recovery_code(code: &str) -> String {
+    let code = code.trim().to_lowercase();
+    let hash = digest::digest(&digest::SHA256, code.as_bytes());
+    base64::encode_config(hash.as_ref(), base64::URL_SAFE_NO_PAD)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn random_bytes(len: usize) -> Result<Vec<u8>> {
+    let mut bytes = vec![0u8; len];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| Error::internal("two_factor", "random"))?;
+    Ok(bytes)
+}
+
+// Base32 (RFC 4648) without padding.
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for byte in bytes.iter() {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    encoded
+}
+
+fn base32_decode(encoded: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in encoded.trim_end_matches('=').chars() {
+        let value = match BASE32_ALPHABET
+            .iter()
+            .position(|a| *a as char == c.to_ascii_uppercase())
+        {
+            Some(value) => value as u32,
+            None => return Err(Error::internal("two_factor", "invalid_secret")),
+        };
+
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Secret of the test vectors of RFC 6238 for SHA1.
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn rfc6238() {
+        let two_factor = TwoFactor::build(base32_encode(RFC_SECRET), true, Vec::new(), None);
+        assert_eq!(two_factor.secret(), "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+
+        // The RFC uses 8 digits, these are their last 6.
+        assert_eq!(two_factor.code_at(59).unwrap(), "287082");
+        assert_eq!(two_factor.code_at(1111111109).unwrap(), "081804");
+        assert_eq!(two_factor.code_at(1234567890).unwrap(), "005924");
+        assert_eq!(two_factor.code_at(2000000000).unwrap(), "279037");
+    }
+
+    #[test]
+    fn verify() {
+        let mut two_factor = TwoFactor::new().unwrap();
+        assert_eq!(base32_decode(two_factor.secret()).unwrap().len(), 20);
+
+        let timestamp = 1600000000;
+        let code = two_factor.code_at(timestamp).unwrap();
+        let invalid = format!("{:06}", (code.parse::<u32>().unwrap() + 1) % 1_000_000);
+        assert!(!two_factor.verify_at(&invalid, timestamp).unwrap());
+
+        // Previous step
+        assert!(two_factor.verify_at(&code, timestamp + 30).unwrap());
+        // Used
+        assert!(!two_factor.verify_at(&code, timestamp + 30).unwrap());
+        // Out of the window
+        let code = two_factor.code_at(timestamp + 60).unwrap();
+        assert!(!two_factor.verify_at(&code, timestamp + 150).unwrap());
+    }
+
+    #[test]
+    fn recovery_codes() {
+        let mut two_factor = TwoFactor::new().unwrap();
+        let codes = two_factor.enable().unwrap();
+        assert!(two_factor.is_enabled());
+        assert_eq!(codes.len(), RECOVERY_CODES);
+        assert_eq!(two_factor.recovery_codes_left(), RECOVERY_CODES);
+
+        assert!(two_factor.use_recovery_code(&codes[0].to_uppercase()));
+        assert!(!two_factor.use_recovery_code(&codes[0]));
+        assert!(!two_factor.use_recovery_code("invalid"));
+        assert_eq!(two_factor.recovery_codes_left(), RECOVERY_CODES - 1);
+    }
+
+    #[test]
+    fn otpauth_uri() {
+        let two_factor = TwoFactor::build("GEZDGNBV", false, Vec::new(), None);
+        assert_eq!(
+            two_factor.otpauth_uri("Omics", "user@omics.com"),
+            "otpauth://totp/Omics%3Auser%40omics.com?secret=GEZDGNBV&issuer=Omics\
+             &algorithm=SHA1&digits=6&period=30"
+        );
+    }
+}
@@ -0,0 +1,152 @@
+mod repository;
+mod sender;
+mod service;
+pub use repository::*;
+pub use sender::*;
+pub use service::*;
+
+use std::fmt;
+
+use chrono::{DateTime, Duration, Utc};
+use ring::digest;
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::token::random_string;
+use crate::domain::user::UserId;
+
+// VerificationKind
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerificationKind {
+    Email,
+    PasswordReset,
+}
+
+impl VerificationKind {
+    // Time the user has to follow the link.
+    pub fn duration(&self) -> Duration {
+        match self {
+            VerificationKind::Email => Duration::days(1),
+            VerificationKind::PasswordReset => Duration::hours(1),
+        }
+    }
+}
+
+impl fmt::Display for VerificationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationKind::Email => write!(f, "email"),
+            VerificationKind::PasswordReset => write!(f, "password-reset"),
+        }
+    }
+}
+
+// Verification
+// A single-use token sent to the user to validate the email or reset the password. Only the hash
+// of the token is stored.
+#[derive(Debug, Clone)]
+pub struct Verification {
+    token_hash: String,
+    user_id: UserId,
+    kind: VerificationKind,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    used_at: Option<DateTime<Utc>>,
+}
+
+impl Verification {
+    // Returns the verification and the token to send.
+    pub fn new(user_id: UserId, kind: VerificationKind) -> Result<(Self, String)> {
+        let token = random_string()?;
+        let now = Utc::now();
+
+        Ok((
+            Verification {
+                token_hash: Self::hash(&token),
+                user_id,
+                expires_at: now + kind.duration(),
+                kind,
+                created_at: now,
+                used_at: None,
+            },
+            token,
+        ))
+    }
+
+    pub fn hash(token: &str) -> String {
+        let hash = digest::digest(&digest::SHA256, token.as_bytes());
+        base64::encode_config(hash.as_ref(), base64::URL_SAFE_NO_PAD)
+    }
+
+    pub fn token_hash(&self) -> &str {
+        &self.token_hash
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    pub fn kind(&self) -> &VerificationKind {
+        &self.kind
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn expires_at(&self) -> &DateTime<Utc> {
+        &self.expires_at
+    }
+
+    pub fn used_at(&self) -> Option<&DateTime<Utc>> {
+        self.used_at.as_ref()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.used_at.is_none() && self.expires_at > Utc::now()
+    }
+
+    pub fn use_token(&mut self) -> Result<()> {
+        if !self.is_active() {
+            return Err(Error::new("verification", "invalid")
+                .set_status(400)
+                .set_message("The link has expired or has already been used")
+                .build());
+        }
+
+        self.used_at = Some(Utc::now());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_use() {
+        let (mut verification, token) =
+            Verification::new(UserId::new("#user01").unwrap(), VerificationKind::Email).unwrap();
+        assert_ne!(verification.token_hash(), token);
+        assert_eq!(verification.token_hash(), Verification::hash(&token));
+        assert!(verification.is_active());
+
+        assert!(verification.use_token().is_ok());
+        assert!(!verification.is_active());
+        assert!(verification.use_token().is_err());
+    }
+
+    #[test]
+    fn expired() {
+        let (mut verification, _) = Verification::new(
+            UserId::new("#user01").unwrap(),
+            VerificationKind::PasswordReset,
+        )
+        .unwrap();
+        verification.expires_at = Utc::now() - Duration::seconds(1);
+        assert!(!verification.is_active());
+        assert!(verification.use_token().is_err());
+    }
+}
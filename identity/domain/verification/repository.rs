@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use common::result::Result;
+
+use crate::domain::user::UserId;
+use crate::domain::verification::Verification;
+
+#[async_trait]
+pub trait VerificationRepository: Sync + Send {
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Verification>;
+    async fn find_by_user_id(&self, user_id: &UserId) -> Result<Vec<Verification>>;
+
+    async fn save(&self, verification: &Verification) -> Result<()>;
+}
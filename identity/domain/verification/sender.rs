@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+
+use common::result::Result;
+
+use crate::domain::user::User;
+use crate::domain::verification::VerificationKind;
+
+// Delivers the link with the verification token to the user.
+#[async_trait]
+pub trait VerificationSender: Sync + Send {
+    async fn send(&self, user: &User, kind: &VerificationKind, token: &str) -> Result<()>;
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::user::{User, UserId};
+use crate::domain::verification::{
+    Verification, VerificationKind, VerificationRepository, VerificationSender,
+};
+
+pub struct VerificationService {
+    verification_repo: Arc<dyn VerificationRepository>,
+
+    verification_sender: Arc<dyn VerificationSender>,
+}
+
+impl VerificationService {
+    pub fn new(
+        verification_repo: Arc<dyn VerificationRepository>,
+        verification_sender: Arc<dyn VerificationSender>,
+    ) -> Self {
+        VerificationService {
+            verification_repo,
+            verification_sender,
+        }
+    }
+
+    // Sends a new token to the user. Tokens of the same kind sent before are no longer valid.
+    pub async fn issue(&self, user: &User, kind: VerificationKind) -> Result<()> {
+        for mut previous in self
+            .verification_repo
+            .find_by_user_id(user.base().id())
+            .await?
+            .into_iter()
+            .filter(|verification| verification.kind() == &kind && verification.is_active())
+        {
+            previous.use_token()?;
+            self.verification_repo.save(&previous).await?;
+        }
+
+        let (verification, token) = Verification::new(user.base().id().clone(), kind)?;
+        self.verification_repo.save(&verification).await?;
+
+        self.verification_sender
+            .send(user, verification.kind(), &token)
+            .await
+    }
+
+    // Uses the token and returns the user it was sent to.
+    pub async fn verify(&self, token: &str, kind: &VerificationKind) -> Result<UserId> {
+        let mut verification = match self
+            .verification_repo
+            .find_by_token_hash(&Verification::hash(token))
+            .await
+        {
+            Ok(verification) if verification.kind() == kind => verification,
+            _ => {
+                return Err(Error::new("verification", "invalid")
+                    .set_status(400)
+                    .build())
+            }
+        };
+
+        verification.use_token()?;
+        self.verification_repo.save(&verification).await?;
+
+        Ok(verification.user_id().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks::{self, FakeVerificationSender};
+
+    #[tokio::test]
+    async fn issue_and_verify() {
+        let sender = Arc::new(FakeVerificationSender::new());
+        let c = mocks::container_with_verification_sender(sender.clone());
+        let user = mocks::user1();
+        let serv = c.verification_serv();
+
+        serv.issue(&user, VerificationKind::Email).await.unwrap();
+        let token = sender
+            .token(user.base().id(), &VerificationKind::Email)
+            .unwrap();
+
+        assert!(serv
+            .verify(&token, &VerificationKind::PasswordReset)
+            .await
+            .is_err());
+        assert_eq!(
+            &serv.verify(&token, &VerificationKind::Email).await.unwrap(),
+            user.base().id()
+        );
+        assert!(serv.verify(&token, &VerificationKind::Email).await.is_err());
+        assert!(serv
+            .verify("invalid", &VerificationKind::Email)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn previous_tokens() {
+        let sender = Arc::new(FakeVerificationSender::new());
+        let c = mocks::container_with_verification_sender(sender.clone());
+        let user = mocks::user1();
+        let serv = c.verification_serv();
+
+        serv.issue(&user, VerificationKind::PasswordReset)
+            .await
+            .unwrap();
+        let previous = sender
+            .token(user.base().id(), &VerificationKind::PasswordReset)
+            .unwrap();
+        serv.issue(&user, VerificationKind::Email).await.unwrap();
+        serv.issue(&user, VerificationKind::PasswordReset)
+            .await
+            .unwrap();
+        let token = sender
+            .token(user.base().id(), &VerificationKind::PasswordReset)
+            .unwrap();
+
+        assert!(serv
+            .verify(&previous, &VerificationKind::PasswordReset)
+            .await
+            .is_err());
+        assert!(serv
+            .verify(&token, &VerificationKind::PasswordReset)
+            .await
+            .is_ok());
+
+        // Other kinds are still valid
+        let token = sender
+            .token(user.base().id(), &VerificationKind::Email)
+            .unwrap();
+        assert!(serv.verify(&token, &VerificationKind::Email).await.is_ok());
+    }
+}
@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+
+use common::cache::Cache;
+use common::infrastructure::cache::InMemCache;
+use common::result::Result;
+
+use crate::domain::oidc::{AuthorizationRequest, AuthorizationRequestRepository};
+
+pub struct InMemAuthorizationRequestRepository {
+    cache: InMemCache<String, AuthorizationRequest>,
+}
+
+impl InMemAuthorizationRequestRepository {
+    pub fn new() -> Self {
+        InMemAuthorizationRequestRepository {
+            cache: InMemCache::new(),
+        }
+    }
+}
+
+impl Default for InMemAuthorizationRequestRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Cache<String, AuthorizationRequest> for InMemAuthorizationRequestRepository {
+    async fn get(&self, state: &String) -> Option<AuthorizationRequest> {
+        self.cache.get(state).await
+    }
+
+    async fn set(&self, state: String, req: AuthorizationRequest) -> Result<()> {
+        self.cache.set(state, req).await
+    }
+
+    async fn delete(&self, state: &String) -> Result<()> {
+        self.cache.delete(state).await
+    }
+}
+
+impl AuthorizationRequestRepository for InMemAuthorizationRequestRepository {}
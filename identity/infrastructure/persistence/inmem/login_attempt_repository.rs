@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+
+use common::cache::Cache;
+use common::error::Error;
+use common::infrastructure::cache::InMemCache;
+use common::result::Result;
+
+use crate::domain::user::{LoginAttemptRepository, LoginAttempts};
+
+pub struct InMemLoginAttemptRepository {
+    cache: InMemCache<String, LoginAttempts>,
+}
+
+impl InMemLoginAttemptRepository {
+    pub fn new() -> Self {
+        InMemLoginAttemptRepository {
+            cache: InMemCache::new(),
+        }
+    }
+}
+
+impl Default for InMemLoginAttemptRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LoginAttemptRepository for InMemLoginAttemptRepository {
+    async fn find_by_key(&self, key: &str) -> Result<LoginAttempts> {
+        self.cache
+            .get(&key.to_owned())
+            .await
+            .ok_or(Error::new("login_attempts", "not_found"))
+    }
+
+    async fn save(&self, attempts: &LoginAttempts) -> Result<()> {
+        self.cache
+            .set(attempts.key().to_owned(), attempts.clone())
+            .await
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.cache.delete(&key.to_owned()).await
+    }
+}
@@ -1,6 +1,12 @@
+mod authorization_request_repository;
+mod login_attempt_repository;
 mod role_repository;
 mod token_repository;
 mod user_repository;
+mod verification_repository;
+pub use authorization_request_repository::*;
+pub use login_attempt_repository::*;
 pub use role_repository::*;
 pub use token_repository::*;
 pub use user_repository::*;
+pub use verification_repository::*;
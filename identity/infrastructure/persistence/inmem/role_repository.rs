@@ -32,13 +32,17 @@ impl InMemRoleRepository {
 #[async_trait]
 impl RoleRepository for InMemRoleRepository {
     async fn find_all(&self) -> Result<Vec<Role>> {
-        Ok(self.cache.all().await)
+        Ok(self
+            .cache
+            .filter(|(_, role)| role.base().deleted_at().is_none())
+            .await)
     }
 
     async fn find_by_id(&self, id: &RoleId) -> Result<Role> {
         self.cache
             .get(id)
             .await
+            .filter(|role| role.base().deleted_at().is_none())
             .ok_or(Error::new("role", "not_found"))
     }
 
@@ -1,20 +1,27 @@
 use async_trait::async_trait;
 
 use common::cache::Cache;
+use common::error::Error;
 use common::infrastructure::cache::InMemCache;
 use common::result::Result;
 
-use crate::domain::token::{Data, TokenId, TokenRepository};
+use crate::domain::token::{
+    Data, PersonalAccessToken, PersonalAccessTokenId, Session, SessionId, TokenId, TokenRepository,
+};
+use crate::domain::user::UserId;
 
-#[derive(Default)]
 pub struct InMemTokenRepository {
     cache: InMemCache<TokenId, Data>,
+    sessions: InMemCache<SessionId, Session>,
+    personal_access_tokens: InMemCache<PersonalAccessTokenId, PersonalAccessToken>,
 }
 
 impl InMemTokenRepository {
     pub fn new() -> Self {
         InMemTokenRepository {
             cache: InMemCache::new(),
+            sessions: InMemCache::new(),
+            personal_access_tokens: InMemCache::new(),
         }
     }
 
@@ -23,6 +30,12 @@ impl InMemTokenRepository {
     }
 }
 
+impl Default for InMemTokenRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl Cache<TokenId, Data> for InMemTokenRepository {
     async fn get(&self, token_id: &TokenId) -> Option<Data> {
@@ -38,12 +51,86 @@ impl Cache<TokenId, Data> for InMemTokenRepository {
     }
 }
 
-impl TokenRepository for InMemTokenRepository {}
+#[async_trait]
+impl TokenRepository for InMemTokenRepository {
+    async fn find_session_by_id(&self, id: &SessionId) -> Result<Session> {
+        self.sessions
+            .get(id)
+            .await
+            .ok_or(Error::new("session", "not_found"))
+    }
+
+    async fn find_session_by_refresh_token_hash(&self, token_hash: &str) -> Result<Session> {
+        self.sessions
+            .find(|(_, session)| {
+                session.refresh_token_hash() == token_hash
+                    || session
+                        .used_refresh_token_hashes()
+                        .iter()
+                        .any(|used| used == token_hash)
+            })
+            .await
+            .ok_or(Error::new("session", "not_found"))
+    }
+
+    async fn find_sessions_by_user_id(&self, user_id: &UserId) -> Result<Vec<Session>> {
+        Ok(self
+            .sessions
+            .filter(|(_, session)| session.user_id() == user_id)
+            .await)
+    }
+
+    async fn save_session(&self, session: &Session) -> Result<()> {
+        self.sessions
+            .set(session.id().clone(), session.clone())
+            .await
+    }
+
+    async fn find_personal_access_token_by_id(
+        &self,
+        id: &PersonalAccessTokenId,
+    ) -> Result<PersonalAccessToken> {
+        self.personal_access_tokens
+            .get(id)
+            .await
+            .ok_or(Error::new("personal_access_token", "not_found"))
+    }
+
+    async fn find_personal_access_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<PersonalAccessToken> {
+        self.personal_access_tokens
+            .find(|(_, token)| token.token_hash() == token_hash)
+            .await
+            .ok_or(Error::new("personal_access_token", "not_found"))
+    }
+
+    async fn find_personal_access_tokens_by_user_id(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Vec<PersonalAccessToken>> {
+        Ok(self
+            .personal_access_tokens
+            .filter(|(_, token)| token.user_id() == user_id)
+            .await)
+    }
+
+    async fn save_personal_access_token(&self, token: &PersonalAccessToken) -> Result<()> {
+        self.personal_access_tokens
+            .set(token.id().clone(), token.clone())
+            .await
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use chrono::Duration;
+
+    use crate::domain::token::Token;
+
     #[tokio::test]
     async fn test() {
         fn check_trait_impl<T: TokenRepository>(_repo: &T) {}
@@ -67,4 +154,48 @@ mod tests {
         assert!(repo.delete(&TokenId::from("T123")).await.is_ok());
         assert!(repo.get(&TokenId::from("T124")).await.is_some());
     }
+
+    #[tokio::test]
+    async fn sessions() {
+        let repo = InMemTokenRepository::new();
+
+        let mut session = Session::new(
+            SessionId::new("#session01").unwrap(),
+            UserId::new("#user01").unwrap(),
+            "Firefox",
+            &Token::new("#refresh01"),
+            Duration::days(1),
+        );
+        session
+            .rotate(&Token::new("#refresh01"), &Token::new("#refresh02"))
+            .unwrap();
+        repo.save_session(&session).await.unwrap();
+
+        let saved = repo
+            .find_session_by_refresh_token_hash(&Token::new("#refresh01").hash())
+            .await
+            .unwrap();
+        assert_eq!(saved.id(), session.id());
+        assert!(repo
+            .find_session_by_refresh_token_hash(&Token::new("#refresh02").hash())
+            .await
+            .is_ok());
+        assert!(repo
+            .find_session_by_refresh_token_hash(&Token::new("#refresh03").hash())
+            .await
+            .is_err());
+
+        assert_eq!(
+            repo.find_sessions_by_user_id(&UserId::new("#user01").unwrap())
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(repo
+            .find_sessions_by_user_id(&UserId::new("#user02").unwrap())
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -4,9 +4,11 @@ use uuid::Uuid;
 use common::cache::Cache;
 use common::error::Error;
 use common::infrastructure::cache::InMemCache;
+use common::model::{Paginated, Pagination};
 use common::result::Result;
 
-use crate::domain::user::{Email, User, UserId, UserRepository, Username};
+use crate::domain::role::RoleId;
+use crate::domain::user::{Email, LinkedAccount, User, UserId, UserRepository, Username};
 use crate::mocks;
 
 pub struct InMemUserRepository {
@@ -70,8 +72,48 @@ impl UserRepository for InMemUserRepository {
             .ok_or(Error::new("user", "not_found"))
     }
 
+    async fn find_by_linked_account(&self, account: &LinkedAccount) -> Result<User> {
+        self.cache
+            .find(|(_, user)| user.linked_accounts().contains(account))
+            .await
+            .ok_or(Error::new("user", "not_found"))
+    }
+
+    async fn find_by_role(&self, role_id: &RoleId) -> Result<Vec<User>> {
+        Ok(self
+            .cache
+            .filter(|(_, user)| user.role().base().id() == role_id)
+            .await)
+    }
+
+    async fn find_page(&self, pagination: &Pagination) -> Result<Paginated<User>> {
+        let users = self.cache.all().await;
+
+        match pagination.sort() {
+            "created_at" => pagination.paginate(
+                users,
+                |user| user.base().id().value(),
+                |user| *user.base().created_at(),
+            ),
+            "username" => pagination.paginate(
+                users,
+                |user| user.base().id().value(),
+                |user| user.identity().username().value().to_owned(),
+            ),
+            _ => Err(pagination.unsupported_sort()),
+        }
+    }
+
     async fn save(&self, user: &mut User) -> Result<()> {
-        self.cache.set(user.base().id().clone(), user.clone()).await
+        self.cache
+            .set_checked(user.base().id().clone(), user.clone(), |stored| {
+                user.base()
+                    .check_version(stored.map(|stored| stored.base().version()))
+            })
+            .await?;
+
+        user.base_mut().commit_version();
+        Ok(())
     }
 }
 
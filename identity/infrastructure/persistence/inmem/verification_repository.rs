@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+
+use common::cache::Cache;
+use common::error::Error;
+use common::infrastructure::cache::InMemCache;
+use common::result::Result;
+
+use crate::domain::user::UserId;
+use crate::domain::verification::{Verification, VerificationRepository};
+
+pub struct InMemVerificationRepository {
+    cache: InMemCache<String, Verification>,
+}
+
+impl InMemVerificationRepository {
+    pub fn new() -> Self {
+        InMemVerificationRepository {
+            cache: InMemCache::new(),
+        }
+    }
+}
+
+impl Default for InMemVerificationRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl VerificationRepository for InMemVerificationRepository {
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Verification> {
+        self.cache
+            .get(&token_hash.to_owned())
+            .await
+            .ok_or(Error::new("verification", "not_found"))
+    }
+
+    async fn find_by_user_id(&self, user_id: &UserId) -> Result<Vec<Verification>> {
+        Ok(self
+            .cache
+            .filter(|(_, verification)| verification.user_id() == user_id)
+            .await)
+    }
+
+    async fn save(&self, verification: &Verification) -> Result<()> {
+        self.cache
+            .set(verification.token_hash().to_owned(), verification.clone())
+            .await
+    }
+}
@@ -0,0 +1,162 @@
+use std::convert::TryFrom;
+
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::user::PasswordHasher;
+
+const SALT_LEN: usize = 16;
+const HASH_LEN: usize = 32;
+
+// Argon2id (RFC 9106) hasher. Hashes are stored as PHC strings, with the parameters they were
+// computed with:
+// $argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>
+pub struct Argon2Hasher {
+    params: Params,
+}
+
+impl Argon2Hasher {
+    pub fn new() -> Self {
+        // Minimum recommended by OWASP.
+        Argon2Hasher::with_params(19456, 2, 1).unwrap()
+    }
+
+    pub fn with_params(memory: u32, iterations: u32, parallelism: u32) -> Result<Self> {
+        let params =
+            Params::new(memory, iterations, parallelism, Some(HASH_LEN)).map_err(|err| {
+                Error::new("argon2", "invalid_params")
+                    .add_context("params", &err.to_string())
+                    .build()
+            })?;
+
+        Ok(Argon2Hasher { params })
+    }
+
+    fn argon2(&self) -> Argon2<'_> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+
+    // Only Argon2id hashes are accepted.
+    fn decode(hashed_password: &str) -> Option<PasswordHash<'_>> {
+        match PasswordHash::new(hashed_password) {
+            Ok(hash) if hash.algorithm == Algorithm::Argon2id.ident() => Some(hash),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Argon2Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PasswordHasher for Argon2Hasher {
+    fn hash(&self, plain_password: &str) -> Result<String> {
+        let mut salt = [0u8; SALT_LEN];
+        SystemRandom::new()
+            .fill(&mut salt)
+            .map_err(|_| Error::internal("password", "hash"))?;
+        let salt =
+            SaltString::b64_encode(&salt).map_err(|_| Error::internal("password", "hash"))?;
+
+        self.argon2()
+            .hash_password(plain_password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|_| Error::internal("password", "hash"))
+    }
+
+    // The hash is verified with its own parameters.
+    fn compare(&self, hashed_password: &str, plain_password: &str) -> bool {
+        match Argon2Hasher::decode(hashed_password) {
+            Some(hash) => self
+                .argon2()
+                .verify_password(plain_password.as_bytes(), &hash)
+                .is_ok(),
+            None => false,
+        }
+    }
+
+    fn needs_rehash(&self, hashed_password: &str) -> bool {
+        let hash = match Argon2Hasher::decode(hashed_password) {
+            Some(hash) => hash,
+            None => return true,
+        };
+
+        match Params::try_from(&hash) {
+            Ok(params) => {
+                hash.version != Some(Version::V0x13.into())
+                    || params.m_cost() != self.params.m_cost()
+                    || params.t_cost() != self.params.t_cost()
+                    || params.p_cost() != self.params.p_cost()
+                    || hash.hash.map(|output| output.len()) != Some(HASH_LEN)
+                    || hash.salt.and_then(|salt| {
+                        let mut buf = [0u8; 64];
+                        salt.b64_decode(&mut buf).ok().map(|salt| salt.len())
+                    }) != Some(SALT_LEN)
+            }
+            Err(_) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn rfc9106() {
+        let params = argon2::ParamsBuilder::new()
+            .m_cost(32)
+            .and_then(|b| b.t_cost(3))
+            .and_then(|b| b.p_cost(4))
+            .and_then(|b| b.data(&[0x04; 12]))
+            .and_then(|b| b.clone().params())
+            .unwrap();
+        let argon2 =
+            Argon2::new_with_secret(&[0x03; 8], Algorithm::Argon2id, Version::V0x13, params)
+                .unwrap();
+
+        let mut hash = [0u8; 32];
+        argon2
+            .hash_password_into(&[0x01; 32], &[0x02; 16], &mut hash)
+            .unwrap();
+        assert_eq!(
+            hex(&hash),
+            "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659"
+        );
+    }
+
+    #[test]
+    fn hash_compare() {
+        let hasher = Argon2Hasher::with_params(64, 1, 1).unwrap();
+
+        let hashed = hasher.hash("P@asswd!").unwrap();
+        assert!(hashed.starts_with("$argon2id$v=19$m=64,t=1,p=1$"));
+        assert!(hasher.compare(&hashed, "P@asswd!"));
+        assert!(!hasher.compare(&hashed, "P@asswd"));
+        assert!(!hasher.needs_rehash(&hashed));
+
+        let stronger = Argon2Hasher::with_params(128, 2, 1).unwrap();
+        assert!(stronger.compare(&hashed, "P@asswd!"));
+        assert!(stronger.needs_rehash(&hashed));
+    }
+
+    #[test]
+    fn invalid_hash() {
+        let hasher = Argon2Hasher::with_params(64, 1, 1).unwrap();
+        assert!(!hasher.compare("$argon2id$v=19$m=64,t=1,p=1$c2FsdA", "P@asswd!"));
+        assert!(!hasher.compare("$argon2i$v=19$m=64,t=1,p=1$c2FsdA$aGFzaA", "P@asswd!"));
+        assert!(!hasher.compare("$argon2id$v=19$m=4,t=1,p=1$c2FsdA$aGFzaA", "P@asswd!"));
+        assert!(hasher.needs_rehash("$2b$12$invalid"));
+        assert!(Argon2Hasher::with_params(64, 0, 1).is_err());
+    }
+}
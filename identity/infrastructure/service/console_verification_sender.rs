@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+
+use common::config::Config;
+use common::result::Result;
+
+use crate::domain::user::User;
+use crate::domain::verification::{VerificationKind, VerificationSender};
+
+// Prints the links instead of emailing them, until there is a mail service.
+pub struct ConsoleVerificationSender {
+    web_url: String,
+}
+
+impl ConsoleVerificationSender {
+    pub fn new<S: Into<String>>(web_url: S) -> Self {
+        ConsoleVerificationSender {
+            web_url: web_url.into(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.web_url())
+    }
+
+    // Page of the web application where the link is followed.
+    pub fn link(&self, user: &User, kind: &VerificationKind, token: &str) -> String {
+        let web_url = self.web_url.trim_end_matches('/');
+        match kind {
+            VerificationKind::Email => format!(
+                "{}/users/{}/validate/{}",
+                web_url,
+                user.base().id().value(),
+                token
+            ),
+            VerificationKind::PasswordReset => {
+                format!("{}/reset-password?token={}", web_url, token)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl VerificationSender for ConsoleVerificationSender {
+    async fn send(&self, user: &User, kind: &VerificationKind, token: &str) -> Result<()> {
+        println!(
+            "[{}] {}: {}",
+            kind,
+            user.identity().email().value(),
+            self.link(user, kind, token)
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    #[test]
+    fn link() {
+        let sender = ConsoleVerificationSender::new("http://localhost:4200/");
+        let user = mocks::user1();
+
+        assert_eq!(
+            sender.link(&user, &VerificationKind::Email, "token"),
+            "http://localhost:4200/users/#user1/validate/token"
+        );
+        assert_eq!(
+            sender.link(&user, &VerificationKind::PasswordReset, "token"),
+            "http://localhost:4200/reset-password?token=token"
+        );
+    }
+}
@@ -1,6 +1,15 @@
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use std::collections::HashMap;
+use std::fs;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+use common::config::Config;
 use common::error::Error;
 use common::result::Result;
 
@@ -9,29 +18,179 @@ use crate::domain::token::{Token, TokenEncoder, TokenId};
 #[derive(Serialize, Deserialize)]
 struct Claims {
     sub: String,
-    company: String,
-    exp: usize,
+    iss: String,
+    aud: String,
+    iat: u64,
+    exp: u64,
+}
+
+struct VerificationKey {
+    algorithm: Algorithm,
+    key: DecodingKey<'static>,
 }
 
-#[derive(Default)]
-pub struct JWTEncoder;
+// Signs tokens with the current key and verifies them with the key named by the `kid` header,
+// so tokens signed with a previous key are still valid while keys are rotated. HMAC keys are
+// secrets, RSA and ECDSA keys are PEM encoded.
+pub struct JWTEncoder {
+    kid: String,
+    algorithm: Algorithm,
+    signing_key: EncodingKey,
+    verification_keys: HashMap<String, VerificationKey>,
+    issuer: String,
+    audience: String,
+    expiration: Duration,
+}
 
 impl JWTEncoder {
-    pub fn new() -> Self {
-        JWTEncoder
+    pub fn new<S: Into<String>>(
+        kid: S,
+        algorithm: Algorithm,
+        signing_key: &[u8],
+        verification_key: &[u8],
+    ) -> Result<Self> {
+        let kid = kid.into();
+        let signing_key = match algorithm {
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                Ok(EncodingKey::from_secret(signing_key))
+            }
+            Algorithm::ES256 | Algorithm::ES384 => EncodingKey::from_ec_pem(signing_key),
+            _ => EncodingKey::from_rsa_pem(signing_key),
+        }
+        .map_err(|err| {
+            Error::internal("token", "invalid_signing_key")
+                .add_context("kid", &kid)
+                .wrap_raw(err)
+                .build()
+        })?;
+
+        let mut encoder = JWTEncoder {
+            kid: kid.clone(),
+            algorithm,
+            signing_key,
+            verification_keys: HashMap::new(),
+            issuer: "omics".to_owned(),
+            audience: "omics".to_owned(),
+            expiration: Duration::from_secs(900),
+        };
+        encoder.add_verification_key(kid, algorithm, verification_key)?;
+
+        Ok(encoder)
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let algorithm = parse_algorithm(config.jwt_algorithm())?;
+
+        let mut encoder = match algorithm {
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                let secret = match config.jwt_secret() {
+                    Some(secret) => secret.to_owned(),
+                    None if config.env() == "production" => {
+                        return Err(Error::internal("token", "missing_secret")
+                            .add_context("env", "JWT_SECRET")
+                            .build())
+                    }
+                    // Tokens only last while the server is running.
+                    None => {
+                        println!("JWT_SECRET is not set, using a random secret");
+                        format!("{}{}", Uuid::new_v4(), Uuid::new_v4())
+                    }
+                };
+                Self::new(
+                    config.jwt_key_id(),
+                    algorithm,
+                    secret.as_bytes(),
+                    secret.as_bytes(),
+                )?
+            }
+            _ => {
+                let private_key = read_key(config.jwt_private_key_path(), "JWT_PRIVATE_KEY_PATH")?;
+                let public_key = read_key(config.jwt_public_key_path(), "JWT_PUBLIC_KEY_PATH")?;
+                Self::new(config.jwt_key_id(), algorithm, &private_key, &public_key)?
+            }
+        };
+
+        for previous_key in config.jwt_previous_keys().iter() {
+            let parts: Vec<&str> = previous_key.splitn(3, ':').collect();
+            if parts.len() != 3 {
+                return Err(Error::internal("token", "invalid_previous_key")
+                    .add_context("key", previous_key)
+                    .build());
+            }
+
+            let key = read_key(Some(parts[2]), "JWT_PREVIOUS_KEYS")?;
+            encoder.add_verification_key(parts[0], parse_algorithm(parts[1])?, &key)?;
+        }
+
+        encoder.set_issuer(config.jwt_issuer());
+        encoder.set_audience(config.jwt_audience());
+        encoder.set_expiration(Duration::from_secs(config.jwt_expiration()));
+
+        Ok(encoder)
+    }
+
+    pub fn set_issuer<S: Into<String>>(&mut self, issuer: S) {
+        self.issuer = issuer.into();
+    }
+
+    pub fn set_audience<S: Into<String>>(&mut self, audience: S) {
+        self.audience = audience.into();
+    }
+
+    pub fn set_expiration(&mut self, expiration: Duration) {
+        self.expiration = expiration;
+    }
+
+    // Accepts tokens signed with the given key, usually one that has been rotated.
+    pub fn add_verification_key<S: Into<String>>(
+        &mut self,
+        kid: S,
+        algorithm: Algorithm,
+        key: &[u8],
+    ) -> Result<()> {
+        let kid = kid.into();
+        let key = match algorithm {
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                Ok(DecodingKey::from_secret(key).into_static())
+            }
+            Algorithm::ES256 | Algorithm::ES384 => {
+                DecodingKey::from_ec_pem(key).map(DecodingKey::into_static)
+            }
+            _ => DecodingKey::from_rsa_pem(key).map(DecodingKey::into_static),
+        }
+        .map_err(|err| {
+            Error::internal("token", "invalid_verification_key")
+                .add_context("kid", &kid)
+                .wrap_raw(err)
+                .build()
+        })?;
+
+        self.verification_keys
+            .insert(kid, VerificationKey { algorithm, key });
+
+        Ok(())
     }
 }
 
 impl TokenEncoder for JWTEncoder {
     fn encode(&self, token_id: &TokenId) -> Result<Token> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs())
+            .unwrap_or(0);
+
         let claims = Claims {
             sub: token_id.to_string(),
-            company: "Omics".to_owned(),
-            exp: 10000000000,
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+            iat: now,
+            exp: now + self.expiration.as_secs(),
         };
-        let key = b"secret";
 
-        let token = match encode(&Header::default(), &claims, &EncodingKey::from_secret(key)) {
+        let mut header = Header::new(self.algorithm);
+        header.kid = Some(self.kid.clone());
+
+        let token = match encode(&header, &claims, &self.signing_key) {
             Ok(token) => token,
             Err(err) => return Err(Error::new("token", "encode").wrap_raw(err).build()),
         };
@@ -40,13 +199,25 @@ impl TokenEncoder for JWTEncoder {
     }
 
     fn decode(&self, token: &Token) -> Result<TokenId> {
-        let key = b"secret";
+        let header = match decode_header(token.value()) {
+            Ok(header) => header,
+            Err(err) => return Err(Error::new("token", "decode").wrap_raw(err).build()),
+        };
 
-        let token_data = match decode::<Claims>(
-            token.value(),
-            &DecodingKey::from_secret(key),
-            &Validation::default(),
-        ) {
+        let key = match header
+            .kid
+            .as_ref()
+            .and_then(|kid| self.verification_keys.get(kid))
+        {
+            Some(key) => key,
+            None => return Err(Error::new("token", "unknown_key")),
+        };
+
+        let mut validation = Validation::new(key.algorithm);
+        validation.iss = Some(self.issuer.clone());
+        validation.set_audience(&[&self.audience]);
+
+        let token_data = match decode::<Claims>(token.value(), &key.key, &validation) {
             Ok(data) => data,
             Err(err) => return Err(Error::new("token", "decode").wrap_raw(err).build()),
         };
@@ -55,17 +226,109 @@ impl TokenEncoder for JWTEncoder {
     }
 }
 
+fn parse_algorithm(algorithm: &str) -> Result<Algorithm> {
+    Algorithm::from_str(algorithm).map_err(|err| {
+        Error::internal("token", "invalid_algorithm")
+            .add_context("algorithm", algorithm)
+            .wrap_raw(err)
+            .build()
+    })
+}
+
+fn read_key(path: Option<&str>, env: &str) -> Result<Vec<u8>> {
+    let path = path.ok_or_else(|| {
+        Error::internal("token", "missing_key")
+            .add_context("env", env)
+            .build()
+    })?;
+
+    fs::read(path).map_err(|err| {
+        Error::internal("token", "read_key")
+            .add_context("path", path)
+            .wrap_raw(err)
+            .build()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const RSA_PRIVATE: &[u8] = include_bytes!("testdata/rsa_private.pem");
+    const RSA_PUBLIC: &[u8] = include_bytes!("testdata/rsa_public.pem");
+    const EC_PRIVATE: &[u8] = include_bytes!("testdata/ec_private.pem");
+    const EC_PUBLIC: &[u8] = include_bytes!("testdata/ec_public.pem");
+
     #[test]
     fn encode_decode() {
         let token_id = TokenId::build("#token01");
-        let enc = JWTEncoder::new();
+        let encoders = [
+            JWTEncoder::new("hmac", Algorithm::HS256, b"secret", b"secret").unwrap(),
+            JWTEncoder::new("rsa", Algorithm::RS256, RSA_PRIVATE, RSA_PUBLIC).unwrap(),
+            JWTEncoder::new("ec", Algorithm::ES256, EC_PRIVATE, EC_PUBLIC).unwrap(),
+        ];
+
+        for enc in encoders.iter() {
+            let token = enc.encode(&token_id).unwrap();
+            assert!(token.value().len() > 10);
+            assert_eq!(enc.decode(&token).unwrap(), token_id);
+        }
+
+        assert!(JWTEncoder::new("rsa", Algorithm::RS256, EC_PRIVATE, EC_PUBLIC).is_err());
+    }
+
+    #[test]
+    fn claims() {
+        let token_id = TokenId::build("#token01");
+        let enc = JWTEncoder::new("hmac", Algorithm::HS256, b"secret", b"secret").unwrap();
+
+        // Expired
+        let claims = Claims {
+            sub: token_id.to_string(),
+            iss: "omics".to_owned(),
+            aud: "omics".to_owned(),
+            iat: 1600000000,
+            exp: 1600003600,
+        };
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("hmac".to_owned());
+        let token = encode(&header, &claims, &EncodingKey::from_secret(b"secret")).unwrap();
+        assert!(enc.decode(&Token::new(token)).is_err());
+
+        // Other issuer or audience
+        let mut other = JWTEncoder::new("hmac", Algorithm::HS256, b"secret", b"secret").unwrap();
+        other.set_issuer("other");
+        assert!(enc.decode(&other.encode(&token_id).unwrap()).is_err());
+
+        let mut other = JWTEncoder::new("hmac", Algorithm::HS256, b"secret", b"secret").unwrap();
+        other.set_audience("other");
+        assert!(enc.decode(&other.encode(&token_id).unwrap()).is_err());
+
+        // Other secret
+        let other = JWTEncoder::new("hmac", Algorithm::HS256, b"other", b"other").unwrap();
+        assert!(enc.decode(&other.encode(&token_id).unwrap()).is_err());
+    }
+
+    #[test]
+    fn rotation() {
+        let token_id = TokenId::build("#token01");
+        let previous = JWTEncoder::new("2020-09", Algorithm::ES256, EC_PRIVATE, EC_PUBLIC).unwrap();
+        let previous_token = previous.encode(&token_id).unwrap();
+
+        let mut enc =
+            JWTEncoder::new("2020-10", Algorithm::RS256, RSA_PRIVATE, RSA_PUBLIC).unwrap();
+        assert!(enc.decode(&previous_token).is_err());
+
+        enc.add_verification_key("2020-09", Algorithm::ES256, EC_PUBLIC)
+            .unwrap();
+        assert_eq!(enc.decode(&previous_token).unwrap(), token_id);
 
         let token = enc.encode(&token_id).unwrap();
-        assert!(token.value().len() > 10);
         assert_eq!(enc.decode(&token).unwrap(), token_id);
+        assert!(previous.decode(&token).is_err());
+
+        // A token whose kid names a key of another algorithm.
+        let forged = JWTEncoder::new("2020-10", Algorithm::HS256, RSA_PUBLIC, RSA_PUBLIC).unwrap();
+        assert!(enc.decode(&forged.encode(&token_id).unwrap()).is_err());
     }
 }
@@ -1,4 +1,12 @@
+mod argon2_hasher;
 mod bcrypt_hasher;
+mod console_verification_sender;
 mod jwt_encoder;
+mod oidc_client;
+mod phc_hasher;
+pub use argon2_hasher::*;
 pub use bcrypt_hasher::*;
+pub use console_verification_sender::*;
 pub use jwt_encoder::*;
+pub use oidc_client::*;
+pub use phc_hasher::*;
@@ -0,0 +1,500 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use awc::{Client, SendClientRequest};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::RwLock;
+use url::Url;
+
+use common::config::{Config, OidcProviderConfig};
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::oidc::{AuthorizationRequest, IdentityClaims, OidcClient};
+use crate::domain::user::Provider;
+
+const TIMEOUT_SECS: u64 = 10;
+
+// Provider metadata from the discovery document.
+#[derive(Debug, Clone, Deserialize)]
+struct Metadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Deserialize)]
+struct Jwks {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Deserialize)]
+struct Jwk {
+    kid: Option<String>,
+    kty: String,
+    n: Option<String>,
+    e: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+    email: Option<String>,
+    // Some providers send it as a string.
+    email_verified: Option<Value>,
+    name: Option<String>,
+    nonce: Option<String>,
+}
+
+struct ProviderClient {
+    discovery_url: String,
+    client_id: String,
+    client_secret: String,
+    redirect_url: String,
+    metadata: RwLock<Option<Metadata>>,
+}
+
+// Authorization code flow with PKCE against the discovered endpoints of each provider. ID tokens
+// must be signed with RS256 by one of the keys published by the provider.
+#[derive(Default)]
+pub struct HttpOidcClient {
+    providers: HashMap<String, ProviderClient>,
+}
+
+impl HttpOidcClient {
+    pub fn new() -> Self {
+        HttpOidcClient {
+            providers: HashMap::new(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        let mut client = Self::new();
+
+        let providers = vec![
+            (Provider::Google, config.oidc_google()),
+            (Provider::Facebook, config.oidc_facebook()),
+        ];
+        for (provider, provider_config) in providers.into_iter() {
+            if let Some(provider_config) = provider_config {
+                client.add_provider_config(provider, provider_config);
+            }
+        }
+
+        client
+    }
+
+    pub fn add_provider<S: Into<String>>(
+        &mut self,
+        provider: Provider,
+        discovery_url: S,
+        client_id: S,
+        client_secret: S,
+        redirect_url: S,
+    ) {
+        self.providers.insert(
+            provider.to_string(),
+            ProviderClient {
+                discovery_url: discovery_url.into(),
+                client_id: client_id.into(),
+                client_secret: client_secret.into(),
+                redirect_url: redirect_url.into(),
+                metadata: RwLock::new(None),
+            },
+        );
+    }
+
+    fn add_provider_config(&mut self, provider: Provider, config: &OidcProviderConfig) {
+        self.add_provider(
+            provider,
+            config.discovery_url(),
+            config.client_id(),
+            config.client_secret(),
+            config.redirect_url(),
+        );
+    }
+
+    fn provider(&self, provider: &Provider) -> Result<&ProviderClient> {
+        self.providers.get(&provider.to_string()).ok_or_else(|| {
+            Error::new("oidc", "provider_not_configured")
+                .add_context("provider", &provider.to_string())
+                .build()
+        })
+    }
+
+    // The discovery document is requested once.
+    async fn metadata(&self, provider: &ProviderClient) -> Result<Metadata> {
+        if let Some(metadata) = provider.metadata.read().await.as_ref() {
+            return Ok(metadata.clone());
+        }
+
+        let metadata: Metadata = send(
+            Client::default()
+                .get(&provider.discovery_url)
+                .timeout(Duration::from_secs(TIMEOUT_SECS))
+                .send(),
+            &provider.discovery_url,
+        )
+        .await?;
+        *provider.metadata.write().await = Some(metadata.clone());
+
+        Ok(metadata)
+    }
+
+    async fn verify(
+        &self,
+        provider: &ProviderClient,
+        metadata: &Metadata,
+        id_token: &str,
+    ) -> Result<Claims> {
+        let header = decode_header(id_token).map_err(|err| {
+            Error::new("oidc", "invalid_id_token")
+                .set_status(401)
+                .wrap_raw(err)
+                .build()
+        })?;
+        if header.alg != Algorithm::RS256 {
+            return Err(Error::new("oidc", "invalid_id_token")
+                .set_status(401)
+                .add_context("alg", &format!("{:?}", header.alg))
+                .build());
+        }
+
+        let jwks: Jwks = send(
+            Client::default()
+                .get(&metadata.jwks_uri)
+                .timeout(Duration::from_secs(TIMEOUT_SECS))
+                .send(),
+            &metadata.jwks_uri,
+        )
+        .await?;
+
+        let jwk = jwks
+            .keys
+            .iter()
+            .filter(|jwk| jwk.kty == "RSA")
+            .find(|jwk| header.kid.is_none() || jwk.kid == header.kid);
+        let (n, e) = match jwk.and_then(|jwk| Some((jwk.n.as_ref()?, jwk.e.as_ref()?))) {
+            Some(components) => components,
+            None => {
+                return Err(Error::new("oidc", "unknown_key")
+                    .set_status(401)
+                    .add_context("kid", header.kid.as_deref().unwrap_or(""))
+                    .build())
+            }
+        };
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.iss = Some(metadata.issuer.clone());
+        validation.set_audience(&[&provider.client_id]);
+
+        decode::<Claims>(
+            id_token,
+            &DecodingKey::from_rsa_components(n, e),
+            &validation,
+        )
+        .map(|data| data.claims)
+        .map_err(|err| {
+            Error::new("oidc", "invalid_id_token")
+                .set_status(401)
+                .wrap_raw(err)
+                .build()
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl OidcClient for HttpOidcClient {
+    async fn authorization_url(&self, req: &AuthorizationRequest) -> Result<String> {
+        let provider = self.provider(req.provider())?;
+        let metadata = self.metadata(provider).await?;
+
+        let url = Url::parse_with_params(
+            &metadata.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", &provider.client_id),
+                ("redirect_uri", &provider.redirect_url),
+                ("scope", "openid email profile"),
+                ("state", req.state()),
+                ("nonce", req.nonce()),
+                ("code_challenge", &req.code_challenge()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(|err| {
+            Error::internal("oidc", "invalid_authorization_endpoint")
+                .add_context("url", &metadata.authorization_endpoint)
+                .wrap_raw(err)
+                .build()
+        })?;
+
+        Ok(url.to_string())
+    }
+
+    async fn exchange_code(
+        &self,
+        req: &AuthorizationRequest,
+        code: &str,
+    ) -> Result<IdentityClaims> {
+        let provider = self.provider(req.provider())?;
+        let metadata = self.metadata(provider).await?;
+
+        let res: TokenResponse = send(
+            Client::default()
+                .post(&metadata.token_endpoint)
+                .timeout(Duration::from_secs(TIMEOUT_SECS))
+                .send_form(&[
+                    ("grant_type", "authorization_code"),
+                    ("code", code),
+                    ("redirect_uri", &provider.redirect_url),
+                    ("client_id", &provider.client_id),
+                    ("client_secret", &provider.client_secret),
+                    ("code_verifier", req.code_verifier()),
+                ]),
+            &metadata.token_endpoint,
+        )
+        .await?;
+
+        let claims = self.verify(provider, &metadata, &res.id_token).await?;
+
+        // Emails are only trusted when the provider says they are verified.
+        let email_verified = match claims.email_verified {
+            Some(Value::Bool(verified)) => verified,
+            Some(Value::String(verified)) => verified == "true",
+            _ => false,
+        };
+
+        Ok(IdentityClaims::new(
+            claims.sub,
+            claims.email,
+            email_verified,
+            claims.name,
+            claims.nonce,
+        ))
+    }
+}
+
+async fn send<T: DeserializeOwned>(req: SendClientRequest, url: &str) -> Result<T> {
+    let mut res = req.await.map_err(|err| {
+        Error::internal("oidc", "request")
+            .add_context("url", url)
+            .add_context("error", &err.to_string())
+            .build()
+    })?;
+
+    let body = res.body().await.map_err(|err| {
+        Error::internal("oidc", "response")
+            .add_context("url", url)
+            .add_context("error", &err.to_string())
+            .build()
+    })?;
+
+    if !res.status().is_success() {
+        return Err(Error::new("oidc", "provider_error")
+            .set_status(401)
+            .add_context("url", url)
+            .add_context("status", res.status().as_str())
+            .add_context("body", &String::from_utf8_lossy(&body))
+            .build());
+    }
+
+    serde_json::from_slice(&body).map_err(|err| {
+        Error::internal("oidc", "invalid_response")
+            .add_context("url", url)
+            .wrap_raw(err)
+            .build()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use chrono::Utc;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde::Serialize;
+    use serde_json::json;
+
+    const RSA_PRIVATE: &[u8] = include_bytes!("testdata/rsa_private.pem");
+    const JWKS: &str = include_str!("testdata/jwks.json");
+
+    // Local identity provider issuing the ID token set by the test for the code "code01".
+    #[derive(Default)]
+    struct MockIdp {
+        issuer: String,
+        code_challenge: String,
+        id_token: String,
+    }
+
+    type Idp = web::Data<Arc<Mutex<MockIdp>>>;
+
+    #[derive(Deserialize)]
+    struct TokenForm {
+        code: String,
+        client_id: String,
+        code_verifier: String,
+    }
+
+    #[derive(Serialize)]
+    struct IdTokenClaims {
+        iss: String,
+        aud: String,
+        sub: String,
+        email: String,
+        email_verified: bool,
+        nonce: String,
+        exp: i64,
+    }
+
+    async fn discovery(idp: Idp) -> HttpResponse {
+        let issuer = idp.lock().unwrap().issuer.clone();
+        HttpResponse::Ok().json(json!({
+            "issuer": issuer,
+            "authorization_endpoint": format!("{}/authorize", issuer),
+            "token_endpoint": format!("{}/token", issuer),
+            "jwks_uri": format!("{}/jwks", issuer),
+        }))
+    }
+
+    async fn jwks() -> HttpResponse {
+        HttpResponse::Ok()
+            .content_type("application/json")
+            .body(JWKS)
+    }
+
+    async fn token(form: web::Form<TokenForm>, idp: Idp) -> HttpResponse {
+        let idp = idp.lock().unwrap();
+
+        let challenge = code_challenge(&form.code_verifier);
+        if form.code != "code01" || form.client_id != "client01" || challenge != idp.code_challenge
+        {
+            return HttpResponse::BadRequest().json(json!({ "error": "invalid_grant" }));
+        }
+
+        HttpResponse::Ok().json(json!({ "id_token": idp.id_token }))
+    }
+
+    fn code_challenge(code_verifier: &str) -> String {
+        let hash = ring::digest::digest(&ring::digest::SHA256, code_verifier.as_bytes());
+        base64::encode_config(hash.as_ref(), base64::URL_SAFE_NO_PAD)
+    }
+
+    fn id_token(issuer: &str, aud: &str, kid: &str, nonce: &str, exp: i64) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(kid.to_owned());
+        encode(
+            &header,
+            &IdTokenClaims {
+                iss: issuer.to_owned(),
+                aud: aud.to_owned(),
+                sub: "google-01".to_owned(),
+                email: "user@gmail.com".to_owned(),
+                email_verified: true,
+                nonce: nonce.to_owned(),
+                exp,
+            },
+            &EncodingKey::from_rsa_pem(RSA_PRIVATE).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn query_param(url: &str, name: &str) -> String {
+        Url::parse(url)
+            .unwrap()
+            .query_pairs()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.to_string())
+            .unwrap()
+    }
+
+    // Runs a local HTTP server: cargo test -- --ignored
+    #[actix_rt::test]
+    #[ignore]
+    async fn mock_idp() {
+        let idp = Arc::new(Mutex::new(MockIdp::default()));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let issuer = format!("http://{}", listener.local_addr().unwrap());
+        idp.lock().unwrap().issuer = issuer.clone();
+
+        let data = Arc::clone(&idp);
+        let server = HttpServer::new(move || {
+            App::new()
+                .data(Arc::clone(&data))
+                .route(
+                    "/.well-known/openid-configuration",
+                    web::get().to(discovery),
+                )
+                .route("/jwks", web::get().to(jwks))
+                .route("/token", web::post().to(token))
+        })
+        .workers(1)
+        .listen(listener)
+        .unwrap();
+        let server = server.run();
+
+        let mut client = HttpOidcClient::new();
+        client.add_provider(
+            Provider::Google,
+            format!("{}/.well-known/openid-configuration", issuer),
+            "client01".to_owned(),
+            "secret01".to_owned(),
+            "http://localhost:3000/api/oidc/google/callback".to_owned(),
+        );
+
+        let req = AuthorizationRequest::new(Provider::Google).unwrap();
+        let url = client.authorization_url(&req).await.unwrap();
+        assert!(url.starts_with(&format!("{}/authorize?", issuer)));
+        assert_eq!(query_param(&url, "client_id"), "client01");
+        assert_eq!(query_param(&url, "state"), req.state());
+        assert_eq!(query_param(&url, "nonce"), req.nonce());
+        assert_eq!(query_param(&url, "code_challenge_method"), "S256");
+        idp.lock().unwrap().code_challenge = query_param(&url, "code_challenge");
+
+        let exp = Utc::now().timestamp() + 300;
+        idp.lock().unwrap().id_token = id_token(&issuer, "client01", "idp-key", req.nonce(), exp);
+        let claims = client.exchange_code(&req, "code01").await.unwrap();
+        assert_eq!(claims.subject(), "google-01");
+        assert_eq!(claims.email(), Some("user@gmail.com"));
+        assert!(claims.email_verified());
+        assert_eq!(claims.nonce(), Some(req.nonce()));
+
+        // Invalid code or code verifier
+        assert!(client.exchange_code(&req, "code02").await.is_err());
+        let other = AuthorizationRequest::new(Provider::Google).unwrap();
+        assert!(client.exchange_code(&other, "code01").await.is_err());
+
+        // Invalid ID tokens
+        let invalid = vec![
+            id_token("http://other", "client01", "idp-key", req.nonce(), exp),
+            id_token(&issuer, "client02", "idp-key", req.nonce(), exp),
+            id_token(&issuer, "client01", "other-key", req.nonce(), exp),
+            id_token(&issuer, "client01", "idp-key", req.nonce(), exp - 3600),
+        ];
+        for id_token in invalid.into_iter() {
+            idp.lock().unwrap().id_token = id_token;
+            assert!(client.exchange_code(&req, "code01").await.is_err());
+        }
+
+        // Not configured
+        let req = AuthorizationRequest::new(Provider::Facebook).unwrap();
+        assert!(client.authorization_url(&req).await.is_err());
+
+        server.stop(true).await;
+    }
+}
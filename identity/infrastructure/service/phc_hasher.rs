@@ -0,0 +1,84 @@
+use common::result::Result;
+
+use crate::domain::user::PasswordHasher;
+use crate::infrastructure::service::{Argon2Hasher, BcryptHasher};
+
+// Hashes new passwords with Argon2id and verifies the stored ones by the algorithm of their
+// PHC string, so bcrypt hashes keep working until they are replaced.
+pub struct PhcHasher {
+    argon2: Argon2Hasher,
+    bcrypt: BcryptHasher,
+}
+
+impl PhcHasher {
+    pub fn new() -> Self {
+        PhcHasher {
+            argon2: Argon2Hasher::new(),
+            bcrypt: BcryptHasher::new(),
+        }
+    }
+
+    pub fn with_argon2(argon2: Argon2Hasher) -> Self {
+        PhcHasher {
+            argon2,
+            bcrypt: BcryptHasher::new(),
+        }
+    }
+}
+
+impl Default for PhcHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_bcrypt(hashed_password: &str) -> bool {
+    ["$2a$", "$2b$", "$2x$", "$2y$"]
+        .iter()
+        .any(|prefix| hashed_password.starts_with(prefix))
+}
+
+impl PasswordHasher for PhcHasher {
+    fn hash(&self, plain_password: &str) -> Result<String> {
+        self.argon2.hash(plain_password)
+    }
+
+    fn compare(&self, hashed_password: &str, plain_password: &str) -> bool {
+        if hashed_password.starts_with("$argon2id$") {
+            self.argon2.compare(hashed_password, plain_password)
+        } else if is_bcrypt(hashed_password) {
+            self.bcrypt.compare(hashed_password, plain_password)
+        } else {
+            false
+        }
+    }
+
+    fn needs_rehash(&self, hashed_password: &str) -> bool {
+        self.argon2.needs_rehash(hashed_password)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_hash() {
+        let hasher = PhcHasher::with_argon2(Argon2Hasher::with_params(64, 1, 1).unwrap());
+
+        let legacy = bcrypt::hash("P@asswd!", 4).unwrap();
+        assert!(hasher.compare(&legacy, "P@asswd!"));
+        assert!(!hasher.compare(&legacy, "invalid"));
+        assert!(hasher.needs_rehash(&legacy));
+
+        let hashed = hasher.hash("P@asswd!").unwrap();
+        assert!(hashed.starts_with("$argon2id$"));
+        assert!(hasher.compare(&hashed, "P@asswd!"));
+        assert!(!hasher.needs_rehash(&hashed));
+
+        assert!(!hasher.compare(
+            "$$XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX",
+            "P@asswd!"
+        ));
+    }
+}
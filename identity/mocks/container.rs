@@ -4,17 +4,30 @@ use common::mocks::FakeEventPublisher;
 
 use crate::container::Container;
 use crate::infrastructure::persistence::inmem::{
-    InMemRoleRepository, InMemTokenRepository, InMemUserRepository,
+    InMemAuthorizationRequestRepository, InMemLoginAttemptRepository, InMemRoleRepository,
+    InMemTokenRepository, InMemUserRepository, InMemVerificationRepository,
 };
-use crate::mocks::{FakePasswordHasher, FakeTokenEncoder};
+use crate::mocks::{FakeOidcClient, FakePasswordHasher, FakeTokenEncoder, FakeVerificationSender};
 
 pub fn container() -> Container<FakeEventPublisher> {
+    container_with_verification_sender(Arc::new(FakeVerificationSender::new()))
+}
+
+// Container whose sent verification tokens can be read from the given sender.
+pub fn container_with_verification_sender(
+    verification_sender: Arc<FakeVerificationSender>,
+) -> Container<FakeEventPublisher> {
     Container::new(
         Arc::new(FakeEventPublisher::new()),
+        Arc::new(InMemAuthorizationRequestRepository::new()),
+        Arc::new(InMemLoginAttemptRepository::new()),
         Arc::new(InMemRoleRepository::new()),
         Arc::new(InMemTokenRepository::new()),
         Arc::new(InMemUserRepository::new()),
+        Arc::new(InMemVerificationRepository::new()),
         Arc::new(FakePasswordHasher::new()),
         Arc::new(FakeTokenEncoder::new()),
+        Arc::new(FakeOidcClient::new()),
+        verification_sender,
     )
 }
@@ -1,4 +1,4 @@
-use crate::domain::role::{Role, RoleId};
+use crate::domain::role::{Permission, Role, RoleId};
 use crate::domain::user::{
     Email, Fullname, Identity, Password, PasswordHasher, Person, Provider, User, UserId, Username,
 };
@@ -39,8 +39,7 @@ pub fn user2() -> User {
 pub fn validated_user1() -> User {
     let mut user = user1();
 
-    let validation = user.validation().cloned().unwrap();
-    user.validate(&validation).unwrap();
+    user.validate().unwrap();
 
     user
 }
@@ -48,8 +47,7 @@ pub fn validated_user1() -> User {
 pub fn validated_user2() -> User {
     let mut user = user2();
 
-    let validation = user.validation().cloned().unwrap();
-    user.validate(&validation).unwrap();
+    user.validate().unwrap();
 
     user
 }
@@ -75,7 +73,10 @@ pub fn user_role() -> Role {
 }
 
 pub fn admin_role() -> Role {
-    Role::new(RoleId::new("admin").unwrap(), "Administrator").unwrap()
+    let mut role = Role::new(RoleId::new("admin").unwrap(), "Administrator").unwrap();
+    role.add_permissions(Permission::new("users", "CRUD").unwrap());
+    role.add_permissions(Permission::new("roles", "CRUD").unwrap());
+    role
 }
 
 pub fn person1() -> Person {
@@ -1,8 +1,12 @@
 mod container;
 mod domain;
+mod oidc_client;
 mod password_hasher;
 mod token_encoder;
+mod verification_sender;
 pub use self::domain::*;
 pub use container::*;
+pub use oidc_client::*;
 pub use password_hasher::*;
 pub use token_encoder::*;
+pub use verification_sender::*;
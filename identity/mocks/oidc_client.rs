@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+
+use common::result::Result;
+
+use crate::domain::oidc::{AuthorizationRequest, IdentityClaims, OidcClient};
+
+// Exchanges codes containing the email of the account: `<email>`, `unverified:<email>` for an
+// unverified email, `invalid-nonce:<email>` for an ID token issued for another request and
+// `other-account:<email>` for another account of the provider with the same email.
+#[derive(Default)]
+pub struct FakeOidcClient;
+
+impl FakeOidcClient {
+    pub fn new() -> Self {
+        FakeOidcClient
+    }
+}
+
+#[async_trait(?Send)]
+impl OidcClient for FakeOidcClient {
+    async fn authorization_url(&self, req: &AuthorizationRequest) -> Result<String> {
+        Ok(format!(
+            "https://{}.test/authorize?state={}",
+            req.provider().to_string(),
+            req.state()
+        ))
+    }
+
+    async fn exchange_code(
+        &self,
+        req: &AuthorizationRequest,
+        code: &str,
+    ) -> Result<IdentityClaims> {
+        let mut email_verified = true;
+        let mut nonce = req.nonce().to_owned();
+        let mut subject = req.provider().to_string();
+        let email = if let Some(email) = code.strip_prefix("unverified:") {
+            email_verified = false;
+            email
+        } else if let Some(email) = code.strip_prefix("invalid-nonce:") {
+            nonce = "invalid".to_owned();
+            email
+        } else if let Some(email) = code.strip_prefix("other-account:") {
+            subject = format!("{}-other", subject);
+            email
+        } else {
+            code
+        };
+
+        Ok(IdentityClaims::new(
+            format!("{}|{}", subject, email),
+            Some(email.to_owned()),
+            email_verified,
+            None,
+            Some(nonce),
+        ))
+    }
+}
@@ -9,6 +9,11 @@ impl FakePasswordHasher {
     pub fn new() -> Self {
         FakePasswordHasher
     }
+
+    // Hash of an outdated algorithm, still accepted but replaced on login.
+    pub fn legacy_hash(plain_pasword: &str) -> String {
+        format!("$legacy${:X>50}", plain_pasword)
+    }
 }
 
 impl PasswordHasher for FakePasswordHasher {
@@ -18,6 +23,11 @@ impl PasswordHasher for FakePasswordHasher {
 
     fn compare(&self, hashed_password: &str, plain_pasword: &str) -> bool {
         hashed_password == format!("$${:X>50}##", plain_pasword)
+            || hashed_password == Self::legacy_hash(plain_pasword)
+    }
+
+    fn needs_rehash(&self, hashed_password: &str) -> bool {
+        hashed_password.starts_with("$legacy$")
     }
 }
 
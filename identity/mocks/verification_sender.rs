@@ -0,0 +1,44 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use common::result::Result;
+
+use crate::domain::user::{User, UserId};
+use crate::domain::verification::{VerificationKind, VerificationSender};
+
+// Keeps the sent tokens so tests can follow the links.
+#[derive(Default)]
+pub struct FakeVerificationSender {
+    sent: Mutex<Vec<(UserId, VerificationKind, String)>>,
+}
+
+impl FakeVerificationSender {
+    pub fn new() -> Self {
+        FakeVerificationSender {
+            sent: Mutex::new(Vec::new()),
+        }
+    }
+
+    // Last token of the given kind sent to the user.
+    pub fn token(&self, user_id: &UserId, kind: &VerificationKind) -> Option<String> {
+        self.sent
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|(id, k, _)| id == user_id && k == kind)
+            .map(|(_, _, token)| token.clone())
+    }
+}
+
+#[async_trait]
+impl VerificationSender for FakeVerificationSender {
+    async fn send(&self, user: &User, kind: &VerificationKind, token: &str) -> Result<()> {
+        self.sent
+            .lock()
+            .unwrap()
+            .push((user.base().id().clone(), kind.clone(), token.to_owned()));
+        Ok(())
+    }
+}
@@ -1,21 +1,223 @@
-use actix_web::HttpRequest;
+use std::cell::RefCell;
+use std::future::{self, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use actix_web::dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::Method;
+use actix_web::{web, FromRequest, HttpMessage, HttpRequest};
 
 use common::error::Error;
+use identity::domain::role::Role;
 use identity::domain::token::Token;
+use identity::domain::user::{Authorization, UserId};
 
 use crate::container::Container;
 use crate::error::PublicError;
 
-pub async fn auth(req: &HttpRequest, c: &Container) -> Result<String, PublicError> {
+// Authenticated user of a request, with the role and permissions. It is resolved once per
+// request and extracted by handlers as `Principal`, or as `Option<Principal>` in routes that
+// allow anonymous access.
+#[derive(Debug, Clone)]
+pub struct Principal {
+    authorization: Authorization,
+    role: Role,
+}
+
+impl Principal {
+    pub fn id(&self) -> &str {
+        self.authorization.user_id()
+    }
+
+    pub fn authorization(&self) -> &Authorization {
+        &self.authorization
+    }
+
+    // Checks a permission declared as "module:CRUD". Personal access tokens also need it in their
+    // scopes.
+    pub fn require(&self, permission: &str) -> Result<(), PublicError> {
+        let (module, permissions) = parse_permission(permission).map_err(PublicError::from)?;
+        if !self.role.has_permissions(module, permissions)
+            || !self.authorization.allows(module, permissions)
+        {
+            return Err(PublicError::from(
+                Error::new("authorization", "forbidden")
+                    .set_status(403)
+                    .set_message("User does not have permission")
+                    .build(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Personal access tokens can only be used in the modules and for the operations of their
+    // scopes, which are taken from the path and the method of the request.
+    fn check_scopes(&self, method: &Method, path: &str) -> Result<(), PublicError> {
+        if self.authorization.scopes().is_none() {
+            return Ok(());
+        }
+
+        match request_permission(method, path) {
+            Some((module, permissions)) if self.authorization.allows(module, permissions) => Ok(()),
+            _ => Err(PublicError::from(
+                Error::new("authorization", "forbidden")
+                    .set_status(403)
+                    .set_message("Token does not have the scope")
+                    .build(),
+            )),
+        }
+    }
+
+    async fn resolve<R: HttpMessage>(
+        req: &R,
+        method: &Method,
+        path: &str,
+        c: &Container,
+    ) -> Result<Principal, PublicError> {
+        let cached = req.extensions().get::<Principal>().cloned();
+        let principal = match cached {
+            Some(principal) => principal,
+            None => {
+                let authorization = auth(req, c).await?;
+                let user = c
+                    .identity
+                    .user_repo()
+                    .find_by_id(&UserId::new(authorization.user_id()).map_err(PublicError::from)?)
+                    .await
+                    .map_err(|err| PublicError::from(Error::unauthorized().wrap(err).build()))?;
+
+                let principal = Principal {
+                    authorization,
+                    role: user.role().clone(),
+                };
+                req.extensions_mut().insert(principal.clone());
+                principal
+            }
+        };
+        principal.check_scopes(method, path)?;
+
+        Ok(principal)
+    }
+}
+
+impl FromRequest for Principal {
+    type Config = ();
+    type Error = PublicError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let c = req.app_data::<web::Data<Container>>().cloned();
+
+        Box::pin(async move {
+            match c {
+                Some(c) => Principal::resolve(&req, req.method(), req.path(), &c).await,
+                None => Err(PublicError::from(Error::internal(
+                    "authorization",
+                    "container",
+                ))),
+            }
+        })
+    }
+}
+
+// Middleware declaring the permission required by a resource or scope, as "module:CRUD".
+// Unauthenticated requests get a 401 and users without the permission a 403.
+pub struct Require {
+    permission: Rc<String>,
+}
+
+impl Require {
+    pub fn new<S: Into<String>>(permission: S) -> Self {
+        Require {
+            permission: Rc::new(permission.into()),
+        }
+    }
+}
+
+impl<S, B> Transform<S> for Require
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>
+        + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type InitError = ();
+    type Transform = RequireMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        future::ready(Ok(RequireMiddleware {
+            service: Rc::new(RefCell::new(service)),
+            permission: self.permission.clone(),
+        }))
+    }
+}
+
+pub struct RequireMiddleware<S> {
+    service: Rc<RefCell<S>>,
+    permission: Rc<String>,
+}
+
+impl<S, B> Service for RequireMiddleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>
+        + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.borrow_mut().poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let permission = self.permission.clone();
+
+        let c = req.app_data::<Container>();
+
+        Box::pin(async move {
+            let c = match c {
+                Some(c) => c,
+                None => {
+                    return Err(
+                        PublicError::from(Error::internal("authorization", "container")).into(),
+                    )
+                }
+            };
+            let principal = Principal::resolve(&req, req.method(), req.path(), &c).await?;
+            principal.require(&permission)?;
+
+            let res = service.borrow_mut().call(req);
+            res.await
+        })
+    }
+}
+
+async fn auth<R: HttpMessage>(req: &R, c: &Container) -> Result<Authorization, PublicError> {
     let auth_header = match req.headers().get("authorization") {
         Some(header) => {
             if let Ok(header) = header.to_str() {
                 Ok(header.to_owned())
             } else {
-                Err(Error::new("authorization", "invalid_header"))
+                Err(Error::new("authorization", "invalid_header")
+                    .set_status(401)
+                    .build())
             }
         }
-        None => Err(Error::new("authorization", "header_is_not_present")),
+        None => Err(Error::new("authorization", "header_is_not_present")
+            .set_status(401)
+            .build()),
     }
     .map_err(PublicError::from)?;
 
@@ -43,10 +245,44 @@ fn extract_token<S: Into<String>>(authorization: S) -> Result<Token, Error> {
         .build())
 }
 
+fn parse_permission(permission: &str) -> Result<(&str, &str), Error> {
+    let mut parts = permission.splitn(2, ':');
+    match (parts.next(), parts.next()) {
+        (Some(module), Some(permissions)) if !module.is_empty() && !permissions.is_empty() => {
+            Ok((module, permissions))
+        }
+        _ => Err(Error::internal("authorization", "invalid_permission")),
+    }
+}
+
+// Permission a request needs in the scopes of a personal access token: the module is the first
+// segment of the path, and the method is the operation. Creating an item of a module is a POST to
+// the module itself, any other POST changes an existing one.
+fn request_permission<'a>(method: &Method, path: &'a str) -> Option<(&'a str, &'static str)> {
+    let mut segments = path
+        .trim_start_matches("/api")
+        .split('/')
+        .filter(|segment| !segment.is_empty());
+    let module = segments.next()?;
+    let item = segments.next().is_some();
+
+    let permissions = match *method {
+        Method::GET | Method::HEAD => "R",
+        Method::POST if !item => "C",
+        Method::POST | Method::PUT | Method::PATCH => "U",
+        Method::DELETE => "D",
+        _ => return None,
+    };
+
+    Some((module, permissions))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use identity::domain::role::{Permission, RoleId};
+
     #[test]
     fn valid_token() {
         let token = extract_token("Bearer token#123").unwrap();
@@ -57,4 +293,78 @@ mod tests {
     fn invalid_token() {
         assert!(extract_token("token#123").is_err());
     }
+
+    #[test]
+    fn require() {
+        let mut role = Role::new(RoleId::new("moderator").unwrap(), "Moderator").unwrap();
+        role.add_permissions(Permission::new("publications", "RU").unwrap());
+        let principal = Principal {
+            authorization: Authorization::new("user-1", None),
+            role,
+        };
+
+        assert!(principal.require("publications:U").is_ok());
+        assert!(principal.require("publications:ru").is_ok());
+        assert!(principal.require("publications:D").is_err());
+        assert!(principal.require("users:R").is_err());
+        assert!(principal.require("publications").is_err());
+    }
+
+    #[test]
+    fn scopes() {
+        let mut role = Role::new(RoleId::new("moderator").unwrap(), "Moderator").unwrap();
+        role.add_permissions(Permission::new("publications", "RU").unwrap());
+        role.add_permissions(Permission::new("users", "R").unwrap());
+        let principal = Principal {
+            authorization: Authorization::new(
+                "user-1",
+                Some(vec![
+                    Permission::new("publications", "R").unwrap(),
+                    Permission::new("collections", "C").unwrap(),
+                ]),
+            ),
+            role,
+        };
+
+        assert!(principal.require("publications:R").is_ok());
+        assert!(principal.require("publications:U").is_err());
+        assert!(principal.require("users:R").is_err());
+
+        assert!(principal
+            .check_scopes(&Method::GET, "/api/publications/P01/reviews")
+            .is_ok());
+        assert!(principal
+            .check_scopes(&Method::POST, "/api/publications/P01/approve")
+            .is_err());
+        assert!(principal
+            .check_scopes(&Method::POST, "/api/collections")
+            .is_ok());
+        assert!(principal
+            .check_scopes(&Method::DELETE, "/api/collections/C01")
+            .is_err());
+        assert!(principal
+            .check_scopes(&Method::GET, "/api/users/U01")
+            .is_err());
+    }
+
+    #[test]
+    fn permission_of_request() {
+        assert_eq!(
+            request_permission(&Method::POST, "/api/publications"),
+            Some(("publications", "C"))
+        );
+        assert_eq!(
+            request_permission(&Method::POST, "/api/catalogue/rebuild"),
+            Some(("catalogue", "U"))
+        );
+        assert_eq!(
+            request_permission(&Method::GET, "/api/users/U01/sessions"),
+            Some(("users", "R"))
+        );
+        assert_eq!(
+            request_permission(&Method::DELETE, "/api/users/U01"),
+            Some(("users", "D"))
+        );
+        assert_eq!(request_permission(&Method::GET, "/api"), None);
+    }
 }
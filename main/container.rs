@@ -3,58 +3,126 @@ use std::sync::Arc;
 use catalogue::container::Container as CatalogueContainer;
 use catalogue::infrastructure::persistence::inmem::InMemCatalogueRepository;
 use catalogue::infrastructure::service::{SyncCollectionService, SyncPublicationService};
-use common::event::EventSubscriber;
-use common::infrastructure::event::{InMemEventBus, InMemEventRepository};
+use common::config::Config;
+use common::event::{EventSubscriber, Outbox, UpcasterRegistry};
+use common::infrastructure::event::{
+    FileEventRepository, InMemEventBus, InMemOutbox, OutboxEventPublisher, OutboxRelay,
+    ProjectionRunner,
+};
 use common::result::Result;
 use identity::container::Container as IdentityContainer;
 use identity::infrastructure::persistence::inmem::{
-    InMemRoleRepository, InMemTokenRepository, InMemUserRepository,
+    InMemAuthorizationRequestRepository, InMemLoginAttemptRepository, InMemRoleRepository,
+    InMemTokenRepository, InMemUserRepository, InMemVerificationRepository,
+};
+use identity::infrastructure::service::{
+    ConsoleVerificationSender, HttpOidcClient, JWTEncoder, PhcHasher,
+};
+use payment::container::Container as PaymentContainer;
+use payment::infrastructure::gateway::{FakePaymentGateway, WebhookSignature};
+use payment::infrastructure::persistence::inmem::{
+    InMemContractRepository, InMemNotificationRepository, InMemPayoutRepository,
+    InMemPlanRepository, InMemSubscriptionRepository,
 };
-use identity::infrastructure::service::{BcryptHasher, JWTEncoder};
 use publishing::container::Container as PublishingContainer;
+use publishing::domain::category::CategoryRepository;
+use publishing::domain::collection::CollectionRepository;
+use publishing::domain::interaction::InteractionRepository;
+use publishing::domain::publication::{PublicationRepository, StatisticsService};
+use publishing::infrastructure::persistence::eventsourced::{
+    EventSourcedCollectionRepository, EventSourcedPublicationRepository,
+};
 use publishing::infrastructure::persistence::inmem::{
     InMemCategoryRepository, InMemCollectionRepository, InMemInteractionRepository,
     InMemPublicationRepository,
 };
+use publishing::infrastructure::persistence::sqlite::{
+    Database, SqliteCategoryRepository, SqliteCollectionRepository, SqliteInteractionRepository,
+    SqliteOutbox, SqlitePublicationRepository,
+};
+use shared::event;
 
 use crate::development::EventLogger;
+use crate::infrastructure::payment::{AdminTranslator, PublicationTranslator};
 use crate::infrastructure::publishing::{
     AuthorTranslator, ContentManagerTranslator, ReaderTranslator,
 };
 
 pub struct Container {
     pub event_bus: Arc<InMemEventBus>,
-    pub event_repo: Arc<InMemEventRepository>,
-    pub identity: IdentityContainer<InMemEventBus>,
-    pub publishing: PublishingContainer<InMemEventBus>,
+    pub event_repo: Arc<FileEventRepository>,
+    pub upcasters: UpcasterRegistry,
+    // Publications and collections as they were at any date, rebuilt from the event log.
+    pub publication_history: EventSourcedPublicationRepository,
+    pub collection_history: EventSourcedCollectionRepository,
+    pub outbox_relay: Arc<OutboxRelay>,
+    pub projection_runner: Arc<ProjectionRunner>,
+    pub identity: IdentityContainer<OutboxEventPublisher>,
+    pub publishing: PublishingContainer<OutboxEventPublisher>,
     pub catalogue: CatalogueContainer<InMemEventBus>,
+    pub payment: PaymentContainer<OutboxEventPublisher>,
+    pub webhook_signature: WebhookSignature,
+    pub trusted_proxies: Vec<String>,
 }
 
 impl Container {
-    pub async fn new() -> Self {
+    pub async fn new(config: &Config) -> Result<Self> {
         // Common
-        let event_bus = Arc::new(InMemEventBus::new());
-        let event_repo = Arc::new(InMemEventRepository::new());
+        let mut event_bus = InMemEventBus::new();
+        event_bus.set_upcasters(event::upcasters());
+        let event_bus = Arc::new(event_bus);
+        let event_repo = Arc::new(FileEventRepository::open(config.event_store_path())?);
+        let mut projection_runner = ProjectionRunner::new(event_repo.clone());
+        projection_runner.set_upcasters(event::upcasters());
+        let projection_runner = Arc::new(projection_runner);
 
         // Identity
+        let authorization_request_repo = Arc::new(InMemAuthorizationRequestRepository::new());
+        let login_attempt_repo = Arc::new(InMemLoginAttemptRepository::new());
         let role_repo = Arc::new(InMemRoleRepository::new());
         let token_repo = Arc::new(InMemTokenRepository::new());
         let user_repo = Arc::new(InMemUserRepository::new());
-        let password_hasher = Arc::new(BcryptHasher::new());
-        let token_enc = Arc::new(JWTEncoder::new());
+        let verification_repo = Arc::new(InMemVerificationRepository::new());
+        let password_hasher = Arc::new(PhcHasher::new());
+        let token_enc = Arc::new(JWTEncoder::from_config(config)?);
+        let oidc_client = Arc::new(HttpOidcClient::from_config(config));
+        let verification_sender = Arc::new(ConsoleVerificationSender::from_config(config));
 
         // Publishing
-        let category_repo = Arc::new(InMemCategoryRepository::new());
-        let collection_repo = Arc::new(InMemCollectionRepository::new());
-        let interaction_repo = Arc::new(InMemInteractionRepository::new());
-        let publication_repo = Arc::new(InMemPublicationRepository::new());
+        let category_repo: Arc<dyn CategoryRepository>;
+        let collection_repo: Arc<dyn CollectionRepository>;
+        let interaction_repo: Arc<dyn InteractionRepository>;
+        let publication_repo: Arc<dyn PublicationRepository>;
+        let outbox: Arc<dyn Outbox>;
+        if config.persistence() == "sqlite" {
+            let db = Arc::new(Database::open(config.database_path())?);
+            category_repo = Arc::new(SqliteCategoryRepository::new(db.clone()));
+            collection_repo = Arc::new(SqliteCollectionRepository::new(db.clone()));
+            interaction_repo = Arc::new(SqliteInteractionRepository::new(db.clone()));
+            publication_repo = Arc::new(SqlitePublicationRepository::new(db.clone()));
+            outbox = Arc::new(SqliteOutbox::new(db));
+        } else {
+            category_repo = Arc::new(InMemCategoryRepository::new());
+            collection_repo = Arc::new(InMemCollectionRepository::new());
+            interaction_repo = Arc::new(InMemInteractionRepository::new());
+            publication_repo = Arc::new(InMemPublicationRepository::new());
+            outbox = Arc::new(InMemOutbox::new());
+        }
+
+        // Events are published through the outbox, which relays them to the event bus.
+        let outbox_relay = Arc::new(OutboxRelay::new(outbox.clone(), event_bus.clone()));
+        let event_pub = Arc::new(OutboxEventPublisher::new(outbox, outbox_relay.clone()));
 
         let author_repo = Arc::new(AuthorTranslator::new(
             publication_repo.clone(),
             user_repo.clone(),
         ));
         let content_manager_repo = Arc::new(ContentManagerTranslator::new(user_repo.clone()));
-        let reader_repo = Arc::new(ReaderTranslator::new(user_repo.clone()));
+        let subscription_repo = Arc::new(InMemSubscriptionRepository::new());
+        let reader_repo = Arc::new(ReaderTranslator::new(
+            subscription_repo.clone(),
+            user_repo.clone(),
+        ));
 
         // Catalogue
         let catalogue_repo = Arc::new(InMemCatalogueRepository::new());
@@ -69,17 +137,48 @@ impl Container {
             publication_repo.clone(),
         ));
 
+        // Payment
+        let admin_repo = Arc::new(AdminTranslator::new(user_repo.clone()));
+        let contract_repo = Arc::new(InMemContractRepository::new());
+        let notification_repo = Arc::new(InMemNotificationRepository::new());
+        let payout_repo = Arc::new(InMemPayoutRepository::new());
+        let plan_repo = Arc::new(InMemPlanRepository::new());
+        let payment_publication_repo = Arc::new(PublicationTranslator::new(
+            publication_repo.clone(),
+            Arc::new(StatisticsService::new(interaction_repo.clone())),
+        ));
+        // There is no real provider yet.
+        let payment_gateway = Arc::new(FakePaymentGateway::new());
+        let webhook_signature = WebhookSignature::from_config(config)?;
+
         let identity = IdentityContainer::new(
-            event_bus.clone(),
+            event_pub.clone(),
+            authorization_request_repo,
+            login_attempt_repo,
             role_repo,
             token_repo,
             user_repo,
+            verification_repo,
             password_hasher,
             token_enc,
+            oidc_client,
+            verification_sender,
+        );
+
+        let payment = PaymentContainer::new(
+            event_pub.clone(),
+            admin_repo,
+            contract_repo,
+            notification_repo,
+            payout_repo,
+            plan_repo,
+            payment_publication_repo,
+            subscription_repo,
+            payment_gateway,
         );
 
         let publishing = PublishingContainer::new(
-            event_bus.clone(),
+            event_pub,
             author_repo,
             category_repo,
             collection_repo,
@@ -96,19 +195,28 @@ impl Container {
             publication_serv,
         );
 
-        Container {
+        Ok(Container {
             event_bus,
+            publication_history: EventSourcedPublicationRepository::new(event_repo.clone()),
+            collection_history: EventSourcedCollectionRepository::new(event_repo.clone()),
             event_repo,
+            upcasters: event::upcasters(),
+            outbox_relay,
+            projection_runner,
             identity,
             publishing,
             catalogue,
-        }
+            payment,
+            webhook_signature,
+            trusted_proxies: config.trusted_proxies().to_vec(),
+        })
     }
 
     pub async fn subscribe(&self) -> Result<()> {
         let event_logger = EventLogger::new(self.event_repo.clone());
         self.event_bus.subscribe(Box::new(event_logger)).await?;
 
+        self.publishing.subscribe(self.event_bus.as_ref()).await?;
         self.catalogue.subscribe(self.event_bus.as_ref()).await?;
 
         Ok(())
@@ -118,7 +226,19 @@ impl Container {
         &self.event_bus
     }
 
-    pub fn event_repo(&self) -> &InMemEventRepository {
+    pub fn event_repo(&self) -> &FileEventRepository {
         &self.event_repo
     }
+
+    pub fn upcasters(&self) -> &UpcasterRegistry {
+        &self.upcasters
+    }
+
+    pub fn publication_history(&self) -> &EventSourcedPublicationRepository {
+        &self.publication_history
+    }
+
+    pub fn collection_history(&self) -> &EventSourcedCollectionRepository {
+        &self.collection_history
+    }
 }
@@ -2,6 +2,7 @@ use common::model::AggregateRoot;
 use common::result::Result;
 use identity::domain::role::*;
 use identity::domain::user::*;
+use payment::domain::plan::{Period, Plan, PlanId};
 use publishing::domain::category::{Name as CategoryName, *};
 
 use crate::container::Container;
@@ -11,6 +12,18 @@ pub async fn populate(c: &Container) -> Result<()> {
     let mut admin_role = Role::new(RoleId::new("admin")?, "Administrator")?;
     let mut content_manager_role = Role::new(RoleId::new("content-manager")?, "Content Manager")?;
     let mut user_role = Role::new(RoleId::new("user")?, "User")?;
+    for module in &[
+        "users",
+        "roles",
+        "publications",
+        "catalogue",
+        "events",
+        "contracts",
+        "payouts",
+    ] {
+        admin_role.add_permissions(Permission::new(*module, "CRUD")?);
+    }
+    content_manager_role.add_permissions(Permission::new("publications", "RU")?);
     c.identity.role_repo().save(&mut admin_role).await?;
     c.identity
         .role_repo()
@@ -30,7 +43,9 @@ pub async fn populate(c: &Container) -> Result<()> {
         )?,
         Some(Person::new(Fullname::new("Admin", "Superpowers")?)?),
         admin_role.clone(),
+        true,
         None,
+        Vec::new(),
     );
     let mut content_manager = User::build(
         AggregateRoot::new(UserId::new("content_manager-1")?),
@@ -42,7 +57,9 @@ pub async fn populate(c: &Container) -> Result<()> {
         )?,
         Some(Person::new(Fullname::new("Content", "Manager")?)?),
         content_manager_role.clone(),
+        true,
         None,
+        Vec::new(),
     );
     let mut user = User::build(
         AggregateRoot::new(UserId::new("user-1")?),
@@ -54,7 +71,9 @@ pub async fn populate(c: &Container) -> Result<()> {
         )?,
         Some(Person::new(Fullname::new("TheFirst", "User")?)?),
         user_role.clone(),
+        true,
         None,
+        Vec::new(),
     );
     c.identity.user_repo().save(&mut admin).await?;
     c.identity.user_repo().save(&mut content_manager).await?;
@@ -69,5 +88,11 @@ pub async fn populate(c: &Container) -> Result<()> {
     c.publishing.category_repo().save(&mut category_1).await?;
     c.publishing.category_repo().save(&mut category_2).await?;
 
+    // Payment
+    let mut monthly_plan = Plan::new(PlanId::new("monthly")?, "Monthly", 250.0, Period::Monthly)?;
+    let mut yearly_plan = Plan::new(PlanId::new("yearly")?, "Yearly", 2500.0, Period::Yearly)?;
+    c.payment.plan_repo().save(&mut monthly_plan).await?;
+    c.payment.plan_repo().save(&mut yearly_plan).await?;
+
     Ok(())
 }
@@ -1,21 +1,25 @@
-use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use actix_web::{web, HttpResponse, Responder};
+
+use common::model::PaginationQuery;
 
 use publishing::application::author::{GetAll, GetById, GetPublications};
 
-use crate::authorization::auth;
+use crate::authorization::Principal;
 use crate::container::Container;
 use crate::error::PublicError;
 
-// GET /authors
-async fn get_all(req: HttpRequest, c: web::Data<Container>) -> impl Responder {
-    let _auth_id = auth(&req, &c).await?;
-
+// GET /authors?cursor&limit&sort&direction
+async fn get_all(
+    _principal: Principal,
+    pagination: web::Query<PaginationQuery>,
+    c: web::Data<Container>,
+) -> impl Responder {
     GetAll::new(
         c.publishing.author_repo(),
         c.publishing.collection_repo(),
         c.publishing.publication_repo(),
     )
-    .exec()
+    .exec(pagination.into_inner())
     .await
     .map(|res| HttpResponse::Ok().json(res))
     .map_err(PublicError::from)
@@ -23,12 +27,10 @@ async fn get_all(req: HttpRequest, c: web::Data<Container>) -> impl Responder {
 
 // GET /authors/:id
 async fn get_by_id(
-    req: HttpRequest,
+    _principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let _auth_id = auth(&req, &c).await?;
-
     GetById::new(
         c.publishing.author_repo(),
         c.publishing.category_repo(),
@@ -42,12 +44,10 @@ async fn get_by_id(
 
 // GET /authors/:id/publications
 async fn publications(
-    req: HttpRequest,
+    _principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let _auth_id = auth(&req, &c).await?;
-
     GetPublications::new(
         c.publishing.category_repo(),
         c.publishing.publication_repo(),
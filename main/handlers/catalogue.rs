@@ -1,10 +1,19 @@
 use actix_web::{web, HttpResponse, Responder};
+use serde::Serialize;
 
 use catalogue::application::catalogue::Get;
 
+use crate::authorization::Require;
 use crate::container::Container;
 use crate::error::PublicError;
 
+#[derive(Serialize)]
+pub struct RebuildResponse {
+    pub events: u32,
+    pub ok_handlers: u32,
+    pub err_handlers: u32,
+}
+
 // GET /catalogue
 async fn get(c: web::Data<Container>) -> impl Responder {
     Get::new(c.catalogue.catalogue_repo())
@@ -14,6 +23,29 @@ async fn get(c: web::Data<Container>) -> impl Responder {
         .map_err(PublicError::from)
 }
 
+// POST /catalogue/rebuild
+async fn rebuild(c: web::Data<Container>) -> impl Responder {
+    c.projection_runner
+        .rebuild(&c.catalogue.projection())
+        .await
+        .map(|res| {
+            HttpResponse::Ok().json(RebuildResponse {
+                events: res.events(),
+                ok_handlers: res.ok_handlers(),
+                err_handlers: res.err_handlers(),
+            })
+        })
+        .map_err(PublicError::from)
+}
+
 pub fn routes(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::scope("/catalogue").route("", web::get().to(get)));
+    cfg.service(
+        web::scope("/catalogue")
+            .route("", web::get().to(get))
+            .service(
+                web::resource("/rebuild")
+                    .wrap(Require::new("catalogue:U"))
+                    .route(web::post().to(rebuild)),
+            ),
+    );
 }
@@ -1,21 +1,23 @@
-use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use actix_web::{web, HttpResponse, Responder};
+
+use common::model::PaginationQuery;
 
 use publishing::application::collection::{
     AddPublication, Create, CreateCommand, Delete, GetAll, GetById, RemovePublication, Update,
     UpdateCommand,
 };
 
-use crate::authorization::auth;
+use crate::authorization::Principal;
 use crate::container::Container;
 use crate::error::PublicError;
 
 // POST /collections
 async fn create(
-    req: HttpRequest,
+    principal: Principal,
     cmd: web::Json<CreateCommand>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let user_id = auth(&req, &c).await?;
+    let user_id = principal.id().to_owned();
 
     Create::new(
         c.publishing.event_pub(),
@@ -29,17 +31,19 @@ async fn create(
     .map_err(PublicError::from)
 }
 
-// GET /collections
-async fn get_all(req: HttpRequest, c: web::Data<Container>) -> impl Responder {
-    let _user_id = auth(&req, &c).await?;
-
+// GET /collections?cursor&limit&sort&direction
+async fn get_all(
+    _principal: Principal,
+    pagination: web::Query<PaginationQuery>,
+    c: web::Data<Container>,
+) -> impl Responder {
     GetAll::new(
         c.publishing.author_repo(),
         c.publishing.category_repo(),
         c.publishing.collection_repo(),
         c.publishing.publication_repo(),
     )
-    .exec()
+    .exec(pagination.into_inner())
     .await
     .map(|res| HttpResponse::Ok().json(res))
     .map_err(PublicError::from)
@@ -47,12 +51,10 @@ async fn get_all(req: HttpRequest, c: web::Data<Container>) -> impl Responder {
 
 // GET /collections/:id
 async fn get_by_id(
-    req: HttpRequest,
+    _principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let _user_id = auth(&req, &c).await?;
-
     GetById::new(
         c.publishing.author_repo(),
         c.publishing.category_repo(),
@@ -67,12 +69,12 @@ async fn get_by_id(
 
 // PUT /collections/:id
 async fn update(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     cmd: web::Json<UpdateCommand>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let user_id = auth(&req, &c).await?;
+    let user_id = principal.id().to_owned();
 
     Update::new(
         c.publishing.event_pub(),
@@ -87,11 +89,11 @@ async fn update(
 
 // DELETE /collections/:id
 async fn delete(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let user_id = auth(&req, &c).await?;
+    let user_id = principal.id().to_owned();
 
     Delete::new(c.publishing.event_pub(), c.publishing.collection_repo())
         .exec(user_id, path.into_inner())
@@ -102,12 +104,10 @@ async fn delete(
 
 // POST /collections/:id/publication/:publication_id
 async fn add_publication(
-    req: HttpRequest,
+    _principal: Principal,
     path: web::Path<(String, String)>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let _user_id = auth(&req, &c).await?;
-
     let path = path.into_inner();
     AddPublication::new(
         c.publishing.event_pub(),
@@ -122,12 +122,10 @@ async fn add_publication(
 
 // DELETE /collections/:id/publication/:publication_id
 async fn remove_publication(
-    req: HttpRequest,
+    _principal: Principal,
     path: web::Path<(String, String)>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let _user_id = auth(&req, &c).await?;
-
     let path = path.into_inner();
     RemovePublication::new(c.publishing.event_pub(), c.publishing.collection_repo())
         .exec(path.0, path.1)
@@ -0,0 +1,135 @@
+use actix_web::{web, HttpResponse, Responder};
+
+use payment::application::contract::{
+    Approve, Cancel, GetById, Reject, Request, RequestCommand, Search, SearchCommand,
+};
+
+use crate::authorization::{Principal, Require};
+use crate::container::Container;
+use crate::error::PublicError;
+
+// POST /contracts
+async fn request(
+    principal: Principal,
+    cmd: web::Json<RequestCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    Request::new(
+        c.payment.event_pub(),
+        c.payment.contract_repo(),
+        c.payment.publication_repo(),
+    )
+    .exec(auth_id, cmd.into_inner())
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+// GET /contracts?status=requested
+async fn search(
+    principal: Principal,
+    cmd: web::Query<SearchCommand>,
+    c: web::Data<Container>,
+) -> Result<HttpResponse, PublicError> {
+    principal.require("contracts:R")?;
+
+    Search::new(c.payment.contract_repo())
+        .exec(cmd.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// GET /contracts/:id
+async fn get_by_id(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    GetById::new(c.payment.admin_repo(), c.payment.contract_repo())
+        .exec(auth_id, path.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// POST /contracts/:id/approve
+async fn approve(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    Approve::new(
+        c.payment.event_pub(),
+        c.payment.admin_repo(),
+        c.payment.contract_repo(),
+    )
+    .exec(auth_id, path.into_inner())
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+// POST /contracts/:id/reject
+async fn reject(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    Reject::new(
+        c.payment.event_pub(),
+        c.payment.admin_repo(),
+        c.payment.contract_repo(),
+    )
+    .exec(auth_id, path.into_inner())
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+// POST /contracts/:id/cancel
+async fn cancel(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    Cancel::new(
+        c.payment.event_pub(),
+        c.payment.admin_repo(),
+        c.payment.contract_repo(),
+    )
+    .exec(auth_id, path.into_inner())
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/contracts")
+            .route("", web::post().to(request))
+            .route("", web::get().to(search))
+            .route("/{contract_id}", web::get().to(get_by_id))
+            .service(
+                web::resource("/{contract_id}/approve")
+                    .wrap(Require::new("contracts:U"))
+                    .route(web::post().to(approve)),
+            )
+            .service(
+                web::resource("/{contract_id}/reject")
+                    .wrap(Require::new("contracts:U"))
+                    .route(web::post().to(reject)),
+            )
+            .route("/{contract_id}/cancel", web::post().to(cancel)),
+    );
+}
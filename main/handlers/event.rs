@@ -1,45 +1,221 @@
 use actix_web::{web, HttpResponse, Responder};
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use common::event::EventRepository;
-
+use common::error::Error;
+use common::event::{DeadLetterId, EventId, EventRepository};
+use publishing::application::dtos::{CollectionDto, PublicationDto};
+use publishing::domain::collection::CollectionId;
+use publishing::domain::publication::PublicationId;
 
+use crate::authorization::Require;
 use crate::container::Container;
 use crate::error::PublicError;
 
 #[derive(Serialize)]
 pub struct PublicEvent {
     pub id: String,
+    pub version: u32,
     pub timestamp: String,
     pub payload: Value,
 }
 
+#[derive(Deserialize)]
+pub struct GetAllQuery {
+    pub from: Option<String>,
+}
+
 #[derive(Serialize)]
 pub struct GetAllResponse {
     pub events: Vec<PublicEvent>,
 }
 
-// GET /events
-async fn get(c: web::Data<Container>) -> impl Responder {
-    c.event_repo()
+// GET /events?from=<event_id>
+async fn get(query: web::Query<GetAllQuery>, c: web::Data<Container>) -> impl Responder {
+    let events = match &query.from {
+        Some(from) => match EventId::new(from) {
+            Ok(id) => c.event_repo().find_from(&id).await,
+            Err(err) => Err(err),
+        },
+        None => c.event_repo().find_all().await,
+    }
+    .map_err(PublicError::from)?;
+
+    let mut public_events = Vec::new();
+    for event in events.iter() {
+        // Payloads of old versions could contain data that is no longer published.
+        let event = c.upcasters().upcast(event).map_err(PublicError::from)?;
+        let payload = serde_json::from_slice(event.payload()).map_err(|err| {
+            PublicError::from(
+                Error::internal("event", "invalid_payload")
+                    .add_context("event_id", event.id().value())
+                    .wrap_raw(err)
+                    .build(),
+            )
+        })?;
+
+        public_events.push(PublicEvent {
+            id: event.id().to_string(),
+            version: event.version(),
+            timestamp: event.timestamp().to_string(),
+            payload,
+        });
+    }
+
+    Ok::<_, PublicError>(HttpResponse::Ok().json(GetAllResponse {
+        events: public_events,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct HistoryQuery {
+    pub at: Option<String>,
+}
+
+impl HistoryQuery {
+    fn at(&self) -> Result<Option<DateTime<Utc>>, Error> {
+        match &self.at {
+            Some(at) => DateTime::parse_from_rfc3339(at)
+                .map(|at| Some(at.with_timezone(&Utc)))
+                .map_err(|err| {
+                    Error::new("event", "invalid_date")
+                        .add_context("at", at)
+                        .wrap_raw(err)
+                        .build()
+                }),
+            None => Ok(None),
+        }
+    }
+}
+
+// GET /events/publications/:id?at=<rfc3339 date>
+async fn get_publication(
+    path: web::Path<String>,
+    query: web::Query<HistoryQuery>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let id = PublicationId::new(path.into_inner()).map_err(PublicError::from)?;
+    let publication = match query.at().map_err(PublicError::from)? {
+        Some(at) => c.publication_history().find_by_id_at(&id, &at).await,
+        None => c.publication_history().find_by_id(&id).await,
+    }
+    .map_err(PublicError::from)?;
+
+    Ok::<_, PublicError>(
+        HttpResponse::Ok().json(
+            PublicationDto::from(&publication)
+                .author_id(&publication)
+                .category_id(&publication)
+                .pages(&publication)
+                .status(&publication),
+        ),
+    )
+}
+
+// GET /events/collections/:id?at=<rfc3339 date>
+async fn get_collection(
+    path: web::Path<String>,
+    query: web::Query<HistoryQuery>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let id = CollectionId::new(path.into_inner()).map_err(PublicError::from)?;
+    let collection = match query.at().map_err(PublicError::from)? {
+        Some(at) => c.collection_history().find_by_id_at(&id, &at).await,
+        None => c.collection_history().find_by_id(&id).await,
+    }
+    .map_err(PublicError::from)?;
+
+    Ok::<_, PublicError>(
+        HttpResponse::Ok().json(
+            CollectionDto::from(&collection)
+                .author_id(&collection)
+                .category_id(&collection)
+                .publication_count(collection.items().len()),
+        ),
+    )
+}
+
+#[derive(Serialize)]
+pub struct PublicDeadLetter {
+    pub id: String,
+    pub event_id: String,
+    pub topic: String,
+    pub code: String,
+    pub handler: String,
+    pub error: String,
+    pub attempts: u32,
+    pub failed_at: String,
+}
+
+#[derive(Serialize)]
+pub struct GetDeadLettersResponse {
+    pub dead_letters: Vec<PublicDeadLetter>,
+}
+
+// GET /events/dead-letters
+async fn get_dead_letters(c: web::Data<Container>) -> impl Responder {
+    c.event_bus()
+        .dead_letter_repo()
         .find_all()
         .await
-        .map(|mut events| {
-            events.sort_by(|a, b| a.timestamp().cmp(b.timestamp()));
-            events
+        .map(|dead_letters| {
+            dead_letters
                 .into_iter()
-                .map(|event| PublicEvent {
-                    id: event.id().to_string(),
-                    timestamp: event.timestamp().to_string(),
-                    payload: serde_json::from_slice(event.payload()).unwrap(),
+                .map(|dead_letter| PublicDeadLetter {
+                    id: dead_letter.id().to_string(),
+                    event_id: dead_letter.event().id().to_string(),
+                    topic: dead_letter.event().topic().to_owned(),
+                    code: dead_letter.event().code().to_owned(),
+                    handler: dead_letter.handler().to_owned(),
+                    error: dead_letter.error().to_owned(),
+                    attempts: dead_letter.attempts(),
+                    failed_at: dead_letter.failed_at().to_string(),
                 })
                 .collect()
         })
-        .map(|events| HttpResponse::Ok().json(GetAllResponse { events }))
+        .map(|dead_letters| HttpResponse::Ok().json(GetDeadLettersResponse { dead_letters }))
+        .map_err(PublicError::from)
+}
+
+// POST /events/dead-letters/:id/replay
+async fn replay_dead_letter(path: web::Path<String>, c: web::Data<Container>) -> impl Responder {
+    let id = DeadLetterId::new(path.into_inner()).map_err(PublicError::from)?;
+
+    c.event_bus()
+        .replay(&id)
+        .await
+        .map(|_| HttpResponse::Ok().finish())
         .map_err(PublicError::from)
 }
 
 pub fn routes(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::scope("/events").route("", web::get().to(get)));
+    cfg.service(
+        web::scope("/events")
+            .service(
+                web::resource("")
+                    .wrap(Require::new("events:R"))
+                    .route(web::get().to(get)),
+            )
+            .service(
+                web::resource("/publications/{id}")
+                    .wrap(Require::new("events:R"))
+                    .route(web::get().to(get_publication)),
+            )
+            .service(
+                web::resource("/collections/{id}")
+                    .wrap(Require::new("events:R"))
+                    .route(web::get().to(get_collection)),
+            )
+            .service(
+                web::resource("/dead-letters")
+                    .wrap(Require::new("events:R"))
+                    .route(web::get().to(get_dead_letters)),
+            )
+            .service(
+                web::resource("/dead-letters/{id}/replay")
+                    .wrap(Require::new("events:U"))
+                    .route(web::post().to(replay_dead_letter)),
+            ),
+    );
 }
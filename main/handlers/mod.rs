@@ -2,7 +2,11 @@ pub mod author;
 pub mod catalogue;
 pub mod category;
 pub mod collection;
+pub mod contract;
 pub mod event;
+pub mod payment;
+pub mod payout;
 pub mod publication;
 pub mod role;
+pub mod subscription;
 pub mod user;
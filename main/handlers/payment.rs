@@ -0,0 +1,44 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+
+use common::error::Error;
+use payment::application::gateway::{Notify, NotifyCommand};
+
+use crate::container::Container;
+use crate::error::PublicError;
+
+// POST /payments/webhook
+// Notifications of the payment provider, signed in the Omics-Signature header.
+async fn webhook(
+    req: HttpRequest,
+    body: web::Bytes,
+    c: web::Data<Container>,
+) -> Result<HttpResponse, PublicError> {
+    let signature = req
+        .headers()
+        .get("omics-signature")
+        .and_then(|header| header.to_str().ok())
+        .unwrap_or_default();
+    c.webhook_signature.verify(signature, &body)?;
+
+    let cmd: NotifyCommand = serde_json::from_slice(&body).map_err(|err| {
+        Error::new("notification", "invalid")
+            .set_status(400)
+            .wrap_raw(err)
+            .build()
+    })?;
+
+    Notify::new(
+        c.payment.event_pub(),
+        c.payment.notification_repo(),
+        c.payment.payout_repo(),
+        c.payment.subscription_repo(),
+    )
+    .exec(cmd)
+    .await
+    .map(|_| HttpResponse::Ok().finish())
+    .map_err(PublicError::from)
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/payments").route("/webhook", web::post().to(webhook)));
+}
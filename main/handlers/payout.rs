@@ -0,0 +1,96 @@
+use actix_web::{web, HttpResponse};
+
+use payment::application::payout::{GetAll, GetById, Pay, Run, RunCommand};
+
+use crate::authorization::Principal;
+use crate::container::Container;
+use crate::error::PublicError;
+
+// POST /payouts
+async fn run(
+    principal: Principal,
+    cmd: web::Json<RunCommand>,
+    c: web::Data<Container>,
+) -> Result<HttpResponse, PublicError> {
+    principal.require("payouts:C")?;
+
+    let auth_id = principal.id().to_owned();
+
+    Run::new(
+        c.payment.event_pub(),
+        c.payment.admin_repo(),
+        c.payment.contract_repo(),
+        c.payment.payout_repo(),
+        c.payment.publication_repo(),
+        c.payment.subscription_repo(),
+    )
+    .exec(auth_id, cmd.into_inner())
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+// GET /payouts
+async fn get_all(
+    principal: Principal,
+    c: web::Data<Container>,
+) -> Result<HttpResponse, PublicError> {
+    principal.require("payouts:R")?;
+
+    let auth_id = principal.id().to_owned();
+
+    GetAll::new(c.payment.admin_repo(), c.payment.payout_repo())
+        .exec(auth_id)
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// GET /payouts/:month
+async fn get_by_id(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> Result<HttpResponse, PublicError> {
+    principal.require("payouts:R")?;
+
+    let auth_id = principal.id().to_owned();
+
+    GetById::new(c.payment.admin_repo(), c.payment.payout_repo())
+        .exec(auth_id, path.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// POST /payouts/:month/pay
+async fn pay(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> Result<HttpResponse, PublicError> {
+    principal.require("payouts:U")?;
+
+    let auth_id = principal.id().to_owned();
+
+    Pay::new(
+        c.payment.event_pub(),
+        c.payment.admin_repo(),
+        c.payment.payout_repo(),
+        c.payment.payment_gateway(),
+    )
+    .exec(auth_id, path.into_inner())
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/payouts")
+            .route("", web::post().to(run))
+            .route("", web::get().to(get_all))
+            .route("/{month}", web::get().to(get_by_id))
+            .route("/{month}/pay", web::post().to(pay)),
+    );
+}
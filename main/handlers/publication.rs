@@ -1,4 +1,6 @@
-use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use actix_web::{web, HttpResponse, Responder};
+
+use common::model::PaginationQuery;
 
 use publishing::application::publication::{
     AddReview, AddReviewCommand, Approve, Create, CreateCommand, Delete, DeleteReview, GetById,
@@ -6,17 +8,17 @@ use publishing::application::publication::{
     UpdatePages, UpdatePagesCommand,
 };
 
-use crate::authorization::auth;
+use crate::authorization::{Principal, Require};
 use crate::container::Container;
 use crate::error::PublicError;
 
 // POST /publications
 async fn create(
-    req: HttpRequest,
+    principal: Principal,
     cmd: web::Json<CreateCommand>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     Create::new(
         c.publishing.event_pub(),
@@ -30,13 +32,14 @@ async fn create(
     .map_err(PublicError::from)
 }
 
-// GET /publications?q
+// GET /publications?q&cursor&limit&sort&direction
 async fn search(
-    req: HttpRequest,
+    principal: Principal,
     cmd: web::Query<SearchCommand>,
+    pagination: web::Query<PaginationQuery>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     Search::new(
         c.publishing.author_repo(),
@@ -44,7 +47,7 @@ async fn search(
         c.publishing.content_manager_repo(),
         c.publishing.publication_repo(),
     )
-    .exec(auth_id, cmd.into_inner())
+    .exec(auth_id, cmd.into_inner(), pagination.into_inner())
     .await
     .map(|res| HttpResponse::Ok().json(res))
     .map_err(PublicError::from)
@@ -52,11 +55,11 @@ async fn search(
 
 // GET /publications/:id
 async fn get_by_id(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     GetById::new(
         c.publishing.event_pub(),
@@ -74,12 +77,12 @@ async fn get_by_id(
 
 // PUT /publications/:id
 async fn update(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     cmd: web::Json<UpdateCommand>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     Update::new(
         c.publishing.event_pub(),
@@ -94,12 +97,12 @@ async fn update(
 
 // PUT /publications/:id/pages
 async fn update_pages(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     cmd: web::Json<UpdatePagesCommand>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     UpdatePages::new(c.publishing.event_pub(), c.publishing.publication_repo())
         .exec(auth_id, path.into_inner(), cmd.into_inner())
@@ -110,11 +113,11 @@ async fn update_pages(
 
 // DELETE /publications/:id
 async fn delete(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     Delete::new(c.publishing.event_pub(), c.publishing.publication_repo())
         .exec(auth_id, path.into_inner())
@@ -125,11 +128,11 @@ async fn delete(
 
 // POST /publications/:id/publish
 async fn publish(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     Publish::new(
         c.publishing.event_pub(),
@@ -144,11 +147,11 @@ async fn publish(
 
 // POST /publications/:id/approve
 async fn approve(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     Approve::new(
         c.publishing.event_pub(),
@@ -163,11 +166,11 @@ async fn approve(
 
 // POST /publications/:id/reject
 async fn reject(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     Reject::new(
         c.publishing.event_pub(),
@@ -182,11 +185,11 @@ async fn reject(
 
 // POST /publications/:id/read
 async fn read(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     Read::new(
         c.publishing.event_pub(),
@@ -202,11 +205,11 @@ async fn read(
 
 // POST /publications/:id/like
 async fn like(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     Like::new(
         c.publishing.event_pub(),
@@ -222,11 +225,11 @@ async fn like(
 
 // POST /publications/:id/unlike
 async fn unlike(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     Unlike::new(
         c.publishing.event_pub(),
@@ -242,12 +245,12 @@ async fn unlike(
 
 // POST /publications/:id/review
 async fn review(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     cmd: web::Json<AddReviewCommand>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     AddReview::new(
         c.publishing.event_pub(),
@@ -263,11 +266,11 @@ async fn review(
 
 // DELETE /publications/:id/review
 async fn delete_review(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     DeleteReview::new(
         c.publishing.event_pub(),
@@ -281,16 +284,15 @@ async fn delete_review(
     .map_err(PublicError::from)
 }
 
-// GET /publications/:id/reviews
+// GET /publications/:id/reviews?cursor&limit&sort&direction
 async fn reviews(
-    req: HttpRequest,
+    _principal: Principal,
     path: web::Path<String>,
+    pagination: web::Query<PaginationQuery>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let _user_id = auth(&req, &c).await?;
-
     Reviews::new(c.publishing.interaction_repo(), c.publishing.reader_repo())
-        .exec(path.into_inner())
+        .exec(path.into_inner(), pagination.into_inner())
         .await
         .map(|res| HttpResponse::Ok().json(res))
         .map_err(PublicError::from)
@@ -306,8 +308,16 @@ pub fn routes(cfg: &mut web::ServiceConfig) {
             .route("/{publicaton_id}/pages", web::put().to(update_pages))
             .route("/{publicaton_id}", web::delete().to(delete))
             .route("/{publicaton_id}/publish", web::post().to(publish))
-            .route("/{publicaton_id}/approve", web::post().to(approve))
-            .route("/{publicaton_id}/reject", web::post().to(reject))
+            .service(
+                web::resource("/{publicaton_id}/approve")
+                    .wrap(Require::new("publications:U"))
+                    .route(web::post().to(approve)),
+            )
+            .service(
+                web::resource("/{publicaton_id}/reject")
+                    .wrap(Require::new("publications:U"))
+                    .route(web::post().to(reject)),
+            )
             .route("/{publicaton_id}/read", web::post().to(read)) // TODO: should be GET with pages
             .route("/{publicaton_id}/like", web::post().to(like))
             .route("/{publicaton_id}/unlike", web::post().to(unlike))
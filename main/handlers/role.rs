@@ -1,13 +1,17 @@
-use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use actix_web::{web, HttpResponse, Responder};
 
-use identity::application::role::GetAll;
+use identity::application::role::{
+    Create, CreateCommand, Delete, GetAll, GetById, RequireTwoFactor, RequireTwoFactorCommand,
+    Update, UpdateCommand,
+};
 
-use crate::authorization::auth;
+use crate::authorization::{Principal, Require};
 use crate::container::Container;
 use crate::error::PublicError;
 
-async fn get_all(req: HttpRequest, c: web::Data<Container>) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+// GET /roles
+async fn get_all(principal: Principal, c: web::Data<Container>) -> impl Responder {
+    let auth_id = principal.id().to_owned();
 
     GetAll::new(c.identity.role_repo(), c.identity.user_repo())
         .exec(auth_id)
@@ -16,6 +20,92 @@ async fn get_all(req: HttpRequest, c: web::Data<Container>) -> impl Responder {
         .map_err(PublicError::from)
 }
 
+// POST /roles
+async fn create(
+    principal: Principal,
+    cmd: web::Json<CreateCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    Create::new(c.identity.role_repo(), c.identity.user_repo())
+        .exec(auth_id, cmd.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// GET /roles/:id
+async fn get_by_id(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    GetById::new(c.identity.role_repo(), c.identity.user_repo())
+        .exec(auth_id, path.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// PUT /roles/:id
+async fn update(
+    principal: Principal,
+    path: web::Path<String>,
+    cmd: web::Json<UpdateCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    Update::new(c.identity.role_repo(), c.identity.user_repo())
+        .exec(auth_id, path.into_inner(), cmd.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// DELETE /roles/:id
+async fn delete(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    Delete::new(c.identity.role_repo(), c.identity.user_repo())
+        .exec(auth_id, path.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// PUT /roles/:id/two-factor
+async fn require_two_factor(
+    principal: Principal,
+    path: web::Path<String>,
+    cmd: web::Json<RequireTwoFactorCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    RequireTwoFactor::new(c.identity.role_repo(), c.identity.user_repo())
+        .exec(auth_id, path.into_inner(), cmd.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
 pub fn routes(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::scope("/roles").route("", web::get().to(get_all)));
+    cfg.service(
+        web::scope("/roles")
+            .wrap(Require::new("roles:R"))
+            .route("", web::get().to(get_all))
+            .route("", web::post().to(create))
+            .route("/{role_id}", web::get().to(get_by_id))
+            .route("/{role_id}", web::put().to(update))
+            .route("/{role_id}", web::delete().to(delete))
+            .route("/{role_id}/two-factor", web::put().to(require_two_factor)),
+    );
 }
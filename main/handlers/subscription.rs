@@ -0,0 +1,136 @@
+use actix_web::{web, HttpResponse, Responder};
+
+use payment::application::plan::GetAll;
+use payment::application::subscription::{
+    Cancel, Expire, GetById, GetByUser, GetByUserCommand, Renew, Subscribe, SubscribeCommand,
+};
+
+use crate::authorization::Principal;
+use crate::container::Container;
+use crate::error::PublicError;
+
+// GET /subscriptions/plans
+async fn get_plans(c: web::Data<Container>) -> impl Responder {
+    GetAll::new(c.payment.plan_repo())
+        .exec()
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// POST /subscriptions
+async fn subscribe(
+    principal: Principal,
+    cmd: web::Json<SubscribeCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    Subscribe::new(
+        c.payment.event_pub(),
+        c.payment.plan_repo(),
+        c.payment.subscription_repo(),
+        c.payment.payment_gateway(),
+    )
+    .exec(auth_id, cmd.into_inner())
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+// GET /subscriptions?user_id=...
+async fn get_by_user(
+    principal: Principal,
+    cmd: web::Query<GetByUserCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    GetByUser::new(c.payment.admin_repo(), c.payment.subscription_repo())
+        .exec(auth_id, cmd.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// POST /subscriptions/expire
+async fn expire(principal: Principal, c: web::Data<Container>) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    Expire::new(
+        c.payment.event_pub(),
+        c.payment.admin_repo(),
+        c.payment.subscription_repo(),
+    )
+    .exec(auth_id)
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+// GET /subscriptions/:id
+async fn get_by_id(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    GetById::new(c.payment.admin_repo(), c.payment.subscription_repo())
+        .exec(auth_id, path.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// POST /subscriptions/:id/renew
+async fn renew(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    Renew::new(
+        c.payment.event_pub(),
+        c.payment.subscription_repo(),
+        c.payment.payment_gateway(),
+    )
+    .exec(auth_id, path.into_inner())
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+// POST /subscriptions/:id/cancel
+async fn cancel(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    Cancel::new(
+        c.payment.event_pub(),
+        c.payment.admin_repo(),
+        c.payment.subscription_repo(),
+    )
+    .exec(auth_id, path.into_inner())
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/subscriptions")
+            .route("", web::post().to(subscribe))
+            .route("", web::get().to(get_by_user))
+            // Before the subscription id, which would match them.
+            .route("/plans", web::get().to(get_plans))
+            .route("/expire", web::post().to(expire))
+            .route("/{subscription_id}", web::get().to(get_by_id))
+            .route("/{subscription_id}/renew", web::post().to(renew))
+            .route("/{subscription_id}/cancel", web::post().to(cancel)),
+    );
+}
@@ -1,21 +1,52 @@
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 
+use common::model::PaginationQuery;
+
 use identity::application::user::{
-    ChangePassword, ChangePasswordCommand, ChangeRole, ChangeRoleCommand, Delete, GetAll, GetById,
-    Login, LoginCommand, RecoverPassword, RecoverPasswordCommand, Register, RegisterCommand,
-    Update, UpdateCommand, Validate,
+    ChangePassword, ChangePasswordCommand, ChangeRole, ChangeRoleCommand, ConfirmTwoFactor,
+    ConfirmTwoFactorCommand, CreatePersonalAccessToken, CreatePersonalAccessTokenCommand, Delete,
+    DisableTwoFactor, DisableTwoFactorCommand, EnableTwoFactor, GetAll, GetById,
+    GetPersonalAccessTokens, GetSessions, Login, LoginCommand, OidcLogin, OidcLoginCommand,
+    RecoverPassword, RecoverPasswordCommand, RefreshToken, RefreshTokenCommand, Register,
+    RegisterCommand, ResetPassword, ResetPasswordCommand, RevokePersonalAccessToken, RevokeSession,
+    RevokeSessions, StartOidcLogin, StartTwoFactorSetup, StartTwoFactorSetupCommand,
+    TwoFactorLogin, TwoFactorLoginCommand, Unlock, Update, UpdateCommand, Validate,
 };
 
-use crate::authorization::auth;
+use crate::authorization::Principal;
 use crate::container::Container;
 use crate::error::PublicError;
 
+// Address of the client of the request. Anybody can send forwarded headers, so they are only read
+// when the request comes from a trusted proxy.
+fn client_address(req: &HttpRequest, c: &Container) -> Option<String> {
+    let peer = req.peer_addr()?.ip().to_string();
+    if !c.trusted_proxies.contains(&peer) {
+        return Some(peer);
+    }
+
+    // Proxies append the address they received the request from.
+    req.headers()
+        .get("x-forwarded-for")
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| {
+            header
+                .split(',')
+                .map(|addr| addr.trim())
+                .rev()
+                .find(|addr| !c.trusted_proxies.iter().any(|proxy| proxy == addr))
+                .map(|addr| addr.to_owned())
+        })
+        .or(Some(peer))
+}
+
 // POST /register
 async fn register(cmd: web::Json<RegisterCommand>, c: web::Data<Container>) -> impl Responder {
     Register::new(
         c.identity.event_pub(),
         c.identity.user_repo(),
         c.identity.user_serv(),
+        c.identity.verification_serv(),
     )
     .exec(cmd.into_inner())
     .await
@@ -24,8 +55,101 @@ async fn register(cmd: web::Json<RegisterCommand>, c: web::Data<Container>) -> i
 }
 
 // POST /login
-async fn login(cmd: web::Json<LoginCommand>, c: web::Data<Container>) -> impl Responder {
+async fn login(
+    req: HttpRequest,
+    cmd: web::Json<LoginCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let mut cmd = cmd.into_inner();
+    if cmd.device.is_none() {
+        cmd.device = req
+            .headers()
+            .get("user-agent")
+            .and_then(|header| header.to_str().ok())
+            .map(|header| header.to_owned());
+    }
+    cmd.client = client_address(&req, &c);
+
     Login::new(c.identity.event_pub(), c.identity.authentication_serv())
+        .exec(cmd)
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// POST /login/two-factor
+async fn two_factor_login(
+    req: HttpRequest,
+    cmd: web::Json<TwoFactorLoginCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let mut cmd = cmd.into_inner();
+    if cmd.device.is_none() {
+        cmd.device = req
+            .headers()
+            .get("user-agent")
+            .and_then(|header| header.to_str().ok())
+            .map(|header| header.to_owned());
+    }
+    cmd.client = client_address(&req, &c);
+
+    TwoFactorLogin::new(c.identity.event_pub(), c.identity.authentication_serv())
+        .exec(cmd)
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// POST /login/two-factor/setup
+async fn start_two_factor_setup(
+    cmd: web::Json<StartTwoFactorSetupCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    StartTwoFactorSetup::new(c.identity.user_repo(), c.identity.authentication_serv())
+        .exec(cmd.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// GET /oidc/:provider
+async fn start_oidc_login(path: web::Path<String>, c: web::Data<Container>) -> impl Responder {
+    StartOidcLogin::new(c.identity.oidc_serv())
+        .exec(path.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// GET /oidc/:provider/callback?state&code
+async fn oidc_login(
+    req: HttpRequest,
+    path: web::Path<String>,
+    cmd: web::Query<OidcLoginCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let mut cmd = cmd.into_inner();
+    if cmd.device.is_none() {
+        cmd.device = req
+            .headers()
+            .get("user-agent")
+            .and_then(|header| header.to_str().ok())
+            .map(|header| header.to_owned());
+    }
+
+    OidcLogin::new(c.identity.event_pub(), c.identity.oidc_serv())
+        .exec(path.into_inner(), cmd)
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// POST /refresh-token
+async fn refresh_token(
+    cmd: web::Json<RefreshTokenCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    RefreshToken::new(c.identity.authentication_serv())
         .exec(cmd.into_inner())
         .await
         .map(|res| HttpResponse::Ok().json(res))
@@ -40,7 +164,25 @@ async fn recover_password(
     RecoverPassword::new(
         c.identity.event_pub(),
         c.identity.user_repo(),
+        c.identity.verification_serv(),
+    )
+    .exec(cmd.into_inner())
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+// POST /reset-password
+async fn reset_password(
+    cmd: web::Json<ResetPasswordCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    ResetPassword::new(
+        c.identity.event_pub(),
+        c.identity.user_repo(),
+        c.identity.token_serv(),
         c.identity.user_serv(),
+        c.identity.verification_serv(),
     )
     .exec(cmd.into_inner())
     .await
@@ -48,12 +190,16 @@ async fn recover_password(
     .map_err(PublicError::from)
 }
 
-// GET /users
-async fn get_all(req: HttpRequest, c: web::Data<Container>) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+// GET /users?cursor&limit&sort&direction
+async fn get_all(
+    principal: Principal,
+    pagination: web::Query<PaginationQuery>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
 
     GetAll::new(c.identity.user_repo())
-        .exec(auth_id)
+        .exec(auth_id, pagination.into_inner())
         .await
         .map(|res| HttpResponse::Ok().json(res))
         .map_err(PublicError::from)
@@ -61,11 +207,11 @@ async fn get_all(req: HttpRequest, c: web::Data<Container>) -> impl Responder {
 
 // GET /users/:id
 async fn get_by_id(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     GetById::new(c.identity.user_repo())
         .exec(auth_id, path.into_inner())
@@ -76,12 +222,12 @@ async fn get_by_id(
 
 // PUT /users/:id
 async fn update(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     cmd: web::Json<UpdateCommand>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     Update::new(c.identity.event_pub(), c.identity.user_repo())
         .exec(auth_id, path.into_inner(), cmd.into_inner())
@@ -92,11 +238,11 @@ async fn update(
 
 // DELETE /users/:id
 async fn delete(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
     Delete::new(c.identity.event_pub(), c.identity.user_repo())
         .exec(auth_id, path.into_inner())
@@ -107,50 +253,228 @@ async fn delete(
 
 // PUT /users/password
 async fn change_password(
-    req: HttpRequest,
+    principal: Principal,
     path: web::Path<String>,
     cmd: web::Json<ChangePasswordCommand>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
-    ChangePassword::new(c.identity.user_repo(), c.identity.user_serv())
+    ChangePassword::new(
+        c.identity.user_repo(),
+        c.identity.user_serv(),
+        c.identity.token_serv(),
+    )
+    .exec(auth_id, path.into_inner(), cmd.into_inner())
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+// GET /users/:id/validate/:token
+async fn validate(path: web::Path<(String, String)>, c: web::Data<Container>) -> impl Responder {
+    let path = path.into_inner();
+    Validate::new(
+        c.identity.event_pub(),
+        c.identity.user_repo(),
+        c.identity.verification_serv(),
+    )
+    .exec(path.0, path.1)
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+// PUT /users/:id/role
+async fn change_role(
+    principal: Principal,
+    path: web::Path<String>,
+    cmd: web::Json<ChangeRoleCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    ChangeRole::new(c.identity.role_repo(), c.identity.user_repo())
         .exec(auth_id, path.into_inner(), cmd.into_inner())
         .await
         .map(|res| HttpResponse::Ok().json(res))
         .map_err(PublicError::from)
 }
 
-// GET /users/:id/validate/:code
-async fn validate(path: web::Path<(String, String)>, c: web::Data<Container>) -> impl Responder {
+// GET /users/:id/sessions
+async fn get_sessions(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    GetSessions::new(c.identity.user_repo(), c.identity.token_serv())
+        .exec(auth_id, path.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// DELETE /users/:id/sessions/:session_id
+async fn revoke_session(
+    principal: Principal,
+    path: web::Path<(String, String)>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
     let path = path.into_inner();
-    Validate::new(c.identity.event_pub(), c.identity.user_repo())
-        .exec(path.0, path.1)
+
+    RevokeSession::new(c.identity.user_repo(), c.identity.token_serv())
+        .exec(auth_id, path.0, path.1)
         .await
         .map(|res| HttpResponse::Ok().json(res))
         .map_err(PublicError::from)
 }
 
-// PUT /users/:id/role
-async fn change_role(
-    req: HttpRequest,
+// POST /users/:id/unlock
+async fn unlock(
+    principal: Principal,
     path: web::Path<String>,
-    cmd: web::Json<ChangeRoleCommand>,
     c: web::Data<Container>,
 ) -> impl Responder {
-    let auth_id = auth(&req, &c).await?;
+    let auth_id = principal.id().to_owned();
 
-    ChangeRole::new(c.identity.role_repo(), c.identity.user_repo())
+    Unlock::new(c.identity.user_repo(), c.identity.authentication_serv())
+        .exec(auth_id, path.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// POST /users/:id/two-factor
+async fn enable_two_factor(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    EnableTwoFactor::new(c.identity.user_repo())
+        .exec(auth_id, path.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// POST /users/:id/two-factor/confirm
+async fn confirm_two_factor(
+    principal: Principal,
+    path: web::Path<String>,
+    cmd: web::Json<ConfirmTwoFactorCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    ConfirmTwoFactor::new(c.identity.event_pub(), c.identity.user_repo())
         .exec(auth_id, path.into_inner(), cmd.into_inner())
         .await
         .map(|res| HttpResponse::Ok().json(res))
         .map_err(PublicError::from)
 }
 
+// DELETE /users/:id/two-factor
+async fn disable_two_factor(
+    principal: Principal,
+    path: web::Path<String>,
+    cmd: web::Json<DisableTwoFactorCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    DisableTwoFactor::new(
+        c.identity.event_pub(),
+        c.identity.user_repo(),
+        c.identity.authentication_serv(),
+    )
+    .exec(auth_id, path.into_inner(), cmd.into_inner())
+    .await
+    .map(|res| HttpResponse::Ok().json(res))
+    .map_err(PublicError::from)
+}
+
+// DELETE /users/:id/sessions
+async fn revoke_sessions(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    RevokeSessions::new(c.identity.user_repo(), c.identity.token_serv())
+        .exec(auth_id, path.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// GET /users/:id/access-tokens
+async fn get_personal_access_tokens(
+    principal: Principal,
+    path: web::Path<String>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+
+    GetPersonalAccessTokens::new(c.identity.user_repo(), c.identity.token_serv())
+        .exec(auth_id, path.into_inner())
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// POST /users/:id/access-tokens
+async fn create_personal_access_token(
+    principal: Principal,
+    path: web::Path<String>,
+    cmd: web::Json<CreatePersonalAccessTokenCommand>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    CreatePersonalAccessToken::new(c.identity.user_repo(), c.identity.token_serv())
+        .exec(
+            principal.authorization(),
+            path.into_inner(),
+            cmd.into_inner(),
+        )
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
+// DELETE /users/:id/access-tokens/:token_id
+async fn revoke_personal_access_token(
+    principal: Principal,
+    path: web::Path<(String, String)>,
+    c: web::Data<Container>,
+) -> impl Responder {
+    let auth_id = principal.id().to_owned();
+    let path = path.into_inner();
+
+    RevokePersonalAccessToken::new(c.identity.user_repo(), c.identity.token_serv())
+        .exec(auth_id, path.0, path.1)
+        .await
+        .map(|res| HttpResponse::Ok().json(res))
+        .map_err(PublicError::from)
+}
+
 pub fn routes(cfg: &mut web::ServiceConfig) {
     cfg.route("/register", web::post().to(register))
         .route("/login", web::post().to(login))
+        .route("/login/two-factor", web::post().to(two_factor_login))
+        .route(
+            "/login/two-factor/setup",
+            web::post().to(start_two_factor_setup),
+        )
+        .route("/refresh-token", web::post().to(refresh_token))
+        .route("/oidc/{provider}", web::get().to(start_oidc_login))
+        .route("/oidc/{provider}/callback", web::get().to(oidc_login))
         .route("/recover-password", web::post().to(recover_password))
+        .route("/reset-password", web::post().to(reset_password))
         .service(
             web::scope("/users")
                 .route("", web::get().to(get_all))
@@ -158,10 +482,35 @@ pub fn routes(cfg: &mut web::ServiceConfig) {
                 .route("/{user_id}", web::put().to(update))
                 .route("/{user_id}", web::delete().to(delete))
                 .route("/{user_id}/password", web::put().to(change_password))
+                .route("/{user_id}/validate/{token}", web::get().to(validate))
+                .route("/{user_id}/role", web::put().to(change_role))
+                .route("/{user_id}/unlock", web::post().to(unlock))
+                .route("/{user_id}/two-factor", web::post().to(enable_two_factor))
+                .route(
+                    "/{user_id}/two-factor/confirm",
+                    web::post().to(confirm_two_factor),
+                )
+                .route(
+                    "/{user_id}/two-factor",
+                    web::delete().to(disable_two_factor),
+                )
+                .route("/{user_id}/sessions", web::get().to(get_sessions))
+                .route("/{user_id}/sessions", web::delete().to(revoke_sessions))
                 .route(
-                    "/{user_id}/validate/{validation_code}",
-                    web::get().to(validate),
+                    "/{user_id}/sessions/{session_id}",
+                    web::delete().to(revoke_session),
                 )
-                .route("/{user_id}/role", web::put().to(change_role)),
+                .route(
+                    "/{user_id}/access-tokens",
+                    web::get().to(get_personal_access_tokens),
+                )
+                .route(
+                    "/{user_id}/access-tokens",
+                    web::post().to(create_personal_access_token),
+                )
+                .route(
+                    "/{user_id}/access-tokens/{token_id}",
+                    web::delete().to(revoke_personal_access_token),
+                ),
         );
 }
@@ -1 +1,2 @@
+pub mod payment;
 pub mod publishing;
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use common::error::Error;
+use common::result::Result;
+use identity::domain::user::{UserId, UserRepository};
+use payment::domain::admin::{Admin, AdminId, AdminRepository};
+
+pub struct AdminTranslator {
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl AdminTranslator {
+    pub fn new(user_repo: Arc<dyn UserRepository>) -> Self {
+        AdminTranslator { user_repo }
+    }
+}
+
+#[async_trait]
+impl AdminRepository for AdminTranslator {
+    async fn find_by_id(&self, id: &AdminId) -> Result<Admin> {
+        let user = self.user_repo.find_by_id(&UserId::new(id.value())?).await?;
+
+        if !user.role().has_permissions("contracts", "U") {
+            return Err(Error::new("user", "unauthorized"));
+        }
+
+        let payout_permissions = user
+            .role()
+            .permissions()
+            .iter()
+            .find(|permission| permission.module() == "payouts")
+            .map(|permission| permission.permissions().to_owned())
+            .unwrap_or_default();
+
+        Admin::new(AdminId::new(user.base().id().value())?, payout_permissions)
+    }
+
+    async fn save(&self, _admin: &mut Admin) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+mod admin_translator;
+mod publication_translator;
+pub use admin_translator::*;
+pub use publication_translator::*;
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use common::error::Error;
+use common::result::Result;
+use payment::domain::publication::{Publication, PublicationId, PublicationRepository, Statistics};
+use payment::domain::user::{User, UserId};
+use publishing::domain::publication::{
+    PublicationId as PublishingPublicationId,
+    PublicationRepository as PublishingPublicationRepository, Statistics as PublishingStatistics,
+    StatisticsService,
+};
+
+// Publications of the publishing context with their statistics, which decide whether they can
+// get a contract.
+pub struct PublicationTranslator {
+    publication_repo: Arc<dyn PublishingPublicationRepository>,
+    statistics_serv: Arc<StatisticsService>,
+}
+
+impl PublicationTranslator {
+    pub fn new(
+        publication_repo: Arc<dyn PublishingPublicationRepository>,
+        statistics_serv: Arc<StatisticsService>,
+    ) -> Self {
+        PublicationTranslator {
+            publication_repo,
+            statistics_serv,
+        }
+    }
+}
+
+fn translate_statistics(statistics: &PublishingStatistics) -> Result<Statistics> {
+    // Publications without reviews do not have an average.
+    let stars = if statistics.reviews() > 0 {
+        statistics.stars()
+    } else {
+        0.0
+    };
+
+    Statistics::new(
+        statistics.unique_views(),
+        statistics.readings(),
+        statistics.likes(),
+        statistics.reviews(),
+        stars,
+    )
+}
+
+#[async_trait]
+impl PublicationRepository for PublicationTranslator {
+    async fn find_by_id(&self, id: &PublicationId) -> Result<Publication> {
+        let publication = self
+            .publication_repo
+            .find_by_id(&PublishingPublicationId::new(id.value())?)
+            .await?;
+
+        if !publication.is_published() {
+            return Err(Error::new("publication", "not_published"));
+        }
+
+        Publication::new(
+            PublicationId::new(publication.base().id().value())?,
+            User::new(UserId::new(publication.author_id().value())?)?,
+            translate_statistics(publication.statistics())?,
+        )
+    }
+
+    async fn find_statistics(
+        &self,
+        id: &PublicationId,
+        from: Option<&DateTime<Utc>>,
+        to: &DateTime<Utc>,
+    ) -> Result<Statistics> {
+        let statistics = self
+            .statistics_serv
+            .get_history(
+                None,
+                Some(&PublishingPublicationId::new(id.value())?),
+                from,
+                Some(to),
+            )
+            .await?;
+
+        translate_statistics(&statistics)
+    }
+
+    async fn save(&self, _publication: &mut Publication) -> Result<()> {
+        Ok(())
+    }
+}
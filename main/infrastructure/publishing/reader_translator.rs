@@ -4,15 +4,24 @@ use async_trait::async_trait;
 
 use common::result::Result;
 use identity::domain::user::{UserId, UserRepository};
+use payment::domain::subscription::SubscriptionRepository;
+use payment::domain::user::UserId as PaymentUserId;
 use publishing::domain::reader::{Reader, ReaderId, ReaderRepository};
 
 pub struct ReaderTranslator {
+    subscription_repo: Arc<dyn SubscriptionRepository>,
     user_repo: Arc<dyn UserRepository>,
 }
 
 impl ReaderTranslator {
-    pub fn new(user_repo: Arc<dyn UserRepository>) -> Self {
-        ReaderTranslator { user_repo }
+    pub fn new(
+        subscription_repo: Arc<dyn SubscriptionRepository>,
+        user_repo: Arc<dyn UserRepository>,
+    ) -> Self {
+        ReaderTranslator {
+            subscription_repo,
+            user_repo,
+        }
     }
 }
 
@@ -26,11 +35,20 @@ impl ReaderRepository for ReaderTranslator {
     async fn find_by_id(&self, id: &ReaderId) -> Result<Reader> {
         let user = self.user_repo.find_by_id(&UserId::new(id.value())?).await?;
 
-        Ok(Reader::new(
+        // A cancelled subscription can still be active while a new one waits for its payment.
+        let subscribed = self
+            .subscription_repo
+            .find_by_user_id(&PaymentUserId::new(id.value())?)
+            .await?
+            .iter()
+            .any(|subscription| subscription.is_active());
+
+        Ok(Reader::build(
             ReaderId::new(user.base().id().value())?,
             user.identity().username().value(),
             user.person().unwrap().fullname().name(),
             user.person().unwrap().fullname().lastname(),
+            subscribed,
         )?)
     }
 
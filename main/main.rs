@@ -6,13 +6,20 @@ mod handlers;
 mod infrastructure;
 mod response;
 
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
 use actix_cors::Cors;
 use actix_web::{web, App, HttpResponse, HttpServer, Responder};
 
 use common::config::Config;
 
 use container::Container;
-use handlers::{author, catalogue, category, collection, event, publication, role, user};
+use handlers::{
+    author, catalogue, category, collection, contract, event, payment, payout, publication, role,
+    subscription, user,
+};
 
 async fn index() -> impl Responder {
     HttpResponse::Ok().body("Omics")
@@ -25,11 +32,37 @@ async fn main() -> std::io::Result<()> {
     let config = Config::get();
 
     // Dependencies
-    let container = web::Data::new(Container::new().await);
+    let container = match Container::new(&config).await {
+        Ok(container) => web::Data::new(container),
+        Err(err) => {
+            println!("Container: {}", err);
+            return Ok(());
+        }
+    };
     if let Err(err) = container.subscribe().await {
         println!("Subscriptions: {}", err);
         return Ok(());
     }
+    Arc::clone(&container.outbox_relay).start(Duration::from_secs(5));
+
+    // Reconstructs the catalogue from the stored events before serving it.
+    if env::args().any(|arg| arg == "--rebuild-catalogue") {
+        match container
+            .projection_runner
+            .rebuild(&container.catalogue.projection())
+            .await
+        {
+            Ok(res) => println!(
+                "Catalogue rebuilt: {} events, {} failed handlers",
+                res.events(),
+                res.err_handlers()
+            ),
+            Err(err) => {
+                println!("Catalogue: {}", err);
+                return Ok(());
+            }
+        }
+    }
 
     if config.env() == "development" {
         if let Err(err) = development::populate(&container).await {
@@ -50,9 +83,13 @@ async fn main() -> std::io::Result<()> {
                     .configure(catalogue::routes)
                     .configure(category::routes)
                     .configure(collection::routes)
+                    .configure(contract::routes)
                     .configure(event::routes)
+                    .configure(payment::routes)
+                    .configure(payout::routes)
                     .configure(publication::routes)
                     .configure(role::routes)
+                    .configure(subscription::routes)
                     .configure(user::routes),
             )
     })
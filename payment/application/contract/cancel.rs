@@ -0,0 +1,87 @@
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::domain::admin::{AdminId, AdminRepository};
+use crate::domain::contract::{ContractId, ContractRepository};
+
+pub struct Cancel<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    admin_repo: &'a dyn AdminRepository,
+    contract_repo: &'a dyn ContractRepository,
+}
+
+impl<'a> Cancel<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        admin_repo: &'a dyn AdminRepository,
+        contract_repo: &'a dyn ContractRepository,
+    ) -> Self {
+        Cancel {
+            event_pub,
+            admin_repo,
+            contract_repo,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, contract_id: String) -> Result<()> {
+        let contract_id = ContractId::new(contract_id)?;
+        let mut contract = self.contract_repo.find_by_id(&contract_id).await?;
+
+        // The author or an admin can cancel it.
+        if contract.publication().author().id().value() != auth_id {
+            self.admin_repo.find_by_id(&AdminId::new(auth_id)?).await?;
+        }
+
+        contract.cancel()?;
+
+        self.contract_repo.save(&mut contract).await?;
+
+        self.event_pub
+            .publish_all(contract.base().events()?)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::contract::Contract;
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn cancel() {
+        let c = mocks::container();
+        let uc = Cancel::new(c.event_pub(), c.admin_repo(), c.contract_repo());
+
+        let mut contract = Contract::new(
+            ContractId::new("#contract01").unwrap(),
+            mocks::publication1(),
+        )
+        .unwrap();
+        contract.approve(&mocks::admin1()).unwrap();
+        c.contract_repo().save(&mut contract).await.unwrap();
+
+        // Neither the author nor an admin
+        assert!(uc
+            .exec("#user02".to_owned(), "#contract01".to_owned())
+            .await
+            .is_err());
+
+        uc.exec("#user01".to_owned(), "#contract01".to_owned())
+            .await
+            .unwrap();
+        let contract = c
+            .contract_repo()
+            .find_by_id(&ContractId::new("#contract01").unwrap())
+            .await
+            .unwrap();
+        assert!(!contract.is_active());
+
+        let events = c.event_pub().events().await;
+        assert_eq!(events.last().unwrap().code(), "cancelled");
+    }
+}
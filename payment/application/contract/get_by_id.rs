@@ -1,24 +1,34 @@
 use common::result::Result;
 
 use crate::application::dtos::ContractDto;
+use crate::domain::admin::{AdminId, AdminRepository};
 use crate::domain::contract::{ContractId, ContractRepository};
 
-pub struct GetById<'a, CRepo> {
-    contract_repo: &'a CRepo,
+pub struct GetById<'a> {
+    admin_repo: &'a dyn AdminRepository,
+    contract_repo: &'a dyn ContractRepository,
 }
 
-impl<'a, CRepo> GetById<'a, CRepo>
-where
-    CRepo: ContractRepository,
-{
-    pub fn new(contract_repo: &'a CRepo) -> Self {
-        GetById { contract_repo }
+impl<'a> GetById<'a> {
+    pub fn new(
+        admin_repo: &'a dyn AdminRepository,
+        contract_repo: &'a dyn ContractRepository,
+    ) -> Self {
+        GetById {
+            admin_repo,
+            contract_repo,
+        }
     }
 
-    pub async fn exec(&self, contract_id: String) -> Result<ContractDto> {
+    pub async fn exec(&self, auth_id: String, contract_id: String) -> Result<ContractDto> {
         let contract_id = ContractId::new(contract_id)?;
         let contract = self.contract_repo.find_by_id(&contract_id).await?;
 
+        // Only the author and the admins can see the contract.
+        if contract.publication().author().id().value() != auth_id {
+            self.admin_repo.find_by_id(&AdminId::new(auth_id)?).await?;
+        }
+
         Ok(ContractDto::from(&contract))
     }
 }
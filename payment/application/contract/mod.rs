@@ -1,8 +1,12 @@
 mod approve;
+mod cancel;
 mod get_by_id;
 mod reject;
 mod request;
+mod search;
 pub use approve::*;
+pub use cancel::*;
 pub use get_by_id::*;
 pub use reject::*;
 pub use request::*;
+pub use search::*;
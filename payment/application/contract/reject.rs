@@ -4,20 +4,19 @@ use common::result::Result;
 use crate::domain::admin::{AdminId, AdminRepository};
 use crate::domain::contract::{ContractId, ContractRepository};
 
-pub struct Reject<'a, EPub, ARepo, CRepo> {
-    event_pub: &'a EPub,
+pub struct Reject<'a> {
+    event_pub: &'a dyn EventPublisher,
 
-    admin_repo: &'a ARepo,
-    contract_repo: &'a CRepo,
+    admin_repo: &'a dyn AdminRepository,
+    contract_repo: &'a dyn ContractRepository,
 }
 
-impl<'a, EPub, ARepo, CRepo> Reject<'a, EPub, ARepo, CRepo>
-where
-    EPub: EventPublisher,
-    ARepo: AdminRepository,
-    CRepo: ContractRepository,
-{
-    pub fn new(event_pub: &'a EPub, admin_repo: &'a ARepo, contract_repo: &'a CRepo) -> Self {
+impl<'a> Reject<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        admin_repo: &'a dyn AdminRepository,
+        contract_repo: &'a dyn ContractRepository,
+    ) -> Self {
         Reject {
             event_pub,
             admin_repo,
@@ -1,30 +1,35 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+use common::error::Error;
 use common::event::EventPublisher;
 use common::result::Result;
 
 use crate::domain::contract::{Contract, ContractRepository};
 use crate::domain::publication::{PublicationId, PublicationRepository};
 
+#[derive(Deserialize)]
+pub struct RequestCommand {
+    pub publication_id: String,
+}
+
 #[derive(Serialize)]
 pub struct RequestReponse {
     id: String,
 }
 
-pub struct Request<'a, EPub, CRepo, PRepo> {
-    event_pub: &'a EPub,
+pub struct Request<'a> {
+    event_pub: &'a dyn EventPublisher,
 
-    contract_repo: &'a CRepo,
-    publication_repo: &'a PRepo,
+    contract_repo: &'a dyn ContractRepository,
+    publication_repo: &'a dyn PublicationRepository,
 }
 
-impl<'a, EPub, CRepo, PRepo> Request<'a, EPub, CRepo, PRepo>
-where
-    EPub: EventPublisher,
-    CRepo: ContractRepository,
-    PRepo: PublicationRepository,
-{
-    pub fn new(event_pub: &'a EPub, contract_repo: &'a CRepo, publication_repo: &'a PRepo) -> Self {
+impl<'a> Request<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        contract_repo: &'a dyn ContractRepository,
+        publication_repo: &'a dyn PublicationRepository,
+    ) -> Self {
         Request {
             event_pub,
             contract_repo,
@@ -32,10 +37,25 @@ where
         }
     }
 
-    pub async fn exec(&self, publication_id: String) -> Result<RequestReponse> {
-        let publication_id = PublicationId::new(publication_id)?;
+    pub async fn exec(&self, auth_id: String, cmd: RequestCommand) -> Result<RequestReponse> {
+        let publication_id = PublicationId::new(cmd.publication_id)?;
         let publication = self.publication_repo.find_by_id(&publication_id).await?;
 
+        // Only the author can apply for a contract for the publication.
+        if publication.author().id().value() != auth_id {
+            return Err(Error::unauthorized());
+        }
+
+        if let Ok(contract) = self
+            .contract_repo
+            .find_by_publication_id(&publication_id)
+            .await
+        {
+            if contract.is_active() {
+                return Err(Error::new("contract", "already_exists"));
+            }
+        }
+
         let mut contract = Contract::new(self.contract_repo.next_id().await?, publication)?;
 
         self.contract_repo.save(&mut contract).await?;
@@ -49,3 +69,62 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::contract::ContractId;
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn request() {
+        let c = mocks::container();
+        let uc = Request::new(c.event_pub(), c.contract_repo(), c.publication_repo());
+
+        let mut publication = mocks::publication1();
+        c.publication_repo().save(&mut publication).await.unwrap();
+
+        let cmd = || RequestCommand {
+            publication_id: publication.id().to_string(),
+        };
+
+        // Not the author
+        assert!(uc.exec("#user02".to_owned(), cmd()).await.is_err());
+
+        let res = uc.exec("#user01".to_owned(), cmd()).await.unwrap();
+        let mut contract = c
+            .contract_repo()
+            .find_by_id(&ContractId::new(res.id).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(contract.publication().id(), publication.id());
+
+        // Already requested
+        assert!(uc.exec("#user01".to_owned(), cmd()).await.is_err());
+
+        // It can apply again after being rejected.
+        contract.reject(&mocks::admin1()).unwrap();
+        c.contract_repo().save(&mut contract).await.unwrap();
+        assert!(uc.exec("#user01".to_owned(), cmd()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn low_views() {
+        let c = mocks::container();
+        let uc = Request::new(c.event_pub(), c.contract_repo(), c.publication_repo());
+
+        let mut publication = mocks::publication2();
+        c.publication_repo().save(&mut publication).await.unwrap();
+
+        assert!(uc
+            .exec(
+                "#user01".to_owned(),
+                RequestCommand {
+                    publication_id: publication.id().to_string(),
+                },
+            )
+            .await
+            .is_err());
+    }
+}
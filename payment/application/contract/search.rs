@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use common::result::Result;
+
+use crate::application::dtos::ContractDto;
+use crate::domain::contract::ContractRepository;
+
+#[derive(Deserialize)]
+pub struct SearchCommand {
+    // Requested contracts by default, the ones waiting for approval.
+    pub status: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SearchResponse {
+    contracts: Vec<ContractDto>,
+}
+
+pub struct Search<'a> {
+    contract_repo: &'a dyn ContractRepository,
+}
+
+impl<'a> Search<'a> {
+    pub fn new(contract_repo: &'a dyn ContractRepository) -> Self {
+        Search { contract_repo }
+    }
+
+    pub async fn exec(&self, cmd: SearchCommand) -> Result<SearchResponse> {
+        let status = cmd.status.unwrap_or_else(|| "requested".to_owned());
+        let contracts = self.contract_repo.find_by_status(&status).await?;
+
+        Ok(SearchResponse {
+            contracts: contracts.iter().map(ContractDto::from).collect(),
+        })
+    }
+}
@@ -1,7 +1,10 @@
 use serde::Serialize;
 
 use crate::domain::contract::Contract;
+use crate::domain::payout::{Line, Payout, Summary};
+use crate::domain::plan::Plan;
 use crate::domain::publication::Publication;
+use crate::domain::subscription::Subscription;
 use crate::domain::user::User;
 
 #[derive(Serialize)]
@@ -36,6 +39,8 @@ impl From<&Publication> for PublicationDto {
 pub struct ContractDto {
     pub id: String,
     pub publication: PublicationDto,
+    pub status: String,
+    pub created_at: String,
 }
 
 impl From<&Contract> for ContractDto {
@@ -43,6 +48,144 @@ impl From<&Contract> for ContractDto {
         ContractDto {
             id: contract.base().id().to_string(),
             publication: PublicationDto::from(contract.publication()),
+            status: contract.status_history().current().status().to_string(),
+            created_at: contract.base().created_at().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PlanDto {
+    pub id: String,
+    pub name: String,
+    pub price: f64,
+    pub period: String,
+}
+
+impl From<&Plan> for PlanDto {
+    fn from(plan: &Plan) -> Self {
+        PlanDto {
+            id: plan.id().to_string(),
+            name: plan.name().to_owned(),
+            price: plan.price(),
+            period: plan.period().to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SubscriptionDto {
+    pub id: String,
+    pub user: UserDto,
+    pub plan: PlanDto,
+    pub status: String,
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paid_until: Option<String>,
+    pub created_at: String,
+}
+
+impl From<&Subscription> for SubscriptionDto {
+    fn from(subscription: &Subscription) -> Self {
+        SubscriptionDto {
+            id: subscription.base().id().to_string(),
+            user: UserDto {
+                id: subscription.user_id().to_string(),
+            },
+            plan: PlanDto::from(subscription.plan()),
+            status: subscription.status_history().current().status().to_string(),
+            active: subscription.is_active(),
+            paid_until: subscription
+                .paid_until()
+                .map(|paid_until| paid_until.to_rfc3339()),
+            created_at: subscription.base().created_at().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SummaryDto {
+    pub contract_id: String,
+    pub publication: PublicationDto,
+    pub unique_views: u32,
+    pub readings: u32,
+    pub likes: u32,
+    pub reviews: u32,
+    pub stars: f32,
+    pub score: f64,
+    pub eligible: bool,
+    pub amount: f64,
+}
+
+impl From<&Summary> for SummaryDto {
+    fn from(summary: &Summary) -> Self {
+        let statistics = summary.statistics();
+
+        SummaryDto {
+            contract_id: summary.contract_id().to_string(),
+            publication: PublicationDto::from(summary.publication()),
+            unique_views: statistics.unique_views(),
+            readings: statistics.readings(),
+            likes: statistics.likes(),
+            reviews: statistics.reviews(),
+            stars: statistics.stars(),
+            score: summary.score(),
+            eligible: summary.is_eligible(),
+            amount: summary.amount(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PayoutLineDto {
+    pub author: UserDto,
+    pub amount: f64,
+    pub publication_ids: Vec<String>,
+    pub status: String,
+}
+
+impl From<&Line> for PayoutLineDto {
+    fn from(line: &Line) -> Self {
+        PayoutLineDto {
+            author: UserDto {
+                id: line.author_id().to_string(),
+            },
+            amount: line.amount(),
+            publication_ids: line
+                .publication_ids()
+                .iter()
+                .map(|id| id.to_string())
+                .collect(),
+            status: line.status().to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PayoutDto {
+    pub id: String,
+    pub month: String,
+    pub income: f64,
+    pub subscriptions: usize,
+    pub amount: f64,
+    pub paid: bool,
+    pub summaries: Vec<SummaryDto>,
+    pub lines: Vec<PayoutLineDto>,
+    pub created_at: String,
+}
+
+impl From<&Payout> for PayoutDto {
+    fn from(payout: &Payout) -> Self {
+        PayoutDto {
+            id: payout.base().id().to_string(),
+            month: payout.month().to_string(),
+            income: payout.financial_summary().income(),
+            subscriptions: payout.financial_summary().subscriptions(),
+            amount: payout.amount(),
+            paid: payout.is_paid(),
+            summaries: payout.summaries().iter().map(SummaryDto::from).collect(),
+            lines: payout.lines().iter().map(PayoutLineDto::from).collect(),
+            created_at: payout.base().created_at().to_rfc3339(),
         }
     }
 }
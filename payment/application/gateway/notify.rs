@@ -0,0 +1,322 @@
+use serde::Deserialize;
+
+use common::error::Error;
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::domain::gateway::{
+    Notification, NotificationId, NotificationRepository, TransactionKind,
+};
+use crate::domain::payout::{Payout, PayoutRepository};
+use crate::domain::subscription::{SubscriptionId, SubscriptionRepository};
+
+#[derive(Deserialize)]
+pub struct NotifyCommand {
+    pub id: String,
+    // charge.succeeded, charge.failed, payout.succeeded, etc.
+    pub event: String,
+    pub transaction_id: String,
+    pub reference: String,
+    pub amount: f64,
+}
+
+// Processes the result of a transaction notified by the provider. The notification must have been
+// verified before.
+pub struct Notify<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    notification_repo: &'a dyn NotificationRepository,
+    payout_repo: &'a dyn PayoutRepository,
+    subscription_repo: &'a dyn SubscriptionRepository,
+}
+
+impl<'a> Notify<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        notification_repo: &'a dyn NotificationRepository,
+        payout_repo: &'a dyn PayoutRepository,
+        subscription_repo: &'a dyn SubscriptionRepository,
+    ) -> Self {
+        Notify {
+            event_pub,
+            notification_repo,
+            payout_repo,
+            subscription_repo,
+        }
+    }
+
+    pub async fn exec(&self, cmd: NotifyCommand) -> Result<()> {
+        let mut notification = Notification::new(
+            NotificationId::new(cmd.id)?,
+            &cmd.event,
+            cmd.transaction_id,
+            cmd.reference,
+            cmd.amount,
+        )?;
+
+        self.notification_repo.reserve(&mut notification).await?;
+
+        // Notifications that fail are kept with the error, and can be sent again.
+        match self.process(&notification).await {
+            Ok(()) => {
+                notification.process();
+                self.notification_repo.save(&mut notification).await
+            }
+            Err(err) => {
+                notification.fail(err.to_string());
+                self.notification_repo.save(&mut notification).await?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn process(&self, notification: &Notification) -> Result<()> {
+        match notification.kind() {
+            TransactionKind::Charge => {
+                let subscription_id = SubscriptionId::new(notification.reference())?;
+                let mut subscription = self.subscription_repo.find_by_id(&subscription_id).await?;
+
+                if notification.succeeded() {
+                    if (notification.amount() - subscription.plan().price()).abs() >= 0.01 {
+                        return Err(Error::new("notification", "invalid_amount"));
+                    }
+
+                    subscription.pay()?;
+                } else {
+                    subscription.fail_payment()?;
+                }
+
+                self.subscription_repo.save(&mut subscription).await?;
+
+                self.event_pub
+                    .publish_all(subscription.base().events()?)
+                    .await?;
+            }
+            TransactionKind::Payout => {
+                let (payout_id, author_id) =
+                    Payout::parse_line_reference(notification.reference())?;
+                let mut payout = self.payout_repo.find_by_id(&payout_id).await?;
+
+                if notification.succeeded() {
+                    payout.confirm_line(&author_id, notification.transaction_id())?;
+                } else {
+                    payout.fail_line(&author_id, notification.transaction_id())?;
+                }
+
+                self.payout_repo.save(&mut payout).await?;
+
+                self.event_pub.publish_all(payout.base().events()?).await?;
+            }
+            // Refunds are requested by admins, and nothing depends on their result.
+            TransactionKind::Refund => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::contract::ContractId;
+    use crate::domain::payout::{FinancialSummary, Month, Summary};
+    use crate::domain::subscription::Subscription;
+    use crate::domain::user::UserId;
+    use crate::mocks;
+
+    fn cmd(
+        id: &str,
+        event: &str,
+        transaction_id: &str,
+        reference: &str,
+        amount: f64,
+    ) -> NotifyCommand {
+        NotifyCommand {
+            id: id.to_owned(),
+            event: event.to_owned(),
+            transaction_id: transaction_id.to_owned(),
+            reference: reference.to_owned(),
+            amount,
+        }
+    }
+
+    #[tokio::test]
+    async fn charge() {
+        let c = mocks::container();
+        let uc = Notify::new(
+            c.event_pub(),
+            c.notification_repo(),
+            c.payout_repo(),
+            c.subscription_repo(),
+        );
+
+        let mut subscription = Subscription::new(
+            SubscriptionId::new("#subscription01").unwrap(),
+            mocks::user1().id().clone(),
+            mocks::plan1(),
+        )
+        .unwrap();
+        c.subscription_repo().save(&mut subscription).await.unwrap();
+
+        assert!(uc
+            .exec(cmd(
+                "#n01",
+                "charge.paid",
+                "#tx01",
+                "#subscription01",
+                250.0
+            ))
+            .await
+            .is_err());
+        assert!(uc
+            .exec(cmd(
+                "#n01",
+                "charge.succeeded",
+                "#tx01",
+                "#subscription01",
+                25.0
+            ))
+            .await
+            .is_err());
+        let notification = c
+            .notification_repo()
+            .find_by_id(&NotificationId::new("#n01").unwrap())
+            .await
+            .unwrap();
+        assert!(notification.error().is_some());
+        assert!(notification.processed_at().is_none());
+
+        uc.exec(cmd(
+            "#n01",
+            "charge.succeeded",
+            "#tx01",
+            "#subscription01",
+            250.0,
+        ))
+        .await
+        .unwrap();
+        let subscription = c
+            .subscription_repo()
+            .find_by_id(&SubscriptionId::new("#subscription01").unwrap())
+            .await
+            .unwrap();
+        assert!(subscription.is_active());
+
+        // Replayed
+        let err = uc
+            .exec(cmd(
+                "#n01",
+                "charge.succeeded",
+                "#tx01",
+                "#subscription01",
+                250.0,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "already_processed");
+
+        let events = c.event_pub().events().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].code(), "paid");
+
+        // A failed renewal keeps the subscription.
+        let mut subscription = c
+            .subscription_repo()
+            .find_by_id(&SubscriptionId::new("#subscription01").unwrap())
+            .await
+            .unwrap();
+        subscription.renew().unwrap();
+        c.subscription_repo().save(&mut subscription).await.unwrap();
+
+        uc.exec(cmd(
+            "#n02",
+            "charge.failed",
+            "#tx02",
+            "#subscription01",
+            250.0,
+        ))
+        .await
+        .unwrap();
+        let subscription = c
+            .subscription_repo()
+            .find_by_id(&SubscriptionId::new("#subscription01").unwrap())
+            .await
+            .unwrap();
+        assert!(subscription.is_active());
+        assert!(subscription.is_open());
+        assert_eq!(
+            subscription.status_history().current().status().to_string(),
+            "payment_failed"
+        );
+    }
+
+    #[tokio::test]
+    async fn payout() {
+        let c = mocks::container();
+        let uc = Notify::new(
+            c.event_pub(),
+            c.notification_repo(),
+            c.payout_repo(),
+            c.subscription_repo(),
+        );
+
+        let mut payout = Payout::new(
+            Month::new(2020, 10).unwrap(),
+            FinancialSummary::build(250.0, 1),
+            vec![Summary::new(
+                ContractId::new("#contract01").unwrap(),
+                mocks::publication1(),
+                mocks::publication1().statistics().clone(),
+            )],
+        )
+        .unwrap();
+        let user_id = UserId::new("#user01").unwrap();
+        payout.send_line(&user_id, "#tx01").unwrap();
+        c.payout_repo().save(&mut payout).await.unwrap();
+
+        uc.exec(cmd(
+            "#n01",
+            "payout.failed",
+            "#tx01",
+            "2020-10:#user01",
+            250.0,
+        ))
+        .await
+        .unwrap();
+        assert!(uc
+            .exec(cmd(
+                "#n02",
+                "payout.succeeded",
+                "#tx01",
+                "2020-10:#user01",
+                250.0
+            ))
+            .await
+            .is_err());
+
+        let mut payout = c
+            .payout_repo()
+            .find_by_id(payout.base().id())
+            .await
+            .unwrap();
+        payout.send_line(&user_id, "#tx02").unwrap();
+        c.payout_repo().save(&mut payout).await.unwrap();
+
+        uc.exec(cmd(
+            "#n02",
+            "payout.succeeded",
+            "#tx02",
+            "2020-10:#user01",
+            250.0,
+        ))
+        .await
+        .unwrap();
+        let payout = c
+            .payout_repo()
+            .find_by_id(payout.base().id())
+            .await
+            .unwrap();
+        assert!(payout.is_paid());
+    }
+}
@@ -1,2 +1,6 @@
 pub mod contract;
 pub mod dtos;
+pub mod gateway;
+pub mod payout;
+pub mod plan;
+pub mod subscription;
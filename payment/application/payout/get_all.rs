@@ -0,0 +1,40 @@
+use serde::Serialize;
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::application::dtos::PayoutDto;
+use crate::domain::admin::{AdminId, AdminRepository};
+use crate::domain::payout::PayoutRepository;
+
+#[derive(Serialize)]
+pub struct GetAllResponse {
+    payouts: Vec<PayoutDto>,
+}
+
+pub struct GetAll<'a> {
+    admin_repo: &'a dyn AdminRepository,
+    payout_repo: &'a dyn PayoutRepository,
+}
+
+impl<'a> GetAll<'a> {
+    pub fn new(admin_repo: &'a dyn AdminRepository, payout_repo: &'a dyn PayoutRepository) -> Self {
+        GetAll {
+            admin_repo,
+            payout_repo,
+        }
+    }
+
+    pub async fn exec(&self, admin_id: String) -> Result<GetAllResponse> {
+        let admin = self.admin_repo.find_by_id(&AdminId::new(admin_id)?).await?;
+        if !admin.can_manage_payouts("R") {
+            return Err(Error::unauthorized());
+        }
+
+        let payouts = self.payout_repo.find_all().await?;
+
+        Ok(GetAllResponse {
+            payouts: payouts.iter().map(PayoutDto::from).collect(),
+        })
+    }
+}
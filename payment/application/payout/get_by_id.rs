@@ -0,0 +1,35 @@
+use common::error::Error;
+use common::result::Result;
+
+use crate::application::dtos::PayoutDto;
+use crate::domain::admin::{AdminId, AdminRepository};
+use crate::domain::payout::{PayoutId, PayoutRepository};
+
+pub struct GetById<'a> {
+    admin_repo: &'a dyn AdminRepository,
+    payout_repo: &'a dyn PayoutRepository,
+}
+
+impl<'a> GetById<'a> {
+    pub fn new(admin_repo: &'a dyn AdminRepository, payout_repo: &'a dyn PayoutRepository) -> Self {
+        GetById {
+            admin_repo,
+            payout_repo,
+        }
+    }
+
+    // The id of a payout is its month (2020-10).
+    pub async fn exec(&self, admin_id: String, payout_id: String) -> Result<PayoutDto> {
+        let admin = self.admin_repo.find_by_id(&AdminId::new(admin_id)?).await?;
+        if !admin.can_manage_payouts("R") {
+            return Err(Error::unauthorized());
+        }
+
+        let payout = self
+            .payout_repo
+            .find_by_id(&PayoutId::new(payout_id)?)
+            .await?;
+
+        Ok(PayoutDto::from(&payout))
+    }
+}
@@ -0,0 +1,8 @@
+mod get_all;
+mod get_by_id;
+mod pay;
+mod run;
+pub use get_all::*;
+pub use get_by_id::*;
+pub use pay::*;
+pub use run::*;
@@ -0,0 +1,161 @@
+use serde::Serialize;
+
+use common::error::Error;
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::domain::admin::{AdminId, AdminRepository};
+use crate::domain::gateway::PaymentGateway;
+use crate::domain::payout::{PayoutId, PayoutRepository};
+
+#[derive(Serialize)]
+pub struct PayResponse {
+    sent: usize,
+}
+
+// Sends the pending and failed lines of a payout to the payment gateway. They are paid when the
+// provider notifies it.
+pub struct Pay<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    admin_repo: &'a dyn AdminRepository,
+    payout_repo: &'a dyn PayoutRepository,
+
+    payment_gateway: &'a dyn PaymentGateway,
+}
+
+impl<'a> Pay<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        admin_repo: &'a dyn AdminRepository,
+        payout_repo: &'a dyn PayoutRepository,
+        payment_gateway: &'a dyn PaymentGateway,
+    ) -> Self {
+        Pay {
+            event_pub,
+            admin_repo,
+            payout_repo,
+            payment_gateway,
+        }
+    }
+
+    pub async fn exec(&self, admin_id: String, payout_id: String) -> Result<PayResponse> {
+        let admin = self.admin_repo.find_by_id(&AdminId::new(admin_id)?).await?;
+        if !admin.can_manage_payouts("U") {
+            return Err(Error::unauthorized());
+        }
+
+        let mut payout = self
+            .payout_repo
+            .find_by_id(&PayoutId::new(payout_id)?)
+            .await?;
+
+        let lines: Vec<_> = payout
+            .lines()
+            .iter()
+            .filter(|line| line.can_be_sent())
+            .map(|line| (line.author_id().clone(), line.amount()))
+            .collect();
+
+        // Lines already sent are saved even if a later one fails, so they are not sent twice.
+        let mut res = Ok(());
+        let mut sent = 0;
+        for (author_id, amount) in lines.iter() {
+            match self
+                .payment_gateway
+                .payout(author_id, &payout.line_reference(author_id), *amount)
+                .await
+            {
+                Ok(transaction) => {
+                    payout.send_line(author_id, transaction.id())?;
+                    sent += 1;
+                }
+                Err(err) => {
+                    res = Err(err);
+                    break;
+                }
+            }
+        }
+
+        if sent > 0 {
+            self.payout_repo.save(&mut payout).await?;
+
+            self.event_pub.publish_all(payout.base().events()?).await?;
+        }
+
+        res.map(|_| PayResponse { sent })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::admin::Admin;
+    use crate::domain::contract::ContractId;
+    use crate::domain::payout::{FinancialSummary, LineStatus, Month, Payout, Summary};
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn pay() {
+        let c = mocks::container();
+        let uc = Pay::new(
+            c.event_pub(),
+            c.admin_repo(),
+            c.payout_repo(),
+            c.payment_gateway(),
+        );
+
+        let mut admin = mocks::admin1();
+        c.admin_repo().save(&mut admin).await.unwrap();
+
+        let mut payout = Payout::new(
+            Month::new(2020, 10).unwrap(),
+            FinancialSummary::build(250.0, 1),
+            vec![Summary::new(
+                ContractId::new("#contract01").unwrap(),
+                mocks::publication1(),
+                mocks::publication1().statistics().clone(),
+            )],
+        )
+        .unwrap();
+        c.payout_repo().save(&mut payout).await.unwrap();
+
+        assert!(uc
+            .exec("#user01".to_owned(), "2020-10".to_owned())
+            .await
+            .is_err());
+
+        // Admins of contracts that cannot pay payouts.
+        let mut admin = Admin::new(AdminId::new("#admin02").unwrap(), "CR").unwrap();
+        c.admin_repo().save(&mut admin).await.unwrap();
+        match uc.exec("#admin02".to_owned(), "2020-10".to_owned()).await {
+            Err(err) => assert_eq!(err.code(), "unauthorized"),
+            Ok(_) => panic!("payout paid without permission"),
+        }
+
+        let res = uc
+            .exec("#admin01".to_owned(), "2020-10".to_owned())
+            .await
+            .unwrap();
+        assert_eq!(res.sent, 1);
+
+        let payout = c
+            .payout_repo()
+            .find_by_id(payout.base().id())
+            .await
+            .unwrap();
+        assert!(matches!(
+            payout.lines()[0].status(),
+            LineStatus::Sent { transaction_id } if transaction_id == "fake_payout_000001"
+        ));
+
+        // Already sent
+        let res = uc
+            .exec("#admin01".to_owned(), "2020-10".to_owned())
+            .await
+            .unwrap();
+        assert_eq!(res.sent, 0);
+        assert_eq!(c.event_pub().events().await.len(), 1);
+    }
+}
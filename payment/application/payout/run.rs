@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+
+use chrono::Utc;
+
+use common::error::Error;
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::application::dtos::PayoutDto;
+use crate::domain::admin::{AdminId, AdminRepository};
+use crate::domain::contract::ContractRepository;
+use crate::domain::payout::{FinancialSummary, Month, Payout, PayoutId, PayoutRepository, Summary};
+use crate::domain::publication::{Publication, PublicationRepository};
+use crate::domain::subscription::SubscriptionRepository;
+
+#[derive(Deserialize)]
+pub struct RunCommand {
+    // The previous month by default (2020-10).
+    pub month: Option<String>,
+    // Calculates the payout without saving it.
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct RunResponse {
+    // False when the payout of the month had already been generated, which is returned as it is.
+    generated: bool,
+    dry_run: bool,
+    payout: PayoutDto,
+}
+
+// Distributes the income of the subscriptions paid in a month among the authors of the
+// publications whose contracts were approved at the end of the month, according to their
+// statistics in the month. Running it again for the same month gives the same result.
+pub struct Run<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    admin_repo: &'a dyn AdminRepository,
+    contract_repo: &'a dyn ContractRepository,
+    payout_repo: &'a dyn PayoutRepository,
+    publication_repo: &'a dyn PublicationRepository,
+    subscription_repo: &'a dyn SubscriptionRepository,
+}
+
+impl<'a> Run<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        admin_repo: &'a dyn AdminRepository,
+        contract_repo: &'a dyn ContractRepository,
+        payout_repo: &'a dyn PayoutRepository,
+        publication_repo: &'a dyn PublicationRepository,
+        subscription_repo: &'a dyn SubscriptionRepository,
+    ) -> Self {
+        Run {
+            event_pub,
+            admin_repo,
+            contract_repo,
+            payout_repo,
+            publication_repo,
+            subscription_repo,
+        }
+    }
+
+    pub async fn exec(&self, admin_id: String, cmd: RunCommand) -> Result<RunResponse> {
+        let admin = self.admin_repo.find_by_id(&AdminId::new(admin_id)?).await?;
+        if !admin.can_manage_payouts("C") {
+            return Err(Error::unauthorized());
+        }
+
+        let month = match cmd.month {
+            Some(month) => month.parse::<Month>()?,
+            None => Month::previous_to(&Utc::now())?,
+        };
+        if month.end() > Utc::now() {
+            return Err(Error::new("payout", "month_not_ended"));
+        }
+        let dry_run = cmd.dry_run.unwrap_or(false);
+
+        if !dry_run {
+            if let Ok(payout) = self
+                .payout_repo
+                .find_by_id(&PayoutId::new(month.to_string())?)
+                .await
+            {
+                return Ok(RunResponse {
+                    generated: false,
+                    dry_run,
+                    payout: PayoutDto::from(&payout),
+                });
+            }
+        }
+
+        let subscriptions = self.subscription_repo.find_all().await?;
+        let financial_summary = FinancialSummary::new(&month, &subscriptions);
+
+        // Contracts approved at the end of the month, even if they were cancelled later, with the
+        // statistics their publications had until then.
+        let mut summaries = Vec::new();
+        for contract in self.contract_repo.find_all().await? {
+            if !contract.was_approved_at(&month.end()) {
+                continue;
+            }
+
+            let publication_id = contract.publication().id();
+            let publication = Publication::new(
+                publication_id.clone(),
+                contract.publication().author().clone(),
+                self.publication_repo
+                    .find_statistics(publication_id, None, &month.end())
+                    .await?,
+            )?;
+            let statistics = self
+                .publication_repo
+                .find_statistics(publication_id, Some(&month.start()), &month.end())
+                .await?;
+
+            summaries.push(Summary::new(
+                contract.base().id().clone(),
+                publication,
+                statistics,
+            ));
+        }
+
+        let mut payout = Payout::new(month, financial_summary, summaries)?;
+
+        if !dry_run {
+            self.payout_repo.save(&mut payout).await?;
+
+            self.event_pub.publish_all(payout.base().events()?).await?;
+        }
+
+        Ok(RunResponse {
+            generated: true,
+            dry_run,
+            payout: PayoutDto::from(&payout),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+
+    use common::model::{AggregateRoot, StatusHistory, StatusItem};
+
+    use crate::domain::contract::{Contract, ContractId, Status as ContractStatus};
+    use crate::domain::subscription::{Status, Subscription, SubscriptionId};
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn run() {
+        let c = mocks::container();
+        let uc = Run::new(
+            c.event_pub(),
+            c.admin_repo(),
+            c.contract_repo(),
+            c.payout_repo(),
+            c.publication_repo(),
+            c.subscription_repo(),
+        );
+
+        let mut admin = mocks::admin1();
+        c.admin_repo().save(&mut admin).await.unwrap();
+
+        let month = Month::previous_to(&Utc::now()).unwrap();
+        let date = month.start() + Duration::days(1);
+
+        // Approved in the previous month, and cancelled after it.
+        let mut publication = mocks::publication1();
+        c.publication_repo().save(&mut publication).await.unwrap();
+        let mut contract = Contract::build(
+            AggregateRoot::new(ContractId::new("#contract01").unwrap()),
+            publication,
+            StatusHistory::build(vec![
+                StatusItem::build(ContractStatus::Requested, date),
+                StatusItem::build(
+                    ContractStatus::Approved {
+                        admin_id: admin.base().id().clone(),
+                    },
+                    date,
+                ),
+                StatusItem::build(ContractStatus::Cancelled, month.end() + Duration::hours(1)),
+            ])
+            .unwrap(),
+        );
+        c.contract_repo().save(&mut contract).await.unwrap();
+
+        // Approved after the previous month.
+        let mut publication = mocks::publication2();
+        c.publication_repo().save(&mut publication).await.unwrap();
+        let mut contract = Contract::build(
+            AggregateRoot::new(ContractId::new("#contract02").unwrap()),
+            publication,
+            StatusHistory::build(vec![
+                StatusItem::build(ContractStatus::Requested, date),
+                StatusItem::build(
+                    ContractStatus::Approved {
+                        admin_id: admin.base().id().clone(),
+                    },
+                    month.end() + Duration::hours(1),
+                ),
+            ])
+            .unwrap(),
+        );
+        c.contract_repo().save(&mut contract).await.unwrap();
+
+        // Paid in the previous month.
+        let mut subscription = Subscription::build(
+            AggregateRoot::new(SubscriptionId::new("#subscription01").unwrap()),
+            mocks::user1().id().clone(),
+            mocks::plan1(),
+            StatusHistory::build(vec![
+                StatusItem::build(Status::PendingPayment, date),
+                StatusItem::build(Status::Paid { amount: 250.0 }, date),
+            ])
+            .unwrap(),
+            Some(date + Duration::days(30)),
+        );
+        c.subscription_repo().save(&mut subscription).await.unwrap();
+
+        let cmd = |month: Option<Month>, dry_run| RunCommand {
+            month: month.map(|month| month.to_string()),
+            dry_run: Some(dry_run),
+        };
+
+        // Not an admin
+        assert!(uc
+            .exec("#user01".to_owned(), cmd(None, false))
+            .await
+            .is_err());
+
+        // The current month has not ended.
+        let current = Month::previous_to(&(month.end() + Duration::days(40))).unwrap();
+        assert!(uc
+            .exec("#admin01".to_owned(), cmd(Some(current), false))
+            .await
+            .is_err());
+
+        let res = uc
+            .exec("#admin01".to_owned(), cmd(None, true))
+            .await
+            .unwrap();
+        assert!(res.generated);
+        assert!(res.dry_run);
+        assert_eq!(res.payout.income, 250.0);
+        assert_eq!(res.payout.summaries.len(), 1);
+        assert_eq!(res.payout.summaries[0].contract_id, "#contract01");
+        assert_eq!(res.payout.lines.len(), 1);
+        assert_eq!(res.payout.lines[0].amount, 250.0);
+        assert!(c.event_pub().events().await.is_empty());
+        assert!(c.payout_repo().find_all().await.unwrap().is_empty());
+
+        let res = uc
+            .exec("#admin01".to_owned(), cmd(None, false))
+            .await
+            .unwrap();
+        assert!(res.generated);
+        assert_eq!(res.payout.amount, 250.0);
+        assert_eq!(c.event_pub().events().await.len(), 2);
+
+        // The payout of a month is generated once.
+        let res = uc
+            .exec("#admin01".to_owned(), cmd(None, false))
+            .await
+            .unwrap();
+        assert!(!res.generated);
+        assert_eq!(res.payout.amount, 250.0);
+        assert_eq!(c.event_pub().events().await.len(), 2);
+
+        // Nothing was paid two months ago.
+        let res = uc
+            .exec(
+                "#admin01".to_owned(),
+                cmd(Some(Month::previous_to(&month.start()).unwrap()), false),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.payout.income, 0.0);
+        assert!(res.payout.lines.is_empty());
+    }
+}
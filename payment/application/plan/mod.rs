@@ -0,0 +1,2 @@
+mod get_all;
+pub use get_all::*;
@@ -0,0 +1,46 @@
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::domain::admin::{AdminId, AdminRepository};
+use crate::domain::subscription::{SubscriptionId, SubscriptionRepository};
+
+pub struct Cancel<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    admin_repo: &'a dyn AdminRepository,
+    subscription_repo: &'a dyn SubscriptionRepository,
+}
+
+impl<'a> Cancel<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        admin_repo: &'a dyn AdminRepository,
+        subscription_repo: &'a dyn SubscriptionRepository,
+    ) -> Self {
+        Cancel {
+            event_pub,
+            admin_repo,
+            subscription_repo,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, subscription_id: String) -> Result<()> {
+        let subscription_id = SubscriptionId::new(subscription_id)?;
+        let mut subscription = self.subscription_repo.find_by_id(&subscription_id).await?;
+
+        // The subscriber or an admin can cancel it.
+        if subscription.user_id().value() != auth_id {
+            self.admin_repo.find_by_id(&AdminId::new(auth_id)?).await?;
+        }
+
+        subscription.cancel()?;
+
+        self.subscription_repo.save(&mut subscription).await?;
+
+        self.event_pub
+            .publish_all(subscription.base().events()?)
+            .await?;
+
+        Ok(())
+    }
+}
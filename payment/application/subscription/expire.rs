@@ -0,0 +1,120 @@
+use serde::Serialize;
+
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::domain::admin::{AdminId, AdminRepository};
+use crate::domain::subscription::SubscriptionRepository;
+
+#[derive(Serialize)]
+pub struct ExpireResponse {
+    expired: usize,
+}
+
+// Expires the subscriptions whose paid period ended without being renewed.
+pub struct Expire<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    admin_repo: &'a dyn AdminRepository,
+    subscription_repo: &'a dyn SubscriptionRepository,
+}
+
+impl<'a> Expire<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        admin_repo: &'a dyn AdminRepository,
+        subscription_repo: &'a dyn SubscriptionRepository,
+    ) -> Self {
+        Expire {
+            event_pub,
+            admin_repo,
+            subscription_repo,
+        }
+    }
+
+    pub async fn exec(&self, admin_id: String) -> Result<ExpireResponse> {
+        self.admin_repo.find_by_id(&AdminId::new(admin_id)?).await?;
+
+        let mut subscriptions = self.subscription_repo.find_by_status("paid").await?;
+        subscriptions.extend(
+            self.subscription_repo
+                .find_by_status("pending_payment")
+                .await?,
+        );
+
+        let mut expired = 0;
+        for mut subscription in subscriptions.into_iter() {
+            // New subscriptions waiting for their first payment are kept.
+            if subscription.paid_until().is_none() || subscription.is_active() {
+                continue;
+            }
+
+            subscription.expire()?;
+
+            self.subscription_repo.save(&mut subscription).await?;
+
+            self.event_pub
+                .publish_all(subscription.base().events()?)
+                .await?;
+
+            expired += 1;
+        }
+
+        Ok(ExpireResponse { expired })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{Duration, Utc};
+
+    use common::model::{AggregateRoot, StatusHistory};
+
+    use crate::domain::subscription::{Status, Subscription, SubscriptionId};
+    use crate::mocks;
+
+    fn subscription(id: &str, status: Status, paid_until: Option<i64>) -> Subscription {
+        Subscription::build(
+            AggregateRoot::new(SubscriptionId::new(id).unwrap()),
+            mocks::user1().id().clone(),
+            mocks::plan1(),
+            StatusHistory::new(status),
+            paid_until.map(|days| Utc::now() + Duration::days(days)),
+        )
+    }
+
+    #[tokio::test]
+    async fn expire() {
+        let c = mocks::container();
+        let uc = Expire::new(c.event_pub(), c.admin_repo(), c.subscription_repo());
+
+        let mut admin = mocks::admin1();
+        c.admin_repo().save(&mut admin).await.unwrap();
+
+        for mut subscription in [
+            subscription("#subscription01", Status::Paid { amount: 250.0 }, Some(-1)),
+            subscription("#subscription02", Status::PendingPayment, Some(-1)),
+            subscription("#subscription03", Status::Paid { amount: 250.0 }, Some(1)),
+            subscription("#subscription04", Status::PendingPayment, None),
+            subscription("#subscription05", Status::Cancelled, Some(-1)),
+        ] {
+            c.subscription_repo().save(&mut subscription).await.unwrap();
+        }
+
+        assert!(uc.exec("#user01".to_owned()).await.is_err());
+
+        let res = uc.exec("#admin01".to_owned()).await.unwrap();
+        assert_eq!(res.expired, 2);
+        assert_eq!(
+            c.subscription_repo()
+                .find_by_status("expired")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(c.event_pub().events().await.len(), 2);
+    }
+}
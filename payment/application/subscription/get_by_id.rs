@@ -0,0 +1,34 @@
+use common::result::Result;
+
+use crate::application::dtos::SubscriptionDto;
+use crate::domain::admin::{AdminId, AdminRepository};
+use crate::domain::subscription::{SubscriptionId, SubscriptionRepository};
+
+pub struct GetById<'a> {
+    admin_repo: &'a dyn AdminRepository,
+    subscription_repo: &'a dyn SubscriptionRepository,
+}
+
+impl<'a> GetById<'a> {
+    pub fn new(
+        admin_repo: &'a dyn AdminRepository,
+        subscription_repo: &'a dyn SubscriptionRepository,
+    ) -> Self {
+        GetById {
+            admin_repo,
+            subscription_repo,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, subscription_id: String) -> Result<SubscriptionDto> {
+        let subscription_id = SubscriptionId::new(subscription_id)?;
+        let subscription = self.subscription_repo.find_by_id(&subscription_id).await?;
+
+        // Only the subscriber and the admins can see the subscription.
+        if subscription.user_id().value() != auth_id {
+            self.admin_repo.find_by_id(&AdminId::new(auth_id)?).await?;
+        }
+
+        Ok(SubscriptionDto::from(&subscription))
+    }
+}
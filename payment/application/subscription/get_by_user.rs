@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use common::result::Result;
+
+use crate::application::dtos::SubscriptionDto;
+use crate::domain::admin::{AdminId, AdminRepository};
+use crate::domain::subscription::SubscriptionRepository;
+use crate::domain::user::UserId;
+
+#[derive(Deserialize)]
+pub struct GetByUserCommand {
+    // The authenticated user by default.
+    pub user_id: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct GetByUserResponse {
+    subscriptions: Vec<SubscriptionDto>,
+}
+
+pub struct GetByUser<'a> {
+    admin_repo: &'a dyn AdminRepository,
+    subscription_repo: &'a dyn SubscriptionRepository,
+}
+
+impl<'a> GetByUser<'a> {
+    pub fn new(
+        admin_repo: &'a dyn AdminRepository,
+        subscription_repo: &'a dyn SubscriptionRepository,
+    ) -> Self {
+        GetByUser {
+            admin_repo,
+            subscription_repo,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, cmd: GetByUserCommand) -> Result<GetByUserResponse> {
+        let user_id = cmd.user_id.unwrap_or_else(|| auth_id.clone());
+        if user_id != auth_id {
+            self.admin_repo.find_by_id(&AdminId::new(auth_id)?).await?;
+        }
+
+        let mut subscriptions = self
+            .subscription_repo
+            .find_by_user_id(&UserId::new(user_id)?)
+            .await?;
+        subscriptions.reverse();
+
+        Ok(GetByUserResponse {
+            subscriptions: subscriptions.iter().map(SubscriptionDto::from).collect(),
+        })
+    }
+}
@@ -0,0 +1,12 @@
+mod cancel;
+mod expire;
+mod get_by_id;
+mod get_by_user;
+mod renew;
+mod subscribe;
+pub use cancel::*;
+pub use expire::*;
+pub use get_by_id::*;
+pub use get_by_user::*;
+pub use renew::*;
+pub use subscribe::*;
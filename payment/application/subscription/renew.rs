@@ -0,0 +1,55 @@
+use common::error::Error;
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::domain::gateway::PaymentGateway;
+use crate::domain::subscription::{SubscriptionId, SubscriptionRepository};
+
+pub struct Renew<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    subscription_repo: &'a dyn SubscriptionRepository,
+
+    payment_gateway: &'a dyn PaymentGateway,
+}
+
+impl<'a> Renew<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        subscription_repo: &'a dyn SubscriptionRepository,
+        payment_gateway: &'a dyn PaymentGateway,
+    ) -> Self {
+        Renew {
+            event_pub,
+            subscription_repo,
+            payment_gateway,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, subscription_id: String) -> Result<()> {
+        let subscription_id = SubscriptionId::new(subscription_id)?;
+        let mut subscription = self.subscription_repo.find_by_id(&subscription_id).await?;
+
+        if subscription.user_id().value() != auth_id {
+            return Err(Error::unauthorized());
+        }
+
+        subscription.renew()?;
+
+        self.payment_gateway
+            .charge(
+                subscription.user_id(),
+                subscription.base().id().value(),
+                subscription.plan().price(),
+            )
+            .await?;
+
+        self.subscription_repo.save(&mut subscription).await?;
+
+        self.event_pub
+            .publish_all(subscription.base().events()?)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+use common::error::Error;
+use common::event::EventPublisher;
+use common::result::Result;
+
+use crate::domain::gateway::PaymentGateway;
+use crate::domain::plan::{PlanId, PlanRepository};
+use crate::domain::subscription::{Subscription, SubscriptionRepository};
+use crate::domain::user::UserId;
+
+#[derive(Deserialize)]
+pub struct SubscribeCommand {
+    pub plan_id: String,
+}
+
+#[derive(Serialize)]
+pub struct SubscribeResponse {
+    id: String,
+}
+
+pub struct Subscribe<'a> {
+    event_pub: &'a dyn EventPublisher,
+
+    plan_repo: &'a dyn PlanRepository,
+    subscription_repo: &'a dyn SubscriptionRepository,
+
+    payment_gateway: &'a dyn PaymentGateway,
+}
+
+impl<'a> Subscribe<'a> {
+    pub fn new(
+        event_pub: &'a dyn EventPublisher,
+        plan_repo: &'a dyn PlanRepository,
+        subscription_repo: &'a dyn SubscriptionRepository,
+        payment_gateway: &'a dyn PaymentGateway,
+    ) -> Self {
+        Subscribe {
+            event_pub,
+            plan_repo,
+            subscription_repo,
+            payment_gateway,
+        }
+    }
+
+    pub async fn exec(&self, auth_id: String, cmd: SubscribeCommand) -> Result<SubscribeResponse> {
+        let user_id = UserId::new(auth_id)?;
+        let plan = self
+            .plan_repo
+            .find_by_id(&PlanId::new(cmd.plan_id)?)
+            .await?;
+
+        let subscriptions = self.subscription_repo.find_by_user_id(&user_id).await?;
+        if let Some(subscription) = subscriptions.last() {
+            if subscription.is_open() {
+                return Err(Error::new("subscription", "already_exists"));
+            }
+        }
+
+        let mut subscription =
+            Subscription::new(self.subscription_repo.next_id().await?, user_id, plan)?;
+
+        // It is paid when the provider notifies the charge.
+        self.payment_gateway
+            .charge(
+                subscription.user_id(),
+                subscription.base().id().value(),
+                subscription.plan().price(),
+            )
+            .await?;
+
+        self.subscription_repo.save(&mut subscription).await?;
+
+        self.event_pub
+            .publish_all(subscription.base().events()?)
+            .await?;
+
+        Ok(SubscribeResponse {
+            id: subscription.base().id().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::subscription::SubscriptionId;
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn subscribe() {
+        let c = mocks::container();
+        let uc = Subscribe::new(
+            c.event_pub(),
+            c.plan_repo(),
+            c.subscription_repo(),
+            c.payment_gateway(),
+        );
+
+        let mut plan = mocks::plan1();
+        c.plan_repo().save(&mut plan).await.unwrap();
+
+        let cmd = |plan_id: &str| SubscribeCommand {
+            plan_id: plan_id.to_owned(),
+        };
+
+        assert!(uc.exec("#user01".to_owned(), cmd("#plan02")).await.is_err());
+
+        let res = uc.exec("#user01".to_owned(), cmd("#plan01")).await.unwrap();
+        let mut subscription = c
+            .subscription_repo()
+            .find_by_id(&SubscriptionId::new(res.id).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(subscription.user_id().value(), "#user01");
+        assert_eq!(
+            subscription.status_history().current().status().to_string(),
+            "pending_payment"
+        );
+
+        // Already subscribed
+        assert!(uc.exec("#user01".to_owned(), cmd("#plan01")).await.is_err());
+
+        // It can subscribe again after cancelling it.
+        subscription.cancel().unwrap();
+        c.subscription_repo().save(&mut subscription).await.unwrap();
+        assert!(uc.exec("#user01".to_owned(), cmd("#plan01")).await.is_ok());
+
+        let events = c.event_pub().events().await;
+        assert_eq!(events[0].topic(), "subscription");
+        assert_eq!(events[0].code(), "created");
+    }
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use common::event::{EventPublisher, EventSubscriber};
+use common::result::Result;
+
+use crate::domain::admin::AdminRepository;
+use crate::domain::contract::ContractRepository;
+use crate::domain::gateway::{NotificationRepository, PaymentGateway};
+use crate::domain::payout::PayoutRepository;
+use crate::domain::plan::PlanRepository;
+use crate::domain::publication::PublicationRepository;
+use crate::domain::subscription::SubscriptionRepository;
+
+pub struct Container<EPub> {
+    event_pub: Arc<EPub>,
+
+    admin_repo: Arc<dyn AdminRepository>,
+    contract_repo: Arc<dyn ContractRepository>,
+    notification_repo: Arc<dyn NotificationRepository>,
+    payout_repo: Arc<dyn PayoutRepository>,
+    plan_repo: Arc<dyn PlanRepository>,
+    publication_repo: Arc<dyn PublicationRepository>,
+    subscription_repo: Arc<dyn SubscriptionRepository>,
+
+    payment_gateway: Arc<dyn PaymentGateway>,
+}
+
+impl<EPub> Container<EPub>
+where
+    EPub: EventPublisher,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        event_pub: Arc<EPub>,
+        admin_repo: Arc<dyn AdminRepository>,
+        contract_repo: Arc<dyn ContractRepository>,
+        notification_repo: Arc<dyn NotificationRepository>,
+        payout_repo: Arc<dyn PayoutRepository>,
+        plan_repo: Arc<dyn PlanRepository>,
+        publication_repo: Arc<dyn PublicationRepository>,
+        subscription_repo: Arc<dyn SubscriptionRepository>,
+        payment_gateway: Arc<dyn PaymentGateway>,
+    ) -> Self {
+        Container {
+            event_pub,
+            admin_repo,
+            contract_repo,
+            notification_repo,
+            payout_repo,
+            plan_repo,
+            publication_repo,
+            subscription_repo,
+            payment_gateway,
+        }
+    }
+
+    pub async fn subscribe<ES>(&self, _event_sub: &ES) -> Result<()>
+    where
+        ES: EventSubscriber,
+    {
+        Ok(())
+    }
+
+    pub fn event_pub(&self) -> &EPub {
+        &self.event_pub
+    }
+
+    pub fn admin_repo(&self) -> &dyn AdminRepository {
+        self.admin_repo.as_ref()
+    }
+
+    pub fn contract_repo(&self) -> &dyn ContractRepository {
+        self.contract_repo.as_ref()
+    }
+
+    pub fn notification_repo(&self) -> &dyn NotificationRepository {
+        self.notification_repo.as_ref()
+    }
+
+    pub fn payout_repo(&self) -> &dyn PayoutRepository {
+        self.payout_repo.as_ref()
+    }
+
+    pub fn plan_repo(&self) -> &dyn PlanRepository {
+        self.plan_repo.as_ref()
+    }
+
+    pub fn publication_repo(&self) -> &dyn PublicationRepository {
+        self.publication_repo.as_ref()
+    }
+
+    pub fn subscription_repo(&self) -> &dyn SubscriptionRepository {
+        self.subscription_repo.as_ref()
+    }
+
+    pub fn payment_gateway(&self) -> &dyn PaymentGateway {
+        self.payment_gateway.as_ref()
+    }
+}
@@ -10,16 +10,29 @@ pub type AdminId = StringId;
 #[derive(Debug, Clone)]
 pub struct Admin {
     base: AggregateRoot<AdminId, Event>,
+    // What the admin can do with payouts: "C" to run them, "R" to see them and "U" to pay them.
+    payout_permissions: String,
 }
 
 impl Admin {
-    pub fn new(id: AdminId) -> Result<Self> {
+    pub fn new<S: Into<String>>(id: AdminId, payout_permissions: S) -> Result<Self> {
         Ok(Admin {
             base: AggregateRoot::new(id),
+            payout_permissions: payout_permissions.into(),
         })
     }
 
     pub fn base(&self) -> &AggregateRoot<AdminId, Event> {
         &self.base
     }
+
+    pub fn payout_permissions(&self) -> &str {
+        &self.payout_permissions
+    }
+
+    pub fn can_manage_payouts(&self, permissions: &str) -> bool {
+        permissions
+            .chars()
+            .all(|permission| self.payout_permissions.contains(permission))
+    }
 }
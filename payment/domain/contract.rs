@@ -3,10 +3,12 @@ mod status;
 pub use repository::*;
 pub use status::*;
 
+use chrono::{DateTime, Utc};
+
 use common::error::Error;
-use common::event::Event;
 use common::model::{AggregateRoot, StatusHistory, StringId};
 use common::result::Result;
+use shared::event::ContractEvent;
 
 use crate::domain::admin::Admin;
 use crate::domain::publication::Publication;
@@ -15,7 +17,7 @@ pub type ContractId = StringId;
 
 #[derive(Debug, Clone)]
 pub struct Contract {
-    base: AggregateRoot<ContractId, Event>,
+    base: AggregateRoot<ContractId, ContractEvent>,
     publication: Publication,
     status_history: StatusHistory<Status>,
 }
@@ -26,17 +28,41 @@ impl Contract {
             return Err(Error::new("contract", "publication_has_low_views"));
         }
 
-        Ok(Contract {
+        let mut contract = Contract {
             base: AggregateRoot::new(id),
             publication,
             status_history: StatusHistory::new(Status::Requested),
-        })
+        };
+
+        contract.base.record_event(ContractEvent::Requested {
+            id: contract.base().id().to_string(),
+            publication_id: contract.publication().id().to_string(),
+            author_id: contract.publication().author().id().to_string(),
+        });
+
+        Ok(contract)
+    }
+
+    pub fn build(
+        base: AggregateRoot<ContractId, ContractEvent>,
+        publication: Publication,
+        status_history: StatusHistory<Status>,
+    ) -> Self {
+        Contract {
+            base,
+            publication,
+            status_history,
+        }
     }
 
-    pub fn base(&self) -> &AggregateRoot<ContractId, Event> {
+    pub fn base(&self) -> &AggregateRoot<ContractId, ContractEvent> {
         &self.base
     }
 
+    pub fn base_mut(&mut self) -> &mut AggregateRoot<ContractId, ContractEvent> {
+        &mut self.base
+    }
+
     pub fn publication(&self) -> &Publication {
         &self.publication
     }
@@ -45,6 +71,22 @@ impl Contract {
         &self.status_history
     }
 
+    // Requested or approved, so the publication cannot apply again.
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.status_history().current().status(),
+            Status::Requested | Status::Approved { .. }
+        )
+    }
+
+    // Whether it was approved at the given date, even if it was cancelled later.
+    pub fn was_approved_at(&self, date: &DateTime<Utc>) -> bool {
+        matches!(
+            self.status_history.at(date).map(|item| item.status()),
+            Some(Status::Approved { .. })
+        )
+    }
+
     pub fn approve(&mut self, admin: &Admin) -> Result<()> {
         if !matches!(self.status_history().current().status(), Status::Requested) {
             return Err(Error::new("contract", "not_requested"));
@@ -54,6 +96,13 @@ impl Contract {
             admin_id: admin.base().id().clone(),
         });
 
+        self.base.record_event(ContractEvent::Approved {
+            id: self.base().id().to_string(),
+            publication_id: self.publication().id().to_string(),
+            author_id: self.publication().author().id().to_string(),
+            content_manager_id: admin.base().id().to_string(),
+        });
+
         Ok(())
     }
 
@@ -66,12 +115,71 @@ impl Contract {
             admin_id: admin.base().id().clone(),
         });
 
+        self.base.record_event(ContractEvent::Rejected {
+            id: self.base().id().to_string(),
+            publication_id: self.publication().id().to_string(),
+            author_id: self.publication().author().id().to_string(),
+            content_manager_id: admin.base().id().to_string(),
+        });
+
         Ok(())
     }
 
     pub fn cancel(&mut self) -> Result<()> {
+        if !self.is_active() {
+            return Err(Error::new("contract", "not_active"));
+        }
+
         self.status_history.add_status(Status::Cancelled);
 
+        self.base.record_event(ContractEvent::Cancelled {
+            id: self.base().id().to_string(),
+            publication_id: self.publication().id().to_string(),
+            author_id: self.publication().author().id().to_string(),
+        });
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    #[test]
+    fn approve_and_cancel() {
+        let mut contract = Contract::new(
+            ContractId::new("#contract01").unwrap(),
+            mocks::publication1(),
+        )
+        .unwrap();
+        assert!(contract.is_active());
+
+        contract.approve(&mocks::admin1()).unwrap();
+        assert!(contract.approve(&mocks::admin1()).is_err());
+        assert!(contract.reject(&mocks::admin1()).is_err());
+        assert!(contract.is_active());
+
+        contract.cancel().unwrap();
+        assert!(!contract.is_active());
+        assert!(contract.cancel().is_err());
+
+        let events = contract.base().events().unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].topic(), "contract");
+        assert_eq!(events[0].code(), "requested");
+        assert_eq!(events[1].code(), "approved");
+        assert_eq!(events[2].code(), "cancelled");
+    }
+
+    #[test]
+    fn low_views() {
+        assert!(Contract::new(
+            ContractId::new("#contract01").unwrap(),
+            mocks::publication2()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,8 @@
+mod notification;
+mod payment_gateway;
+mod repository;
+mod transaction;
+pub use notification::*;
+pub use payment_gateway::*;
+pub use repository::*;
+pub use transaction::*;
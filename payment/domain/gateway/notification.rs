@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+
+use common::error::Error;
+use common::model::StringId;
+use common::result::Result;
+
+use crate::domain::gateway::TransactionKind;
+
+pub type NotificationId = StringId;
+
+// Result of a transaction notified by the provider.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    id: NotificationId,
+    kind: TransactionKind,
+    succeeded: bool,
+    transaction_id: String,
+    reference: String,
+    amount: f64,
+    received_at: DateTime<Utc>,
+    processed_at: Option<DateTime<Utc>>,
+    // Why it could not be processed the last time it was received.
+    error: Option<String>,
+}
+
+impl Notification {
+    // The event is notified as "charge.succeeded", "payout.failed", etc.
+    pub fn new<S: Into<String>>(
+        id: NotificationId,
+        event: &str,
+        transaction_id: S,
+        reference: S,
+        amount: f64,
+    ) -> Result<Self> {
+        let mut parts = event.splitn(2, '.');
+        let kind = parts
+            .next()
+            .and_then(|kind| kind.parse::<TransactionKind>().ok());
+        let succeeded = match parts.next() {
+            Some("succeeded") => Some(true),
+            Some("failed") => Some(false),
+            _ => None,
+        };
+
+        let (kind, succeeded) = match (kind, succeeded) {
+            (Some(kind), Some(succeeded)) => (kind, succeeded),
+            _ => {
+                return Err(Error::new("notification", "invalid_event")
+                    .add_context("event", event)
+                    .build())
+            }
+        };
+
+        Ok(Notification {
+            id,
+            kind,
+            succeeded,
+            transaction_id: transaction_id.into(),
+            reference: reference.into(),
+            amount,
+            received_at: Utc::now(),
+            processed_at: None,
+            error: None,
+        })
+    }
+
+    pub fn id(&self) -> &NotificationId {
+        &self.id
+    }
+
+    pub fn kind(&self) -> &TransactionKind {
+        &self.kind
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.succeeded
+    }
+
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    pub fn reference(&self) -> &str {
+        &self.reference
+    }
+
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    pub fn received_at(&self) -> &DateTime<Utc> {
+        &self.received_at
+    }
+
+    pub fn processed_at(&self) -> Option<&DateTime<Utc>> {
+        self.processed_at.as_ref()
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    // Only notifications that failed can be received again, since the provider retries them.
+    pub fn can_be_received_again(&self) -> bool {
+        self.error.is_some()
+    }
+
+    pub fn process(&mut self) {
+        self.processed_at = Some(Utc::now());
+        self.error = None;
+    }
+
+    pub fn fail<S: Into<String>>(&mut self, error: S) {
+        self.error = Some(error.into());
+    }
+}
@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+
+use common::result::Result;
+
+use crate::domain::gateway::Transaction;
+use crate::domain::user::UserId;
+
+// Provider that moves the money. Transactions are confirmed later by its notifications.
+#[async_trait]
+pub trait PaymentGateway: Sync + Send {
+    // Charges a subscription to its user.
+    async fn charge(&self, user_id: &UserId, reference: &str, amount: f64) -> Result<Transaction>;
+
+    // Gives back part or all of a charge.
+    async fn refund(&self, charge_id: &str, amount: f64) -> Result<Transaction>;
+
+    // Pays a payout line to its author.
+    async fn payout(&self, author_id: &UserId, reference: &str, amount: f64)
+        -> Result<Transaction>;
+}
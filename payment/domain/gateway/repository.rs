@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use common::result::Result;
+
+use crate::domain::gateway::{Notification, NotificationId};
+
+// Notifications already processed, so they are not processed again if they are replayed.
+#[async_trait]
+pub trait NotificationRepository: Sync + Send {
+    async fn find_by_id(&self, id: &NotificationId) -> Result<Notification>;
+
+    // Stores a notification before processing it, so it cannot be processed twice at the same
+    // time. It fails if it was already received, unless it could not be processed.
+    async fn reserve(&self, notification: &mut Notification) -> Result<()>;
+    async fn save(&self, notification: &mut Notification) -> Result<()>;
+}
@@ -0,0 +1,73 @@
+use std::fmt;
+use std::str::FromStr;
+
+use common::error::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionKind {
+    Charge,
+    Refund,
+    Payout,
+}
+
+impl fmt::Display for TransactionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionKind::Charge => write!(f, "charge"),
+            TransactionKind::Refund => write!(f, "refund"),
+            TransactionKind::Payout => write!(f, "payout"),
+        }
+    }
+}
+
+impl FromStr for TransactionKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "charge" => Ok(TransactionKind::Charge),
+            "refund" => Ok(TransactionKind::Refund),
+            "payout" => Ok(TransactionKind::Payout),
+            _ => Err(Error::new("transaction", "invalid_kind")),
+        }
+    }
+}
+
+// A movement of money requested to the provider. Its result is notified later through the
+// webhook, so it starts pending.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    id: String,
+    kind: TransactionKind,
+    // What is paid: the subscription of a charge, the charge of a refund or the payout line of a
+    // payout.
+    reference: String,
+    amount: f64,
+}
+
+impl Transaction {
+    pub fn new<S: Into<String>>(id: S, kind: TransactionKind, reference: S, amount: f64) -> Self {
+        Transaction {
+            id: id.into(),
+            kind,
+            reference: reference.into(),
+            amount,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn kind(&self) -> &TransactionKind {
+        &self.kind
+    }
+
+    pub fn reference(&self) -> &str {
+        &self.reference
+    }
+
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+}
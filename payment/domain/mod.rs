@@ -1,4 +1,8 @@
 pub mod admin;
 pub mod contract;
+pub mod gateway;
+pub mod payout;
+pub mod plan;
 pub mod publication;
+pub mod subscription;
 pub mod user;
@@ -0,0 +1,371 @@
+mod financial_summary;
+mod line;
+mod month;
+mod repository;
+mod summary;
+pub use financial_summary::*;
+pub use line::*;
+pub use month::*;
+pub use repository::*;
+pub use summary::*;
+
+use std::collections::BTreeMap;
+
+use common::error::Error;
+use common::model::{AggregateRoot, StringId};
+use common::result::Result;
+use shared::event::PayoutEvent;
+
+use crate::domain::user::UserId;
+
+pub type PayoutId = StringId;
+
+// Distribution of the income of a month among the authors with approved contracts. There is one
+// payout per month, identified by the month.
+#[derive(Debug, Clone)]
+pub struct Payout {
+    base: AggregateRoot<PayoutId, PayoutEvent>,
+    month: Month,
+    financial_summary: FinancialSummary,
+    summaries: Vec<Summary>,
+    lines: Vec<Line>,
+}
+
+impl Payout {
+    pub fn new(
+        month: Month,
+        financial_summary: FinancialSummary,
+        mut summaries: Vec<Summary>,
+    ) -> Result<Self> {
+        // Sorted, so the same summaries always get the same amounts.
+        summaries.sort_by(|a, b| {
+            a.publication()
+                .id()
+                .value()
+                .cmp(b.publication().id().value())
+        });
+
+        // The income is distributed in cents, proportionally to the score of each eligible
+        // publication. The cents left by rounding down go to the largest remainders.
+        let income = (financial_summary.income() * 100.0).round() as u64;
+        let total_score: f64 = summaries
+            .iter()
+            .filter(|summary| summary.is_eligible())
+            .map(|summary| summary.score())
+            .sum();
+
+        let mut cents = vec![0; summaries.len()];
+        if total_score > 0.0 {
+            let mut remainders = Vec::new();
+            for (i, summary) in summaries.iter().enumerate() {
+                if !summary.is_eligible() {
+                    continue;
+                }
+
+                let exact = income as f64 * summary.score() / total_score;
+                cents[i] = exact.floor() as u64;
+                remainders.push((i, exact - exact.floor()));
+            }
+
+            remainders.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+            let left = income - cents.iter().sum::<u64>();
+            for (i, _) in remainders.into_iter().take(left as usize) {
+                cents[i] += 1;
+            }
+        }
+
+        let mut authors: BTreeMap<String, (u64, Vec<Summary>)> = BTreeMap::new();
+        for (summary, cents) in summaries.iter_mut().zip(cents) {
+            summary.set_amount(cents as f64 / 100.0);
+            if cents > 0 {
+                let author = authors
+                    .entry(summary.publication().author().id().to_string())
+                    .or_insert((0, Vec::new()));
+                author.0 += cents;
+                author.1.push(summary.clone());
+            }
+        }
+
+        let lines: Vec<Line> = authors
+            .into_iter()
+            .map(|(_, (cents, summaries))| {
+                Line::new(
+                    summaries[0].publication().author().id().clone(),
+                    cents as f64 / 100.0,
+                    summaries
+                        .iter()
+                        .map(|summary| summary.publication().id().clone())
+                        .collect(),
+                )
+            })
+            .collect();
+
+        let mut payout = Payout {
+            base: AggregateRoot::new(PayoutId::new(month.to_string())?),
+            month,
+            financial_summary,
+            summaries,
+            lines,
+        };
+
+        payout.base.record_event(PayoutEvent::Generated {
+            id: payout.base().id().to_string(),
+            month: payout.month().to_string(),
+            income: payout.financial_summary().income(),
+            amount: payout.amount(),
+            contracts: payout.summaries().len(),
+        });
+
+        let events: Vec<PayoutEvent> = payout
+            .lines()
+            .iter()
+            .map(|line| PayoutEvent::AuthorPayoutGenerated {
+                payout_id: payout.base().id().to_string(),
+                month: payout.month().to_string(),
+                author_id: line.author_id().to_string(),
+                amount: line.amount(),
+                publication_ids: line
+                    .publication_ids()
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect(),
+            })
+            .collect();
+        for event in events.into_iter() {
+            payout.base.record_event(event);
+        }
+
+        Ok(payout)
+    }
+
+    pub fn base(&self) -> &AggregateRoot<PayoutId, PayoutEvent> {
+        &self.base
+    }
+
+    pub fn base_mut(&mut self) -> &mut AggregateRoot<PayoutId, PayoutEvent> {
+        &mut self.base
+    }
+
+    pub fn month(&self) -> &Month {
+        &self.month
+    }
+
+    pub fn financial_summary(&self) -> &FinancialSummary {
+        &self.financial_summary
+    }
+
+    pub fn summaries(&self) -> &[Summary] {
+        &self.summaries
+    }
+
+    pub fn lines(&self) -> &[Line] {
+        &self.lines
+    }
+
+    // All the lines were paid.
+    pub fn is_paid(&self) -> bool {
+        self.lines
+            .iter()
+            .all(|line| matches!(line.status(), LineStatus::Paid { .. }))
+    }
+
+    // Identifies a line in the transactions sent to the payment gateway: 2020-10:author-1.
+    pub fn line_reference(&self, author_id: &UserId) -> String {
+        format!("{}:{}", self.base().id().value(), author_id.value())
+    }
+
+    pub fn parse_line_reference(reference: &str) -> Result<(PayoutId, UserId)> {
+        let mut parts = reference.splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some(payout_id), Some(author_id)) => {
+                Ok((PayoutId::new(payout_id)?, UserId::new(author_id)?))
+            }
+            _ => Err(Error::new("payout", "invalid_line_reference")),
+        }
+    }
+
+    pub fn send_line(&mut self, author_id: &UserId, transaction_id: &str) -> Result<()> {
+        let line = self.line_mut(author_id)?;
+        line.send(transaction_id)?;
+        let amount = line.amount();
+
+        self.base.record_event(PayoutEvent::AuthorPayoutSent {
+            payout_id: self.base().id().to_string(),
+            author_id: author_id.to_string(),
+            transaction_id: transaction_id.to_owned(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn confirm_line(&mut self, author_id: &UserId, transaction_id: &str) -> Result<()> {
+        let line = self.line_mut(author_id)?;
+        line.confirm(transaction_id)?;
+        let amount = line.amount();
+
+        self.base.record_event(PayoutEvent::AuthorPaid {
+            payout_id: self.base().id().to_string(),
+            author_id: author_id.to_string(),
+            transaction_id: transaction_id.to_owned(),
+            amount,
+        });
+
+        if self.is_paid() {
+            self.base.record_event(PayoutEvent::Paid {
+                id: self.base().id().to_string(),
+                month: self.month().to_string(),
+                amount: self.amount(),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn fail_line(&mut self, author_id: &UserId, transaction_id: &str) -> Result<()> {
+        self.line_mut(author_id)?.fail(transaction_id)?;
+
+        self.base.record_event(PayoutEvent::AuthorPayoutFailed {
+            payout_id: self.base().id().to_string(),
+            author_id: author_id.to_string(),
+            transaction_id: transaction_id.to_owned(),
+        });
+
+        Ok(())
+    }
+
+    fn line_mut(&mut self, author_id: &UserId) -> Result<&mut Line> {
+        self.lines
+            .iter_mut()
+            .find(|line| line.author_id() == author_id)
+            .ok_or_else(|| Error::new("payout_line", "not_found"))
+    }
+
+    // Total paid to the authors.
+    pub fn amount(&self) -> f64 {
+        let cents: u64 = self
+            .lines
+            .iter()
+            .map(|line| (line.amount() * 100.0).round() as u64)
+            .sum();
+        cents as f64 / 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::contract::ContractId;
+    use crate::domain::publication::{Publication, PublicationId, Statistics};
+    use crate::domain::user::{User, UserId};
+
+    fn summary(id: &str, author_id: &str, unique_views: u32) -> Summary {
+        let statistics = Statistics::new(unique_views, 0, 0, 0, 0.0).unwrap();
+        Summary::new(
+            ContractId::new(format!("#contract-{}", id)).unwrap(),
+            Publication::new(
+                PublicationId::new(id).unwrap(),
+                User::new(UserId::new(author_id).unwrap()).unwrap(),
+                statistics.clone(),
+            )
+            .unwrap(),
+            statistics,
+        )
+    }
+
+    #[test]
+    fn distribute() {
+        let month = Month::new(2020, 10).unwrap();
+        let payout = Payout::new(
+            month,
+            FinancialSummary::build(100.0, 1),
+            vec![
+                summary("#publication03", "#user02", 1000),
+                summary("#publication01", "#user01", 1000),
+                summary("#publication02", "#user01", 1000),
+                // Not eligible
+                summary("#publication04", "#user03", 999),
+            ],
+        )
+        .unwrap();
+        assert_eq!(payout.base().id().value(), "2020-10");
+
+        // 33.34 + 33.33 + 33.33: the cent left goes to the first publication.
+        let amounts: Vec<f64> = payout.summaries().iter().map(|s| s.amount()).collect();
+        assert_eq!(amounts, vec![33.34, 33.33, 33.33, 0.0]);
+        assert_eq!(payout.amount(), 100.0);
+
+        let lines = payout.lines();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].author_id().value(), "#user01");
+        assert_eq!(lines[0].amount(), 66.67);
+        assert_eq!(lines[0].publication_ids().len(), 2);
+        assert_eq!(lines[1].author_id().value(), "#user02");
+        assert_eq!(lines[1].amount(), 33.33);
+
+        let events = payout.base().events().unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].topic(), "payout");
+        assert_eq!(events[0].code(), "generated");
+        assert_eq!(events[1].code(), "author-payout-generated");
+    }
+
+    #[test]
+    fn pay_lines() {
+        let mut payout = Payout::new(
+            Month::new(2020, 10).unwrap(),
+            FinancialSummary::build(100.0, 1),
+            vec![
+                summary("#publication01", "#user01", 1000),
+                summary("#publication02", "#user02", 1000),
+            ],
+        )
+        .unwrap();
+        let user1 = UserId::new("#user01").unwrap();
+        let user2 = UserId::new("#user02").unwrap();
+
+        let reference = payout.line_reference(&user1);
+        assert_eq!(reference, "2020-10:#user01");
+        let (payout_id, author_id) = Payout::parse_line_reference(&reference).unwrap();
+        assert_eq!(&payout_id, payout.base().id());
+        assert_eq!(author_id, user1);
+        assert!(Payout::parse_line_reference("2020-10").is_err());
+
+        assert!(payout.confirm_line(&user1, "#tx01").is_err());
+        payout.send_line(&user1, "#tx01").unwrap();
+        payout.send_line(&user2, "#tx02").unwrap();
+        assert!(payout.send_line(&user1, "#tx03").is_err());
+        assert!(payout.confirm_line(&user1, "#tx02").is_err());
+
+        payout.confirm_line(&user1, "#tx01").unwrap();
+        payout.fail_line(&user2, "#tx02").unwrap();
+        assert!(!payout.is_paid());
+
+        // Failed lines are sent again.
+        payout.send_line(&user2, "#tx04").unwrap();
+        payout.confirm_line(&user2, "#tx04").unwrap();
+        assert!(payout.is_paid());
+
+        let events = payout.base().events().unwrap();
+        assert_eq!(events.last().unwrap().code(), "paid");
+    }
+
+    #[test]
+    fn without_income_or_contracts() {
+        let month = Month::new(2020, 10).unwrap();
+
+        let payout = Payout::new(
+            month,
+            FinancialSummary::build(0.0, 0),
+            vec![summary("#publication01", "#user01", 1000)],
+        )
+        .unwrap();
+        assert!(payout.lines().is_empty());
+
+        let payout = Payout::new(month, FinancialSummary::build(250.0, 1), Vec::new()).unwrap();
+        assert!(payout.lines().is_empty());
+        assert_eq!(payout.amount(), 0.0);
+        assert_eq!(payout.base().events().unwrap().len(), 1);
+    }
+}
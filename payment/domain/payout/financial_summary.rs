@@ -0,0 +1,45 @@
+use crate::domain::payout::Month;
+use crate::domain::subscription::Subscription;
+
+// Income of the subscriptions paid in a month, which is distributed among the authors.
+#[derive(Debug, Clone)]
+pub struct FinancialSummary {
+    income: f64,
+    subscriptions: usize,
+}
+
+impl FinancialSummary {
+    pub fn new(month: &Month, subscriptions: &[Subscription]) -> Self {
+        let (start, end) = (month.start(), month.end());
+
+        let mut income = 0.0;
+        let mut paid_subscriptions = 0;
+        for subscription in subscriptions.iter() {
+            let paid = subscription.paid_between(&start, &end);
+            if paid > 0.0 {
+                income += paid;
+                paid_subscriptions += 1;
+            }
+        }
+
+        FinancialSummary {
+            income,
+            subscriptions: paid_subscriptions,
+        }
+    }
+
+    pub fn build(income: f64, subscriptions: usize) -> Self {
+        FinancialSummary {
+            income,
+            subscriptions,
+        }
+    }
+
+    pub fn income(&self) -> f64 {
+        self.income
+    }
+
+    pub fn subscriptions(&self) -> usize {
+        self.subscriptions
+    }
+}
@@ -0,0 +1,110 @@
+use std::fmt;
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::publication::PublicationId;
+use crate::domain::user::UserId;
+
+#[derive(Debug, Clone)]
+pub enum LineStatus {
+    Pending,
+    Sent { transaction_id: String },
+    Paid { transaction_id: String },
+    Failed { transaction_id: String },
+}
+
+impl fmt::Display for LineStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineStatus::Pending => write!(f, "pending"),
+            LineStatus::Sent { .. } => write!(f, "sent"),
+            LineStatus::Paid { .. } => write!(f, "paid"),
+            LineStatus::Failed { .. } => write!(f, "failed"),
+        }
+    }
+}
+
+// What an author is paid in a month for all their contracted publications.
+#[derive(Debug, Clone)]
+pub struct Line {
+    author_id: UserId,
+    amount: f64,
+    publication_ids: Vec<PublicationId>,
+    status: LineStatus,
+}
+
+impl Line {
+    pub fn new(author_id: UserId, amount: f64, publication_ids: Vec<PublicationId>) -> Self {
+        Line {
+            author_id,
+            amount,
+            publication_ids,
+            status: LineStatus::Pending,
+        }
+    }
+
+    pub fn author_id(&self) -> &UserId {
+        &self.author_id
+    }
+
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    pub fn publication_ids(&self) -> &[PublicationId] {
+        &self.publication_ids
+    }
+
+    pub fn status(&self) -> &LineStatus {
+        &self.status
+    }
+
+    // Pending lines, or failed ones to retry them.
+    pub fn can_be_sent(&self) -> bool {
+        matches!(self.status, LineStatus::Pending | LineStatus::Failed { .. })
+    }
+
+    pub fn send<S: Into<String>>(&mut self, transaction_id: S) -> Result<()> {
+        if !self.can_be_sent() {
+            return Err(Error::new("payout_line", "already_sent"));
+        }
+
+        self.status = LineStatus::Sent {
+            transaction_id: transaction_id.into(),
+        };
+
+        Ok(())
+    }
+
+    pub fn confirm(&mut self, transaction_id: &str) -> Result<()> {
+        self.check_sent(transaction_id)?;
+
+        self.status = LineStatus::Paid {
+            transaction_id: transaction_id.to_owned(),
+        };
+
+        Ok(())
+    }
+
+    pub fn fail(&mut self, transaction_id: &str) -> Result<()> {
+        self.check_sent(transaction_id)?;
+
+        self.status = LineStatus::Failed {
+            transaction_id: transaction_id.to_owned(),
+        };
+
+        Ok(())
+    }
+
+    fn check_sent(&self, transaction_id: &str) -> Result<()> {
+        match &self.status {
+            LineStatus::Sent {
+                transaction_id: sent_id,
+            } if sent_id == transaction_id => Ok(()),
+            _ => Err(Error::new("payout_line", "not_sent")
+                .add_context("transaction_id", transaction_id)
+                .build()),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+
+use common::error::Error;
+use common::result::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Month {
+    year: i32,
+    month: u32,
+}
+
+impl Month {
+    pub fn new(year: i32, month: u32) -> Result<Self> {
+        if !(1970..=9999).contains(&year) || !(1..=12).contains(&month) {
+            return Err(Error::new("month", "invalid"));
+        }
+
+        Ok(Month { year, month })
+    }
+
+    // The month before the one of the date.
+    pub fn previous_to(date: &DateTime<Utc>) -> Result<Self> {
+        if date.month() == 1 {
+            Month::new(date.year() - 1, 12)
+        } else {
+            Month::new(date.year(), date.month() - 1)
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn start(&self) -> DateTime<Utc> {
+        Utc.ymd(self.year, self.month, 1).and_hms(0, 0, 0)
+    }
+
+    // The start of the next month, excluded from this one.
+    pub fn end(&self) -> DateTime<Utc> {
+        if self.month == 12 {
+            Utc.ymd(self.year + 1, 1, 1).and_hms(0, 0, 0)
+        } else {
+            Utc.ymd(self.year, self.month + 1, 1).and_hms(0, 0, 0)
+        }
+    }
+}
+
+impl fmt::Display for Month {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+impl FromStr for Month {
+    type Err = Error;
+
+    // 2020-10
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.splitn(2, '-');
+        match (
+            parts.next().and_then(|year| year.parse().ok()),
+            parts.next().and_then(|month| month.parse().ok()),
+        ) {
+            (Some(year), Some(month)) => Month::new(year, month),
+            _ => Err(Error::new("month", "invalid")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let month: Month = "2020-12".parse().unwrap();
+        assert_eq!(month.to_string(), "2020-12");
+        assert_eq!(month.start(), Utc.ymd(2020, 12, 1).and_hms(0, 0, 0));
+        assert_eq!(month.end(), Utc.ymd(2021, 1, 1).and_hms(0, 0, 0));
+
+        assert_eq!(
+            Month::previous_to(&Utc.ymd(2021, 1, 15).and_hms(10, 0, 0)).unwrap(),
+            month
+        );
+
+        assert!("2020-13".parse::<Month>().is_err());
+        assert!("2020".parse::<Month>().is_err());
+        assert!("october".parse::<Month>().is_err());
+    }
+}
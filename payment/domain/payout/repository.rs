@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+
+use common::result::Result;
+
+use crate::domain::payout::{Payout, PayoutId};
+
+#[async_trait]
+pub trait PayoutRepository: Sync + Send {
+    async fn find_all(&self) -> Result<Vec<Payout>>;
+    async fn find_by_id(&self, id: &PayoutId) -> Result<Payout>;
+
+    async fn save(&self, payout: &mut Payout) -> Result<()>;
+}
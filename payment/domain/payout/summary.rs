@@ -0,0 +1,67 @@
+use crate::domain::contract::ContractId;
+use crate::domain::publication::{Publication, Statistics};
+
+// Same as required to apply for a contract.
+const MIN_UNIQUE_VIEWS: u32 = 1000;
+
+// Share of a contracted publication in the payout of a month, according to its statistics in the
+// month. The publication has the statistics it had at the end of the month, which decide whether
+// it is eligible.
+#[derive(Debug, Clone)]
+pub struct Summary {
+    contract_id: ContractId,
+    publication: Publication,
+    statistics: Statistics,
+    score: f64,
+    amount: f64,
+}
+
+impl Summary {
+    pub fn new(contract_id: ContractId, publication: Publication, statistics: Statistics) -> Self {
+        let score = Self::score_of(&statistics);
+
+        Summary {
+            contract_id,
+            publication,
+            statistics,
+            score,
+            amount: 0.0,
+        }
+    }
+
+    // Readings, likes and good reviews are worth more than views.
+    pub fn score_of(statistics: &Statistics) -> f64 {
+        f64::from(statistics.unique_views())
+            + 2.0 * f64::from(statistics.readings())
+            + 5.0 * f64::from(statistics.likes())
+            + 2.0 * f64::from(statistics.reviews()) * f64::from(statistics.stars())
+    }
+
+    pub fn contract_id(&self) -> &ContractId {
+        &self.contract_id
+    }
+
+    pub fn publication(&self) -> &Publication {
+        &self.publication
+    }
+
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    pub fn is_eligible(&self) -> bool {
+        self.publication.statistics().unique_views() >= MIN_UNIQUE_VIEWS
+    }
+
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    pub fn set_amount(&mut self, amount: f64) {
+        self.amount = amount;
+    }
+}
@@ -0,0 +1,60 @@
+mod period;
+mod repository;
+pub use period::*;
+pub use repository::*;
+
+use common::error::Error;
+use common::model::StringId;
+use common::result::Result;
+
+pub type PlanId = StringId;
+
+#[derive(Debug, Clone)]
+pub struct Plan {
+    id: PlanId,
+    name: String,
+    price: f64,
+    period: Period,
+}
+
+impl Plan {
+    pub fn new<S: Into<String>>(id: PlanId, name: S, price: f64, period: Period) -> Result<Self> {
+        let name = name.into();
+
+        let mut err = Error::new("plan", "invalid");
+        if name.trim().is_empty() {
+            err.add_context("name", "empty");
+        }
+
+        if !price.is_finite() || price <= 0.0 {
+            err.add_context("price", "not_positive");
+        }
+
+        if err.has_context() {
+            return Err(err);
+        }
+
+        Ok(Plan {
+            id,
+            name,
+            price,
+            period,
+        })
+    }
+
+    pub fn id(&self) -> &PlanId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn price(&self) -> f64 {
+        self.price
+    }
+
+    pub fn period(&self) -> &Period {
+        &self.period
+    }
+}
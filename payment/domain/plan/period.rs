@@ -0,0 +1,42 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::Duration;
+
+use common::error::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Period {
+    Monthly,
+    Yearly,
+}
+
+impl Period {
+    pub fn duration(&self) -> Duration {
+        match self {
+            Period::Monthly => Duration::days(30),
+            Period::Yearly => Duration::days(365),
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Period::Monthly => write!(f, "monthly"),
+            Period::Yearly => write!(f, "yearly"),
+        }
+    }
+}
+
+impl FromStr for Period {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "monthly" => Ok(Period::Monthly),
+            "yearly" => Ok(Period::Yearly),
+            _ => Err(Error::new("plan", "invalid_period")),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+
+use common::result::Result;
+
+use crate::domain::plan::{Plan, PlanId};
+
+#[async_trait]
+pub trait PlanRepository: Sync + Send {
+    async fn find_all(&self) -> Result<Vec<Plan>>;
+    async fn find_by_id(&self, id: &PlanId) -> Result<Plan>;
+
+    async fn save(&self, plan: &mut Plan) -> Result<()>;
+}
@@ -1,12 +1,21 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use common::result::Result;
 
-use crate::domain::publication::{Publication, PublicationId};
+use crate::domain::publication::{Publication, PublicationId, Statistics};
 
 #[async_trait]
-pub trait PublicationRepository {
+pub trait PublicationRepository: Sync + Send {
     async fn find_by_id(&self, id: &PublicationId) -> Result<Publication>;
+    // Statistics of the interactions with the publication until a date, and since another one if
+    // given.
+    async fn find_statistics(
+        &self,
+        id: &PublicationId,
+        from: Option<&DateTime<Utc>>,
+        to: &DateTime<Utc>,
+    ) -> Result<Statistics>;
 
     async fn save(&self, publication: &mut Publication) -> Result<()>;
 }
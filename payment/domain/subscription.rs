@@ -0,0 +1,323 @@
+mod repository;
+mod status;
+pub use repository::*;
+pub use status::*;
+
+use chrono::{DateTime, Utc};
+
+use common::error::Error;
+use common::model::{AggregateRoot, StatusHistory, StringId};
+use common::result::Result;
+use shared::event::SubscriptionEvent;
+
+use crate::domain::plan::Plan;
+use crate::domain::user::UserId;
+
+pub type SubscriptionId = StringId;
+
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    base: AggregateRoot<SubscriptionId, SubscriptionEvent>,
+    user_id: UserId,
+    plan: Plan,
+    status_history: StatusHistory<Status>,
+    paid_until: Option<DateTime<Utc>>,
+}
+
+impl Subscription {
+    pub fn new(id: SubscriptionId, user_id: UserId, plan: Plan) -> Result<Self> {
+        let mut subscription = Subscription {
+            base: AggregateRoot::new(id),
+            user_id,
+            plan,
+            status_history: StatusHistory::new(Status::PendingPayment),
+            paid_until: None,
+        };
+
+        subscription.base.record_event(SubscriptionEvent::Created {
+            id: subscription.base().id().to_string(),
+            user_id: subscription.user_id().to_string(),
+            plan_id: subscription.plan().id().to_string(),
+            amount: subscription.plan().price(),
+        });
+
+        Ok(subscription)
+    }
+
+    pub fn build(
+        base: AggregateRoot<SubscriptionId, SubscriptionEvent>,
+        user_id: UserId,
+        plan: Plan,
+        status_history: StatusHistory<Status>,
+        paid_until: Option<DateTime<Utc>>,
+    ) -> Self {
+        Subscription {
+            base,
+            user_id,
+            plan,
+            status_history,
+            paid_until,
+        }
+    }
+
+    pub fn base(&self) -> &AggregateRoot<SubscriptionId, SubscriptionEvent> {
+        &self.base
+    }
+
+    pub fn base_mut(&mut self) -> &mut AggregateRoot<SubscriptionId, SubscriptionEvent> {
+        &mut self.base
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    pub fn plan(&self) -> &Plan {
+        &self.plan
+    }
+
+    pub fn status_history(&self) -> &StatusHistory<Status> {
+        &self.status_history
+    }
+
+    pub fn paid_until(&self) -> Option<&DateTime<Utc>> {
+        self.paid_until.as_ref()
+    }
+
+    // The paid period has not ended. A cancelled subscription, or one waiting for the payment
+    // of its renewal, keeps giving access until then.
+    pub fn is_active(&self) -> bool {
+        !matches!(self.status_history().current().status(), Status::Expired)
+            && self
+                .paid_until
+                .map(|paid_until| paid_until > Utc::now())
+                .unwrap_or(false)
+    }
+
+    // Sum of the payments confirmed between both dates.
+    pub fn paid_between(&self, from: &DateTime<Utc>, to: &DateTime<Utc>) -> f64 {
+        self.status_history()
+            .history()
+            .iter()
+            .filter(|item| item.date() >= from && item.date() < to)
+            .map(|item| match item.status() {
+                Status::Paid { amount } => *amount,
+                _ => 0.0,
+            })
+            .sum()
+    }
+
+    // Not expired nor cancelled, so the user cannot subscribe again.
+    pub fn is_open(&self) -> bool {
+        !matches!(
+            self.status_history().current().status(),
+            Status::Expired | Status::Cancelled
+        )
+    }
+
+    pub fn pay(&mut self) -> Result<()> {
+        if !matches!(
+            self.status_history().current().status(),
+            Status::PendingPayment
+        ) {
+            return Err(Error::new("subscription", "not_pending_payment"));
+        }
+
+        // A renewal paid in advance is added to the remaining period.
+        let from = match self.paid_until {
+            Some(paid_until) if paid_until > Utc::now() => paid_until,
+            _ => Utc::now(),
+        };
+        let paid_until = from + self.plan.period().duration();
+        self.paid_until = Some(paid_until);
+
+        self.status_history.add_status(Status::Paid {
+            amount: self.plan.price(),
+        });
+
+        self.base.record_event(SubscriptionEvent::Paid {
+            id: self.base().id().to_string(),
+            user_id: self.user_id().to_string(),
+            plan_id: self.plan().id().to_string(),
+            amount: self.plan().price(),
+            paid_until: paid_until.to_rfc3339(),
+        });
+
+        Ok(())
+    }
+
+    // The subscription stays open, so the payment can be tried again by renewing it, and a renewal
+    // keeps the period already paid.
+    pub fn fail_payment(&mut self) -> Result<()> {
+        if !matches!(
+            self.status_history().current().status(),
+            Status::PendingPayment
+        ) {
+            return Err(Error::new("subscription", "not_pending_payment"));
+        }
+
+        self.status_history.add_status(Status::PaymentFailed);
+
+        self.base.record_event(SubscriptionEvent::PaymentFailed {
+            id: self.base().id().to_string(),
+            user_id: self.user_id().to_string(),
+            plan_id: self.plan().id().to_string(),
+            amount: self.plan().price(),
+        });
+
+        Ok(())
+    }
+
+    pub fn renew(&mut self) -> Result<()> {
+        if !matches!(
+            self.status_history().current().status(),
+            Status::Paid { .. } | Status::PaymentFailed | Status::Expired
+        ) {
+            return Err(Error::new("subscription", "not_renewable"));
+        }
+
+        self.status_history.add_status(Status::PendingPayment);
+
+        self.base.record_event(SubscriptionEvent::Renewed {
+            id: self.base().id().to_string(),
+            user_id: self.user_id().to_string(),
+            plan_id: self.plan().id().to_string(),
+            amount: self.plan().price(),
+        });
+
+        Ok(())
+    }
+
+    pub fn expire(&mut self) -> Result<()> {
+        if !self.is_open() {
+            return Err(Error::new("subscription", "not_open"));
+        }
+
+        if self.is_active() {
+            return Err(Error::new("subscription", "not_expired"));
+        }
+
+        self.status_history.add_status(Status::Expired);
+
+        self.base.record_event(SubscriptionEvent::Expired {
+            id: self.base().id().to_string(),
+            user_id: self.user_id().to_string(),
+        });
+
+        Ok(())
+    }
+
+    pub fn cancel(&mut self) -> Result<()> {
+        if !self.is_open() {
+            return Err(Error::new("subscription", "not_open"));
+        }
+
+        self.status_history.add_status(Status::Cancelled);
+
+        self.base.record_event(SubscriptionEvent::Cancelled {
+            id: self.base().id().to_string(),
+            user_id: self.user_id().to_string(),
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+
+    use crate::mocks;
+
+    #[test]
+    fn pay_and_renew() {
+        let mut subscription = Subscription::new(
+            SubscriptionId::new("#subscription01").unwrap(),
+            mocks::user1().id().clone(),
+            mocks::plan1(),
+        )
+        .unwrap();
+        assert!(!subscription.is_active());
+        assert!(subscription.renew().is_err());
+
+        subscription.pay().unwrap();
+        assert!(subscription.is_active());
+        assert!(subscription.pay().is_err());
+        let paid_until = *subscription.paid_until().unwrap();
+
+        // The renewal is added to the paid period, which keeps giving access meanwhile.
+        subscription.renew().unwrap();
+        assert!(subscription.is_active());
+        subscription.pay().unwrap();
+        assert_eq!(
+            *subscription.paid_until().unwrap(),
+            paid_until + Duration::days(30)
+        );
+
+        assert!(subscription.expire().is_err());
+
+        let events = subscription.base().events().unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0].topic(), "subscription");
+        assert_eq!(events[0].code(), "created");
+        assert_eq!(events[1].code(), "paid");
+        assert_eq!(events[2].code(), "renewed");
+        assert_eq!(events[3].code(), "paid");
+    }
+
+    #[test]
+    fn fail_payment() {
+        let mut subscription = Subscription::new(
+            SubscriptionId::new("#subscription01").unwrap(),
+            mocks::user1().id().clone(),
+            mocks::plan1(),
+        )
+        .unwrap();
+        subscription.pay().unwrap();
+        let paid_until = *subscription.paid_until().unwrap();
+        assert!(subscription.fail_payment().is_err());
+
+        // A failed renewal keeps the paid period and can be tried again.
+        subscription.renew().unwrap();
+        subscription.fail_payment().unwrap();
+        assert!(subscription.is_active());
+        assert!(subscription.is_open());
+        assert_eq!(subscription.paid_until().unwrap(), &paid_until);
+
+        subscription.renew().unwrap();
+        subscription.pay().unwrap();
+        assert_eq!(
+            *subscription.paid_until().unwrap(),
+            paid_until + Duration::days(30)
+        );
+    }
+
+    #[test]
+    fn expire_and_cancel() {
+        let mut subscription = Subscription::new(
+            SubscriptionId::new("#subscription01").unwrap(),
+            mocks::user1().id().clone(),
+            mocks::plan1(),
+        )
+        .unwrap();
+        subscription.pay().unwrap();
+
+        subscription.paid_until = Some(Utc::now() - Duration::days(1));
+        assert!(!subscription.is_active());
+        subscription.expire().unwrap();
+        assert!(!subscription.is_open());
+        assert!(subscription.cancel().is_err());
+
+        subscription.renew().unwrap();
+        subscription.pay().unwrap();
+        assert!(subscription.is_active());
+
+        // It gives access until the end of the paid period.
+        subscription.cancel().unwrap();
+        assert!(subscription.is_active());
+        assert!(subscription.renew().is_err());
+        assert!(subscription.cancel().is_err());
+    }
+}
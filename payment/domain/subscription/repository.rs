@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+
+use common::result::Result;
+
+use crate::domain::subscription::{Subscription, SubscriptionId};
+use crate::domain::user::UserId;
+
+#[async_trait]
+pub trait SubscriptionRepository: Sync + Send {
+    async fn next_id(&self) -> Result<SubscriptionId>;
+
+    async fn find_all(&self) -> Result<Vec<Subscription>>;
+    async fn find_by_id(&self, id: &SubscriptionId) -> Result<Subscription>;
+    async fn find_by_user_id(&self, user_id: &UserId) -> Result<Vec<Subscription>>;
+    async fn find_by_status(&self, status: &str) -> Result<Vec<Subscription>>;
+
+    async fn save(&self, subscription: &mut Subscription) -> Result<()>;
+}
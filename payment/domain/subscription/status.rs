@@ -0,0 +1,22 @@
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub enum Status {
+    PendingPayment,
+    Paid { amount: f64 },
+    PaymentFailed,
+    Expired,
+    Cancelled,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::PendingPayment => write!(f, "pending_payment"),
+            Status::Paid { .. } => write!(f, "paid"),
+            Status::PaymentFailed => write!(f, "payment_failed"),
+            Status::Expired => write!(f, "expired"),
+            Status::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use common::error::Error;
+use common::result::Result;
+
+use crate::domain::gateway::{PaymentGateway, Transaction, TransactionKind};
+use crate::domain::user::UserId;
+
+// In-process gateway that does not move money. Transaction ids are sequential, so the same
+// requests always get the same ids, and results are notified by calling the webhook.
+pub struct FakePaymentGateway {
+    transactions: Mutex<Vec<Transaction>>,
+}
+
+impl FakePaymentGateway {
+    pub fn new() -> Self {
+        FakePaymentGateway {
+            transactions: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn transactions(&self) -> Vec<Transaction> {
+        self.transactions.lock().unwrap().clone()
+    }
+
+    fn add(&self, kind: TransactionKind, reference: &str, amount: f64) -> Result<Transaction> {
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(Error::new("payment_gateway", "invalid_amount"));
+        }
+
+        let mut transactions = self.transactions.lock().unwrap();
+        let transaction = Transaction::new(
+            format!("fake_{}_{:06}", kind, transactions.len() + 1),
+            kind,
+            reference.to_owned(),
+            amount,
+        );
+        transactions.push(transaction.clone());
+
+        Ok(transaction)
+    }
+}
+
+impl Default for FakePaymentGateway {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PaymentGateway for FakePaymentGateway {
+    async fn charge(&self, _user_id: &UserId, reference: &str, amount: f64) -> Result<Transaction> {
+        self.add(TransactionKind::Charge, reference, amount)
+    }
+
+    async fn refund(&self, charge_id: &str, amount: f64) -> Result<Transaction> {
+        let refunded = {
+            let transactions = self.transactions.lock().unwrap();
+            let charge = transactions
+                .iter()
+                .find(|t| t.id() == charge_id && t.kind() == &TransactionKind::Charge)
+                .ok_or_else(|| Error::new("payment_gateway", "charge_not_found"))?;
+
+            let refunded: f64 = transactions
+                .iter()
+                .filter(|t| t.kind() == &TransactionKind::Refund && t.reference() == charge_id)
+                .map(|t| t.amount())
+                .sum();
+            charge.amount() - refunded
+        };
+
+        if amount > refunded {
+            return Err(Error::new("payment_gateway", "refund_exceeds_charge"));
+        }
+
+        self.add(TransactionKind::Refund, charge_id, amount)
+    }
+
+    async fn payout(
+        &self,
+        _author_id: &UserId,
+        reference: &str,
+        amount: f64,
+    ) -> Result<Transaction> {
+        self.add(TransactionKind::Payout, reference, amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn transactions() {
+        let gateway = FakePaymentGateway::new();
+        let user_id = UserId::new("#user01").unwrap();
+
+        let charge = gateway
+            .charge(&user_id, "#subscription01", 250.0)
+            .await
+            .unwrap();
+        assert_eq!(charge.id(), "fake_charge_000001");
+        assert_eq!(charge.reference(), "#subscription01");
+        assert!(gateway
+            .charge(&user_id, "#subscription02", 0.0)
+            .await
+            .is_err());
+
+        let refund = gateway.refund(charge.id(), 200.0).await.unwrap();
+        assert_eq!(refund.id(), "fake_refund_000002");
+        assert!(gateway.refund(charge.id(), 100.0).await.is_err());
+        assert!(gateway.refund(refund.id(), 10.0).await.is_err());
+        gateway.refund(charge.id(), 50.0).await.unwrap();
+
+        let payout = gateway
+            .payout(&user_id, "2020-10:#user01", 100.0)
+            .await
+            .unwrap();
+        assert_eq!(payout.id(), "fake_payout_000004");
+        assert_eq!(gateway.transactions().len(), 4);
+    }
+}
@@ -0,0 +1,4 @@
+mod fake_payment_gateway;
+mod webhook_signature;
+pub use fake_payment_gateway::*;
+pub use webhook_signature::*;
@@ -0,0 +1,156 @@
+use chrono::{TimeZone, Utc};
+use ring::hmac;
+use uuid::Uuid;
+
+use common::config::Config;
+use common::error::Error;
+use common::result::Result;
+
+// Notifications older than this are rejected, so they cannot be replayed later. The ones replayed
+// before are rejected by their id.
+const TOLERANCE_SECONDS: i64 = 5 * 60;
+
+// Signs and verifies the notifications sent by the payment provider to the webhook. The signature
+// header is "t=<unix timestamp>,v1=<HMAC-SHA256 of "<timestamp>.<body>" in hex>".
+pub struct WebhookSignature {
+    key: hmac::Key,
+}
+
+impl WebhookSignature {
+    pub fn new(secret: &[u8]) -> Self {
+        WebhookSignature {
+            key: hmac::Key::new(hmac::HMAC_SHA256, secret),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let secret = match config.payment_webhook_secret() {
+            Some(secret) => secret.to_owned(),
+            None if config.env() == "production" => {
+                return Err(Error::internal("webhook", "missing_secret")
+                    .add_context("env", "PAYMENT_WEBHOOK_SECRET")
+                    .build())
+            }
+            // No notification can be verified.
+            None => {
+                println!("PAYMENT_WEBHOOK_SECRET is not set, using a random secret");
+                format!("{}{}", Uuid::new_v4(), Uuid::new_v4())
+            }
+        };
+
+        Ok(Self::new(secret.as_bytes()))
+    }
+
+    pub fn sign(&self, timestamp: i64, body: &[u8]) -> String {
+        let tag = hmac::sign(&self.key, &Self::message(timestamp, body));
+        format!("t={},v1={}", timestamp, to_hex(tag.as_ref()))
+    }
+
+    pub fn verify(&self, header: &str, body: &[u8]) -> Result<()> {
+        self.verify_at(header, body, Utc::now().timestamp())
+    }
+
+    fn verify_at(&self, header: &str, body: &[u8], now: i64) -> Result<()> {
+        let mut timestamp = None;
+        let mut signatures = Vec::new();
+        for part in header.split(',') {
+            let mut kv = part.trim().splitn(2, '=');
+            match (kv.next(), kv.next()) {
+                (Some("t"), Some(value)) => timestamp = value.parse::<i64>().ok(),
+                (Some("v1"), Some(value)) => signatures.extend(from_hex(value)),
+                _ => {}
+            }
+        }
+
+        let timestamp = match timestamp {
+            Some(timestamp) if !signatures.is_empty() => timestamp,
+            _ => return Err(invalid_signature("malformed")),
+        };
+
+        // Any of the signatures, so the secret can be rotated.
+        let message = Self::message(timestamp, body);
+        if !signatures
+            .iter()
+            .any(|signature| hmac::verify(&self.key, &message, signature).is_ok())
+        {
+            return Err(invalid_signature("mismatch"));
+        }
+
+        if (now - timestamp).abs() > TOLERANCE_SECONDS {
+            return Err(invalid_signature("expired")
+                .add_context("timestamp", &Utc.timestamp(timestamp, 0).to_rfc3339())
+                .build());
+        }
+
+        Ok(())
+    }
+
+    fn message(timestamp: i64, body: &[u8]) -> Vec<u8> {
+        let mut message = format!("{}.", timestamp).into_bytes();
+        message.extend_from_slice(body);
+        message
+    }
+}
+
+fn invalid_signature(reason: &str) -> Error {
+    Error::new("webhook", "invalid_signature")
+        .set_status(401)
+        .add_context("reason", reason)
+        .build()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return None;
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify() {
+        let signature = WebhookSignature::new(b"secret");
+        let body = br##"{"id":"#notification01"}"##;
+        let now = 1_602_000_000;
+
+        let header = signature.sign(now, body);
+        assert!(header.starts_with("t=1602000000,v1="));
+        assert!(signature.verify_at(&header, body, now).is_ok());
+        assert!(signature.verify_at(&header, body, now + 60).is_ok());
+
+        // Modified body
+        assert!(signature
+            .verify_at(&header, br##"{"id":"#notification02"}"##, now)
+            .is_err());
+
+        // Modified timestamp
+        let replayed = header.replace("t=1602000000", "t=1602000600");
+        assert!(signature.verify_at(&replayed, body, now + 600).is_err());
+
+        // Too old
+        assert!(signature.verify_at(&header, body, now + 301).is_err());
+
+        // Another secret
+        let other = WebhookSignature::new(b"other");
+        assert!(other.verify_at(&header, body, now).is_err());
+
+        assert!(signature.verify_at("", body, now).is_err());
+        assert!(signature
+            .verify_at("t=1602000000,v1=zz", body, now)
+            .is_err());
+        assert!(signature
+            .verify_at(&format!("v1=abc,{}", header), body, now)
+            .is_ok());
+    }
+}
@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use common::cache::Cache;
+use common::error::Error;
+use common::infrastructure::cache::InMemCache;
+use common::result::Result;
+
+use crate::domain::contract::{Contract, ContractId, ContractRepository};
+use crate::domain::publication::PublicationId;
+
+pub struct InMemContractRepository {
+    cache: InMemCache<ContractId, Contract>,
+}
+
+impl InMemContractRepository {
+    pub fn new() -> Self {
+        InMemContractRepository {
+            cache: InMemCache::new(),
+        }
+    }
+}
+
+impl Default for InMemContractRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ContractRepository for InMemContractRepository {
+    async fn next_id(&self) -> Result<ContractId> {
+        let id = Uuid::new_v4();
+        ContractId::new(id.to_string())
+    }
+
+    async fn find_by_id(&self, id: &ContractId) -> Result<Contract> {
+        self.cache
+            .get(id)
+            .await
+            .ok_or(Error::new("contract", "not_found"))
+    }
+
+    // The last contract requested for the publication.
+    async fn find_by_publication_id(&self, publication_id: &PublicationId) -> Result<Contract> {
+        self.cache
+            .filter(|(_, contract)| contract.publication().id() == publication_id)
+            .await
+            .into_iter()
+            .max_by(|a, b| a.base().created_at().cmp(b.base().created_at()))
+            .ok_or(Error::new("contract", "not_found"))
+    }
+
+    async fn find_all(&self) -> Result<Vec<Contract>> {
+        let mut contracts = self.cache.all().await;
+        contracts.sort_by(|a, b| a.base().created_at().cmp(b.base().created_at()));
+
+        Ok(contracts)
+    }
+
+    async fn find_by_status(&self, status: &str) -> Result<Vec<Contract>> {
+        let mut contracts = self
+            .cache
+            .filter(|(_, contract)| {
+                contract.status_history().current().status().to_string() == status
+            })
+            .await;
+        contracts.sort_by(|a, b| a.base().created_at().cmp(b.base().created_at()));
+
+        Ok(contracts)
+    }
+
+    async fn save(&self, contract: &mut Contract) -> Result<()> {
+        self.cache
+            .set_checked(contract.base().id().clone(), contract.clone(), |stored| {
+                contract
+                    .base()
+                    .check_version(stored.map(|stored| stored.base().version()))
+            })
+            .await?;
+
+        contract.base_mut().commit_version();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn find() {
+        let repo = InMemContractRepository::new();
+        let publication = mocks::publication1();
+
+        let mut rejected =
+            Contract::new(repo.next_id().await.unwrap(), publication.clone()).unwrap();
+        rejected.reject(&mocks::admin1()).unwrap();
+        repo.save(&mut rejected).await.unwrap();
+
+        let mut requested =
+            Contract::new(repo.next_id().await.unwrap(), publication.clone()).unwrap();
+        repo.save(&mut requested).await.unwrap();
+
+        assert_eq!(
+            repo.find_by_publication_id(publication.id())
+                .await
+                .unwrap()
+                .base()
+                .id(),
+            requested.base().id()
+        );
+        assert!(repo
+            .find_by_publication_id(&PublicationId::new("#publication02").unwrap())
+            .await
+            .is_err());
+
+        let contracts = repo.find_by_status("requested").await.unwrap();
+        assert_eq!(contracts.len(), 1);
+        assert_eq!(contracts[0].base().id(), requested.base().id());
+        assert_eq!(repo.find_by_status("rejected").await.unwrap().len(), 1);
+        assert!(repo.find_by_status("approved").await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,14 @@
+mod admin_repository;
+mod contract_repository;
+mod notification_repository;
+mod payout_repository;
+mod plan_repository;
+mod publication_repository;
+mod subscription_repository;
+pub use admin_repository::*;
+pub use contract_repository::*;
+pub use notification_repository::*;
+pub use payout_repository::*;
+pub use plan_repository::*;
+pub use publication_repository::*;
+pub use subscription_repository::*;
@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+
+use common::cache::Cache;
+use common::error::Error;
+use common::infrastructure::cache::InMemCache;
+use common::result::Result;
+
+use crate::domain::gateway::{Notification, NotificationId, NotificationRepository};
+
+pub struct InMemNotificationRepository {
+    cache: InMemCache<NotificationId, Notification>,
+}
+
+impl InMemNotificationRepository {
+    pub fn new() -> Self {
+        InMemNotificationRepository {
+            cache: InMemCache::new(),
+        }
+    }
+}
+
+impl Default for InMemNotificationRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for InMemNotificationRepository {
+    async fn find_by_id(&self, id: &NotificationId) -> Result<Notification> {
+        self.cache
+            .get(id)
+            .await
+            .ok_or(Error::new("notification", "not_found"))
+    }
+
+    async fn reserve(&self, notification: &mut Notification) -> Result<()> {
+        self.cache
+            .set_checked(
+                notification.id().clone(),
+                notification.clone(),
+                |stored| match stored {
+                    Some(stored) if !stored.can_be_received_again() => {
+                        Err(Error::new("notification", "already_processed")
+                            .set_status(409)
+                            .build())
+                    }
+                    _ => Ok(()),
+                },
+            )
+            .await
+    }
+
+    async fn save(&self, notification: &mut Notification) -> Result<()> {
+        self.cache
+            .set(notification.id().clone(), notification.clone())
+            .await
+    }
+}
@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+
+use common::cache::Cache;
+use common::error::Error;
+use common::infrastructure::cache::InMemCache;
+use common::result::Result;
+
+use crate::domain::payout::{Payout, PayoutId, PayoutRepository};
+
+pub struct InMemPayoutRepository {
+    cache: InMemCache<PayoutId, Payout>,
+}
+
+impl InMemPayoutRepository {
+    pub fn new() -> Self {
+        InMemPayoutRepository {
+            cache: InMemCache::new(),
+        }
+    }
+}
+
+impl Default for InMemPayoutRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PayoutRepository for InMemPayoutRepository {
+    // From the latest month.
+    async fn find_all(&self) -> Result<Vec<Payout>> {
+        let mut payouts = self.cache.all().await;
+        payouts.sort_by(|a, b| b.base().id().value().cmp(a.base().id().value()));
+
+        Ok(payouts)
+    }
+
+    async fn find_by_id(&self, id: &PayoutId) -> Result<Payout> {
+        self.cache
+            .get(id)
+            .await
+            .ok_or(Error::new("payout", "not_found"))
+    }
+
+    async fn save(&self, payout: &mut Payout) -> Result<()> {
+        self.cache
+            .set_checked(payout.base().id().clone(), payout.clone(), |stored| {
+                payout
+                    .base()
+                    .check_version(stored.map(|stored| stored.base().version()))
+            })
+            .await?;
+
+        payout.base_mut().commit_version();
+        Ok(())
+    }
+}
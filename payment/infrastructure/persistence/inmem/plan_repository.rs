@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+
+use common::cache::Cache;
+use common::error::Error;
+use common::infrastructure::cache::InMemCache;
+use common::result::Result;
+
+use crate::domain::plan::{Plan, PlanId, PlanRepository};
+
+pub struct InMemPlanRepository {
+    cache: InMemCache<PlanId, Plan>,
+}
+
+impl InMemPlanRepository {
+    pub fn new() -> Self {
+        InMemPlanRepository {
+            cache: InMemCache::new(),
+        }
+    }
+}
+
+impl Default for InMemPlanRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PlanRepository for InMemPlanRepository {
+    async fn find_all(&self) -> Result<Vec<Plan>> {
+        let mut plans = self.cache.all().await;
+        plans.sort_by(|a, b| a.price().partial_cmp(&b.price()).unwrap());
+        Ok(plans)
+    }
+
+    async fn find_by_id(&self, id: &PlanId) -> Result<Plan> {
+        self.cache
+            .get(id)
+            .await
+            .ok_or(Error::new("plan", "not_found"))
+    }
+
+    async fn save(&self, plan: &mut Plan) -> Result<()> {
+        self.cache.set(plan.id().clone(), plan.clone()).await
+    }
+}
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use common::cache::Cache;
+use common::error::Error;
+use common::infrastructure::cache::InMemCache;
+use common::result::Result;
+
+use crate::domain::publication::{Publication, PublicationId, PublicationRepository, Statistics};
+
+pub struct InMemPublicationRepository {
+    cache: InMemCache<PublicationId, Publication>,
+}
+
+impl InMemPublicationRepository {
+    pub fn new() -> Self {
+        InMemPublicationRepository {
+            cache: InMemCache::new(),
+        }
+    }
+}
+
+impl Default for InMemPublicationRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PublicationRepository for InMemPublicationRepository {
+    async fn find_by_id(&self, id: &PublicationId) -> Result<Publication> {
+        self.cache
+            .get(id)
+            .await
+            .ok_or(Error::new("publication", "not_found"))
+    }
+
+    // Interactions are not kept, so they are the statistics of the publication.
+    async fn find_statistics(
+        &self,
+        id: &PublicationId,
+        _from: Option<&DateTime<Utc>>,
+        _to: &DateTime<Utc>,
+    ) -> Result<Statistics> {
+        self.find_by_id(id)
+            .await
+            .map(|publication| publication.statistics().clone())
+    }
+
+    async fn save(&self, publication: &mut Publication) -> Result<()> {
+        self.cache
+            .set(publication.id().clone(), publication.clone())
+            .await
+    }
+}
@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use common::cache::Cache;
+use common::error::Error;
+use common::infrastructure::cache::InMemCache;
+use common::result::Result;
+
+use crate::domain::subscription::{Subscription, SubscriptionId, SubscriptionRepository};
+use crate::domain::user::UserId;
+
+pub struct InMemSubscriptionRepository {
+    cache: InMemCache<SubscriptionId, Subscription>,
+}
+
+impl InMemSubscriptionRepository {
+    pub fn new() -> Self {
+        InMemSubscriptionRepository {
+            cache: InMemCache::new(),
+        }
+    }
+}
+
+impl Default for InMemSubscriptionRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SubscriptionRepository for InMemSubscriptionRepository {
+    async fn next_id(&self) -> Result<SubscriptionId> {
+        let id = Uuid::new_v4();
+        SubscriptionId::new(id.to_string())
+    }
+
+    async fn find_all(&self) -> Result<Vec<Subscription>> {
+        let mut subscriptions = self.cache.all().await;
+        subscriptions.sort_by(|a, b| a.base().created_at().cmp(b.base().created_at()));
+
+        Ok(subscriptions)
+    }
+
+    async fn find_by_id(&self, id: &SubscriptionId) -> Result<Subscription> {
+        self.cache
+            .get(id)
+            .await
+            .ok_or(Error::new("subscription", "not_found"))
+    }
+
+    // From the oldest to the latest subscription of the user.
+    async fn find_by_user_id(&self, user_id: &UserId) -> Result<Vec<Subscription>> {
+        let mut subscriptions = self
+            .cache
+            .filter(|(_, subscription)| subscription.user_id() == user_id)
+            .await;
+        subscriptions.sort_by(|a, b| a.base().created_at().cmp(b.base().created_at()));
+
+        Ok(subscriptions)
+    }
+
+    async fn find_by_status(&self, status: &str) -> Result<Vec<Subscription>> {
+        let mut subscriptions = self
+            .cache
+            .filter(|(_, subscription)| {
+                subscription.status_history().current().status().to_string() == status
+            })
+            .await;
+        subscriptions.sort_by(|a, b| a.base().created_at().cmp(b.base().created_at()));
+
+        Ok(subscriptions)
+    }
+
+    async fn save(&self, subscription: &mut Subscription) -> Result<()> {
+        self.cache
+            .set_checked(
+                subscription.base().id().clone(),
+                subscription.clone(),
+                |stored| {
+                    subscription
+                        .base()
+                        .check_version(stored.map(|stored| stored.base().version()))
+                },
+            )
+            .await?;
+
+        subscription.base_mut().commit_version();
+        Ok(())
+    }
+}
@@ -1,2 +1,5 @@
 pub mod application;
+pub mod container;
 pub mod domain;
+pub mod infrastructure;
+pub mod mocks;
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use common::mocks::FakeEventPublisher;
+
+use crate::container::Container;
+use crate::infrastructure::gateway::FakePaymentGateway;
+use crate::infrastructure::persistence::inmem::{
+    InMemAdminRepository, InMemContractRepository, InMemNotificationRepository,
+    InMemPayoutRepository, InMemPlanRepository, InMemPublicationRepository,
+    InMemSubscriptionRepository,
+};
+
+pub fn container() -> Container<FakeEventPublisher> {
+    Container::new(
+        Arc::new(FakeEventPublisher::new()),
+        Arc::new(InMemAdminRepository::new()),
+        Arc::new(InMemContractRepository::new()),
+        Arc::new(InMemNotificationRepository::new()),
+        Arc::new(InMemPayoutRepository::new()),
+        Arc::new(InMemPlanRepository::new()),
+        Arc::new(InMemPublicationRepository::new()),
+        Arc::new(InMemSubscriptionRepository::new()),
+        Arc::new(FakePaymentGateway::new()),
+    )
+}
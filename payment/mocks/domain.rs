@@ -0,0 +1,51 @@
+use crate::domain::admin::{Admin, AdminId};
+use crate::domain::plan::{Period, Plan, PlanId};
+use crate::domain::publication::{Publication, PublicationId, Statistics};
+use crate::domain::user::{User, UserId};
+
+pub fn user1() -> User {
+    User::new(UserId::new("#user01").unwrap()).unwrap()
+}
+
+pub fn admin1() -> Admin {
+    Admin::new(AdminId::new("#admin01").unwrap(), "CRU").unwrap()
+}
+
+pub fn plan1() -> Plan {
+    Plan::new(
+        PlanId::new("#plan01").unwrap(),
+        "Monthly",
+        250.0,
+        Period::Monthly,
+    )
+    .unwrap()
+}
+
+pub fn plan2() -> Plan {
+    Plan::new(
+        PlanId::new("#plan02").unwrap(),
+        "Yearly",
+        2500.0,
+        Period::Yearly,
+    )
+    .unwrap()
+}
+
+// Popular enough to apply for a contract.
+pub fn publication1() -> Publication {
+    Publication::new(
+        PublicationId::new("#publication01").unwrap(),
+        user1(),
+        Statistics::new(1500, 1200, 300, 40, 4.5).unwrap(),
+    )
+    .unwrap()
+}
+
+pub fn publication2() -> Publication {
+    Publication::new(
+        PublicationId::new("#publication02").unwrap(),
+        user1(),
+        Statistics::new(10, 5, 1, 0, 0.0).unwrap(),
+    )
+    .unwrap()
+}
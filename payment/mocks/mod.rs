@@ -0,0 +1,4 @@
+mod container;
+mod domain;
+pub use container::*;
+pub use domain::*;
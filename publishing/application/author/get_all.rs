@@ -1,5 +1,6 @@
 use serde::Serialize;
 
+use common::model::{Pagination, PaginationQuery};
 use common::result::Result;
 
 use crate::application::dtos::AuthorDto;
@@ -7,9 +8,13 @@ use crate::domain::author::AuthorRepository;
 use crate::domain::collection::CollectionRepository;
 use crate::domain::publication::PublicationRepository;
 
+const SORT_FIELDS: &[&str] = &["created_at", "username"];
+
 #[derive(Serialize)]
 pub struct GetAllResponse {
     pub authors: Vec<AuthorDto>,
+    pub next_cursor: Option<String>,
+    pub total: usize,
 }
 
 pub struct GetAll<'a> {
@@ -31,11 +36,13 @@ impl<'a> GetAll<'a> {
         }
     }
 
-    pub async fn exec(&self) -> Result<GetAllResponse> {
+    // Only authors with publications or collections are listed, so they are paginated here.
+    pub async fn exec(&self, pagination: PaginationQuery) -> Result<GetAllResponse> {
+        let pagination = Pagination::from_query(pagination, SORT_FIELDS)?;
         let authors = self.author_repo.find_all().await?;
 
-        let mut author_dtos = Vec::new();
-        for author in authors.iter() {
+        let mut active_authors = Vec::new();
+        for author in authors.into_iter() {
             let publication_count = self
                 .publication_repo
                 .find_by_author_id(&author.base().id())
@@ -52,15 +59,35 @@ impl<'a> GetAll<'a> {
                 continue;
             }
 
-            author_dtos.push(
-                AuthorDto::from(author)
-                    .publication_count(publication_count)
-                    .collection_count(collection_count),
-            )
+            active_authors.push((author, publication_count, collection_count));
         }
 
+        let authors = match pagination.sort() {
+            "created_at" => pagination.paginate(
+                active_authors,
+                |(author, _, _)| author.base().id().value(),
+                |(author, _, _)| *author.base().created_at(),
+            ),
+            "username" => pagination.paginate(
+                active_authors,
+                |(author, _, _)| author.base().id().value(),
+                |(author, _, _)| author.username().to_owned(),
+            ),
+            _ => Err(pagination.unsupported_sort()),
+        }?;
+
         Ok(GetAllResponse {
-            authors: author_dtos,
+            authors: authors
+                .items()
+                .iter()
+                .map(|(author, publication_count, collection_count)| {
+                    AuthorDto::from(author)
+                        .publication_count(*publication_count)
+                        .collection_count(*collection_count)
+                })
+                .collect(),
+            next_cursor: authors.next_cursor().map(str::to_owned),
+            total: authors.total(),
         })
     }
 }
@@ -1,5 +1,6 @@
 use serde::Serialize;
 
+use common::model::{Pagination, PaginationQuery};
 use common::result::Result;
 
 use crate::application::dtos::{AuthorDto, CategoryDto, CollectionDto, PublicationDto};
@@ -8,9 +9,13 @@ use crate::domain::category::CategoryRepository;
 use crate::domain::collection::CollectionRepository;
 use crate::domain::publication::PublicationRepository;
 
+const SORT_FIELDS: &[&str] = &["created_at", "name"];
+
 #[derive(Serialize)]
 pub struct GetAllResponse {
     collections: Vec<CollectionDto>,
+    next_cursor: Option<String>,
+    total: usize,
 }
 
 pub struct GetAll<'a> {
@@ -35,11 +40,12 @@ impl<'a> GetAll<'a> {
         }
     }
 
-    pub async fn exec(&self) -> Result<GetAllResponse> {
-        let collections = self.collection_repo.find_all().await?;
+    pub async fn exec(&self, pagination: PaginationQuery) -> Result<GetAllResponse> {
+        let pagination = Pagination::from_query(pagination, SORT_FIELDS)?;
+        let collections = self.collection_repo.find_page(&pagination).await?;
 
         let mut collection_dtos = Vec::new();
-        for collection in collections.iter() {
+        for collection in collections.items().iter() {
             let author = self.author_repo.find_by_id(collection.author_id()).await?;
             let category = self
                 .category_repo
@@ -76,6 +82,8 @@ impl<'a> GetAll<'a> {
 
         Ok(GetAllResponse {
             collections: collection_dtos,
+            next_cursor: collections.next_cursor().map(str::to_owned),
+            total: collections.total(),
         })
     }
 }
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use common::error::Error;
+use common::event::{Event, EventHandler, EventPublisher, RetryPolicy};
+use common::result::Result;
+use shared::event::ContractEvent;
+
+use crate::domain::publication::{PublicationId, PublicationRepository};
+
+// Publications get a contract when it is approved and lose it when it is cancelled, so only
+// subscribed readers can read them meanwhile.
+pub struct ContractHandler {
+    event_pub: Arc<dyn EventPublisher>,
+
+    publication_repo: Arc<dyn PublicationRepository>,
+}
+
+impl ContractHandler {
+    pub fn new(
+        event_pub: Arc<dyn EventPublisher>,
+        publication_repo: Arc<dyn PublicationRepository>,
+    ) -> Self {
+        ContractHandler {
+            event_pub,
+            publication_repo,
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler for ContractHandler {
+    fn topic(&self) -> &str {
+        "contract"
+    }
+
+    async fn handle(&mut self, event: &Event) -> Result<bool> {
+        let event = serde_json::from_slice(event.payload())
+            .map_err(|err| Error::new("handler", "deserialize").wrap_raw(err).build())?;
+
+        let (publication_id, contract) = match event {
+            ContractEvent::Approved { publication_id, .. } => (publication_id, true),
+            ContractEvent::Cancelled { publication_id, .. } => (publication_id, false),
+            _ => return Ok(false),
+        };
+
+        let mut publication = self
+            .publication_repo
+            .find_by_id(&PublicationId::new(publication_id)?)
+            .await?;
+
+        // Events can be delivered more than once.
+        if publication.has_contract() == contract {
+            return Ok(true);
+        }
+
+        if contract {
+            publication.add_contract()?;
+        } else {
+            publication.remove_contract()?;
+        }
+
+        self.publication_repo.save(&mut publication).await?;
+
+        self.event_pub
+            .publish_all(publication.base().events()?)
+            .await?;
+
+        Ok(true)
+    }
+
+    // The publication could be saved by somebody else at the same time.
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::new(3, Duration::from_millis(100))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::event::ToEvent;
+    use common::mocks::FakeEventPublisher;
+
+    use crate::infrastructure::persistence::inmem::InMemPublicationRepository;
+    use crate::mocks;
+
+    fn approved() -> Event {
+        ContractEvent::Approved {
+            id: "#contract01".to_owned(),
+            publication_id: "#publication01".to_owned(),
+            author_id: "#user01".to_owned(),
+            content_manager_id: "#admin01".to_owned(),
+        }
+        .to_event()
+        .unwrap()
+    }
+
+    fn cancelled() -> Event {
+        ContractEvent::Cancelled {
+            id: "#contract01".to_owned(),
+            publication_id: "#publication01".to_owned(),
+            author_id: "#user01".to_owned(),
+        }
+        .to_event()
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn add_and_remove_contract() {
+        let event_pub = Arc::new(FakeEventPublisher::new());
+        let publication_repo = Arc::new(InMemPublicationRepository::new());
+        let mut handler = ContractHandler::new(event_pub.clone(), publication_repo.clone());
+
+        let mut publication = mocks::published_publication1();
+        publication_repo.save(&mut publication).await.unwrap();
+        let id = publication.base().id().clone();
+
+        assert!(handler.handle(&approved()).await.unwrap());
+        assert!(publication_repo
+            .find_by_id(&id)
+            .await
+            .unwrap()
+            .has_contract());
+        assert!(event_pub
+            .events()
+            .await
+            .iter()
+            .any(|event| event.code() == "contract-added"));
+
+        // Delivered again
+        assert!(handler.handle(&approved()).await.unwrap());
+
+        assert!(handler.handle(&cancelled()).await.unwrap());
+        assert!(!publication_repo
+            .find_by_id(&id)
+            .await
+            .unwrap()
+            .has_contract());
+    }
+}
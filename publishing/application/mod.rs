@@ -2,4 +2,5 @@ pub mod author;
 pub mod category;
 pub mod collection;
 pub mod dtos;
+pub mod handler;
 pub mod publication;
@@ -4,6 +4,8 @@ use common::result::Result;
 use crate::domain::content_manager::{ContentManagerId, ContentManagerRepository};
 use crate::domain::publication::{PublicationId, PublicationRepository};
 
+const CONFLICT_RETRIES: u32 = 3;
+
 // TODO: add comment
 pub struct Approve<'a> {
     event_pub: &'a dyn EventPublisher,
@@ -33,17 +35,27 @@ impl<'a> Approve<'a> {
             .await?;
 
         let publication_id = PublicationId::new(publication_id)?;
-        let mut publication = self.publication_repo.find_by_id(&publication_id).await?;
-
-        publication.approve(&content_manager)?;
-
-        self.publication_repo.save(&mut publication).await?;
 
-        self.event_pub
-            .publish_all(publication.base().events()?)
-            .await?;
-
-        Ok(())
+        // The publication is approved again over its latest version if it was modified while
+        // being approved. It could not be approvable anymore.
+        let mut retries = 0;
+        loop {
+            let mut publication = self.publication_repo.find_by_id(&publication_id).await?;
+
+            publication.approve(&content_manager)?;
+
+            match self.publication_repo.save(&mut publication).await {
+                Ok(()) => {
+                    self.event_pub
+                        .publish_all(publication.base().events()?)
+                        .await?;
+
+                    return Ok(());
+                }
+                Err(err) if err.is_conflict() && retries < CONFLICT_RETRIES => retries += 1,
+                Err(err) => return Err(err),
+            }
+        }
     }
 }
 
@@ -5,6 +5,8 @@ use crate::domain::interaction::InteractionService;
 use crate::domain::publication::{PublicationId, PublicationRepository};
 use crate::domain::reader::{ReaderId, ReaderRepository};
 
+const CONFLICT_RETRIES: u32 = 3;
+
 pub struct Like<'a> {
     event_pub: &'a dyn EventPublisher,
 
@@ -40,7 +42,18 @@ impl<'a> Like<'a> {
             .add_like(&reader, &mut publication)
             .await?;
 
-        self.publication_repo.save(&mut publication).await?;
+        // Other interactions might have updated the statistics in the meantime. The like is
+        // already stored, so only the latest publication is liked again.
+        let mut retries = 0;
+        while let Err(err) = self.publication_repo.save(&mut publication).await {
+            if !err.is_conflict() || retries == CONFLICT_RETRIES {
+                return Err(err);
+            }
+            retries += 1;
+
+            publication = self.publication_repo.find_by_id(&publication_id).await?;
+            publication.like(&reader)?;
+        }
 
         self.event_pub
             .publish_all(publication.base().events()?)
@@ -54,8 +67,73 @@ impl<'a> Like<'a> {
 mod tests {
     use super::*;
 
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use async_trait::async_trait;
+
+    use common::model::{Paginated, Pagination};
+
+    use crate::domain::author::AuthorId;
+    use crate::domain::category::CategoryId;
+    use crate::domain::publication::{Publication, PublicationFilter};
+    use crate::infrastructure::persistence::inmem::InMemPublicationRepository;
     use crate::mocks;
 
+    // Another reader likes the publication right before it is saved for the first time.
+    struct RacingPublicationRepository {
+        repo: InMemPublicationRepository,
+        raced: AtomicBool,
+    }
+
+    #[async_trait]
+    impl PublicationRepository for RacingPublicationRepository {
+        async fn next_id(&self) -> Result<PublicationId> {
+            self.repo.next_id().await
+        }
+
+        async fn find_all(&self) -> Result<Vec<Publication>> {
+            self.repo.find_all().await
+        }
+
+        async fn find_by_id(&self, id: &PublicationId) -> Result<Publication> {
+            self.repo.find_by_id(id).await
+        }
+
+        async fn find_by_author_id(&self, author_id: &AuthorId) -> Result<Vec<Publication>> {
+            self.repo.find_by_author_id(author_id).await
+        }
+
+        async fn find_by_category_id(&self, category_id: &CategoryId) -> Result<Vec<Publication>> {
+            self.repo.find_by_category_id(category_id).await
+        }
+
+        async fn find_by_status(&self, status: &str) -> Result<Vec<Publication>> {
+            self.repo.find_by_status(status).await
+        }
+
+        async fn search(&self, text: &str) -> Result<Vec<Publication>> {
+            self.repo.search(text).await
+        }
+
+        async fn find_page(
+            &self,
+            filter: &PublicationFilter,
+            pagination: &Pagination,
+        ) -> Result<Paginated<Publication>> {
+            self.repo.find_page(filter, pagination).await
+        }
+
+        async fn save(&self, publication: &mut Publication) -> Result<()> {
+            if !self.raced.swap(true, Ordering::SeqCst) {
+                let mut other = self.repo.find_by_id(publication.base().id()).await?;
+                other.like(&mocks::author_as_reader1())?;
+                self.repo.save(&mut other).await?;
+            }
+
+            self.repo.save(publication).await
+        }
+    }
+
     #[tokio::test]
     async fn valid() {
         let c = mocks::container();
@@ -135,4 +213,38 @@ mod tests {
             .await
             .is_err());
     }
+
+    #[tokio::test]
+    async fn retry_on_conflict() {
+        let c = mocks::container();
+        let publication_repo = RacingPublicationRepository {
+            repo: InMemPublicationRepository::new(),
+            raced: AtomicBool::new(true),
+        };
+        let uc = Like::new(
+            c.event_pub(),
+            &publication_repo,
+            c.reader_repo(),
+            c.interaction_serv(),
+        );
+
+        let mut reader = mocks::reader1();
+        c.reader_repo().save(&mut reader).await.unwrap();
+        let mut publication = mocks::published_publication1();
+        publication_repo.save(&mut publication).await.unwrap();
+        publication_repo.raced.store(false, Ordering::SeqCst);
+
+        uc.exec(
+            reader.base().id().to_string(),
+            publication.base().id().to_string(),
+        )
+        .await
+        .unwrap();
+
+        let publication = publication_repo
+            .find_by_id(&publication.base().id())
+            .await
+            .unwrap();
+        assert_eq!(publication.statistics().likes(), 2);
+    }
 }
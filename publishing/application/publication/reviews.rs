@@ -1,5 +1,6 @@
 use serde::Serialize;
 
+use common::model::{Pagination, PaginationQuery};
 use common::result::Result;
 
 use crate::application::dtos::{ReaderDto, ReviewDto};
@@ -7,9 +8,13 @@ use crate::domain::interaction::InteractionRepository;
 use crate::domain::publication::PublicationId;
 use crate::domain::reader::ReaderRepository;
 
+const SORT_FIELDS: &[&str] = &["date", "stars"];
+
 #[derive(Serialize)]
 pub struct ReviewsResponse {
     pub reviews: Vec<ReviewDto>,
+    pub next_cursor: Option<String>,
+    pub total: usize,
 }
 
 pub struct Reviews<'a> {
@@ -28,14 +33,19 @@ impl<'a> Reviews<'a> {
         }
     }
 
-    pub async fn exec(&self, publication_id: String) -> Result<ReviewsResponse> {
+    pub async fn exec(
+        &self,
+        publication_id: String,
+        pagination: PaginationQuery,
+    ) -> Result<ReviewsResponse> {
+        let pagination = Pagination::from_query(pagination, SORT_FIELDS)?;
         let reviews = self
             .interaction_repo
-            .find_reviews(None, Some(&PublicationId::new(publication_id)?), None, None)
+            .find_reviews_page(&PublicationId::new(publication_id)?, &pagination)
             .await?;
 
         let mut review_dtos = Vec::new();
-        for review in reviews.iter() {
+        for review in reviews.items().iter() {
             let reader = self
                 .reader_repo
                 .find_by_id(review.base().reader_id())
@@ -45,6 +55,8 @@ impl<'a> Reviews<'a> {
 
         Ok(ReviewsResponse {
             reviews: review_dtos,
+            next_cursor: reviews.next_cursor().map(str::to_owned),
+            total: reviews.total(),
         })
     }
 }
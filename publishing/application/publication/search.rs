@@ -1,12 +1,15 @@
 use serde::{Deserialize, Serialize};
 
+use common::model::{Pagination, PaginationQuery};
 use common::result::Result;
 
 use crate::application::dtos::{AuthorDto, CategoryDto, PublicationDto};
 use crate::domain::author::{AuthorId, AuthorRepository};
-use crate::domain::category::CategoryRepository;
+use crate::domain::category::{CategoryId, CategoryRepository};
 use crate::domain::content_manager::{ContentManagerId, ContentManagerRepository};
-use crate::domain::publication::{PublicationRepository, Status};
+use crate::domain::publication::{PublicationFilter, PublicationRepository};
+
+const SORT_FIELDS: &[&str] = &["created_at", "name"];
 
 #[derive(Deserialize)]
 pub struct SearchCommand {
@@ -19,6 +22,8 @@ pub struct SearchCommand {
 #[derive(Serialize)]
 pub struct SearchResponse {
     publications: Vec<PublicationDto>,
+    next_cursor: Option<String>,
+    total: usize,
 }
 
 pub struct Search<'a> {
@@ -43,60 +48,39 @@ impl<'a> Search<'a> {
         }
     }
 
-    pub async fn exec(&self, auth_id: String, cmd: SearchCommand) -> Result<SearchResponse> {
+    pub async fn exec(
+        &self,
+        auth_id: String,
+        cmd: SearchCommand,
+        pagination: PaginationQuery,
+    ) -> Result<SearchResponse> {
         let content_manager_id = ContentManagerId::new(&auth_id)?;
         let is_content_manager = self
             .content_manager_repo
             .find_by_id(&content_manager_id)
             .await
             .is_ok();
-        let author_id = AuthorId::new(&auth_id)?;
-
-        let mut publications = self.publication_repo.find_all().await?;
-
-        if let Some(author_id) = cmd.author_id {
-            publications = publications
-                .into_iter()
-                .filter(|publication| publication.author_id().value() == author_id)
-                .collect();
-        }
-
-        if let Some(category_id) = cmd.category_id {
-            publications = publications
-                .into_iter()
-                .filter(|publication| publication.header().category_id().value() == category_id)
-                .collect();
-        }
-
-        if let Some(status) = cmd.status {
-            publications = publications
-                .into_iter()
-                .filter(|publication| {
-                    publication.status_history().current().status().to_string() == status
-                })
-                .collect();
-        }
-
-        if let Some(name) = cmd.name {
-            publications = publications
-                .into_iter()
-                .filter(|publication| publication.header().name().value().contains(&name))
-                .collect();
-        }
-
-        publications = publications
-            .into_iter()
-            .filter(|publication| {
-                if is_content_manager || publication.author_id() == &author_id {
-                    return true;
-                }
-
-                matches!(publication.status_history().current().status(), Status::Published { .. })
-            })
-            .collect();
+        let pagination = Pagination::from_query(pagination, SORT_FIELDS)?;
+
+        let filter = PublicationFilter {
+            author_id: cmd.author_id.map(AuthorId::new).transpose()?,
+            category_id: cmd.category_id.map(CategoryId::new).transpose()?,
+            status: cmd.status,
+            name: cmd.name,
+            visible_to: if is_content_manager {
+                None
+            } else {
+                Some(AuthorId::new(&auth_id)?)
+            },
+        };
+
+        let publications = self
+            .publication_repo
+            .find_page(&filter, &pagination)
+            .await?;
 
         let mut publication_dtos = Vec::new();
-        for publication in publications.iter() {
+        for publication in publications.items().iter() {
             let author = self.author_repo.find_by_id(publication.author_id()).await?;
             let category = self
                 .category_repo
@@ -112,6 +96,8 @@ impl<'a> Search<'a> {
 
         Ok(SearchResponse {
             publications: publication_dtos,
+            next_cursor: publications.next_cursor().map(str::to_owned),
+            total: publications.total(),
         })
     }
 }
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use common::event::{EventPublisher, EventSubscriber};
 use common::result::Result;
 
+use crate::application::handler::ContractHandler;
 use crate::domain::author::AuthorRepository;
 use crate::domain::category::CategoryRepository;
 use crate::domain::collection::CollectionRepository;
@@ -28,7 +29,7 @@ pub struct Container<EPub> {
 
 impl<EPub> Container<EPub>
 where
-    EPub: EventPublisher,
+    EPub: EventPublisher + 'static,
 {
     pub fn new(
         event_pub: Arc<EPub>,
@@ -59,10 +60,17 @@ where
         }
     }
 
-    pub async fn subscribe<ES>(&self, _event_sub: &ES) -> Result<()>
+    pub async fn subscribe<ES>(&self, event_sub: &ES) -> Result<()>
     where
         ES: EventSubscriber,
     {
+        event_sub
+            .subscribe(Box::new(ContractHandler::new(
+                self.event_pub.clone(),
+                self.publication_repo.clone(),
+            )))
+            .await?;
+
         Ok(())
     }
 
@@ -23,10 +23,18 @@ impl Category {
         })
     }
 
+    pub fn build(base: AggregateRoot<CategoryId, Event>, name: Name) -> Self {
+        Category { base, name }
+    }
+
     pub fn base(&self) -> &AggregateRoot<CategoryId, Event> {
         &self.base
     }
 
+    pub fn base_mut(&mut self) -> &mut AggregateRoot<CategoryId, Event> {
+        &mut self.base
+    }
+
     pub fn name(&self) -> &Name {
         &self.name
     }
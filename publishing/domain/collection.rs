@@ -3,13 +3,16 @@ mod repository;
 pub use item::*;
 pub use repository::*;
 
+use chrono::{DateTime, Utc};
+
 use common::error::Error;
+use common::event::ApplyEvent;
 use common::model::{AggregateRoot, StringId};
 use common::result::Result;
 use shared::event::CollectionEvent;
 
 use crate::domain::author::AuthorId;
-use crate::domain::publication::{Header, Publication, PublicationId};
+use crate::domain::publication::{header_from_event, Header, Publication, PublicationId};
 
 pub type CollectionId = StringId;
 
@@ -49,10 +52,28 @@ impl Collection {
         Ok(collection)
     }
 
+    pub fn build(
+        base: AggregateRoot<CollectionId, CollectionEvent>,
+        author_id: AuthorId,
+        header: Header,
+        items: Vec<Item>,
+    ) -> Self {
+        Collection {
+            base,
+            author_id,
+            header,
+            items,
+        }
+    }
+
     pub fn base(&self) -> &AggregateRoot<CollectionId, CollectionEvent> {
         &self.base
     }
 
+    pub fn base_mut(&mut self) -> &mut AggregateRoot<CollectionId, CollectionEvent> {
+        &mut self.base
+    }
+
     pub fn author_id(&self) -> &AuthorId {
         &self.author_id
     }
@@ -123,3 +144,85 @@ impl Collection {
         Ok(())
     }
 }
+
+impl Collection {
+    // Rebuilds a collection from its event stream. The first event must be `Created`. Replayed
+    // events are not recorded again.
+    pub fn from_events<I>(events: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (CollectionEvent, DateTime<Utc>)>,
+    {
+        let mut events = events.into_iter();
+
+        let mut collection = match events.next() {
+            Some((
+                CollectionEvent::Created {
+                    id,
+                    author_id,
+                    name,
+                    synopsis,
+                    category_id,
+                    tags,
+                    cover,
+                },
+                date,
+            )) => Collection::build(
+                AggregateRoot::build(CollectionId::new(id)?, date, None, None, 1),
+                AuthorId::new(author_id)?,
+                header_from_event(name, synopsis, category_id, tags, cover)?,
+                Vec::new(),
+            ),
+            _ => return Err(Error::new("collection", "stream_without_creation")),
+        };
+
+        for (event, date) in events {
+            collection.apply_at(event, date)?;
+            collection.base.replay_event();
+        }
+
+        Ok(collection)
+    }
+
+    pub fn apply_at(&mut self, event: CollectionEvent, date: DateTime<Utc>) -> Result<()> {
+        match event {
+            CollectionEvent::Created { .. } => {
+                return Err(Error::new("collection", "already_created"));
+            }
+            CollectionEvent::HeaderUpdated {
+                name,
+                synopsis,
+                category_id,
+                tags,
+                cover,
+                ..
+            } => {
+                self.header = header_from_event(name, synopsis, category_id, tags, cover)?;
+            }
+            CollectionEvent::PublicationAdded { publication_id, .. } => {
+                self.items
+                    .push(Item::build(PublicationId::new(publication_id)?, date));
+            }
+            CollectionEvent::PublicationRemoved { publication_id, .. } => {
+                self.items
+                    .retain(|item| item.publication_id().value() != publication_id);
+            }
+            CollectionEvent::Deleted { .. } => {
+                self.base = AggregateRoot::build(
+                    self.base.id().clone(),
+                    *self.base.created_at(),
+                    self.base.updated_at().cloned(),
+                    Some(date),
+                    self.base.version(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ApplyEvent<CollectionEvent> for Collection {
+    fn apply(&mut self, event: CollectionEvent) -> Result<()> {
+        self.apply_at(event, Utc::now())
+    }
+}
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 
+use common::model::{Paginated, Pagination};
 use common::result::Result;
 
 use crate::domain::author::AuthorId;
@@ -15,6 +16,8 @@ pub trait CollectionRepository: Sync + Send {
     async fn find_by_author_id(&self, author_id: &AuthorId) -> Result<Vec<Collection>>;
     async fn find_by_category_id(&self, category_id: &CategoryId) -> Result<Vec<Collection>>;
     async fn search(&self, text: &str) -> Result<Vec<Collection>>;
+    // Sortable by created_at and name.
+    async fn find_page(&self, pagination: &Pagination) -> Result<Paginated<Collection>>;
 
     async fn save(&self, collection: &mut Collection) -> Result<()>;
 }
@@ -21,6 +21,14 @@ impl Base {
         })
     }
 
+    pub fn build(reader_id: ReaderId, publication_id: PublicationId, date: DateTime<Utc>) -> Self {
+        Base {
+            reader_id,
+            publication_id,
+            date,
+        }
+    }
+
     pub fn reader_id(&self) -> &ReaderId {
         &self.reader_id
     }
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 
+use common::model::{Paginated, Pagination};
 use common::result::Result;
 
 use crate::domain::interaction::{Like, Reading, Review, View};
@@ -37,6 +38,12 @@ pub trait InteractionRepository: Sync + Send {
         from: Option<&DateTime<Utc>>,
         to: Option<&DateTime<Utc>>,
     ) -> Result<Vec<Review>>;
+    // Reviews of a publication, sortable by date and stars. The cursor is the reader id.
+    async fn find_reviews_page(
+        &self,
+        publication_id: &PublicationId,
+        pagination: &Pagination,
+    ) -> Result<Paginated<Review>>;
 
     async fn save_view(&self, view: &mut View) -> Result<()>;
     async fn save_reading(&self, reading: &mut Reading) -> Result<()>;
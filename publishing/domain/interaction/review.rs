@@ -25,6 +25,14 @@ impl Review {
         })
     }
 
+    pub fn build(base: Base, stars: Stars, comment: Comment) -> Self {
+        Review {
+            base,
+            stars,
+            comment,
+        }
+    }
+
     pub fn base(&self) -> &Base {
         &self.base
     }
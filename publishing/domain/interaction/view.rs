@@ -18,6 +18,10 @@ impl View {
         })
     }
 
+    pub fn build(base: Base, unique: bool) -> Self {
+        View { base, unique }
+    }
+
     pub fn base(&self) -> &Base {
         &self.base
     }
@@ -21,13 +21,16 @@ pub use status::*;
 pub use synopsis::*;
 pub use tag::*;
 
+use chrono::{DateTime, Utc};
+
 use common::error::Error;
-use common::model::{AggregateRoot, StatusHistory, StringId};
+use common::event::ApplyEvent;
+use common::model::{AggregateRoot, StatusHistory, StatusItem, StringId};
 use common::result::Result;
-use shared::event::PublicationEvent;
+use shared::event::{PublicationEvent, PublicationFrame, PublicationImage, PublicationPage};
 
 use crate::domain::author::{Author, AuthorId};
-use crate::domain::content_manager::ContentManager;
+use crate::domain::content_manager::{ContentManager, ContentManagerId};
 use crate::domain::interaction::{Comment, Like, Reading, Review, Stars, View};
 use crate::domain::reader::Reader;
 
@@ -76,10 +79,34 @@ impl Publication {
         Ok(publication)
     }
 
+    pub fn build(
+        base: AggregateRoot<PublicationId, PublicationEvent>,
+        author_id: AuthorId,
+        header: Header,
+        pages: Vec<Page>,
+        contract: bool,
+        statistics: Statistics,
+        status_history: StatusHistory<Status>,
+    ) -> Self {
+        Publication {
+            base,
+            author_id,
+            header,
+            pages,
+            contract,
+            statistics,
+            status_history,
+        }
+    }
+
     pub fn base(&self) -> &AggregateRoot<PublicationId, PublicationEvent> {
         &self.base
     }
 
+    pub fn base_mut(&mut self) -> &mut AggregateRoot<PublicationId, PublicationEvent> {
+        &mut self.base
+    }
+
     pub fn author_id(&self) -> &AuthorId {
         &self.author_id
     }
@@ -139,6 +166,7 @@ impl Publication {
         self.base.record_event(PublicationEvent::PagesUpdated {
             id: self.base().id().to_string(),
             pages_count: self.pages().len(),
+            pages: self.pages().iter().map(page_to_event).collect(),
         });
 
         Ok(())
@@ -357,7 +385,7 @@ impl Publication {
 
         self.contract = false;
 
-        self.base.record_event(PublicationEvent::ContractAdded {
+        self.base.record_event(PublicationEvent::ContractRemoved {
             id: self.base().id().to_string(),
         });
 
@@ -432,6 +460,7 @@ impl Publication {
                 .collect(),
             cover: self.header().cover().url().to_string(),
             pages_count: self.pages().len(),
+            admin_id: Some(content_manager.base().id().to_string()),
         });
 
         Ok(())
@@ -451,6 +480,7 @@ impl Publication {
 
         self.base.record_event(PublicationEvent::Rejected {
             id: self.base().id().to_string(),
+            admin_id: Some(content_manager.base().id().to_string()),
         });
 
         Ok(())
@@ -467,10 +497,190 @@ impl Publication {
     }
 }
 
+impl Publication {
+    // Rebuilds a publication from its event stream. The first event must be `Created`. Replayed
+    // events are not recorded again.
+    pub fn from_events<I>(events: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (PublicationEvent, DateTime<Utc>)>,
+    {
+        let mut events = events.into_iter();
+
+        let mut publication = match events.next() {
+            Some((
+                PublicationEvent::Created {
+                    id,
+                    author_id,
+                    name,
+                    synopsis,
+                    category_id,
+                    tags,
+                    cover,
+                },
+                date,
+            )) => Publication::build(
+                AggregateRoot::build(PublicationId::new(id)?, date, None, None, 1),
+                AuthorId::new(author_id)?,
+                header_from_event(name, synopsis, category_id, tags, cover)?,
+                Vec::new(),
+                false,
+                Statistics::default(),
+                StatusHistory::build(vec![StatusItem::build(Status::Draft, date)])?,
+            ),
+            _ => return Err(Error::new("publication", "stream_without_creation")),
+        };
+
+        for (event, date) in events {
+            publication.apply_at(event, date)?;
+            publication.base.replay_event();
+        }
+
+        Ok(publication)
+    }
+
+    pub fn apply_at(&mut self, event: PublicationEvent, date: DateTime<Utc>) -> Result<()> {
+        match event {
+            PublicationEvent::Created { .. } => {
+                return Err(Error::new("publication", "already_created"));
+            }
+            PublicationEvent::HeaderUpdated {
+                name,
+                synopsis,
+                category_id,
+                tags,
+                cover,
+                ..
+            } => {
+                self.header = header_from_event(name, synopsis, category_id, tags, cover)?;
+            }
+            PublicationEvent::PagesUpdated { pages, .. } => {
+                self.pages = pages
+                    .into_iter()
+                    .map(page_from_event)
+                    .collect::<Result<Vec<Page>>>()?;
+            }
+            PublicationEvent::ChangedToDraft { .. } => {
+                self.status_history
+                    .add_item(StatusItem::build(Status::Draft, date));
+            }
+            PublicationEvent::ApprovalWaited { .. } => {
+                self.status_history
+                    .add_item(StatusItem::build(Status::WaitingApproval, date));
+            }
+            PublicationEvent::Published { admin_id, .. } => {
+                let admin_id = content_manager_from_event(admin_id)?;
+                self.status_history
+                    .add_item(StatusItem::build(Status::Published { admin_id }, date));
+            }
+            PublicationEvent::Rejected { admin_id, .. } => {
+                let admin_id = content_manager_from_event(admin_id)?;
+                self.status_history
+                    .add_item(StatusItem::build(Status::Rejected { admin_id }, date));
+            }
+            PublicationEvent::Deleted { .. } => {
+                self.base = AggregateRoot::build(
+                    self.base.id().clone(),
+                    *self.base.created_at(),
+                    self.base.updated_at().cloned(),
+                    Some(date),
+                    self.base.version(),
+                );
+            }
+            PublicationEvent::StatisticsUpdated {
+                views,
+                unique_views,
+                readings,
+                likes,
+                reviews,
+                stars,
+                ..
+            } => {
+                self.statistics =
+                    Statistics::new(views, unique_views, readings, likes, reviews, stars)?;
+            }
+            PublicationEvent::ContractAdded { .. } => {
+                self.contract = true;
+            }
+            PublicationEvent::ContractRemoved { .. } => {
+                self.contract = false;
+            }
+            // Interactions are reflected by the StatisticsUpdated event recorded after them.
+            PublicationEvent::Viewed { .. }
+            | PublicationEvent::Read { .. }
+            | PublicationEvent::Liked { .. }
+            | PublicationEvent::Unliked { .. }
+            | PublicationEvent::Reviewed { .. }
+            | PublicationEvent::ReviewDeleted { .. } => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl ApplyEvent<PublicationEvent> for Publication {
+    fn apply(&mut self, event: PublicationEvent) -> Result<()> {
+        self.apply_at(event, Utc::now())
+    }
+}
+
+fn content_manager_from_event(admin_id: Option<String>) -> Result<ContentManagerId> {
+    match admin_id {
+        Some(admin_id) => ContentManagerId::new(admin_id),
+        None => Err(Error::new("publication", "event_without_content_manager")),
+    }
+}
+
+fn page_to_event(page: &Page) -> PublicationPage {
+    PublicationPage {
+        number: page.number(),
+        images: page
+            .images()
+            .iter()
+            .map(|image| PublicationImage {
+                url: image.url().to_owned(),
+                frames: image
+                    .frames()
+                    .iter()
+                    .map(|frame| PublicationFrame {
+                        order: frame.order(),
+                        x: frame.position().x(),
+                        y: frame.position().y(),
+                        width: frame.size().width(),
+                        height: frame.size().height(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+fn page_from_event(event_page: PublicationPage) -> Result<Page> {
+    let mut images = Vec::new();
+    for event_image in event_page.images.into_iter() {
+        let mut frames = Vec::new();
+        for frame in event_image.frames.into_iter() {
+            frames.push(Frame::new(
+                frame.order,
+                Position::new(frame.x, frame.y)?,
+                Size::new(frame.width, frame.height)?,
+            )?);
+        }
+
+        let mut image = Image::new(event_image.url)?;
+        image.set_frames(frames)?;
+        images.push(image);
+    }
+
+    let mut page = Page::new(event_page.number)?;
+    page.set_images(images)?;
+    Ok(page)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::domain::reader::ReaderId;
     use crate::mocks;
 
     #[test]
@@ -566,4 +776,59 @@ mod tests {
         // First events: Created, PagesUpdated, ApprovalWaited (publish), Published (approve)
         assert!(publication.base().events().unwrap().len() > 0);
     }
+
+    #[test]
+    fn apply_events() {
+        let mut publication = mocks::publication1().clone();
+        let id = publication.base().id().to_string();
+
+        publication
+            .apply_all(vec![
+                PublicationEvent::ApprovalWaited { id: id.clone() },
+                PublicationEvent::Rejected {
+                    id: id.clone(),
+                    admin_id: Some("#content-manager01".to_owned()),
+                },
+                PublicationEvent::ContractAdded { id: id.clone() },
+            ])
+            .unwrap();
+        assert!(matches!(
+            publication.status_history().current().status(),
+            Status::Rejected { .. }
+        ));
+        assert!(publication.has_contract());
+        assert!(publication.base().events().unwrap().is_empty());
+
+        assert!(publication
+            .apply(PublicationEvent::Rejected { id, admin_id: None })
+            .is_err());
+    }
+
+    #[test]
+    fn remove_contract_event() {
+        let mut publication = mocks::published_publication1();
+        publication.add_contract().unwrap();
+        publication.remove_contract().unwrap();
+
+        let events = publication.base().events().unwrap();
+        assert_eq!(events.last().unwrap().code(), "contract-removed");
+    }
+
+    #[test]
+    fn read_with_contract() {
+        let mut publication = mocks::published_publication1();
+        publication.add_contract().unwrap();
+
+        assert!(publication.read(&mocks::reader1()).is_err());
+
+        let reader = Reader::build(
+            ReaderId::new("#reader01").unwrap(),
+            "reader-01",
+            "Name 01",
+            "Lastname 01",
+            true,
+        )
+        .unwrap();
+        assert!(publication.read(&reader).is_ok());
+    }
 }
@@ -54,3 +54,22 @@ impl Header {
         &self.cover
     }
 }
+
+// Builds a header from the fields of a Created or HeaderUpdated event.
+pub(crate) fn header_from_event(
+    name: String,
+    synopsis: String,
+    category_id: String,
+    tags: Vec<String>,
+    cover: String,
+) -> Result<Header> {
+    Header::new(
+        Name::new(name)?,
+        Synopsis::new(synopsis)?,
+        CategoryId::new(category_id)?,
+        tags.into_iter()
+            .map(Tag::new)
+            .collect::<Result<Vec<Tag>>>()?,
+        Image::new(cover)?,
+    )
+}
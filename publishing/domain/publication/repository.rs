@@ -1,10 +1,59 @@
 use async_trait::async_trait;
 
+use common::model::{Paginated, Pagination};
 use common::result::Result;
 
 use crate::domain::author::AuthorId;
 use crate::domain::category::CategoryId;
-use crate::domain::publication::{Publication, PublicationId};
+use crate::domain::publication::{Publication, PublicationId, Status};
+
+// Criteria to search publications. Every criterion is optional.
+#[derive(Debug, Default, Clone)]
+pub struct PublicationFilter {
+    pub author_id: Option<AuthorId>,
+    pub category_id: Option<CategoryId>,
+    pub status: Option<String>,
+    pub name: Option<String>,
+    // Only published publications and the ones of this author.
+    pub visible_to: Option<AuthorId>,
+}
+
+impl PublicationFilter {
+    pub fn matches(&self, publication: &Publication) -> bool {
+        if let Some(author_id) = &self.author_id {
+            if publication.author_id() != author_id {
+                return false;
+            }
+        }
+
+        if let Some(category_id) = &self.category_id {
+            if publication.header().category_id() != category_id {
+                return false;
+            }
+        }
+
+        let status = publication.status_history().current().status();
+        if let Some(s) = &self.status {
+            if &status.to_string() != s {
+                return false;
+            }
+        }
+
+        if let Some(name) = &self.name {
+            if !publication.header().name().value().contains(name) {
+                return false;
+            }
+        }
+
+        if let Some(author_id) = &self.visible_to {
+            if publication.author_id() != author_id && !matches!(status, Status::Published { .. }) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
 
 #[async_trait]
 pub trait PublicationRepository: Sync + Send {
@@ -16,6 +65,12 @@ pub trait PublicationRepository: Sync + Send {
     async fn find_by_category_id(&self, category_id: &CategoryId) -> Result<Vec<Publication>>;
     async fn find_by_status(&self, status: &str) -> Result<Vec<Publication>>;
     async fn search(&self, text: &str) -> Result<Vec<Publication>>;
+    // Sortable by created_at and name.
+    async fn find_page(
+        &self,
+        filter: &PublicationFilter,
+        pagination: &Pagination,
+    ) -> Result<Paginated<Publication>>;
 
     async fn save(&self, publication: &mut Publication) -> Result<()>;
 }
@@ -27,6 +27,20 @@ impl Reader {
         })
     }
 
+    // The subscription is managed by the payment context, which tells whether it is active.
+    pub fn build<S: Into<String>>(
+        id: ReaderId,
+        username: S,
+        name: S,
+        lastname: S,
+        subscribed: bool,
+    ) -> Result<Self> {
+        Ok(Reader {
+            subscribed,
+            ..Reader::new(id, username, name, lastname)?
+        })
+    }
+
     pub fn base(&self) -> &AggregateRoot<ReaderId, Event> {
         &self.base
     }
@@ -46,14 +60,4 @@ impl Reader {
     pub fn is_subscribed(&self) -> bool {
         self.subscribed
     }
-
-    pub fn subscribe(&mut self) -> Result<()> {
-        self.subscribed = true;
-        Ok(())
-    }
-
-    pub fn unsubscribe(&mut self) -> Result<()> {
-        self.subscribed = false;
-        Ok(())
-    }
 }
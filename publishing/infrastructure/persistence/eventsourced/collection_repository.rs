@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use common::error::Error;
+use common::event::{EventRepository, UpcasterRegistry};
+use common::result::Result;
+use shared::event::{self, CollectionEvent};
+
+use crate::domain::collection::{Collection, CollectionId};
+
+// Rebuilds collections by replaying their events, so any past state can be inspected.
+pub struct EventSourcedCollectionRepository {
+    event_repo: Arc<dyn EventRepository>,
+    upcasters: UpcasterRegistry,
+}
+
+impl EventSourcedCollectionRepository {
+    pub fn new(event_repo: Arc<dyn EventRepository>) -> Self {
+        EventSourcedCollectionRepository {
+            event_repo,
+            upcasters: event::upcasters(),
+        }
+    }
+
+    pub async fn find_by_id(&self, id: &CollectionId) -> Result<Collection> {
+        Collection::from_events(self.stream(id, None).await?)
+    }
+
+    // State of the collection right after the last event recorded before or at the given date.
+    pub async fn find_by_id_at(
+        &self,
+        id: &CollectionId,
+        date: &DateTime<Utc>,
+    ) -> Result<Collection> {
+        Collection::from_events(self.stream(id, Some(date)).await?)
+    }
+
+    async fn stream(
+        &self,
+        id: &CollectionId,
+        until: Option<&DateTime<Utc>>,
+    ) -> Result<Vec<(CollectionEvent, DateTime<Utc>)>> {
+        let mut stream = Vec::new();
+
+        for event in self.event_repo.find_all().await?.into_iter() {
+            if event.topic() != "collection" {
+                continue;
+            }
+
+            if let Some(until) = until {
+                if event.timestamp() > until {
+                    continue;
+                }
+            }
+
+            let event = self.upcasters.upcast(&event)?;
+            let collection_event: CollectionEvent = serde_json::from_slice(event.payload())
+                .map_err(|err| {
+                    Error::internal("collection", "invalid_event")
+                        .add_context("event_id", event.id().value())
+                        .wrap_raw(err)
+                        .build()
+                })?;
+
+            let collection_id = match &collection_event {
+                CollectionEvent::Created { id, .. }
+                | CollectionEvent::HeaderUpdated { id, .. }
+                | CollectionEvent::PublicationAdded { id, .. }
+                | CollectionEvent::PublicationRemoved { id, .. }
+                | CollectionEvent::Deleted { id } => id,
+            };
+
+            if collection_id == id.value() {
+                stream.push((collection_event, *event.timestamp()));
+            }
+        }
+
+        if stream.is_empty() {
+            return Err(Error::new("collection", "not_found"));
+        }
+
+        Ok(stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+
+    use common::event::Event;
+    use common::infrastructure::event::InMemEventRepository;
+
+    use crate::domain::publication::PublicationId;
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn replay() {
+        let event_repo = Arc::new(InMemEventRepository::new());
+        let repo = EventSourcedCollectionRepository::new(event_repo.clone());
+
+        // Created, PublicationAdded, PublicationRemoved, Deleted
+        let mut collection = mocks::empty_collection1();
+        collection
+            .add_item(&mocks::published_publication1())
+            .unwrap();
+        collection
+            .remove_item(&PublicationId::new("#publication01").unwrap())
+            .unwrap();
+        collection.delete().unwrap();
+
+        let now = Utc::now();
+        let mut dates = Vec::new();
+        for (i, event) in collection.base().events().unwrap().into_iter().enumerate() {
+            let date = now + Duration::minutes(i as i64);
+            event_repo
+                .save(&Event::build(
+                    event.id().clone(),
+                    event.topic().to_owned(),
+                    event.code().to_owned(),
+                    event.version(),
+                    date,
+                    event.payload().to_vec(),
+                ))
+                .await
+                .unwrap();
+            dates.push(date);
+        }
+
+        let replayed = repo.find_by_id(collection.base().id()).await.unwrap();
+        assert_eq!(replayed.header().name().value(), "Collection 01");
+        assert_eq!(replayed.author_id(), collection.author_id());
+        assert!(replayed.items().is_empty());
+        assert_eq!(replayed.base().deleted_at(), Some(&dates[3]));
+        assert!(replayed.base().events().unwrap().is_empty());
+
+        let replayed = repo
+            .find_by_id_at(collection.base().id(), &dates[1])
+            .await
+            .unwrap();
+        assert_eq!(replayed.items().len(), 1);
+        assert_eq!(
+            replayed.items()[0].publication_id().value(),
+            "#publication01"
+        );
+        assert_eq!(replayed.items()[0].date(), &dates[1]);
+        assert!(replayed.base().deleted_at().is_none());
+
+        assert!(repo
+            .find_by_id(&CollectionId::new("#collection02").unwrap())
+            .await
+            .is_err());
+    }
+}
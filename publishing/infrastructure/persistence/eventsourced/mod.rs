@@ -0,0 +1,4 @@
+mod collection_repository;
+mod publication_repository;
+pub use collection_repository::*;
+pub use publication_repository::*;
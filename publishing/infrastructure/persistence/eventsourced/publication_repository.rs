@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use common::error::Error;
+use common::event::{EventRepository, UpcasterRegistry};
+use common::result::Result;
+use shared::event::{self, PublicationEvent};
+
+use crate::domain::publication::{Publication, PublicationId};
+
+// Rebuilds publications by replaying their events, so any past state can be inspected.
+pub struct EventSourcedPublicationRepository {
+    event_repo: Arc<dyn EventRepository>,
+    upcasters: UpcasterRegistry,
+}
+
+impl EventSourcedPublicationRepository {
+    pub fn new(event_repo: Arc<dyn EventRepository>) -> Self {
+        EventSourcedPublicationRepository {
+            event_repo,
+            upcasters: event::upcasters(),
+        }
+    }
+
+    pub async fn find_by_id(&self, id: &PublicationId) -> Result<Publication> {
+        Publication::from_events(self.stream(id, None).await?)
+    }
+
+    // State of the publication right after the last event recorded before or at the given date.
+    pub async fn find_by_id_at(
+        &self,
+        id: &PublicationId,
+        date: &DateTime<Utc>,
+    ) -> Result<Publication> {
+        Publication::from_events(self.stream(id, Some(date)).await?)
+    }
+
+    async fn stream(
+        &self,
+        id: &PublicationId,
+        until: Option<&DateTime<Utc>>,
+    ) -> Result<Vec<(PublicationEvent, DateTime<Utc>)>> {
+        let mut stream = Vec::new();
+
+        for event in self.event_repo.find_all().await?.into_iter() {
+            if event.topic() != "publication" {
+                continue;
+            }
+
+            if let Some(until) = until {
+                if event.timestamp() > until {
+                    continue;
+                }
+            }
+
+            let event = self.upcasters.upcast(&event)?;
+            let publication_event: PublicationEvent = serde_json::from_slice(event.payload())
+                .map_err(|err| {
+                    Error::internal("publication", "invalid_event")
+                        .add_context("event_id", event.id().value())
+                        .wrap_raw(err)
+                        .build()
+                })?;
+
+            if event_publication_id(&publication_event) == id.value() {
+                stream.push((publication_event, *event.timestamp()));
+            }
+        }
+
+        if stream.is_empty() {
+            return Err(Error::new("publication", "not_found"));
+        }
+
+        Ok(stream)
+    }
+}
+
+fn event_publication_id(event: &PublicationEvent) -> &str {
+    match event {
+        PublicationEvent::Created { id, .. }
+        | PublicationEvent::HeaderUpdated { id, .. }
+        | PublicationEvent::PagesUpdated { id, .. }
+        | PublicationEvent::ChangedToDraft { id }
+        | PublicationEvent::ApprovalWaited { id }
+        | PublicationEvent::Published { id, .. }
+        | PublicationEvent::Rejected { id, .. }
+        | PublicationEvent::Deleted { id }
+        | PublicationEvent::StatisticsUpdated { id, .. }
+        | PublicationEvent::ContractAdded { id }
+        | PublicationEvent::ContractRemoved { id } => id,
+        PublicationEvent::Viewed { publication_id, .. }
+        | PublicationEvent::Read { publication_id, .. }
+        | PublicationEvent::Liked { publication_id, .. }
+        | PublicationEvent::Unliked { publication_id, .. }
+        | PublicationEvent::Reviewed { publication_id, .. }
+        | PublicationEvent::ReviewDeleted { publication_id, .. } => publication_id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+
+    use common::event::Event;
+    use common::infrastructure::event::InMemEventRepository;
+
+    use crate::domain::publication::Status;
+    use crate::mocks;
+
+    // Stores the pending events of the publication, one minute apart from each other.
+    async fn save_events(
+        event_repo: &InMemEventRepository,
+        publication: &Publication,
+        from: DateTime<Utc>,
+    ) -> Vec<DateTime<Utc>> {
+        let mut dates = Vec::new();
+        for (i, event) in publication.base().events().unwrap().into_iter().enumerate() {
+            let date = from + Duration::minutes(i as i64);
+            let event = Event::build(
+                event.id().clone(),
+                event.topic().to_owned(),
+                event.code().to_owned(),
+                event.version(),
+                date,
+                event.payload().to_vec(),
+            );
+            event_repo.save(&event).await.unwrap();
+            dates.push(date);
+        }
+        dates
+    }
+
+    #[tokio::test]
+    async fn replay() {
+        let event_repo = Arc::new(InMemEventRepository::new());
+        let repo = EventSourcedPublicationRepository::new(event_repo.clone());
+
+        // Created, PagesUpdated, PagesUpdated, ApprovalWaited, Published, Viewed,
+        // StatisticsUpdated, Liked, StatisticsUpdated, ContractAdded
+        let mut publication = mocks::published_publication1();
+        publication.view(&mocks::reader1(), true).unwrap();
+        publication.like(&mocks::reader1()).unwrap();
+        publication.add_contract().unwrap();
+        let dates = save_events(&event_repo, &publication, Utc::now()).await;
+        assert_eq!(dates.len(), 10);
+
+        let replayed = repo.find_by_id(publication.base().id()).await.unwrap();
+        assert_eq!(replayed.base().id(), publication.base().id());
+        assert_eq!(replayed.base().created_at(), &dates[0]);
+        assert_eq!(replayed.author_id(), publication.author_id());
+        assert_eq!(replayed.header().name().value(), "Publication 01");
+        assert_eq!(replayed.header().tags().len(), 2);
+        assert_eq!(replayed.pages().len(), 2);
+        assert_eq!(replayed.pages()[0].number(), 2);
+        assert_eq!(replayed.pages()[0].images().len(), 3);
+        assert_eq!(replayed.statistics().views(), 1);
+        assert_eq!(replayed.statistics().likes(), 1);
+        assert!(replayed.has_contract());
+        assert!(replayed.base().deleted_at().is_none());
+        assert!(replayed.base().events().unwrap().is_empty());
+
+        let history = replayed.status_history().history();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[1].status().to_string(), "waiting-approval");
+        assert_eq!(history[1].date(), &dates[3]);
+        if let Status::Published { admin_id } = history[2].status() {
+            assert_eq!(admin_id, mocks::content_manager1().base().id());
+        } else {
+            panic!("publication should be published");
+        }
+
+        // Time travel
+        let waiting = repo
+            .find_by_id_at(publication.base().id(), &dates[3])
+            .await
+            .unwrap();
+        assert_eq!(
+            waiting.status_history().current().status().to_string(),
+            "waiting-approval"
+        );
+        assert_eq!(waiting.statistics().views(), 0);
+        assert!(!waiting.has_contract());
+
+        let created = repo
+            .find_by_id_at(publication.base().id(), &dates[0])
+            .await
+            .unwrap();
+        assert!(created.pages().is_empty());
+
+        assert!(repo
+            .find_by_id_at(publication.base().id(), &(dates[0] - Duration::minutes(1)))
+            .await
+            .is_err());
+        assert!(repo
+            .find_by_id(&PublicationId::new("#publication02").unwrap())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn deleted() {
+        let event_repo = Arc::new(InMemEventRepository::new());
+        let repo = EventSourcedPublicationRepository::new(event_repo.clone());
+
+        let mut publication = mocks::publication1();
+        publication.delete().unwrap();
+        let dates = save_events(&event_repo, &publication, Utc::now()).await;
+
+        let replayed = repo.find_by_id(publication.base().id()).await.unwrap();
+        assert_eq!(replayed.base().deleted_at(), dates.last());
+    }
+}
@@ -56,7 +56,14 @@ impl CategoryRepository for InMemCategoryRepository {
 
     async fn save(&self, category: &mut Category) -> Result<()> {
         self.cache
-            .set(category.base().id().clone(), category.clone())
-            .await
+            .set_checked(category.base().id().clone(), category.clone(), |stored| {
+                category
+                    .base()
+                    .check_version(stored.map(|stored| stored.base().version()))
+            })
+            .await?;
+
+        category.base_mut().commit_version();
+        Ok(())
     }
 }
@@ -4,6 +4,7 @@ use uuid::Uuid;
 use common::cache::Cache;
 use common::error::Error;
 use common::infrastructure::cache::InMemCache;
+use common::model::{Paginated, Pagination};
 use common::result::Result;
 
 use crate::domain::author::AuthorId;
@@ -76,9 +77,38 @@ impl CollectionRepository for InMemCollectionRepository {
             .await)
     }
 
+    async fn find_page(&self, pagination: &Pagination) -> Result<Paginated<Collection>> {
+        let collections = self.cache.all().await;
+
+        match pagination.sort() {
+            "created_at" => pagination.paginate(
+                collections,
+                |collection| collection.base().id().value(),
+                |collection| *collection.base().created_at(),
+            ),
+            "name" => pagination.paginate(
+                collections,
+                |collection| collection.base().id().value(),
+                |collection| collection.header().name().value().to_owned(),
+            ),
+            _ => Err(pagination.unsupported_sort()),
+        }
+    }
+
     async fn save(&self, collection: &mut Collection) -> Result<()> {
         self.cache
-            .set(collection.base().id().clone(), collection.clone())
-            .await
+            .set_checked(
+                collection.base().id().clone(),
+                collection.clone(),
+                |stored| {
+                    collection
+                        .base()
+                        .check_version(stored.map(|stored| stored.base().version()))
+                },
+            )
+            .await?;
+
+        collection.base_mut().commit_version();
+        Ok(())
     }
 }
@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use tokio::sync::Mutex;
 
+use common::model::{Paginated, Pagination};
 use common::result::Result;
 
 use crate::domain::interaction::{InteractionRepository, Like, Reading, Review, View};
@@ -26,14 +27,23 @@ impl InMemInteractionRepository {
     }
 }
 
+// Same as the SQLite repository, both dates are included.
+fn in_range(
+    date: &DateTime<Utc>,
+    from: Option<&DateTime<Utc>>,
+    to: Option<&DateTime<Utc>>,
+) -> bool {
+    from.map(|from| date >= from).unwrap_or(true) && to.map(|to| date <= to).unwrap_or(true)
+}
+
 #[async_trait]
 impl InteractionRepository for InMemInteractionRepository {
     async fn find_views(
         &self,
         reader_id: Option<&ReaderId>,
         publication_id: Option<&PublicationId>,
-        _from: Option<&DateTime<Utc>>,
-        _to: Option<&DateTime<Utc>>,
+        from: Option<&DateTime<Utc>>,
+        to: Option<&DateTime<Utc>>,
     ) -> Result<Vec<View>> {
         Ok(self
             .views
@@ -53,7 +63,7 @@ impl InteractionRepository for InMemInteractionRepository {
                     }
                 }
 
-                true
+                in_range(view.base().date(), from, to)
             })
             .cloned()
             .collect())
@@ -63,8 +73,8 @@ impl InteractionRepository for InMemInteractionRepository {
         &self,
         reader_id: Option<&ReaderId>,
         publication_id: Option<&PublicationId>,
-        _from: Option<&DateTime<Utc>>,
-        _to: Option<&DateTime<Utc>>,
+        from: Option<&DateTime<Utc>>,
+        to: Option<&DateTime<Utc>>,
     ) -> Result<Vec<Reading>> {
         Ok(self
             .readings
@@ -84,7 +94,7 @@ impl InteractionRepository for InMemInteractionRepository {
                     }
                 }
 
-                true
+                in_range(reading.date(), from, to)
             })
             .cloned()
             .collect())
@@ -94,8 +104,8 @@ impl InteractionRepository for InMemInteractionRepository {
         &self,
         reader_id: Option<&ReaderId>,
         publication_id: Option<&PublicationId>,
-        _from: Option<&DateTime<Utc>>,
-        _to: Option<&DateTime<Utc>>,
+        from: Option<&DateTime<Utc>>,
+        to: Option<&DateTime<Utc>>,
     ) -> Result<Vec<Like>> {
         Ok(self
             .likes
@@ -115,7 +125,7 @@ impl InteractionRepository for InMemInteractionRepository {
                     }
                 }
 
-                true
+                in_range(like.date(), from, to)
             })
             .cloned()
             .collect())
@@ -125,8 +135,8 @@ impl InteractionRepository for InMemInteractionRepository {
         &self,
         reader_id: Option<&ReaderId>,
         publication_id: Option<&PublicationId>,
-        _from: Option<&DateTime<Utc>>,
-        _to: Option<&DateTime<Utc>>,
+        from: Option<&DateTime<Utc>>,
+        to: Option<&DateTime<Utc>>,
     ) -> Result<Vec<Review>> {
         Ok(self
             .reviews
@@ -146,12 +156,36 @@ impl InteractionRepository for InMemInteractionRepository {
                     }
                 }
 
-                true
+                in_range(review.base().date(), from, to)
             })
             .cloned()
             .collect())
     }
 
+    async fn find_reviews_page(
+        &self,
+        publication_id: &PublicationId,
+        pagination: &Pagination,
+    ) -> Result<Paginated<Review>> {
+        let reviews = self
+            .find_reviews(None, Some(publication_id), None, None)
+            .await?;
+
+        match pagination.sort() {
+            "date" => pagination.paginate(
+                reviews,
+                |review| review.base().reader_id().value(),
+                |review| *review.base().date(),
+            ),
+            "stars" => pagination.paginate(
+                reviews,
+                |review| review.base().reader_id().value(),
+                |review| review.stars().value(),
+            ),
+            _ => Err(pagination.unsupported_sort()),
+        }
+    }
+
     async fn save_view(&self, view: &mut View) -> Result<()> {
         self.views.lock().await.push(view.clone());
         Ok(())
@@ -4,11 +4,14 @@ use uuid::Uuid;
 use common::cache::Cache;
 use common::error::Error;
 use common::infrastructure::cache::InMemCache;
+use common::model::{Paginated, Pagination};
 use common::result::Result;
 
 use crate::domain::author::AuthorId;
 use crate::domain::category::CategoryId;
-use crate::domain::publication::{Publication, PublicationId, PublicationRepository};
+use crate::domain::publication::{
+    Publication, PublicationFilter, PublicationId, PublicationRepository,
+};
 use crate::mocks;
 
 pub struct InMemPublicationRepository {
@@ -88,9 +91,45 @@ impl PublicationRepository for InMemPublicationRepository {
             .await)
     }
 
+    async fn find_page(
+        &self,
+        filter: &PublicationFilter,
+        pagination: &Pagination,
+    ) -> Result<Paginated<Publication>> {
+        let publications = self
+            .cache
+            .filter(|&(_, publication)| filter.matches(publication))
+            .await;
+
+        match pagination.sort() {
+            "created_at" => pagination.paginate(
+                publications,
+                |publication| publication.base().id().value(),
+                |publication| *publication.base().created_at(),
+            ),
+            "name" => pagination.paginate(
+                publications,
+                |publication| publication.base().id().value(),
+                |publication| publication.header().name().value().to_owned(),
+            ),
+            _ => Err(pagination.unsupported_sort()),
+        }
+    }
+
     async fn save(&self, publication: &mut Publication) -> Result<()> {
         self.cache
-            .set(publication.base().id().clone(), publication.clone())
-            .await
+            .set_checked(
+                publication.base().id().clone(),
+                publication.clone(),
+                |stored| {
+                    publication
+                        .base()
+                        .check_version(stored.map(|stored| stored.base().version()))
+                },
+            )
+            .await?;
+
+        publication.base_mut().commit_version();
+        Ok(())
     }
 }
@@ -1 +1,3 @@
+pub mod eventsourced;
 pub mod inmem;
+pub mod sqlite;
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rusqlite::{params, Connection, ToSql};
+use uuid::Uuid;
+
+use common::error::Error;
+use common::model::AggregateRoot;
+use common::result::Result;
+
+use crate::domain::category::{Category, CategoryId, CategoryRepository, Name};
+use crate::infrastructure::persistence::sqlite::{
+    db_error, from_optional_timestamp, from_timestamp, store_events, stored_version, to_timestamp,
+    Database,
+};
+
+type CategoryRow = (String, String, String, Option<String>, Option<String>, i64);
+
+pub struct SqliteCategoryRepository {
+    db: Arc<Database>,
+}
+
+impl SqliteCategoryRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        SqliteCategoryRepository { db }
+    }
+
+    fn find(conn: &Connection, condition: &str, params: &[&dyn ToSql]) -> Result<Vec<Category>> {
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT id, name, created_at, updated_at, deleted_at, version FROM categories {}
+                ORDER BY created_at, id",
+                condition
+            ))
+            .map_err(db_error)?;
+
+        let rows = stmt
+            .query_map(params, |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            })
+            .map_err(db_error)?
+            .collect::<rusqlite::Result<Vec<CategoryRow>>>()
+            .map_err(db_error)?;
+
+        let mut categories = Vec::new();
+        for (id, name, created_at, updated_at, deleted_at, version) in rows.into_iter() {
+            categories.push(Category::build(
+                AggregateRoot::build(
+                    CategoryId::new(id)?,
+                    from_timestamp(&created_at)?,
+                    from_optional_timestamp(updated_at)?,
+                    from_optional_timestamp(deleted_at)?,
+                    version as u64,
+                ),
+                Name::new(name)?,
+            ));
+        }
+
+        Ok(categories)
+    }
+}
+
+#[async_trait]
+impl CategoryRepository for SqliteCategoryRepository {
+    async fn next_id(&self) -> Result<CategoryId> {
+        let id = Uuid::new_v4();
+        CategoryId::new(id.to_string())
+    }
+
+    async fn find_by_id(&self, id: &CategoryId) -> Result<Category> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, "WHERE id = ?1", &[&id.value()])?
+            .pop()
+            .ok_or(Error::new("category", "not_found"))
+    }
+
+    async fn find_all_categories(&self) -> Result<Vec<Category>> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, "", &[])
+    }
+
+    async fn save(&self, category: &mut Category) -> Result<()> {
+        let mut conn = self.db.conn().await;
+        let tx = conn.transaction().map_err(db_error)?;
+        category.base().check_version(stored_version(
+            &tx,
+            "categories",
+            category.base().id().value(),
+        )?)?;
+        tx.execute(
+            "INSERT OR REPLACE INTO categories (
+                id, name, created_at, updated_at, deleted_at, version
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                category.base().id().value(),
+                category.name().value(),
+                to_timestamp(category.base().created_at()),
+                category.base().updated_at().map(to_timestamp),
+                category.base().deleted_at().map(to_timestamp),
+                category.base().version() as i64,
+            ],
+        )
+        .map_err(db_error)?;
+        store_events(&tx, &category.base().events()?)?;
+        tx.commit().map_err(db_error)?;
+
+        category.base_mut().commit_version();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn round_trip() {
+        let repo = SqliteCategoryRepository::new(Arc::new(Database::in_memory().unwrap()));
+        repo.save(&mut mocks::category1()).await.unwrap();
+        repo.save(&mut mocks::category2()).await.unwrap();
+        repo.save(&mut mocks::category1()).await.unwrap();
+
+        let category = repo
+            .find_by_id(mocks::category1().base().id())
+            .await
+            .unwrap();
+        assert_eq!(category.name().value(), "Category 01");
+        assert_eq!(repo.find_all_categories().await.unwrap().len(), 2);
+        assert!(repo
+            .find_by_id(&CategoryId::new("#category03").unwrap())
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,324 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rusqlite::{params, Connection, ToSql, Transaction};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use common::error::Error;
+use common::model::{AggregateRoot, Paginated, Pagination};
+use common::result::Result;
+
+use crate::domain::author::AuthorId;
+use crate::domain::category::CategoryId;
+use crate::domain::collection::{Collection, CollectionId, CollectionRepository, Item};
+use crate::domain::publication::{Header, Image, Name, PublicationId, Synopsis};
+use crate::infrastructure::persistence::sqlite::publication_repository::{load_tags, store_tags};
+use crate::infrastructure::persistence::sqlite::{
+    db_error, from_optional_timestamp, from_timestamp, paginate, store_events, stored_version,
+    to_timestamp, Database,
+};
+
+const SELECT_COLLECTIONS: &str = "
+    SELECT id, author_id, name, synopsis, category_id, cover,
+        created_at, updated_at, deleted_at, version
+    FROM collections";
+
+struct CollectionRow {
+    id: String,
+    author_id: String,
+    name: String,
+    synopsis: String,
+    category_id: String,
+    cover: String,
+    created_at: String,
+    updated_at: Option<String>,
+    deleted_at: Option<String>,
+    version: i64,
+}
+
+pub struct SqliteCollectionRepository {
+    db: Arc<Database>,
+}
+
+impl SqliteCollectionRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        SqliteCollectionRepository { db }
+    }
+
+    fn find(conn: &Connection, condition: &str, params: &[&dyn ToSql]) -> Result<Vec<Collection>> {
+        Self::query(
+            conn,
+            &format!("{} ORDER BY created_at, id", condition),
+            params,
+        )
+    }
+
+    fn query(conn: &Connection, clause: &str, params: &[&dyn ToSql]) -> Result<Vec<Collection>> {
+        let mut stmt = conn
+            .prepare(&format!("{} {}", SELECT_COLLECTIONS, clause))
+            .map_err(db_error)?;
+
+        let rows = stmt
+            .query_map(params, |row| {
+                Ok(CollectionRow {
+                    id: row.get(0)?,
+                    author_id: row.get(1)?,
+                    name: row.get(2)?,
+                    synopsis: row.get(3)?,
+                    category_id: row.get(4)?,
+                    cover: row.get(5)?,
+                    created_at: row.get(6)?,
+                    updated_at: row.get(7)?,
+                    deleted_at: row.get(8)?,
+                    version: row.get(9)?,
+                })
+            })
+            .map_err(db_error)?
+            .collect::<rusqlite::Result<Vec<CollectionRow>>>()
+            .map_err(db_error)?;
+
+        let mut collections = Vec::new();
+        for row in rows.into_iter() {
+            collections.push(Self::load(conn, row)?);
+        }
+
+        Ok(collections)
+    }
+
+    fn load(conn: &Connection, row: CollectionRow) -> Result<Collection> {
+        let tags = load_tags(conn, "collection_tags", "collection_id", &row.id)?;
+
+        let header = Header::new(
+            Name::new(row.name)?,
+            Synopsis::new(row.synopsis)?,
+            CategoryId::new(row.category_id)?,
+            tags,
+            Image::new(row.cover)?,
+        )?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT publication_id, date FROM collection_items
+                WHERE collection_id = ?1 ORDER BY position",
+            )
+            .map_err(db_error)?;
+        let item_rows = stmt
+            .query_map(params![row.id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(db_error)?
+            .collect::<rusqlite::Result<Vec<(String, String)>>>()
+            .map_err(db_error)?;
+
+        let mut items = Vec::new();
+        for (publication_id, date) in item_rows.into_iter() {
+            items.push(Item::build(
+                PublicationId::new(publication_id)?,
+                from_timestamp(&date)?,
+            ));
+        }
+
+        Ok(Collection::build(
+            AggregateRoot::build(
+                CollectionId::new(&row.id)?,
+                from_timestamp(&row.created_at)?,
+                from_optional_timestamp(row.updated_at)?,
+                from_optional_timestamp(row.deleted_at)?,
+                row.version as u64,
+            ),
+            AuthorId::new(row.author_id)?,
+            header,
+            items,
+        ))
+    }
+
+    fn store(tx: &Transaction, collection: &Collection) -> Result<()> {
+        let id = collection.base().id().value();
+
+        for table in &["collection_tags", "collection_items"] {
+            tx.execute(
+                &format!("DELETE FROM {} WHERE collection_id = ?1", table),
+                params![id],
+            )
+            .map_err(db_error)?;
+        }
+
+        let header = collection.header();
+        tx.execute(
+            "INSERT OR REPLACE INTO collections (
+                id, author_id, name, synopsis, category_id, cover,
+                created_at, updated_at, deleted_at, version
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                id,
+                collection.author_id().value(),
+                header.name().value(),
+                header.synopsis().value(),
+                header.category_id().value(),
+                header.cover().url(),
+                to_timestamp(collection.base().created_at()),
+                collection.base().updated_at().map(to_timestamp),
+                collection.base().deleted_at().map(to_timestamp),
+                collection.base().version() as i64,
+            ],
+        )
+        .map_err(db_error)?;
+
+        store_tags(tx, "collection_tags", "collection_id", id, header.tags())?;
+
+        for (position, item) in collection.items().iter().enumerate() {
+            tx.execute(
+                "INSERT INTO collection_items (collection_id, position, publication_id, date)
+                VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    id,
+                    position as u32,
+                    item.publication_id().value(),
+                    to_timestamp(item.date()),
+                ],
+            )
+            .map_err(db_error)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CollectionRepository for SqliteCollectionRepository {
+    async fn next_id(&self) -> Result<CollectionId> {
+        let id = Uuid::new_v4();
+        CollectionId::new(id.to_string())
+    }
+
+    async fn find_all(&self) -> Result<Vec<Collection>> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, "", &[])
+    }
+
+    async fn find_by_id(&self, id: &CollectionId) -> Result<Collection> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, "WHERE id = ?1", &[&id.value()])?
+            .pop()
+            .ok_or(Error::new("collection", "not_found"))
+    }
+
+    async fn find_by_author_id(&self, author_id: &AuthorId) -> Result<Vec<Collection>> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, "WHERE author_id = ?1", &[&author_id.value()])
+    }
+
+    async fn find_by_category_id(&self, category_id: &CategoryId) -> Result<Vec<Collection>> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, "WHERE category_id = ?1", &[&category_id.value()])
+    }
+
+    async fn search(&self, text: &str) -> Result<Vec<Collection>> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, "WHERE instr(name, ?1) > 0", &[&text])
+    }
+
+    async fn find_page(&self, pagination: &Pagination) -> Result<Paginated<Collection>> {
+        let column = match pagination.sort() {
+            "created_at" => "created_at",
+            "name" => "name",
+            _ => return Err(pagination.unsupported_sort()),
+        };
+
+        let conn = self.db.conn().await;
+        let (clause, params, total) = paginate(
+            &conn,
+            "collections",
+            Vec::new(),
+            Vec::new(),
+            column,
+            "id",
+            pagination,
+        )?;
+        let params: Vec<&dyn ToSql> = params.iter().map(|param| param as &dyn ToSql).collect();
+
+        pagination.page(
+            Self::query(&conn, &clause, &params)?,
+            total,
+            |collection| collection.base().id().value(),
+            |collection| match column {
+                "name" => JsonValue::from(collection.header().name().value()),
+                _ => JsonValue::from(to_timestamp(collection.base().created_at())),
+            },
+        )
+    }
+
+    async fn save(&self, collection: &mut Collection) -> Result<()> {
+        let mut conn = self.db.conn().await;
+        let tx = conn.transaction().map_err(db_error)?;
+        collection.base().check_version(stored_version(
+            &tx,
+            "collections",
+            collection.base().id().value(),
+        )?)?;
+        Self::store(&tx, collection)?;
+        store_events(&tx, &collection.base().events()?)?;
+        tx.commit().map_err(db_error)?;
+
+        collection.base_mut().commit_version();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn round_trip() {
+        let repo = SqliteCollectionRepository::new(Arc::new(Database::in_memory().unwrap()));
+
+        let mut collection = mocks::empty_collection1();
+        collection
+            .add_item(&mocks::published_publication1())
+            .unwrap();
+        repo.save(&mut collection).await.unwrap();
+
+        let found = repo.find_by_id(collection.base().id()).await.unwrap();
+        assert_eq!(found.base().created_at(), collection.base().created_at());
+        assert_eq!(found.author_id(), collection.author_id());
+        assert_eq!(found.header().name().value(), "Collection 01");
+        assert_eq!(found.header().tags().len(), 2);
+        assert_eq!(found.items().len(), 1);
+        assert_eq!(found.items()[0].publication_id().value(), "#publication01");
+        assert_eq!(found.items()[0].date(), collection.items()[0].date());
+
+        let mut found = found;
+        found
+            .remove_item(&PublicationId::new("#publication01").unwrap())
+            .unwrap();
+        found.delete().unwrap();
+        repo.save(&mut found).await.unwrap();
+
+        let found = repo.find_by_id(collection.base().id()).await.unwrap();
+        assert!(found.items().is_empty());
+        assert!(found.base().deleted_at().is_some());
+
+        assert_eq!(repo.find_all().await.unwrap().len(), 1);
+        assert_eq!(
+            repo.find_by_author_id(mocks::author1().base().id())
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(repo
+            .find_by_category_id(mocks::category2().base().id())
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(repo.search("01").await.unwrap().len(), 1);
+        assert!(repo
+            .find_by_id(&CollectionId::new("#collection02").unwrap())
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,376 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::types::Value;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value as JsonValue;
+use tokio::sync::{Mutex, MutexGuard};
+
+use common::error::Error;
+use common::model::{Pagination, SortDirection};
+use common::result::Result;
+
+// Each migration is applied once, in order. The index of the last applied migration is stored
+// in the `user_version` pragma, so new migrations must always be appended to the end.
+const MIGRATIONS: &[&str] = &[
+    // 1: Publications
+    "
+    CREATE TABLE publications (
+        id TEXT PRIMARY KEY,
+        author_id TEXT NOT NULL,
+        name TEXT NOT NULL,
+        synopsis TEXT NOT NULL,
+        category_id TEXT NOT NULL,
+        cover TEXT NOT NULL,
+        contract INTEGER NOT NULL,
+        views INTEGER NOT NULL,
+        unique_views INTEGER NOT NULL,
+        readings INTEGER NOT NULL,
+        likes INTEGER NOT NULL,
+        reviews INTEGER NOT NULL,
+        stars REAL NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT,
+        deleted_at TEXT
+    );
+    CREATE INDEX publications_author_id ON publications (author_id);
+    CREATE INDEX publications_category_id ON publications (category_id);
+
+    CREATE TABLE publication_tags (
+        publication_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        PRIMARY KEY (publication_id, position)
+    );
+
+    CREATE TABLE publication_pages (
+        publication_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        number INTEGER NOT NULL,
+        PRIMARY KEY (publication_id, position)
+    );
+
+    CREATE TABLE publication_images (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        publication_id TEXT NOT NULL,
+        page_position INTEGER NOT NULL,
+        position INTEGER NOT NULL,
+        url TEXT NOT NULL
+    );
+    CREATE INDEX publication_images_publication_id ON publication_images (publication_id);
+
+    CREATE TABLE publication_frames (
+        image_id INTEGER NOT NULL,
+        position INTEGER NOT NULL,
+        frame_order INTEGER NOT NULL,
+        x INTEGER NOT NULL,
+        y INTEGER NOT NULL,
+        width INTEGER NOT NULL,
+        height INTEGER NOT NULL,
+        PRIMARY KEY (image_id, position)
+    );
+
+    CREATE TABLE publication_status_history (
+        publication_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        status TEXT NOT NULL,
+        admin_id TEXT,
+        date TEXT NOT NULL,
+        PRIMARY KEY (publication_id, position)
+    );
+    ",
+    // 2: Collections
+    "
+    CREATE TABLE collections (
+        id TEXT PRIMARY KEY,
+        author_id TEXT NOT NULL,
+        name TEXT NOT NULL,
+        synopsis TEXT NOT NULL,
+        category_id TEXT NOT NULL,
+        cover TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT,
+        deleted_at TEXT
+    );
+    CREATE INDEX collections_author_id ON collections (author_id);
+    CREATE INDEX collections_category_id ON collections (category_id);
+
+    CREATE TABLE collection_tags (
+        collection_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        PRIMARY KEY (collection_id, position)
+    );
+
+    CREATE TABLE collection_items (
+        collection_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        publication_id TEXT NOT NULL,
+        date TEXT NOT NULL,
+        PRIMARY KEY (collection_id, position)
+    );
+    ",
+    // 3: Interactions
+    "
+    CREATE TABLE views (
+        reader_id TEXT NOT NULL,
+        publication_id TEXT NOT NULL,
+        date TEXT NOT NULL,
+        is_unique INTEGER NOT NULL
+    );
+    CREATE INDEX views_publication_id ON views (publication_id);
+
+    CREATE TABLE readings (
+        reader_id TEXT NOT NULL,
+        publication_id TEXT NOT NULL,
+        date TEXT NOT NULL
+    );
+    CREATE INDEX readings_publication_id ON readings (publication_id);
+
+    CREATE TABLE likes (
+        reader_id TEXT NOT NULL,
+        publication_id TEXT NOT NULL,
+        date TEXT NOT NULL
+    );
+    CREATE INDEX likes_publication_id ON likes (publication_id);
+
+    CREATE TABLE reviews (
+        reader_id TEXT NOT NULL,
+        publication_id TEXT NOT NULL,
+        date TEXT NOT NULL,
+        stars INTEGER NOT NULL,
+        comment TEXT NOT NULL
+    );
+    CREATE INDEX reviews_publication_id ON reviews (publication_id);
+    ",
+    // 4: Categories
+    "
+    CREATE TABLE categories (
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT,
+        deleted_at TEXT
+    );
+    ",
+    // 5: Aggregate versions
+    "
+    ALTER TABLE publications ADD COLUMN version INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE collections ADD COLUMN version INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE categories ADD COLUMN version INTEGER NOT NULL DEFAULT 0;
+    ",
+    // 6: Outbox
+    "
+    CREATE TABLE outbox (
+        sequence INTEGER PRIMARY KEY AUTOINCREMENT,
+        id TEXT NOT NULL UNIQUE,
+        topic TEXT NOT NULL,
+        code TEXT NOT NULL,
+        timestamp TEXT NOT NULL,
+        payload BLOB NOT NULL,
+        published_at TEXT
+    );
+    CREATE INDEX outbox_pending ON outbox (published_at, sequence);
+    ",
+    // 7: Event versions
+    "
+    ALTER TABLE outbox ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+    ",
+];
+
+pub struct Database {
+    conn: Mutex<Connection>,
+}
+
+impl Database {
+    pub fn open(path: &str) -> Result<Self> {
+        let conn = Connection::open(path).map_err(|err| {
+            Error::internal("database", "open")
+                .add_context("path", path)
+                .wrap_raw(err)
+                .build()
+        })?;
+
+        Self::init(conn)
+    }
+
+    pub fn in_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory()
+            .map_err(|err| Error::internal("database", "open").wrap_raw(err).build())?;
+
+        Self::init(conn)
+    }
+
+    fn init(mut conn: Connection) -> Result<Self> {
+        migrate(&mut conn)?;
+
+        Ok(Database {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub async fn conn(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().await
+    }
+}
+
+fn migrate(conn: &mut Connection) -> Result<()> {
+    let version: usize = conn
+        .query_row("PRAGMA user_version", rusqlite::NO_PARAMS, |row| {
+            row.get::<_, i64>(0)
+        })
+        .map_err(db_error)? as usize;
+
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.transaction().map_err(db_error)?;
+        tx.execute_batch(migration).map_err(|err| {
+            Error::internal("database", "migration")
+                .add_context("version", &(i + 1).to_string())
+                .wrap_raw(err)
+                .build()
+        })?;
+        tx.execute_batch(&format!("PRAGMA user_version = {}", i + 1))
+            .map_err(db_error)?;
+        tx.commit().map_err(db_error)?;
+    }
+
+    Ok(())
+}
+
+// Version of the stored aggregate, used to reject writes of stale copies.
+pub(crate) fn stored_version(conn: &Connection, table: &str, id: &str) -> Result<Option<u64>> {
+    conn.query_row(
+        &format!("SELECT version FROM {} WHERE id = ?1", table),
+        params![id],
+        |row| row.get::<_, i64>(0),
+    )
+    .optional()
+    .map(|version| version.map(|version| version as u64))
+    .map_err(db_error)
+}
+
+// Clause selecting a page of the rows of the table matching the conditions, whose params are
+// numbered from ?1. Rows are sorted by the column and then by the id column, and the ones after
+// the sort key and id of the cursor are selected (keyset pagination). One row more than the limit
+// is selected to know if there is a next page. Returns the clause, its params and the number of matching rows.
+pub(crate) fn paginate(
+    conn: &Connection,
+    table: &str,
+    mut conditions: Vec<String>,
+    mut params: Vec<Value>,
+    column: &str,
+    id_column: &str,
+    pagination: &Pagination,
+) -> Result<(String, Vec<Value>, usize)> {
+    let total: i64 = conn
+        .query_row(
+            &format!(
+                "SELECT COUNT(*) FROM {} {}",
+                table,
+                where_clause(&conditions)
+            ),
+            &params,
+            |row| row.get(0),
+        )
+        .map_err(db_error)?;
+
+    let direction = match pagination.direction() {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    };
+
+    if let Some((key, id)) = pagination.cursor_key::<JsonValue>()? {
+        let key = match key {
+            JsonValue::String(key) => Value::Text(key),
+            JsonValue::Number(key) => {
+                Value::Integer(key.as_i64().ok_or_else(|| pagination.invalid_cursor())?)
+            }
+            _ => return Err(pagination.invalid_cursor()),
+        };
+        params.push(key);
+        params.push(Value::Text(id));
+
+        conditions.push(format!(
+            "({}, {}) {} (?{}, ?{})",
+            column,
+            id_column,
+            if direction == "ASC" { ">" } else { "<" },
+            params.len() - 1,
+            params.len(),
+        ));
+    }
+
+    let clause = format!(
+        "{} ORDER BY {} {}, {} {}{}",
+        where_clause(&conditions),
+        column,
+        direction,
+        id_column,
+        direction,
+        pagination
+            .limit()
+            .map(|limit| format!(" LIMIT {}", limit + 1))
+            .unwrap_or_default(),
+    );
+
+    Ok((clause, params, total as usize))
+}
+
+fn where_clause(conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        return String::new();
+    }
+
+    format!("WHERE {}", conditions.join(" AND "))
+}
+
+pub(crate) fn db_error(err: rusqlite::Error) -> Error {
+    Error::internal("database", "query").wrap_raw(err).build()
+}
+
+// Dates are stored as fixed-width RFC 3339 strings, so they can be compared lexicographically.
+pub(crate) fn to_timestamp(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+pub(crate) fn from_timestamp(timestamp: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|err| {
+            Error::internal("database", "invalid_date")
+                .add_context("date", timestamp)
+                .wrap_raw(err)
+                .build()
+        })
+}
+
+pub(crate) fn from_optional_timestamp(timestamp: Option<String>) -> Result<Option<DateTime<Utc>>> {
+    match timestamp {
+        Some(timestamp) => Ok(Some(from_timestamp(&timestamp)?)),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn migrations() {
+        let db = Database::in_memory().unwrap();
+        let mut conn = db.conn().await;
+
+        let version: i64 = conn
+            .query_row("PRAGMA user_version", rusqlite::NO_PARAMS, |row| row.get(0))
+            .unwrap();
+        assert_eq!(version as usize, MIGRATIONS.len());
+
+        // Running migrations again is a no-op.
+        migrate(&mut conn).unwrap();
+    }
+
+    #[test]
+    fn timestamps() {
+        let date = Utc::now();
+        assert_eq!(from_timestamp(&to_timestamp(&date)).unwrap(), date);
+        assert!(from_timestamp("invalid").is_err());
+    }
+}
@@ -0,0 +1,589 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::types::Value;
+use rusqlite::{params, Connection, Row, ToSql};
+use serde_json::Value as JsonValue;
+
+use common::model::{Paginated, Pagination};
+use common::result::Result;
+
+use crate::domain::interaction::{
+    Base, Comment, InteractionRepository, Like, Reading, Review, Stars, View,
+};
+use crate::domain::publication::PublicationId;
+use crate::domain::reader::ReaderId;
+use crate::infrastructure::persistence::sqlite::{
+    db_error, from_timestamp, paginate, to_timestamp, Database,
+};
+
+// reader_id, publication_id, date and up to two extra columns.
+type InteractionRow = (String, String, String, Option<u32>, Option<String>);
+
+pub struct SqliteInteractionRepository {
+    db: Arc<Database>,
+}
+
+impl SqliteInteractionRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        SqliteInteractionRepository { db }
+    }
+
+    // Selects the rows of an interaction table matching the given filters. Every row starts with
+    // reader_id, publication_id and date, followed by the extra columns of the table.
+    fn find<T, F, M>(
+        conn: &Connection,
+        table: &str,
+        columns: &str,
+        filter: Filter,
+        map_row: F,
+        map: M,
+    ) -> Result<Vec<T>>
+    where
+        F: Fn(&Row) -> rusqlite::Result<InteractionRow>,
+        M: FnMut(Base, Option<u32>, Option<String>) -> Result<T>,
+    {
+        let (condition, params) = filter.build();
+        let params: Vec<&dyn ToSql> = params.iter().map(|param| param as &dyn ToSql).collect();
+
+        Self::query(
+            conn,
+            table,
+            columns,
+            &format!("{} ORDER BY date", condition),
+            &params,
+            map_row,
+            map,
+        )
+    }
+
+    fn query<T, F, M>(
+        conn: &Connection,
+        table: &str,
+        columns: &str,
+        clause: &str,
+        params: &[&dyn ToSql],
+        map_row: F,
+        mut map: M,
+    ) -> Result<Vec<T>>
+    where
+        F: Fn(&Row) -> rusqlite::Result<InteractionRow>,
+        M: FnMut(Base, Option<u32>, Option<String>) -> Result<T>,
+    {
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT reader_id, publication_id, date{} FROM {} {}",
+                columns, table, clause
+            ))
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map(params, map_row)
+            .map_err(db_error)?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(db_error)?;
+
+        let mut interactions = Vec::new();
+        for (reader_id, publication_id, date, number, text) in rows.into_iter() {
+            let base = Base::build(
+                ReaderId::new(reader_id)?,
+                PublicationId::new(publication_id)?,
+                from_timestamp(&date)?,
+            );
+            interactions.push(map(base, number, text)?);
+        }
+
+        Ok(interactions)
+    }
+
+    async fn save_base(&self, table: &str, base: &Base) -> Result<()> {
+        let conn = self.db.conn().await;
+        conn.execute(
+            &format!(
+                "INSERT INTO {} (reader_id, publication_id, date) VALUES (?1, ?2, ?3)",
+                table
+            ),
+            params![
+                base.reader_id().value(),
+                base.publication_id().value(),
+                to_timestamp(base.date()),
+            ],
+        )
+        .map_err(db_error)?;
+
+        Ok(())
+    }
+
+    async fn delete(
+        &self,
+        table: &str,
+        reader_id: &ReaderId,
+        publication_id: &PublicationId,
+    ) -> Result<()> {
+        let conn = self.db.conn().await;
+        conn.execute(
+            &format!(
+                "DELETE FROM {} WHERE reader_id = ?1 AND publication_id = ?2",
+                table
+            ),
+            params![reader_id.value(), publication_id.value()],
+        )
+        .map_err(db_error)?;
+
+        Ok(())
+    }
+}
+
+struct Filter<'a> {
+    reader_id: Option<&'a ReaderId>,
+    publication_id: Option<&'a PublicationId>,
+    from: Option<&'a DateTime<Utc>>,
+    to: Option<&'a DateTime<Utc>>,
+}
+
+impl<'a> Filter<'a> {
+    fn build(&self) -> (String, Vec<String>) {
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+
+        if let Some(reader_id) = self.reader_id {
+            params.push(reader_id.to_string());
+            conditions.push(format!("reader_id = ?{}", params.len()));
+        }
+
+        if let Some(publication_id) = self.publication_id {
+            params.push(publication_id.to_string());
+            conditions.push(format!("publication_id = ?{}", params.len()));
+        }
+
+        if let Some(from) = self.from {
+            params.push(to_timestamp(from));
+            conditions.push(format!("date >= ?{}", params.len()));
+        }
+
+        if let Some(to) = self.to {
+            params.push(to_timestamp(to));
+            conditions.push(format!("date <= ?{}", params.len()));
+        }
+
+        if conditions.is_empty() {
+            return (String::new(), params);
+        }
+
+        (format!("WHERE {}", conditions.join(" AND ")), params)
+    }
+}
+
+fn base_row(row: &Row) -> rusqlite::Result<InteractionRow> {
+    Ok((row.get(0)?, row.get(1)?, row.get(2)?, None, None))
+}
+
+fn review_row(row: &Row) -> rusqlite::Result<InteractionRow> {
+    Ok((
+        row.get(0)?,
+        row.get(1)?,
+        row.get(2)?,
+        Some(row.get(3)?),
+        Some(row.get(4)?),
+    ))
+}
+
+fn review(base: Base, stars: Option<u32>, comment: Option<String>) -> Result<Review> {
+    Ok(Review::build(
+        base,
+        Stars::new(stars.unwrap_or(0) as u8)?,
+        Comment::new(comment.unwrap_or_default())?,
+    ))
+}
+
+#[async_trait]
+impl InteractionRepository for SqliteInteractionRepository {
+    async fn find_views(
+        &self,
+        reader_id: Option<&ReaderId>,
+        publication_id: Option<&PublicationId>,
+        from: Option<&DateTime<Utc>>,
+        to: Option<&DateTime<Utc>>,
+    ) -> Result<Vec<View>> {
+        let conn = self.db.conn().await;
+        Self::find(
+            &conn,
+            "views",
+            ", is_unique",
+            Filter {
+                reader_id,
+                publication_id,
+                from,
+                to,
+            },
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    Some(row.get(3)?),
+                    None,
+                ))
+            },
+            |base, unique, _| Ok(View::build(base, unique == Some(1))),
+        )
+    }
+
+    async fn find_readings(
+        &self,
+        reader_id: Option<&ReaderId>,
+        publication_id: Option<&PublicationId>,
+        from: Option<&DateTime<Utc>>,
+        to: Option<&DateTime<Utc>>,
+    ) -> Result<Vec<Reading>> {
+        let conn = self.db.conn().await;
+        Self::find(
+            &conn,
+            "readings",
+            "",
+            Filter {
+                reader_id,
+                publication_id,
+                from,
+                to,
+            },
+            base_row,
+            |base, _, _| Ok(base),
+        )
+    }
+
+    async fn find_likes(
+        &self,
+        reader_id: Option<&ReaderId>,
+        publication_id: Option<&PublicationId>,
+        from: Option<&DateTime<Utc>>,
+        to: Option<&DateTime<Utc>>,
+    ) -> Result<Vec<Like>> {
+        let conn = self.db.conn().await;
+        Self::find(
+            &conn,
+            "likes",
+            "",
+            Filter {
+                reader_id,
+                publication_id,
+                from,
+                to,
+            },
+            base_row,
+            |base, _, _| Ok(base),
+        )
+    }
+
+    async fn find_reviews(
+        &self,
+        reader_id: Option<&ReaderId>,
+        publication_id: Option<&PublicationId>,
+        from: Option<&DateTime<Utc>>,
+        to: Option<&DateTime<Utc>>,
+    ) -> Result<Vec<Review>> {
+        let conn = self.db.conn().await;
+        Self::find(
+            &conn,
+            "reviews",
+            ", stars, comment",
+            Filter {
+                reader_id,
+                publication_id,
+                from,
+                to,
+            },
+            review_row,
+            review,
+        )
+    }
+
+    async fn find_reviews_page(
+        &self,
+        publication_id: &PublicationId,
+        pagination: &Pagination,
+    ) -> Result<Paginated<Review>> {
+        let column = match pagination.sort() {
+            "date" => "date",
+            "stars" => "stars",
+            _ => return Err(pagination.unsupported_sort()),
+        };
+
+        let conn = self.db.conn().await;
+        let (clause, params, total) = paginate(
+            &conn,
+            "reviews",
+            vec!["publication_id = ?1".to_owned()],
+            vec![Value::Text(publication_id.to_string())],
+            column,
+            "reader_id",
+            pagination,
+        )?;
+        let params: Vec<&dyn ToSql> = params.iter().map(|param| param as &dyn ToSql).collect();
+
+        let reviews = Self::query(
+            &conn,
+            "reviews",
+            ", stars, comment",
+            &clause,
+            &params,
+            review_row,
+            review,
+        )?;
+
+        pagination.page(
+            reviews,
+            total,
+            |review| review.base().reader_id().value(),
+            |review| match column {
+                "stars" => JsonValue::from(review.stars().value()),
+                _ => JsonValue::from(to_timestamp(review.base().date())),
+            },
+        )
+    }
+
+    async fn save_view(&self, view: &mut View) -> Result<()> {
+        let conn = self.db.conn().await;
+        conn.execute(
+            "INSERT INTO views (reader_id, publication_id, date, is_unique)
+            VALUES (?1, ?2, ?3, ?4)",
+            params![
+                view.base().reader_id().value(),
+                view.base().publication_id().value(),
+                to_timestamp(view.base().date()),
+                view.is_unique(),
+            ],
+        )
+        .map_err(db_error)?;
+
+        Ok(())
+    }
+
+    async fn save_reading(&self, reading: &mut Reading) -> Result<()> {
+        self.save_base("readings", reading).await
+    }
+
+    async fn save_like(&self, like: &mut Like) -> Result<()> {
+        self.save_base("likes", like).await
+    }
+
+    async fn save_review(&self, review: &mut Review) -> Result<()> {
+        let conn = self.db.conn().await;
+        conn.execute(
+            "INSERT INTO reviews (reader_id, publication_id, date, stars, comment)
+            VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                review.base().reader_id().value(),
+                review.base().publication_id().value(),
+                to_timestamp(review.base().date()),
+                review.stars().value(),
+                review.comment().value(),
+            ],
+        )
+        .map_err(db_error)?;
+
+        Ok(())
+    }
+
+    async fn delete_like(
+        &self,
+        reader_id: &ReaderId,
+        publication_id: &PublicationId,
+    ) -> Result<()> {
+        self.delete("likes", reader_id, publication_id).await
+    }
+
+    async fn delete_review(
+        &self,
+        reader_id: &ReaderId,
+        publication_id: &PublicationId,
+    ) -> Result<()> {
+        self.delete("reviews", reader_id, publication_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+
+    use common::model::SortDirection;
+
+    #[tokio::test]
+    async fn interactions() {
+        let repo = SqliteInteractionRepository::new(Arc::new(Database::in_memory().unwrap()));
+        let reader1 = ReaderId::new("#reader01").unwrap();
+        let reader2 = ReaderId::new("#reader02").unwrap();
+        let publication1 = PublicationId::new("#publication01").unwrap();
+        let publication2 = PublicationId::new("#publication02").unwrap();
+
+        repo.save_view(&mut View::new(reader1.clone(), publication1.clone(), true).unwrap())
+            .await
+            .unwrap();
+        repo.save_view(&mut View::new(reader1.clone(), publication1.clone(), false).unwrap())
+            .await
+            .unwrap();
+        repo.save_reading(&mut Reading::new(reader1.clone(), publication1.clone()).unwrap())
+            .await
+            .unwrap();
+        repo.save_like(&mut Like::new(reader1.clone(), publication1.clone()).unwrap())
+            .await
+            .unwrap();
+        repo.save_like(&mut Like::new(reader2.clone(), publication1.clone()).unwrap())
+            .await
+            .unwrap();
+        repo.save_like(&mut Like::new(reader1.clone(), publication2.clone()).unwrap())
+            .await
+            .unwrap();
+        repo.save_review(
+            &mut Review::new(
+                reader2.clone(),
+                publication2.clone(),
+                Stars::new(3).unwrap(),
+                Comment::new("Comment").unwrap(),
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let views = repo
+            .find_views(None, Some(&publication1), None, None)
+            .await
+            .unwrap();
+        assert_eq!(views.len(), 2);
+        assert!(views[0].is_unique());
+        assert!(!views[1].is_unique());
+
+        assert_eq!(
+            repo.find_readings(Some(&reader1), None, None, None)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            repo.find_likes(Some(&reader1), None, None, None)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            repo.find_likes(Some(&reader1), Some(&publication1), None, None)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let reviews = repo
+            .find_reviews(None, Some(&publication2), None, None)
+            .await
+            .unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].base().reader_id(), &reader2);
+        assert_eq!(reviews[0].stars().value(), 3);
+        assert_eq!(reviews[0].comment().value(), "Comment");
+
+        // Dates
+        let now = Utc::now();
+        assert!(repo
+            .find_likes(None, None, Some(&(now + Duration::days(1))), None)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            repo.find_likes(
+                None,
+                None,
+                Some(&(now - Duration::days(1))),
+                Some(&(now + Duration::days(1)))
+            )
+            .await
+            .unwrap()
+            .len(),
+            3
+        );
+
+        // Only the like of the given reader and publication is deleted.
+        repo.delete_like(&reader1, &publication1).await.unwrap();
+        let likes = repo.find_likes(None, None, None, None).await.unwrap();
+        assert_eq!(likes.len(), 2);
+        repo.delete_review(&reader2, &publication2).await.unwrap();
+        assert!(repo
+            .find_reviews(None, None, None, None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn review_pages() {
+        let repo = SqliteInteractionRepository::new(Arc::new(Database::in_memory().unwrap()));
+        let publication = PublicationId::new("#publication01").unwrap();
+
+        for (i, stars) in [4, 2, 5, 2].iter().enumerate() {
+            repo.save_review(
+                &mut Review::new(
+                    ReaderId::new(format!("#reader0{}", i + 1)).unwrap(),
+                    publication.clone(),
+                    Stars::new(*stars).unwrap(),
+                    Comment::new("Comment").unwrap(),
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        }
+        repo.save_review(
+            &mut Review::new(
+                ReaderId::new("#reader01").unwrap(),
+                PublicationId::new("#publication02").unwrap(),
+                Stars::new(1).unwrap(),
+                Comment::new("Comment").unwrap(),
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let readers = |page: &Paginated<Review>| -> Vec<String> {
+            page.items()
+                .iter()
+                .map(|review| review.base().reader_id().to_string())
+                .collect()
+        };
+
+        let pagination = Pagination::new(None, Some(3), "stars", SortDirection::Asc).unwrap();
+        let page = repo
+            .find_reviews_page(&publication, &pagination)
+            .await
+            .unwrap();
+        assert_eq!(page.total(), 4);
+        assert_eq!(readers(&page), vec!["#reader02", "#reader04", "#reader01"]);
+        assert!(page.next_cursor().is_some());
+
+        let pagination = Pagination::new(
+            page.next_cursor().map(str::to_owned),
+            Some(3),
+            "stars",
+            SortDirection::Asc,
+        )
+        .unwrap();
+        let page = repo
+            .find_reviews_page(&publication, &pagination)
+            .await
+            .unwrap();
+        assert_eq!(readers(&page), vec!["#reader03"]);
+        assert!(page.next_cursor().is_none());
+
+        let pagination = Pagination::new(None, None, "date", SortDirection::Desc).unwrap();
+        let page = repo
+            .find_reviews_page(&publication, &pagination)
+            .await
+            .unwrap();
+        assert_eq!(page.items()[0].base().reader_id().value(), "#reader04");
+    }
+}
@@ -0,0 +1,12 @@
+mod category_repository;
+mod collection_repository;
+mod database;
+mod interaction_repository;
+mod outbox;
+mod publication_repository;
+pub use category_repository::*;
+pub use collection_repository::*;
+pub use database::*;
+pub use interaction_repository::*;
+pub use outbox::*;
+pub use publication_repository::*;
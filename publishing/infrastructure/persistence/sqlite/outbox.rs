@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rusqlite::{params, Connection};
+
+use common::error::Error;
+use common::event::{Event, EventId, Outbox};
+use common::result::Result;
+
+use crate::infrastructure::persistence::sqlite::{
+    db_error, from_timestamp, to_timestamp, Database,
+};
+
+// Outbox stored in the same database as the aggregates, so repositories can add the events of
+// an aggregate in the same transaction used to save it.
+pub struct SqliteOutbox {
+    db: Arc<Database>,
+}
+
+impl SqliteOutbox {
+    pub fn new(db: Arc<Database>) -> Self {
+        SqliteOutbox { db }
+    }
+}
+
+#[async_trait]
+impl Outbox for SqliteOutbox {
+    async fn add_all(&self, events: &[Event]) -> Result<()> {
+        let mut conn = self.db.conn().await;
+        let tx = conn.transaction().map_err(db_error)?;
+        store_events(&tx, events)?;
+        tx.commit().map_err(db_error)
+    }
+
+    async fn pending(&self) -> Result<Vec<Event>> {
+        let conn = self.db.conn().await;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, topic, code, version, timestamp, payload FROM outbox
+                WHERE published_at IS NULL ORDER BY sequence",
+            )
+            .map_err(db_error)?;
+
+        let rows = stmt
+            .query_map(params![], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, u32>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, Vec<u8>>(5)?,
+                ))
+            })
+            .map_err(db_error)?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(db_error)?;
+
+        let mut events = Vec::new();
+        for (id, topic, code, version, timestamp, payload) in rows.into_iter() {
+            events.push(Event::build(
+                EventId::new(id)?,
+                topic,
+                code,
+                version,
+                from_timestamp(&timestamp)?,
+                payload,
+            ));
+        }
+
+        Ok(events)
+    }
+
+    async fn mark_as_published(&self, id: &EventId) -> Result<()> {
+        let conn = self.db.conn().await;
+        let updated = conn
+            .execute(
+                "UPDATE outbox SET published_at = ?1 WHERE id = ?2 AND published_at IS NULL",
+                params![to_timestamp(&Utc::now()), id.value()],
+            )
+            .map_err(db_error)?;
+
+        if updated == 0 {
+            return Err(Error::new("outbox", "not_found"));
+        }
+
+        Ok(())
+    }
+}
+
+// Events already in the outbox are ignored, so an aggregate can be saved many times with the
+// same recorded events.
+pub(crate) fn store_events(conn: &Connection, events: &[Event]) -> Result<()> {
+    for event in events.iter() {
+        conn.execute(
+            "INSERT OR IGNORE INTO outbox (id, topic, code, version, timestamp, payload)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                event.id().value(),
+                event.topic(),
+                event.code(),
+                event.version(),
+                to_timestamp(event.timestamp()),
+                event.payload(),
+            ],
+        )
+        .map_err(db_error)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::event::EventPublisher;
+    use common::infrastructure::event::{InMemEventBus, OutboxEventPublisher, OutboxRelay};
+
+    use crate::domain::publication::PublicationRepository;
+    use crate::infrastructure::persistence::sqlite::SqlitePublicationRepository;
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn events_saved_with_aggregate() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let repo = SqlitePublicationRepository::new(db.clone());
+        let outbox = Arc::new(SqliteOutbox::new(db));
+
+        let mut publication = mocks::published_publication1();
+        repo.save(&mut publication).await.unwrap();
+        repo.save(&mut publication).await.unwrap();
+
+        let events = publication.base().events().unwrap();
+        let pending = outbox.pending().await.unwrap();
+        assert_eq!(pending.len(), events.len());
+        assert_eq!(pending[0].id(), events[0].id());
+        assert_eq!(pending[0].timestamp(), events[0].timestamp());
+        assert_eq!(pending[1].payload(), events[1].payload());
+        assert_eq!(pending[1].version(), events[1].version());
+
+        // Publishing the saved events relays them once
+        let relay = Arc::new(OutboxRelay::new(
+            outbox.clone(),
+            Arc::new(InMemEventBus::new()),
+        ));
+        let event_pub = OutboxEventPublisher::new(outbox.clone(), relay);
+        let res = event_pub
+            .publish_all(events.clone())
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(res.published_events() as usize, events.len());
+        assert!(outbox.pending().await.unwrap().is_empty());
+        assert!(outbox.mark_as_published(events[0].id()).await.is_err());
+    }
+}
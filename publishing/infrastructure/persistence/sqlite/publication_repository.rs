@@ -0,0 +1,884 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rusqlite::types::Value;
+use rusqlite::{params, Connection, ToSql, Transaction};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use common::error::Error;
+use common::model::{AggregateRoot, Paginated, Pagination, StatusHistory, StatusItem};
+use common::result::Result;
+
+use crate::domain::author::AuthorId;
+use crate::domain::category::CategoryId;
+use crate::domain::content_manager::ContentManagerId;
+use crate::domain::publication::{
+    Frame, Header, Image, Name, Page, Position, Publication, PublicationFilter, PublicationId,
+    PublicationRepository, Size, Statistics, Status, Synopsis, Tag,
+};
+use crate::infrastructure::persistence::sqlite::{
+    db_error, from_optional_timestamp, from_timestamp, paginate, store_events, stored_version,
+    to_timestamp, Database,
+};
+
+const SELECT_PUBLICATIONS: &str = "
+    SELECT id, author_id, name, synopsis, category_id, cover, contract,
+        views, unique_views, readings, likes, reviews, stars,
+        created_at, updated_at, deleted_at, version
+    FROM publications";
+
+const CURRENT_STATUS: &str = "(
+    SELECT status FROM publication_status_history
+    WHERE publication_id = publications.id
+    ORDER BY position DESC LIMIT 1
+)";
+
+struct PublicationRow {
+    id: String,
+    author_id: String,
+    name: String,
+    synopsis: String,
+    category_id: String,
+    cover: String,
+    contract: bool,
+    views: u32,
+    unique_views: u32,
+    readings: u32,
+    likes: u32,
+    reviews: u32,
+    stars: f64,
+    created_at: String,
+    updated_at: Option<String>,
+    deleted_at: Option<String>,
+    version: i64,
+}
+
+pub struct SqlitePublicationRepository {
+    db: Arc<Database>,
+}
+
+impl SqlitePublicationRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        SqlitePublicationRepository { db }
+    }
+
+    fn find(conn: &Connection, condition: &str, params: &[&dyn ToSql]) -> Result<Vec<Publication>> {
+        Self::query(
+            conn,
+            &format!("{} ORDER BY created_at, id", condition),
+            params,
+        )
+    }
+
+    fn query(conn: &Connection, clause: &str, params: &[&dyn ToSql]) -> Result<Vec<Publication>> {
+        let mut stmt = conn
+            .prepare(&format!("{} {}", SELECT_PUBLICATIONS, clause))
+            .map_err(db_error)?;
+
+        let rows = stmt
+            .query_map(params, |row| {
+                Ok(PublicationRow {
+                    id: row.get(0)?,
+                    author_id: row.get(1)?,
+                    name: row.get(2)?,
+                    synopsis: row.get(3)?,
+                    category_id: row.get(4)?,
+                    cover: row.get(5)?,
+                    contract: row.get(6)?,
+                    views: row.get(7)?,
+                    unique_views: row.get(8)?,
+                    readings: row.get(9)?,
+                    likes: row.get(10)?,
+                    reviews: row.get(11)?,
+                    stars: row.get(12)?,
+                    created_at: row.get(13)?,
+                    updated_at: row.get(14)?,
+                    deleted_at: row.get(15)?,
+                    version: row.get(16)?,
+                })
+            })
+            .map_err(db_error)?
+            .collect::<rusqlite::Result<Vec<PublicationRow>>>()
+            .map_err(db_error)?;
+
+        let mut publications = Vec::new();
+        for row in rows.into_iter() {
+            publications.push(Self::load(conn, row)?);
+        }
+
+        Ok(publications)
+    }
+
+    fn load(conn: &Connection, row: PublicationRow) -> Result<Publication> {
+        let tags = load_tags(conn, "publication_tags", "publication_id", &row.id)?;
+
+        let header = Header::new(
+            Name::new(row.name)?,
+            Synopsis::new(row.synopsis)?,
+            CategoryId::new(row.category_id)?,
+            tags,
+            Image::new(row.cover)?,
+        )?;
+
+        let statistics = Statistics::new(
+            row.views,
+            row.unique_views,
+            row.readings,
+            row.likes,
+            row.reviews,
+            row.stars as f32,
+        )?;
+
+        Ok(Publication::build(
+            AggregateRoot::build(
+                PublicationId::new(&row.id)?,
+                from_timestamp(&row.created_at)?,
+                from_optional_timestamp(row.updated_at)?,
+                from_optional_timestamp(row.deleted_at)?,
+                row.version as u64,
+            ),
+            AuthorId::new(row.author_id)?,
+            header,
+            Self::load_pages(conn, &row.id)?,
+            row.contract,
+            statistics,
+            Self::load_status_history(conn, &row.id)?,
+        ))
+    }
+
+    fn load_pages(conn: &Connection, publication_id: &str) -> Result<Vec<Page>> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT position, number FROM publication_pages
+                WHERE publication_id = ?1 ORDER BY position",
+            )
+            .map_err(db_error)?;
+        let page_rows = stmt
+            .query_map(params![publication_id], |row| {
+                Ok((row.get::<_, u32>(0)?, row.get::<_, u32>(1)?))
+            })
+            .map_err(db_error)?
+            .collect::<rusqlite::Result<Vec<(u32, u32)>>>()
+            .map_err(db_error)?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, page_position, url FROM publication_images
+                WHERE publication_id = ?1 ORDER BY page_position, position",
+            )
+            .map_err(db_error)?;
+        let image_rows = stmt
+            .query_map(params![publication_id], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, u32>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .map_err(db_error)?
+            .collect::<rusqlite::Result<Vec<(i64, u32, String)>>>()
+            .map_err(db_error)?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT frame_order, x, y, width, height FROM publication_frames
+                WHERE image_id = ?1 ORDER BY position",
+            )
+            .map_err(db_error)?;
+
+        let mut pages = Vec::new();
+        for (position, number) in page_rows.into_iter() {
+            let mut images = Vec::new();
+            for (image_id, _, url) in image_rows
+                .iter()
+                .filter(|(_, page_position, _)| *page_position == position)
+            {
+                let frame_rows = stmt
+                    .query_map(params![image_id], |row| {
+                        Ok((
+                            row.get::<_, u32>(0)?,
+                            row.get::<_, u32>(1)?,
+                            row.get::<_, u32>(2)?,
+                            row.get::<_, u32>(3)?,
+                            row.get::<_, u32>(4)?,
+                        ))
+                    })
+                    .map_err(db_error)?
+                    .collect::<rusqlite::Result<Vec<(u32, u32, u32, u32, u32)>>>()
+                    .map_err(db_error)?;
+
+                let mut frames = Vec::new();
+                for (order, x, y, width, height) in frame_rows.into_iter() {
+                    frames.push(Frame::new(
+                        order,
+                        Position::new(x, y)?,
+                        Size::new(width, height)?,
+                    )?);
+                }
+
+                let mut image = Image::new(url.as_str())?;
+                image.set_frames(frames)?;
+                images.push(image);
+            }
+
+            let mut page = Page::new(number)?;
+            page.set_images(images)?;
+            pages.push(page);
+        }
+
+        Ok(pages)
+    }
+
+    fn load_status_history(
+        conn: &Connection,
+        publication_id: &str,
+    ) -> Result<StatusHistory<Status>> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT status, admin_id, date FROM publication_status_history
+                WHERE publication_id = ?1 ORDER BY position",
+            )
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map(params![publication_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .map_err(db_error)?
+            .collect::<rusqlite::Result<Vec<(String, Option<String>, String)>>>()
+            .map_err(db_error)?;
+
+        let mut history = Vec::new();
+        for (status, admin_id, date) in rows.into_iter() {
+            let status = match (status.as_ref(), admin_id) {
+                ("draft", _) => Status::Draft,
+                ("waiting-approval", _) => Status::WaitingApproval,
+                ("published", Some(admin_id)) => Status::Published {
+                    admin_id: ContentManagerId::new(admin_id)?,
+                },
+                ("rejected", Some(admin_id)) => Status::Rejected {
+                    admin_id: ContentManagerId::new(admin_id)?,
+                },
+                _ => {
+                    return Err(Error::internal("publication", "invalid_status")
+                        .add_context("status", &status)
+                        .build())
+                }
+            };
+
+            history.push(StatusItem::build(status, from_timestamp(&date)?));
+        }
+
+        StatusHistory::build(history)
+    }
+
+    fn store(tx: &Transaction, publication: &Publication) -> Result<()> {
+        let id = publication.base().id().value();
+
+        tx.execute(
+            "DELETE FROM publication_frames WHERE image_id IN
+                (SELECT id FROM publication_images WHERE publication_id = ?1)",
+            params![id],
+        )
+        .map_err(db_error)?;
+        for table in &[
+            "publication_images",
+            "publication_pages",
+            "publication_tags",
+            "publication_status_history",
+        ] {
+            tx.execute(
+                &format!("DELETE FROM {} WHERE publication_id = ?1", table),
+                params![id],
+            )
+            .map_err(db_error)?;
+        }
+
+        let header = publication.header();
+        let statistics = publication.statistics();
+        tx.execute(
+            "INSERT OR REPLACE INTO publications (
+                id, author_id, name, synopsis, category_id, cover, contract,
+                views, unique_views, readings, likes, reviews, stars,
+                created_at, updated_at, deleted_at, version
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17
+            )",
+            params![
+                id,
+                publication.author_id().value(),
+                header.name().value(),
+                header.synopsis().value(),
+                header.category_id().value(),
+                header.cover().url(),
+                publication.has_contract(),
+                statistics.views(),
+                statistics.unique_views(),
+                statistics.readings(),
+                statistics.likes(),
+                statistics.reviews(),
+                statistics.stars() as f64,
+                to_timestamp(publication.base().created_at()),
+                publication.base().updated_at().map(to_timestamp),
+                publication.base().deleted_at().map(to_timestamp),
+                publication.base().version() as i64,
+            ],
+        )
+        .map_err(db_error)?;
+
+        store_tags(tx, "publication_tags", "publication_id", id, header.tags())?;
+
+        for (page_position, page) in publication.pages().iter().enumerate() {
+            tx.execute(
+                "INSERT INTO publication_pages (publication_id, position, number)
+                VALUES (?1, ?2, ?3)",
+                params![id, page_position as u32, page.number()],
+            )
+            .map_err(db_error)?;
+
+            for (position, image) in page.images().iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO publication_images (publication_id, page_position, position, url)
+                    VALUES (?1, ?2, ?3, ?4)",
+                    params![id, page_position as u32, position as u32, image.url()],
+                )
+                .map_err(db_error)?;
+                let image_id = tx.last_insert_rowid();
+
+                for (position, frame) in image.frames().iter().enumerate() {
+                    tx.execute(
+                        "INSERT INTO publication_frames
+                            (image_id, position, frame_order, x, y, width, height)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        params![
+                            image_id,
+                            position as u32,
+                            frame.order(),
+                            frame.position().x(),
+                            frame.position().y(),
+                            frame.size().width(),
+                            frame.size().height(),
+                        ],
+                    )
+                    .map_err(db_error)?;
+                }
+            }
+        }
+
+        for (position, item) in publication.status_history().history().iter().enumerate() {
+            let admin_id = match item.status() {
+                Status::Published { admin_id } | Status::Rejected { admin_id } => {
+                    Some(admin_id.value())
+                }
+                _ => None,
+            };
+
+            tx.execute(
+                "INSERT INTO publication_status_history
+                    (publication_id, position, status, admin_id, date)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    id,
+                    position as u32,
+                    item.status().to_string(),
+                    admin_id,
+                    to_timestamp(item.date()),
+                ],
+            )
+            .map_err(db_error)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PublicationRepository for SqlitePublicationRepository {
+    async fn next_id(&self) -> Result<PublicationId> {
+        let id = Uuid::new_v4();
+        PublicationId::new(id.to_string())
+    }
+
+    async fn find_all(&self) -> Result<Vec<Publication>> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, "", &[])
+    }
+
+    async fn find_by_id(&self, id: &PublicationId) -> Result<Publication> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, "WHERE id = ?1", &[&id.value()])?
+            .pop()
+            .ok_or(Error::new("publication", "not_found"))
+    }
+
+    async fn find_by_author_id(&self, author_id: &AuthorId) -> Result<Vec<Publication>> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, "WHERE author_id = ?1", &[&author_id.value()])
+    }
+
+    async fn find_by_category_id(&self, category_id: &CategoryId) -> Result<Vec<Publication>> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, "WHERE category_id = ?1", &[&category_id.value()])
+    }
+
+    async fn find_by_status(&self, status: &str) -> Result<Vec<Publication>> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, &format!("WHERE {} = ?1", CURRENT_STATUS), &[&status])
+    }
+
+    async fn search(&self, text: &str) -> Result<Vec<Publication>> {
+        let conn = self.db.conn().await;
+        Self::find(&conn, "WHERE instr(name, ?1) > 0", &[&text])
+    }
+
+    async fn find_page(
+        &self,
+        filter: &PublicationFilter,
+        pagination: &Pagination,
+    ) -> Result<Paginated<Publication>> {
+        let column = match pagination.sort() {
+            "created_at" => "created_at",
+            "name" => "name",
+            _ => return Err(pagination.unsupported_sort()),
+        };
+
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+
+        if let Some(author_id) = &filter.author_id {
+            params.push(Value::Text(author_id.to_string()));
+            conditions.push(format!("author_id = ?{}", params.len()));
+        }
+
+        if let Some(category_id) = &filter.category_id {
+            params.push(Value::Text(category_id.to_string()));
+            conditions.push(format!("category_id = ?{}", params.len()));
+        }
+
+        if let Some(status) = &filter.status {
+            params.push(Value::Text(status.to_owned()));
+            conditions.push(format!("{} = ?{}", CURRENT_STATUS, params.len()));
+        }
+
+        if let Some(name) = &filter.name {
+            params.push(Value::Text(name.to_owned()));
+            conditions.push(format!("instr(name, ?{}) > 0", params.len()));
+        }
+
+        if let Some(author_id) = &filter.visible_to {
+            params.push(Value::Text(author_id.to_string()));
+            conditions.push(format!(
+                "(author_id = ?{} OR {} = 'published')",
+                params.len(),
+                CURRENT_STATUS
+            ));
+        }
+
+        let conn = self.db.conn().await;
+        let (clause, params, total) = paginate(
+            &conn,
+            "publications",
+            conditions,
+            params,
+            column,
+            "id",
+            pagination,
+        )?;
+        let params: Vec<&dyn ToSql> = params.iter().map(|param| param as &dyn ToSql).collect();
+
+        pagination.page(
+            Self::query(&conn, &clause, &params)?,
+            total,
+            |publication| publication.base().id().value(),
+            |publication| match column {
+                "name" => JsonValue::from(publication.header().name().value()),
+                _ => JsonValue::from(to_timestamp(publication.base().created_at())),
+            },
+        )
+    }
+
+    async fn save(&self, publication: &mut Publication) -> Result<()> {
+        let mut conn = self.db.conn().await;
+        let tx = conn.transaction().map_err(db_error)?;
+        publication.base().check_version(stored_version(
+            &tx,
+            "publications",
+            publication.base().id().value(),
+        )?)?;
+        Self::store(&tx, publication)?;
+        store_events(&tx, &publication.base().events()?)?;
+        tx.commit().map_err(db_error)?;
+
+        publication.base_mut().commit_version();
+        Ok(())
+    }
+}
+
+pub(super) fn load_tags(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    id: &str,
+) -> Result<Vec<Tag>> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT name FROM {} WHERE {} = ?1 ORDER BY position",
+            table, column
+        ))
+        .map_err(db_error)?;
+    let names = stmt
+        .query_map(params![id], |row| row.get::<_, String>(0))
+        .map_err(db_error)?
+        .collect::<rusqlite::Result<Vec<String>>>()
+        .map_err(db_error)?;
+
+    let mut tags = Vec::new();
+    for name in names.into_iter() {
+        tags.push(Tag::new(name)?);
+    }
+
+    Ok(tags)
+}
+
+pub(super) fn store_tags(
+    tx: &Transaction,
+    table: &str,
+    column: &str,
+    id: &str,
+    tags: &[Tag],
+) -> Result<()> {
+    for (position, tag) in tags.iter().enumerate() {
+        tx.execute(
+            &format!(
+                "INSERT INTO {} ({}, position, name) VALUES (?1, ?2, ?3)",
+                table, column
+            ),
+            params![id, position as u32, tag.name()],
+        )
+        .map_err(db_error)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::model::SortDirection;
+
+    use crate::domain::interaction::{Comment, Stars};
+    use crate::infrastructure::persistence::inmem::InMemPublicationRepository;
+    use crate::mocks;
+
+    #[tokio::test]
+    async fn round_trip() {
+        let repo = SqlitePublicationRepository::new(Arc::new(Database::in_memory().unwrap()));
+
+        let mut publication = mocks::published_publication1();
+        let mut pages = publication.pages().to_vec();
+        let mut image = Image::new("domain.com/framed.jpg").unwrap();
+        image
+            .set_frames(vec![
+                Frame::new(
+                    0,
+                    Position::new(0, 0).unwrap(),
+                    Size::new(800, 600).unwrap(),
+                )
+                .unwrap(),
+                Frame::new(
+                    1,
+                    Position::new(800, 0).unwrap(),
+                    Size::new(400, 600).unwrap(),
+                )
+                .unwrap(),
+            ])
+            .unwrap();
+        pages[0].set_images(vec![image]).unwrap();
+        publication.set_pages(pages).unwrap();
+        publication.publish(&mocks::author1()).unwrap();
+        publication.approve(&mocks::content_manager1()).unwrap();
+        publication.like(&mocks::reader1()).unwrap();
+        publication
+            .review(
+                &mocks::reader1(),
+                Stars::new(4).unwrap(),
+                Comment::new("Good").unwrap(),
+            )
+            .unwrap();
+        publication.add_contract().unwrap();
+        repo.save(&mut publication).await.unwrap();
+
+        let found = repo.find_by_id(publication.base().id()).await.unwrap();
+        assert_eq!(found.base().id(), publication.base().id());
+        assert_eq!(found.base().created_at(), publication.base().created_at());
+        assert_eq!(found.author_id(), publication.author_id());
+        assert_eq!(found.header().name().value(), "Publication 01");
+        assert_eq!(found.header().tags().len(), 2);
+        assert_eq!(found.header().tags()[1].slug(), "tag-2");
+        assert_eq!(found.header().cover().url(), "domain.com/image.jpg");
+        assert!(found.has_contract());
+
+        assert_eq!(found.pages().len(), 2);
+        assert_eq!(found.pages()[0].number(), 2);
+        assert_eq!(found.pages()[0].images().len(), 1);
+        let frames = found.pages()[0].images()[0].frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1].order(), 1);
+        assert_eq!(frames[1].position(), &Position::new(800, 0).unwrap());
+        assert_eq!(frames[1].size(), &Size::new(400, 600).unwrap());
+        assert_eq!(found.pages()[1].images().len(), 2);
+        assert_eq!(found.pages()[1].images()[1].url(), "domain.com/img5.jpg");
+
+        assert_eq!(found.statistics().likes(), 1);
+        assert_eq!(found.statistics().reviews(), 1);
+        assert_eq!(found.statistics().stars(), 4.0);
+
+        let history = found.status_history().history();
+        assert_eq!(history.len(), publication.status_history().history().len());
+        for (found, expected) in history
+            .iter()
+            .zip(publication.status_history().history().iter())
+        {
+            assert_eq!(found.status().to_string(), expected.status().to_string());
+            assert_eq!(found.date(), expected.date());
+        }
+        if let Status::Published { admin_id } = found.status_history().current().status() {
+            assert_eq!(admin_id.value(), "#content-manager01");
+        } else {
+            panic!("publication should be published");
+        }
+
+        // Saving again replaces the previous state.
+        let mut found = found;
+        found.set_pages(Vec::new()).unwrap();
+        repo.save(&mut found).await.unwrap();
+        let found = repo.find_by_id(publication.base().id()).await.unwrap();
+        assert!(found.pages().is_empty());
+        assert_eq!(
+            found.status_history().current().status().to_string(),
+            "draft"
+        );
+    }
+
+    #[tokio::test]
+    async fn queries() {
+        let repo = SqlitePublicationRepository::new(Arc::new(Database::in_memory().unwrap()));
+        repo.save(&mut mocks::publication1()).await.unwrap();
+
+        let published = mocks::published_publication1();
+        let mut publication = Publication::build(
+            AggregateRoot::build(
+                PublicationId::new("#publication02").unwrap(),
+                *published.base().created_at(),
+                None,
+                None,
+                0,
+            ),
+            mocks::author2().base().id().clone(),
+            published.header().clone(),
+            published.pages().to_vec(),
+            false,
+            Statistics::default(),
+            published.status_history().clone(),
+        );
+        repo.save(&mut publication).await.unwrap();
+
+        assert_eq!(repo.find_all().await.unwrap().len(), 2);
+        assert!(repo
+            .find_by_id(&PublicationId::new("#publication03").unwrap())
+            .await
+            .is_err());
+        assert_eq!(
+            repo.find_by_author_id(mocks::author2().base().id())
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            repo.find_by_category_id(mocks::category1().base().id())
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(repo.find_by_status("draft").await.unwrap().len(), 1);
+        let published = repo.find_by_status("published").await.unwrap();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].base().id().value(), "#publication02");
+        assert_eq!(repo.search("Publication").await.unwrap().len(), 2);
+        assert_eq!(repo.search("publication").await.unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn stale_writes() {
+        let repo = SqlitePublicationRepository::new(Arc::new(Database::in_memory().unwrap()));
+
+        let mut publication = mocks::published_publication1();
+        repo.save(&mut publication).await.unwrap();
+        let version = publication.base().version();
+
+        let mut first = repo.find_by_id(publication.base().id()).await.unwrap();
+        let mut second = repo.find_by_id(publication.base().id()).await.unwrap();
+        assert_eq!(first.base().version(), version);
+
+        first.like(&mocks::reader1()).unwrap();
+        repo.save(&mut first).await.unwrap();
+        // Saving the same copy again is not a conflict
+        repo.save(&mut first).await.unwrap();
+
+        second.like(&mocks::reader1()).unwrap();
+        let err = repo.save(&mut second).await.unwrap_err();
+        assert!(err.is_conflict());
+
+        let found = repo.find_by_id(publication.base().id()).await.unwrap();
+        assert_eq!(found.base().version(), version + 2);
+        assert_eq!(found.statistics().likes(), 1);
+    }
+
+    #[tokio::test]
+    async fn pages() {
+        let sqlite = SqlitePublicationRepository::new(Arc::new(Database::in_memory().unwrap()));
+        let inmem = InMemPublicationRepository::new();
+        let repos: Vec<&dyn PublicationRepository> = vec![&sqlite, &inmem];
+
+        let draft = mocks::publication1();
+        let published = mocks::published_publication1();
+        let names = ["Comic C", "Comic A", "Comic E", "Comic B", "Comic D"];
+
+        for repo in repos.into_iter() {
+            // Odd publications are published, the last two ones are of the second author.
+            for (i, name) in names.iter().enumerate() {
+                let author = if i < 3 {
+                    mocks::author1()
+                } else {
+                    mocks::author2()
+                };
+                let source = if i % 2 == 0 { &published } else { &draft };
+                let mut publication = Publication::build(
+                    AggregateRoot::build(
+                        PublicationId::new(format!("#publication0{}", i + 1)).unwrap(),
+                        *draft.base().created_at() + chrono::Duration::minutes(i as i64),
+                        None,
+                        None,
+                        0,
+                    ),
+                    author.base().id().clone(),
+                    Header::new(
+                        Name::new(*name).unwrap(),
+                        draft.header().synopsis().clone(),
+                        draft.header().category_id().clone(),
+                        Vec::new(),
+                        draft.header().cover().clone(),
+                    )
+                    .unwrap(),
+                    Vec::new(),
+                    false,
+                    Statistics::default(),
+                    source.status_history().clone(),
+                );
+                repo.save(&mut publication).await.unwrap();
+            }
+
+            let ids = |page: &Paginated<Publication>| -> Vec<String> {
+                page.items()
+                    .iter()
+                    .map(|publication| publication.base().id().to_string())
+                    .collect()
+            };
+            let all = PublicationFilter::default();
+
+            let mut cursor = None;
+            let mut pages = Vec::new();
+            loop {
+                let pagination =
+                    Pagination::new(cursor, Some(2), "created_at", SortDirection::Asc).unwrap();
+                let page = repo.find_page(&all, &pagination).await.unwrap();
+                assert_eq!(page.total(), 5);
+                pages.push(ids(&page));
+
+                cursor = page.next_cursor().map(str::to_owned);
+                if cursor.is_none() {
+                    break;
+                }
+            }
+            assert_eq!(
+                pages,
+                vec![
+                    vec!["#publication01", "#publication02"],
+                    vec!["#publication03", "#publication04"],
+                    vec!["#publication05"],
+                ]
+            );
+
+            let pagination = Pagination::new(None, Some(3), "name", SortDirection::Desc).unwrap();
+            let page = repo.find_page(&all, &pagination).await.unwrap();
+            assert_eq!(
+                ids(&page),
+                vec!["#publication03", "#publication05", "#publication01"]
+            );
+
+            let pagination = Pagination::new(
+                page.next_cursor().map(str::to_owned),
+                Some(3),
+                "name",
+                SortDirection::Desc,
+            )
+            .unwrap();
+            let next = repo.find_page(&all, &pagination).await.unwrap();
+            assert_eq!(ids(&next), vec!["#publication04", "#publication02"]);
+            assert!(next.next_cursor().is_none());
+
+            // The last publication of the previous page is not in the list anymore.
+            let second_author = PublicationFilter {
+                author_id: Some(mocks::author2().base().id().clone()),
+                ..PublicationFilter::default()
+            };
+            let next = repo.find_page(&second_author, &pagination).await.unwrap();
+            assert_eq!(ids(&next), vec!["#publication04"]);
+
+            let pagination = Pagination::new(None, None, "created_at", SortDirection::Asc).unwrap();
+            let visible = PublicationFilter {
+                visible_to: Some(mocks::author2().base().id().clone()),
+                ..PublicationFilter::default()
+            };
+            let page = repo.find_page(&visible, &pagination).await.unwrap();
+            assert_eq!(page.total(), 4);
+            assert_eq!(
+                ids(&page),
+                vec![
+                    "#publication01",
+                    "#publication03",
+                    "#publication04",
+                    "#publication05"
+                ]
+            );
+
+            let filter = PublicationFilter {
+                author_id: Some(mocks::author1().base().id().clone()),
+                category_id: Some(mocks::category1().base().id().clone()),
+                status: Some("published".to_owned()),
+                name: Some("Comic".to_owned()),
+                visible_to: None,
+            };
+            let page = repo.find_page(&filter, &pagination).await.unwrap();
+            assert_eq!(ids(&page), vec!["#publication01", "#publication03"]);
+
+            let pagination = Pagination::new(
+                Some("#publication02".to_owned()),
+                None,
+                "created_at",
+                SortDirection::Asc,
+            )
+            .unwrap();
+            assert!(repo.find_page(&filter, &pagination).await.is_err());
+            let pagination = Pagination::new(None, None, "stars", SortDirection::Asc).unwrap();
+            assert!(repo.find_page(&all, &pagination).await.is_err());
+        }
+    }
+}
@@ -37,6 +37,10 @@ pub enum CollectionEvent {
     },
 }
 
+impl CollectionEvent {
+    pub const VERSION: u32 = 1;
+}
+
 impl ToString for CollectionEvent {
     fn to_string(&self) -> String {
         match self {
@@ -53,10 +57,8 @@ impl ToEvent for CollectionEvent {
     fn to_event(&self) -> Result<Event> {
         let payload = util::serialize(&self, "collection")?;
 
-        Ok(Event::new(
-            "collection".to_owned(),
-            self.to_string(),
-            payload,
-        ))
+        let mut event = Event::new("collection".to_owned(), self.to_string(), payload);
+        event.set_version(Self::VERSION);
+        Ok(event)
     }
 }
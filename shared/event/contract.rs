@@ -31,6 +31,10 @@ pub enum ContractEvent {
     },
 }
 
+impl ContractEvent {
+    pub const VERSION: u32 = 1;
+}
+
 impl ToString for ContractEvent {
     fn to_string(&self) -> String {
         match self {
@@ -46,6 +50,8 @@ impl ToEvent for ContractEvent {
     fn to_event(&self) -> Result<Event> {
         let payload = util::serialize(&self, "contract")?;
 
-        Ok(Event::new("contract".to_owned(), self.to_string(), payload))
+        let mut event = Event::new("contract".to_owned(), self.to_string(), payload);
+        event.set_version(Self::VERSION);
+        Ok(event)
     }
 }
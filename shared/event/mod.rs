@@ -1,8 +1,14 @@
 mod collection;
 mod contract;
+mod payout;
 mod publication;
+mod subscription;
+mod upcaster;
 mod user;
 pub use collection::*;
 pub use contract::*;
+pub use payout::*;
 pub use publication::*;
+pub use subscription::*;
+pub use upcaster::*;
 pub use user::*;
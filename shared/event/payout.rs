@@ -0,0 +1,75 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use common::event::{Event, ToEvent};
+use common::result::Result;
+
+use crate::util;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum PayoutEvent {
+    Generated {
+        id: String,
+        month: String,
+        income: f64,
+        amount: f64,
+        contracts: usize,
+    },
+    AuthorPayoutGenerated {
+        payout_id: String,
+        month: String,
+        author_id: String,
+        amount: f64,
+        publication_ids: Vec<String>,
+    },
+    AuthorPayoutSent {
+        payout_id: String,
+        author_id: String,
+        transaction_id: String,
+        amount: f64,
+    },
+    AuthorPaid {
+        payout_id: String,
+        author_id: String,
+        transaction_id: String,
+        amount: f64,
+    },
+    AuthorPayoutFailed {
+        payout_id: String,
+        author_id: String,
+        transaction_id: String,
+    },
+    Paid {
+        id: String,
+        month: String,
+        amount: f64,
+    },
+}
+
+impl PayoutEvent {
+    pub const VERSION: u32 = 1;
+}
+
+impl fmt::Display for PayoutEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayoutEvent::Generated { .. } => write!(f, "generated"),
+            PayoutEvent::AuthorPayoutGenerated { .. } => write!(f, "author-payout-generated"),
+            PayoutEvent::AuthorPayoutSent { .. } => write!(f, "author-payout-sent"),
+            PayoutEvent::AuthorPaid { .. } => write!(f, "author-paid"),
+            PayoutEvent::AuthorPayoutFailed { .. } => write!(f, "author-payout-failed"),
+            PayoutEvent::Paid { .. } => write!(f, "paid"),
+        }
+    }
+}
+
+impl ToEvent for PayoutEvent {
+    fn to_event(&self) -> Result<Event> {
+        let payload = util::serialize(&self, "payout")?;
+
+        let mut event = Event::new("payout".to_owned(), self.to_string(), payload);
+        event.set_version(Self::VERSION);
+        Ok(event)
+    }
+}
@@ -1,10 +1,33 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use common::error::Error;
 use common::event::{Event, ToEvent};
 use common::result::Result;
 
 use crate::util;
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PublicationFrame {
+    pub order: u32,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PublicationImage {
+    pub url: String,
+    pub frames: Vec<PublicationFrame>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PublicationPage {
+    pub number: u32,
+    pub images: Vec<PublicationImage>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub enum PublicationEvent {
     Created {
@@ -27,6 +50,7 @@ pub enum PublicationEvent {
     PagesUpdated {
         id: String,
         pages_count: usize,
+        pages: Vec<PublicationPage>,
     },
     ChangedToDraft {
         id: String,
@@ -43,9 +67,11 @@ pub enum PublicationEvent {
         tags: Vec<String>,
         cover: String,
         pages_count: usize,
+        admin_id: Option<String>,
     },
     Rejected {
         id: String,
+        admin_id: Option<String>,
     },
     Deleted {
         id: String,
@@ -94,6 +120,12 @@ pub enum PublicationEvent {
     },
 }
 
+impl PublicationEvent {
+    // Version 2 added the pages to PagesUpdated and the content manager to Published and
+    // Rejected. Older events are migrated by `upcast_v1`.
+    pub const VERSION: u32 = 2;
+}
+
 impl ToString for PublicationEvent {
     fn to_string(&self) -> String {
         match self {
@@ -122,10 +154,27 @@ impl ToEvent for PublicationEvent {
     fn to_event(&self) -> Result<Event> {
         let payload = util::serialize(&self, "publication")?;
 
-        Ok(Event::new(
-            "publication".to_owned(),
-            self.to_string(),
-            payload,
-        ))
+        let mut event = Event::new("publication".to_owned(), self.to_string(), payload);
+        event.set_version(Self::VERSION);
+        Ok(event)
     }
 }
+
+pub(crate) fn upcast_v1(mut payload: Value) -> Result<Value> {
+    let (code, event) = match payload.as_object_mut().and_then(|p| p.iter_mut().next()) {
+        Some((code, Value::Object(event))) => (code.clone(), event),
+        _ => return Err(Error::new("publication", "invalid_event")),
+    };
+
+    match code.as_ref() {
+        "PagesUpdated" => {
+            event.insert("pages".to_owned(), Value::Array(Vec::new()));
+        }
+        "Published" | "Rejected" => {
+            event.insert("admin_id".to_owned(), Value::Null);
+        }
+        _ => {}
+    }
+
+    Ok(payload)
+}
@@ -1,32 +1,72 @@
-use chrono::{DateTime, Utc};
-use serde::{Serialize, Deserialize};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use common::event::{Event, ToEvent};
+use common::result::Result;
+
+use crate::util;
 
 #[derive(Serialize, Deserialize, Debug)]
-pub enum ContractEvent {
-    Requested {
+pub enum SubscriptionEvent {
+    Created {
+        id: String,
+        user_id: String,
+        plan_id: String,
+        amount: f64,
+    },
+    Paid {
+        id: String,
+        user_id: String,
+        plan_id: String,
+        amount: f64,
+        paid_until: String,
+    },
+    Renewed {
         id: String,
-        publication_id: String,
-        author_id: String,
-        timestamp: DateTime<Utc>,
+        user_id: String,
+        plan_id: String,
+        amount: f64,
     },
-    Approved {
+    PaymentFailed {
         id: String,
-        publication_id: String,
-        author_id: String,
-        content_manager_id: String,
-        timestamp: DateTime<Utc>,
+        user_id: String,
+        plan_id: String,
+        amount: f64,
     },
-    Rejected {
+    Expired {
         id: String,
-        publication_id: String,
-        author_id: String,
-        content_manager_id: String,
-        timestamp: DateTime<Utc>,
+        user_id: String,
     },
     Cancelled {
         id: String,
-        publication_id: String,
-        author_id: String,
-        timestamp: DateTime<Utc>,
+        user_id: String,
     },
 }
+
+impl SubscriptionEvent {
+    pub const VERSION: u32 = 1;
+}
+
+impl fmt::Display for SubscriptionEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubscriptionEvent::Created { .. } => write!(f, "created"),
+            SubscriptionEvent::Paid { .. } => write!(f, "paid"),
+            SubscriptionEvent::Renewed { .. } => write!(f, "renewed"),
+            SubscriptionEvent::PaymentFailed { .. } => write!(f, "payment-failed"),
+            SubscriptionEvent::Expired { .. } => write!(f, "expired"),
+            SubscriptionEvent::Cancelled { .. } => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl ToEvent for SubscriptionEvent {
+    fn to_event(&self) -> Result<Event> {
+        let payload = util::serialize(&self, "subscription")?;
+
+        let mut event = Event::new("subscription".to_owned(), self.to_string(), payload);
+        event.set_version(Self::VERSION);
+        Ok(event)
+    }
+}
@@ -0,0 +1,146 @@
+use common::event::UpcasterRegistry;
+
+use crate::event::{publication, user};
+
+// Upcasters of the events defined in this crate.
+pub fn upcasters() -> UpcasterRegistry {
+    let mut registry = UpcasterRegistry::new();
+    registry.register("publication", 1, publication::upcast_v1);
+    registry.register("user", 1, user::upcast_v1);
+    registry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::event::{Event, EventId};
+
+    use crate::event::{CollectionEvent, PublicationEvent, UserEvent};
+
+    // Payloads stored before events were versioned.
+    const PUBLICATION_V1: &[(&str, &str)] = &[
+        (
+            "pages-updated",
+            r##"{"PagesUpdated":{"id":"#publication01","pages_count":2}}"##,
+        ),
+        (
+            "published",
+            r##"{"Published":{"id":"#publication01","author_id":"#author01","name":"Name",
+            "synopsis":"Synopsis","category_id":"#category01","tags":["Tag"],
+            "cover":"cover.jpg","pages_count":2}}"##,
+        ),
+        ("rejected", r##"{"Rejected":{"id":"#publication01"}}"##),
+        (
+            "liked",
+            r##"{"Liked":{"reader_id":"#reader01","publication_id":"#publication01"}}"##,
+        ),
+    ];
+
+    fn fixture(topic: &str, code: &str, version: u32, payload: &str) -> Event {
+        Event::build(
+            EventId::new(format!("#{}-{}", topic, code)).unwrap(),
+            topic.to_owned(),
+            code.to_owned(),
+            version,
+            chrono::Utc::now(),
+            payload.as_bytes().to_vec(),
+        )
+    }
+
+    #[test]
+    fn publication_v1() {
+        let registry = upcasters();
+
+        // Old payloads cannot be decoded without upcasting them
+        let (code, payload) = PUBLICATION_V1[0];
+        let event = fixture("publication", code, 1, payload);
+        assert!(serde_json::from_slice::<PublicationEvent>(event.payload()).is_err());
+
+        for (code, payload) in PUBLICATION_V1.iter() {
+            let event = fixture("publication", code, 1, payload);
+            let event = registry.upcast(&event).unwrap();
+            assert_eq!(event.version(), PublicationEvent::VERSION);
+
+            let event: PublicationEvent = serde_json::from_slice(event.payload()).unwrap();
+            match event {
+                PublicationEvent::PagesUpdated {
+                    pages_count, pages, ..
+                } => {
+                    assert_eq!(pages_count, 2);
+                    assert!(pages.is_empty());
+                }
+                PublicationEvent::Published { name, admin_id, .. } => {
+                    assert_eq!(name, "Name");
+                    assert!(admin_id.is_none());
+                }
+                PublicationEvent::Rejected { admin_id, .. } => assert!(admin_id.is_none()),
+                PublicationEvent::Liked { reader_id, .. } => assert_eq!(reader_id, "#reader01"),
+                _ => panic!("unexpected event"),
+            }
+        }
+    }
+
+    #[test]
+    fn user_v1() {
+        let registry = upcasters();
+
+        let payloads = [
+            (
+                "registered",
+                r##"{"Registered":{"id":"#user01","username":"user","email":"user@omics.com",
+                "validation_code":"secret"}}"##,
+            ),
+            (
+                "logged-in",
+                r##"{"LoggedIn":{"id":"#user01","auth_token":"secret"}}"##,
+            ),
+            (
+                "password-recovery-requested",
+                r##"{"PasswordRecoveryRequested":{"id":"#user01","temp_password":"secret",
+                "email":"user@omics.com"}}"##,
+            ),
+        ];
+
+        for (code, payload) in payloads.iter() {
+            let event = registry.upcast(&fixture("user", code, 1, payload)).unwrap();
+            assert_eq!(event.version(), UserEvent::VERSION);
+            assert!(!String::from_utf8_lossy(event.payload()).contains("secret"));
+            assert!(serde_json::from_slice::<UserEvent>(event.payload()).is_ok());
+        }
+    }
+
+    #[test]
+    fn current_versions() {
+        let registry = upcasters();
+
+        let event = fixture(
+            "collection",
+            "deleted",
+            1,
+            r##"{"Deleted":{"id":"#collection01"}}"##,
+        );
+        let event = registry.upcast(&event).unwrap();
+        assert_eq!(event.version(), CollectionEvent::VERSION);
+        assert!(serde_json::from_slice::<CollectionEvent>(event.payload()).is_ok());
+
+        let event = fixture(
+            "user",
+            "validated",
+            1,
+            r##"{"Validated":{"id":"#user01"}}"##,
+        );
+        let event = registry.upcast(&event).unwrap();
+        assert_eq!(event.version(), UserEvent::VERSION);
+        assert!(serde_json::from_slice::<UserEvent>(event.payload()).is_ok());
+
+        let event = fixture(
+            "publication",
+            "rejected",
+            2,
+            r##"{"Rejected":{"id":"#publication01","admin_id":"#admin01"}}"##,
+        );
+        let upcasted = registry.upcast(&event).unwrap();
+        assert_eq!(upcasted.payload(), event.payload());
+    }
+}
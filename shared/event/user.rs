@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use common::error::Error;
 use common::event::{Event, ToEvent};
 use common::result::Result;
 
@@ -11,11 +13,27 @@ pub enum UserEvent {
         id: String,
         username: String,
         email: String,
-        validation_code: String,
     },
     LoggedIn {
         id: String,
-        auth_token: String,
+    },
+    LoginFailed {
+        id: String,
+        failures: u32,
+    },
+    LockedOut {
+        id: String,
+        until: String,
+    },
+    TwoFactorEnabled {
+        id: String,
+    },
+    TwoFactorDisabled {
+        id: String,
+    },
+    AccountLinked {
+        id: String,
+        provider: String,
     },
     Updated {
         id: String,
@@ -27,22 +45,36 @@ pub enum UserEvent {
     },
     PasswordRecoveryRequested {
         id: String,
-        temp_password: String,
         email: String,
     },
+    PasswordReset {
+        id: String,
+    },
     Deleted {
         id: String,
     },
 }
 
+impl UserEvent {
+    // Version 2 removed the validation code, the authentication token and the temporary password.
+    // Older events are migrated by `upcast_v1`.
+    pub const VERSION: u32 = 2;
+}
+
 impl ToString for UserEvent {
     fn to_string(&self) -> String {
         match self {
             UserEvent::Registered { .. } => "registered".to_owned(),
             UserEvent::LoggedIn { .. } => "logged-in".to_owned(),
+            UserEvent::LoginFailed { .. } => "login-failed".to_owned(),
+            UserEvent::LockedOut { .. } => "locked-out".to_owned(),
+            UserEvent::TwoFactorEnabled { .. } => "two-factor-enabled".to_owned(),
+            UserEvent::TwoFactorDisabled { .. } => "two-factor-disabled".to_owned(),
+            UserEvent::AccountLinked { .. } => "account-linked".to_owned(),
             UserEvent::Updated { .. } => "updated".to_owned(),
             UserEvent::Validated { .. } => "validated".to_owned(),
             UserEvent::PasswordRecoveryRequested { .. } => "password-recovery-requested".to_owned(),
+            UserEvent::PasswordReset { .. } => "password-reset".to_owned(),
             UserEvent::Deleted { .. } => "deleted".to_owned(),
         }
     }
@@ -52,6 +84,30 @@ impl ToEvent for UserEvent {
     fn to_event(&self) -> Result<Event> {
         let payload = util::serialize(&self, "user")?;
 
-        Ok(Event::new("user".to_owned(), self.to_string(), payload))
+        let mut event = Event::new("user".to_owned(), self.to_string(), payload);
+        event.set_version(Self::VERSION);
+        Ok(event)
     }
 }
+
+pub(crate) fn upcast_v1(mut payload: Value) -> Result<Value> {
+    let (code, event) = match payload.as_object_mut().and_then(|p| p.iter_mut().next()) {
+        Some((code, Value::Object(event))) => (code.clone(), event),
+        _ => return Err(Error::new("user", "invalid_event")),
+    };
+
+    match code.as_ref() {
+        "Registered" => {
+            event.remove("validation_code");
+        }
+        "LoggedIn" => {
+            event.remove("auth_token");
+        }
+        "PasswordRecoveryRequested" => {
+            event.remove("temp_password");
+        }
+        _ => {}
+    }
+
+    Ok(payload)
+}
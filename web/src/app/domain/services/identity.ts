@@ -13,7 +13,6 @@ export interface IRegisterCommand {
 
 export interface IRegisterResponse {
   id: string;
-  validation_code: string;
 }
 
 export interface ILoginCommand {